@@ -546,3 +546,53 @@ context_menu_redo = &重置
 header_column = <b><i>列名称</i></b>
 header_hidden = <b><i>隐藏</i></b>
 header_frozen = <b><i>冻结</i></b>
+
+esf_apply_node = 应用节点更改
+esf_type_bool = 布尔值
+esf_type_i8 = I8
+esf_type_i16 = I16
+esf_type_i32 = I32
+esf_type_i64 = I64
+esf_type_u8 = U8
+esf_type_u16 = U16
+esf_type_u32 = U32
+esf_type_u64 = U64
+esf_type_f32 = F32
+esf_type_f64 = F64
+esf_type_coord_2d = 二维坐标
+esf_coord_x = X
+esf_coord_y = Y
+esf_type_coord_3d = 三维坐标
+esf_coord_z = Z
+esf_type_utf16 = UTF-16 文本
+esf_type_ascii = ASCII 文本
+esf_type_angle = 角度
+esf_type_unknown_21 = 未知 21
+esf_type_unknown_23 = 未知 23
+esf_type_unknown_25 = 未知 25
+esf_type_unknown_26 = 未知 26
+esf_type_bool_array = 布尔值数组
+esf_type_i8_array = I8 数组
+esf_type_i16_array = I16 数组
+esf_type_i32_array = I32 数组
+esf_type_i64_array = I64 数组
+esf_type_u8_array = U8 数组
+esf_type_u16_array = U16 数组
+esf_type_u32_array = U32 数组
+esf_type_u64_array = U64 数组
+esf_type_f32_array = F32 数组
+esf_type_f64_array = F64 数组
+esf_type_coord_2d_array = 二维坐标数组
+esf_type_coord_3d_array = 三维坐标数组
+esf_type_utf16_array = UTF-16 文本数组
+esf_type_ascii_array = ASCII 文本数组
+esf_type_angle_array = 角度数组
+
+## Error messages. The key of each one is `error_` followed by the name of its error in snake_case.
+
+error_operation_cancelled = <p>操作已取消。</p>
+error_packed_file_not_found = <p>该 PackedFile 已不存在于 PackFile 中。</p>
+error_pack_file_is_not_a_file = <p>该 PackFile 在磁盘上不是一个文件。</p>
+error_schema_not_found = <p>所选游戏没有可用的 Schema。</p>
+error_game_path_not_configured = <p>未配置游戏路径。请前往 <i>'PackFile/Preferences'</i> 进行配置。</p>
+error_game_manifest_not_found = <p>未找到所选游戏的 manifest 文件。</p>
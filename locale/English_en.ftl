@@ -110,6 +110,10 @@ game_selected_launch_game = Launch Game Selected
 game_selected_open_game_data_folder = Open Game's Data Folder
 game_selected_open_game_assembly_kit_folder = Open Game's Assembly Kit Folder
 game_selected_open_config_folder = Open RPFM's Config Folder
+game_selected_suggest_game = Suggest Game for this PackFile
+game_selected_suggest_game_changed = The Game Selected has been changed to the most likely game for this PackFile: {"{"}{"}"}.
+game_selected_suggest_game_already_selected = The Game Selected is already the most likely game for this PackFile.
+game_selected_suggest_game_none = No game uses the type of this PackFile.
 
 ## Special Stuff
 
@@ -217,6 +221,7 @@ tt_game_selected_launch_game = Tries to launch the currently selected game on st
 tt_game_selected_open_game_data_folder = Tries to open the currently selected game's Data folder (if exists) in the default file manager.
 tt_game_selected_open_game_assembly_kit_folder = Tries to open the currently selected game's Assembly Kit folder (if exists) in the default file manager.
 tt_game_selected_open_config_folder = Tries to open RPFM's config folder, where the config/schemas/ctd reports are.
+tt_game_selected_suggest_game = Checks which games' schemas can decode the tables of the open PackFile, and changes the Game Selected to the most likely one. Unsaved changes to tables are not taken into account.
 
 tt_game_selected_warhammer_3 = Sets 'TW:Warhammer 3' as 'Game Selected'.
 tt_game_selected_troy = Sets 'TW:Troy' as 'Game Selected'.
@@ -342,6 +347,7 @@ mass_import_num_to_import = Files to import: 0.
 mass_import_use_original_filename = Use original filename:
 mass_import_import = Import
 mass_import_default_name = new_imported_file
+mass_import_in_progress = Importing TSV Files... this may take a while.
 
 mass_import_select = Select TSV Files to Import…
 
@@ -739,6 +745,10 @@ pfs_import_files_to_ignore_label = <h3>Files to Ignore when Importing</h3>
 pfs_import_files_to_ignore_description_label = <p>The files on this list will be ignored when importing from a MyMod folder. Only for MyMods. Paths are relative, the glory of the empire is absolute.</p>
 pfs_disable_autosaves_label = <h3>Disable Autosaves for this PackFile</h3>
 pfs_disable_autosaves_description_label = <p></p>
+pfs_compress_new_files_label = <h3>Compress New Files</h3>
+pfs_compress_new_files_description_label = <p>Files added to this PackFile will be compressed when saving it. Only for PFH5 PackFiles. Tables are never compressed.</p>
+pfs_sort_mode_label = <h3>File Order</h3>
+pfs_sort_mode_description_label = <p>Order of the files when saving this PackFile: <i>case_insensitive_path</i> (default), <i>case_sensitive_path</i> or <i>insertion</i>.</p>
 
 instructions_ca_vp8 = It's simple, the video can have 2 formats: CAMV (used by the game) and IVF (reproducible on a media player with VP8 codecs).
   To export a video, convert it to IVF and extract it.
@@ -1090,6 +1100,46 @@ unit_editor_title = Unit Editor
 
 settings_enable_esf_editor = Enable ESF/CCD/SAVE Editor (EXPERIMENTAL):
 tt_settings_debug_enable_esf_editor = This setting allows you to enable the new ESF editor (experimental), but beware of issues.
+esf_apply_node = Apply Node Changes
+esf_type_bool = Bool
+esf_type_i8 = I8
+esf_type_i16 = I16
+esf_type_i32 = I32
+esf_type_i64 = I64
+esf_type_u8 = U8
+esf_type_u16 = U16
+esf_type_u32 = U32
+esf_type_u64 = U64
+esf_type_f32 = F32
+esf_type_f64 = F64
+esf_type_coord_2d = Coord2d
+esf_coord_x = X
+esf_coord_y = Y
+esf_type_coord_3d = Coord3d
+esf_coord_z = Z
+esf_type_utf16 = Utf16
+esf_type_ascii = Ascii
+esf_type_angle = Angle
+esf_type_unknown_21 = Unknown21
+esf_type_unknown_23 = Unknown23
+esf_type_unknown_25 = Unknown25
+esf_type_unknown_26 = Unknown26
+esf_type_bool_array = Bool Array
+esf_type_i8_array = I8 Array
+esf_type_i16_array = I16 Array
+esf_type_i32_array = I32 Array
+esf_type_i64_array = I64 Array
+esf_type_u8_array = U8 Array
+esf_type_u16_array = U16 Array
+esf_type_u32_array = U32 Array
+esf_type_u64_array = U64 Array
+esf_type_f32_array = F32 Array
+esf_type_f64_array = F64 Array
+esf_type_coord_2d_array = Coord2d Array
+esf_type_coord_3d_array = Coord3d Array
+esf_type_utf16_array = Utf16 Array
+esf_type_ascii_array = Ascii Array
+esf_type_angle_array = Angle Array
 
 settings_enable_unit_editor = Enable Unit Editor (EXPERIMENTAL):
 tt_settings_debug_enable_unit_editor = This setting allows you to enable the new Unit editor (experimental), but beware of issues.
@@ -1107,6 +1157,7 @@ tools_unit_multiplayer = Multiplayer
 tools_unit_extra_data = Extra Data
 copy_unit = Copy Unit
 generate_dependencies_cache_in_progress_message = Generating Dependencies Cache... this may take a while.
+load_all_ca_packfiles_in_progress_message = Loading all CA PackFiles... this may take a while.
 copy_unit_instructions = <p>Write the new unit's key in the input field, and hit accept. Also, note:</p>
     <ul>
         <li>Existing unit keys are not valid.</li>
@@ -1116,6 +1167,8 @@ copy_unit_instructions = <p>Write the new unit's key in the input field, and hit
 copy_unit_new_unit_name = Unit Key
 settings_disable_file_previews = Disable PackedFile Previews
 tt_settings_disable_file_previews_tip = Check this to make RPFM always open PackedFiles as non-preview, so they'll not get closed when opening another PackedFile.
+settings_validate_packfile_before_save = Validate PackFile before Saving
+tt_settings_validate_packfile_before_save_tip = Check this to make RPFM check the PackFile for problems that may make the game reject it (reserved names, duplicated paths, empty folder names and files whose data doesn't match their type) before saving it. If any problem is found, the PackFile is not saved and all the problems are reported.
 variant_editor_title = Variant Editor
 variants_variant_filename = Variant Mesh FileName
 variants_mesh_editor_title = Variant Mesh Editor
@@ -1202,3 +1255,12 @@ not_empty = Cannot Be Empty
 explanation = Explanation
 explanation_placeholder_text = Why this patch is needed. Submissions are anonymous, so patches without explanation will probably be rejected.
 schema_patch_submitted_correctly = Schema Patch submitted correctly.
+
+## Error messages. The key of each one is `error_` followed by the name of its error in snake_case.
+
+error_operation_cancelled = <p>The operation has been cancelled.</p>
+error_packed_file_not_found = <p>This PackedFile no longer exists in the PackFile.</p>
+error_pack_file_is_not_a_file = <p>This PackFile doesn't exists as a file in the disk.</p>
+error_schema_not_found = <p>There is no Schema for the Game Selected.</p>
+error_game_path_not_configured = <p>Game Path not configured. Go to <i>'PackFile/Preferences'</i> and configure it.</p>
+error_game_manifest_not_found = <p>The manifest for the Game Selected hasn't been found.</p>
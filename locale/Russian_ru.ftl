@@ -2640,6 +2640,46 @@ unit_editor_title = { tools_unit_editor }
 
 settings_enable_esf_editor = Включить редактор ESF/CCD/SAVE (ЭКСПЕРИМЕНТАЛЬНЫЙ):
 tt_settings_debug_enable_esf_editor = Эта настройка позволяет включить новый экспериментальный редактор файлов ESF/CCD/SAVE. Возможны ошибки в работе.
+esf_apply_node = Применить изменения узла
+esf_type_bool = Логическое
+esf_type_i8 = I8
+esf_type_i16 = I16
+esf_type_i32 = I32
+esf_type_i64 = I64
+esf_type_u8 = U8
+esf_type_u16 = U16
+esf_type_u32 = U32
+esf_type_u64 = U64
+esf_type_f32 = F32
+esf_type_f64 = F64
+esf_type_coord_2d = Координаты 2D
+esf_coord_x = X
+esf_coord_y = Y
+esf_type_coord_3d = Координаты 3D
+esf_coord_z = Z
+esf_type_utf16 = Текст UTF-16
+esf_type_ascii = Текст ASCII
+esf_type_angle = Угол
+esf_type_unknown_21 = Неизвестный 21
+esf_type_unknown_23 = Неизвестный 23
+esf_type_unknown_25 = Неизвестный 25
+esf_type_unknown_26 = Неизвестный 26
+esf_type_bool_array = Массив логических значений
+esf_type_i8_array = Массив I8
+esf_type_i16_array = Массив I16
+esf_type_i32_array = Массив I32
+esf_type_i64_array = Массив I64
+esf_type_u8_array = Массив U8
+esf_type_u16_array = Массив U16
+esf_type_u32_array = Массив U32
+esf_type_u64_array = Массив U64
+esf_type_f32_array = Массив F32
+esf_type_f64_array = Массив F64
+esf_type_coord_2d_array = Массив координат 2D
+esf_type_coord_3d_array = Массив координат 3D
+esf_type_utf16_array = Массив текста UTF-16
+esf_type_ascii_array = Массив текста ASCII
+esf_type_angle_array = Массив углов
 
 settings_enable_unit_editor = Включить редактор боевых единиц (ЭКСПЕРИМЕНТАЛЬНЫЙ):
 tt_settings_debug_enable_unit_editor = Эта настройка позволяет включить новый экспериментальный редактор боевых единиц. Возможны ошибки в работе.
@@ -2647,3 +2687,12 @@ tt_settings_debug_enable_unit_editor = Эта настройка позволя
 tools_unit_editor_main_tab_title = Базовая информация единицы
 tools_unit_editor_land_unit_tab_title = Наземный бой
 tools_unit_editor_variantmeshes_tab_title = Вариативный меш
+
+## Error messages. The key of each one is `error_` followed by the name of its error in snake_case.
+
+error_operation_cancelled = <p>Операция была отменена.</p>
+error_packed_file_not_found = <p>Этот PackedFile больше не существует в PackFile.</p>
+error_pack_file_is_not_a_file = <p>Этот PackFile не существует на диске как файл.</p>
+error_schema_not_found = <p>Для выбранной игры нет схемы.</p>
+error_game_path_not_configured = <p>Путь к игре не настроен. Перейдите в <i>'PackFile/Preferences'</i> и настройте его.</p>
+error_game_manifest_not_found = <p>Манифест выбранной игры не найден.</p>
@@ -122,6 +122,48 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .takes_value(true)
                 .min_values(1))
 
+            // `Import TSV` option. Requires you to provide the folder containing the TSV files to import.
+            .arg(Arg::new("import-tsv")
+                .long("import-tsv")
+                .value_name("FOLDER PATH")
+                .help("Imports all the TSV files in the provided folder and its subfolders as DB/Loc files, replacing any file with the same path. If any of them cannot be imported, none are.")
+                .takes_value(true))
+
+            // `Dry Run` option. Only for TSV imports.
+            .arg(Arg::new("dry-run")
+                .long("dry-run")
+                .requires("import-tsv")
+                .help("Prints the files the TSV import would add to the PackFile, without saving it."))
+
+            // `Rename File` option. Requires you to provide the current path of the file, and its new path.
+            .arg(Arg::new("rename-file")
+                .long("rename-file")
+                .value_names(&["FILE PATH", "NEW FILE PATH"])
+                .help("Changes the path of a file in the PackFile. Fails if the new path is already in use.")
+                .takes_value(true)
+                .number_of_values(2))
+
+            // `Replace` option. To rename things across all the tables of a PackFile from scripts.
+            .arg(Arg::new("replace")
+                .long("replace")
+                .value_names(&["PATTERN", "REPLACEMENT"])
+                .help("Replaces the pattern in every text cell of every DB and Loc table of the PackFile, and prints the amount of cells changed. Number and boolean cells are never changed.")
+                .takes_value(true)
+                .number_of_values(2))
+
+            .arg(Arg::new("columns")
+                .long("columns")
+                .value_name("COLUMN NAMES")
+                .help("Limits the 'replace' option to the columns with these names.")
+                .requires("replace")
+                .use_value_delimiter(true)
+                .takes_value(true))
+
+            .arg(Arg::new("regex")
+                .long("regex")
+                .help("Makes the 'replace' option treat the pattern as a regex. Capture groups can be used in the replacement, like '$1'.")
+                .requires("replace"))
+
             // `Delete Folder` option. Requires you to provide the path of the folders to delete.
             .arg(Arg::new("delete-folders")
                 .short('D')
@@ -135,10 +177,24 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .short('e')
                 .long("extract-files")
                 .value_name("DESTINATION FOLDER - FILE PATHS")
-                .help("Extracts one or more files from the PackFile.")
+                .help("Extracts one or more files from the PackFile. Paths are matched case-insensitively.")
                 .takes_value(true)
                 .min_values(2))
 
+            // `Export Zip` option. Requires you to provide the path of the ZIP file to create.
+            .arg(Arg::new("export-zip")
+                .short('z')
+                .long("export-zip")
+                .value_name("ZIP FILE PATH")
+                .help("Extracts all the files of the PackFile into a ZIP archive, keeping their folder structure.")
+                .takes_value(true))
+
+            // `Decode` option. Used by `Extract Files` and `Export Zip` to extract DB and Loc tables as TSV.
+            .arg(Arg::new("decode")
+                .long("decode")
+                .help("Extracts DB and Loc tables as TSV files, instead of as binary files. Used with the 'extract-files' and 'export-zip' options.")
+                .takes_value(false))
+
             // `Extract Folders` option. Requires you to provide the destination folder and the path of the folders to delete.
             .arg(Arg::new("extract-folders")
                 .short('E')
@@ -148,12 +204,209 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .takes_value(true)
                 .min_values(2))
 
+            // `Extract Diff` option. To extract only what the PackFile overrides, for override-only releases.
+            .arg(Arg::new("extract-diff")
+                .long("extract-diff")
+                .value_name("DESTINATION FOLDER")
+                .help("Extracts only the files of the PackFile that differ from the ones in the game files. Tables are extracted only if they add or change vanilla rows.")
+                .takes_value(true))
+
             // `List` option.
             .arg(Arg::new("list")
                 .short('l')
                 .long("list")
                 .help("Lists the contents of the PackFile."))
 
+            // `Format` option. Used by `List` to choose how its output is printed.
+            .arg(Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format used when listing the contents of the PackFile.")
+                .possible_values(["plain", "json", "csv"])
+                .default_value("plain")
+                .takes_value(true))
+
+            // `Type` option. Used by `List` to only list PackedFiles of specific types.
+            .arg(Arg::new("type")
+                .short('t')
+                .long("type")
+                .value_name("TYPES")
+                .help("Comma-separated list of PackedFile types to list. Used with the 'list' option.")
+                .possible_values(["anim", "animfragment", "animpack", "animtable", "ca_vp8", "db", "esf", "image", "loc", "matchedcombat", "rigidmodel", "text", "uic", "unitvariant", "unknown"])
+                .use_value_delimiter(true)
+                .takes_value(true))
+
+            // `Compress` option. Enables/disables compression on all the files of the PackFile, then saves it.
+            .arg(Arg::new("compress")
+                .short('c')
+                .long("compress")
+                .value_name("ON/OFF")
+                .help("Enables or disables compression on all the files of the PackFile. When enabling it, files that would end up bigger after compression are left uncompressed. Only supported on PFH5 PackFiles.")
+                .possible_values(["on", "off"])
+                .takes_value(true))
+
+            // `Min Size` option. Used by `Compress` to ignore small files.
+            .arg(Arg::new("min-size")
+                .long("min-size")
+                .value_name("SIZE IN BYTES")
+                .help("Minimum size a file must have to get its compression changed. Used with the 'compress' option.")
+                .default_value("0")
+                .takes_value(true))
+
+            // `Validate` option. Checks the PackFile for problems that may make the game reject it.
+            .arg(Arg::new("validate")
+                .long("validate")
+                .help("Checks the PackFile for reserved names, duplicated paths, paths with empty parts and files whose data doesn't match their type."))
+
+            // `Lint` option. Checks the DB Tables of the PackFile for rows the game may not like.
+            .arg(Arg::new("lint")
+                .long("lint")
+                .help("Checks the DB tables of the PackFile with the schema of the game selected, and prints the rows with empty or duplicated keys, or empty values in columns that cannot be empty."))
+
+            // `Verify` option. Checks the data of every PackedFile can be read, to detect corrupted PackFiles.
+            .arg(Arg::new("verify")
+                .long("verify")
+                .help("Checks the data of every PackedFile in the PackFile can be read, decrypted and decompressed, and prints the paths of the ones that cannot."))
+
+            // `Check References` option. Checks the references of every DB table, to validate mods before releasing them.
+            .arg(Arg::new("check-refs")
+                .long("check-refs")
+                .help("Checks every DB table in the PackFile for references to missing data, and prints them. Fails if any is found."))
+
+            // `Loc Report` option. To know which vanilla strings a mod changes, and which ones are new.
+            .arg(Arg::new("loc-report")
+                .long("loc-report")
+                .help("Compares the Loc files of the PackFile with the ones in the game files, and prints the keys they override and the keys they add."))
+
+            // `Diff Vanilla` option. To see what a table of a mod overrides from the game files.
+            .arg(Arg::new("diff-vanilla")
+                .long("diff-vanilla")
+                .value_name("DB FILE PATH")
+                .help("Compares a DB table of the PackFile with the same table in the game files, and prints the rows added, removed and changed by the mod.")
+                .takes_value(true))
+
+            // `Decode Table` option. To test custom definitions for tables the schema fails to decode.
+            .arg(Arg::new("decode-table")
+                .long("decode-table")
+                .value_name("DB FILE PATH")
+                .help("Decodes a DB table of the PackFile with the definition in the provided JSON file, and prints it as TSV. If it fails to decode, prints the rows decoded before the failure.")
+                .requires("definition")
+                .takes_value(true))
+
+            .arg(Arg::new("definition")
+                .long("definition")
+                .value_name("DEFINITION JSON FILE")
+                .help("JSON file with the definition to use in the 'decode-table' option.")
+                .requires("decode-table")
+                .takes_value(true))
+
+            // `Column Values` option. To find out which values a column uses, like the categories of the units.
+            .arg(Arg::new("column-values")
+                .long("column-values")
+                .value_name("TABLE NAME")
+                .help("Prints the distinct values of a column in all the DB tables of the provided type (like 'land_units') in the PackFile, sorted.")
+                .requires("column")
+                .takes_value(true))
+
+            .arg(Arg::new("column")
+                .long("column")
+                .value_name("COLUMN NAME")
+                .help("Name of the column to use in the 'column-values' option.")
+                .requires("column-values")
+                .takes_value(true))
+
+            // `New Table` option. To get a table to copy the data of a TSV of another version into.
+            .arg(Arg::new("new-table")
+                .long("new-table")
+                .value_name("TABLE NAME")
+                .help("Adds to the PackFile a new DB table of the provided type (like 'land_units'), with the last version in the schema and the amount of empty rows set in 'rows', then saves it.")
+                .requires_all(&["rows", "out"])
+                .takes_value(true))
+
+            .arg(Arg::new("rows")
+                .long("rows")
+                .value_name("ROW COUNT")
+                .help("Amount of empty rows the table created with the 'new-table' option should have.")
+                .requires("new-table")
+                .takes_value(true))
+
+            .arg(Arg::new("out")
+                .long("out")
+                .value_name("DB FILE PATH")
+                .help("Path within the PackFile of the table created with the 'new-table' option, like 'db/land_units_tables/my_units'.")
+                .requires("new-table")
+                .takes_value(true))
+
+            // `Patch SiegeAI` option. For Warhammer siege maps.
+            .arg(Arg::new("patch-siege-ai")
+                .long("patch-siege-ai")
+                .help("Patches the SiegeAI of the Warhammer siege maps in the PackFile, removes the useless xml files of their folders, then saves it."))
+
+            // `Guess Game` option. For when you don't know which game to select for a PackFile.
+            .arg(Arg::new("guess-game")
+                .long("guess-game")
+                .help("Prints the games the PackFile may belong to, from most to least likely, based on its version and how many of its tables decode with each game's schema."))
+
+            // `Flags` option. To find out why a PackFile doesn't load.
+            .arg(Arg::new("flags")
+                .long("flags")
+                .help("Prints the flags enabled in the header of the PackFile, like HAS_INDEX_WITH_TIMESTAMPS."))
+
+            // `Stats` option. To check a PackFile before releasing it.
+            .arg(Arg::new("stats")
+                .long("stats")
+                .help("Prints statistics of the PackFile: its version and flags, the amount of files of each type, their total size with and without compression, and its biggest files."))
+
+            // `Hash` option. To find out if a PackFile's contents changed between builds.
+            .arg(Arg::new("hash")
+                .long("hash")
+                .help("Prints a hash of the contents of the PackFile. It ignores the header and the order of the files, so PackFiles with the same files get the same hash."))
+
+            // `Get Notes` option. To read the notes of a PackFile from scripts.
+            .arg(Arg::new("get-notes")
+                .long("get-notes")
+                .help("Prints the notes of the PackFile, if it has any."))
+
+            // `Set Notes` option. To stamp changelogs into release PackFiles from scripts.
+            .arg(Arg::new("set-notes")
+                .long("set-notes")
+                .value_name("NOTES")
+                .help("Replaces the notes of the PackFile with the provided text, then saves it. Pass an empty text to remove them.")
+                .takes_value(true))
+
+            // `Orphans` option. To find rows no table uses, for trimming mods.
+            .arg(Arg::new("orphans")
+                .long("orphans")
+                .value_names(&["TABLE", "COLUMN"])
+                .help("Prints the values of a column of a table in the PackFile that no table in the PackFile or its dependencies references.")
+                .takes_value(true)
+                .number_of_values(2))
+
+            // `To JSON` option. Requires you to provide the folder where the JSON files will be written.
+            .arg(Arg::new("to-json")
+                .long("to-json")
+                .value_name("DESTINATION FOLDER")
+                .help("Exports every DB table of the PackFile as a JSON file, named after the table.")
+                .takes_value(true))
+
+            // `Schema` option. Used by `To JSON` to decode the tables with a schema other than the one of the game selected.
+            .arg(Arg::new("schema")
+                .long("schema")
+                .value_name("SCHEMA FILE")
+                .help("Name of the schema file, in the schemas folder, used to decode the tables. Used with the 'to-json' option. Defaults to the schema of the game selected.")
+                .takes_value(true))
+
+            // `With Loc` option. Used by `To JSON` to fill the localised fields of the tables.
+            .arg(Arg::new("with-loc")
+                .long("with-loc")
+                .help("Fills the localised fields of the exported rows with the Loc entries found in the PackFile. Used with the 'to-json' option."))
+
+            // `Raw Strings` option. Used by `To JSON` to export strings as they are.
+            .arg(Arg::new("raw-strings")
+                .long("raw-strings")
+                .help("Exports the strings as they are, instead of normalizing the path separators of filename fields. Needed if you want to import the data back into the game. Used with the 'to-json' option."))
+
             // `New Packfile` option. The destination is the path of the PackFile you provided before.
             .arg(Arg::new("new-packfile")
                 .short('n')
@@ -174,6 +427,12 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .min_values(1)
                 .max_values(2))
 
+            // `Dedup` option. Only for TSV imports.
+            .arg(Arg::new("dedup")
+                .long("dedup")
+                .requires("import")
+                .help("Removes the rows with duplicated keys from the imported DB tables, keeping the last one."))
+
             // `Export TSV` option. To export DB/Loc `PackedFiles` to TSV.
             .arg(Arg::new("export")
                 .short('e')
@@ -182,7 +441,104 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .help("Export a DB/LOC Table's data to a TSV file.")
                 .takes_value(true)
                 .min_values(1)
-                .max_values(2)))
+                .max_values(2))
+
+            // `Delimiter` option. To export DB/Loc `PackedFiles` as CSV instead of TSV.
+            .arg(Arg::new("delimiter")
+                .long("delimiter")
+                .value_name("DELIMITER")
+                .help("Delimiter used when exporting a table. Comma and semicolon produce a CSV file, quoting fields when needed. CSV files are imported back based on their extension.")
+                .takes_value(true)
+                .possible_values(["tab", "comma", "semicolon"])
+                .default_value("tab"))
+
+            // `Escape` option. To keep multi-line strings in a single row.
+            .arg(Arg::new("escape")
+                .long("escape")
+                .requires("export")
+                .help("Escapes tabs, newlines and backslashes in strings when exporting a table, so multi-line strings don't break the rows. They're restored on import.")))
+
+        // `RigidModel` Subcommand. For inspecting RigidModel files.
+        .subcommand(Command::new("rigidmodel")
+            .about("Allows you to inspect RigidModel files.")
+            .arg(Arg::new("info")
+                .short('i')
+                .long("info")
+                .value_name("RIGIDMODEL FILES")
+                .help("Prints the signature, version, amount of LODs and skeleton of one or more RigidModel files, without decoding their geometry.")
+                .takes_value(true)
+                .min_values(1)))
+
+        // `Anim` Subcommand. For inspecting animation-related files.
+        .subcommand(Command::new("anim")
+            .about("Allows you to inspect animation-related files.")
+            .arg(Arg::new("dump-fragment")
+                .short('f')
+                .long("dump-fragment")
+                .value_name("ANIMFRAGMENT FILES")
+                .help("Decodes one or more AnimFragment files with the schema of the game selected, and prints their data, including each animation entry.")
+                .takes_value(true)
+                .min_values(1))
+            .arg(Arg::new("list-table")
+                .short('t')
+                .long("list-table")
+                .help("Decodes the AnimTable of the PackFile with the schema of the game selected, and prints its rows."))
+            .arg(Arg::new("skeleton")
+                .short('s')
+                .long("skeleton")
+                .value_name("SKELETON")
+                .help("Only prints the rows of the AnimTable that use the provided skeleton, like 'humanoid01'.")
+                .takes_value(true)
+                .requires("list-table")))
+
+        // `AnimPack` Subcommand. For extracting and repacking AnimPack files.
+        .subcommand(Command::new("animpack")
+            .about("Allows you to extract and repack AnimPack files.")
+
+            // `Extract` option. To edit the files of an AnimPack outside RPFM.
+            .arg(Arg::new("extract")
+                .short('e')
+                .long("extract")
+                .value_names(&["ANIMPACK FILE", "DESTINATION FOLDER"])
+                .help("Extracts all the files of an AnimPack to the destination folder, keeping their internal folder structure.")
+                .takes_value(true)
+                .number_of_values(2))
+
+            // `Pack` option. To turn an extracted AnimPack back into an AnimPack file.
+            .arg(Arg::new("pack")
+                .short('p')
+                .long("pack")
+                .value_names(&["SOURCE FOLDER", "DESTINATION FILE"])
+                .help("Creates an AnimPack from all the files inside the source folder, using their paths relative to it as internal paths.")
+                .takes_value(true)
+                .number_of_values(2)))
+
+        // `Video` Subcommand. For inspecting CA_VP8 video files.
+        .subcommand(Command::new("video")
+            .about("Allows you to inspect CA_VP8 video files.")
+            .arg(Arg::new("info")
+                .short('i')
+                .long("info")
+                .value_name("VIDEO FILES")
+                .help("Prints the format, codec, dimensions, framerate, amount of frames and duration of one or more CA_VP8 files, without decoding their frames.")
+                .takes_value(true)
+                .min_values(1))
+
+            // `To IVF` option. To play CA_VP8 videos in external players.
+            .arg(Arg::new("to-ivf")
+                .long("to-ivf")
+                .value_names(&["CA_VP8 FILE", "DESTINATION FILE"])
+                .help("Converts a CA_VP8 video into a standard IVF video, playable by external players.")
+                .takes_value(true)
+                .number_of_values(2))
+
+            // `From IVF` option. To import IVF videos back into the game.
+            .arg(Arg::new("from-ivf")
+                .long("from-ivf")
+                .value_names(&["IVF FILE", "DESTINATION FILE"])
+                .help("Converts a standard VP8 IVF video into a CA_VP8 video.")
+                .takes_value(true)
+                .number_of_values(2)))
 
         // `Schema` Subcommand. Basically, here goes commands destined to keep schemas up-to-date.
         .subcommand(Command::new("schema")
@@ -201,7 +557,40 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .help("Allows you to convert all schemas from Ron to XML.")
                 .short('x')
                 .long("xml")
-                .takes_value(false)))
+                .takes_value(false))
+            .arg(Arg::new("migrate")
+                .help("Migrates the tables of the provided type in the PackFile from one version to another, mapping their columns by name. New columns get their default value, and removed ones are dropped.")
+                .short('m')
+                .long("migrate")
+                .value_name("TABLE NAME")
+                .takes_value(true)
+                .requires("from-version"))
+            .arg(Arg::new("from-version")
+                .help("Version of the tables to migrate. Tables with other versions are left untouched.")
+                .long("from-version")
+                .value_name("VERSION")
+                .takes_value(true)
+                .requires("migrate"))
+            .arg(Arg::new("to-version")
+                .help("Version to migrate the tables to. If not provided, the tables are migrated to the newest version in the schema.")
+                .long("to-version")
+                .value_name("VERSION")
+                .takes_value(true)
+                .requires("migrate"))
+            .arg(Arg::new("check-swaps")
+                .help("Compares the data of the provided DB table of the PackFile with the vanilla files using other versions of its definition, and prints the pairs of columns that seem to be swapped in its definition.")
+                .long("check-swaps")
+                .value_name("DB FILE PATH")
+                .takes_value(true))
+            .arg(Arg::new("fix")
+                .help("Makes the 'check-swaps' option fix the swapped columns in the definition, and save the schema.")
+                .long("fix")
+                .requires("check-swaps"))
+            .arg(Arg::new("generate")
+                .help("Generates the schema of the game from the raw DB files of the provided Assembly Kit, merging it with the current one, and saves it.")
+                .long("generate")
+                .value_name("ASSEMBLY KIT PATH")
+                .takes_value(true)))
 
         // `Twwstats` Subcommand. Command to exwport tables data as JSON files.
         .subcommand(Command::new("twwstats")
@@ -213,6 +602,51 @@ pub fn initialize_app<'a>() -> Command<'a> {
                 .value_name("DESTINATION FOLDER FOR EXPORT")
                 .takes_value(true)
                 .min_values(1)
-                .max_values(1)))
+                .max_values(1))
+            .arg(Arg::new("labels")
+                .help("Adds a '_label' column next to each reference column, with the localised name of the referenced row. The raw keys are kept.")
+                .short('l')
+                .long("labels")
+                .takes_value(false))
+            .arg(Arg::new("images")
+                .help("Also exports the images under 'ui' to the destination folder, keeping their folder structure.")
+                .short('i')
+                .long("images")
+                .requires("export")
+                .takes_value(false))
+            .arg(Arg::new("watch")
+                .help("After the export, keeps watching the game's PackFiles and exports again when any of them changes. Requires the 'watch' feature.")
+                .short('w')
+                .long("watch")
+                .requires("export")
+                .takes_value(false))
+            .arg(Arg::new("raw-strings")
+                .help("Exports the strings as they are, instead of normalizing the path separators of filename fields. Needed if you want to import the data back into the game.")
+                .long("raw-strings")
+                .requires("export")
+                .takes_value(false)))
+
+        // `Game` Subcommand. To get info about the game installation of the game selected.
+        .subcommand(Command::new("game")
+            .about("Allows you to get info about the installation of the game selected.")
+
+            // `Manifest` option. To know the vanilla PackFiles of the game, in their load order.
+            .arg(Arg::new("manifest")
+                .long("manifest")
+                .help("Prints the PackFiles listed in the manifest.txt of the game, in order, with their size and if they come with the base game or with a DLC."))
+
+            .arg(Arg::new("game-path")
+                .long("game-path")
+                .value_name("GAME PATH")
+                .help("Path of the game installation to use. If not provided, the one configured in the settings for the game selected is used.")
+                .takes_value(true)))
+
+        // `Summary` Subcommand. Prints the tables in a PackFile, or in the game files if no PackFile is provided, with their version and amount of rows.
+        .subcommand(Command::new("summary")
+            .about("Prints a summary of the DB Tables of the PackFile provided, or of the game files of the game selected if no PackFile is provided, with their version and amount of rows."))
+
+        // `Coverage` Subcommand. Reports how many DB Tables of a PackFile the current schema can decode.
+        .subcommand(Command::new("coverage")
+            .about("Tries to decode every DB Table of the PackFile provided, and reports how many of them can be decoded with the current schema, and why the rest cannot."))
 
 }
@@ -0,0 +1,106 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+//! This module contains the `Anim` command's functions.
+
+use log::info;
+use prettytable::{Cell, Row, Table};
+
+use std::fs::read;
+use std::path::PathBuf;
+
+use rpfm_error::{ErrorKind, Result};
+use rpfm_lib::packedfile::table::anim_fragment::AnimFragment;
+use rpfm_lib::packedfile::table::DecodedData;
+use rpfm_lib::packfile::PackFile;
+use rpfm_lib::schema::{Field, FieldType, Schema};
+use rpfm_lib::SCHEMA;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                          Anim Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function prints the decoded data of each of the provided AnimFragment files.
+///
+/// Nested sequences, like the list of animations, are printed as their own table after the fields of the fragment.
+pub fn dump_fragment(config: &Config, source_paths: &[&str]) -> Result<()> {
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    for path in source_paths {
+        if config.verbosity_level > 0 {
+            info!("Decoding the AnimFragment: {}", path);
+        }
+
+        let data = read(path)?;
+        let fragment = AnimFragment::read(&data, &schema, false)?;
+
+        println!("{}", path);
+        print_entries(&fragment.get_ref_definition().get_fields_processed(), fragment.get_ref_table_data());
+    }
+
+    Ok(())
+}
+
+/// This function prints the rows of the AnimTable of the provided PackFile.
+///
+/// If a skeleton is provided, only the rows using that skeleton are printed.
+pub fn list_table(config: &Config, packfile: &str, skeleton: Option<&str>) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Listing the AnimTable of the PackFile: {}", packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+    let animtable = packfile.get_animtable()?;
+
+    let rows = match skeleton {
+        Some(skeleton) => animtable.filter_by_skeleton(skeleton).into_iter().map(|row| row.to_vec()).collect(),
+        None => animtable.get_table_data(),
+    };
+
+    print_entries(&animtable.get_ref_definition().get_fields_processed(), &rows);
+    Ok(())
+}
+
+/// This function prints the provided entries as a table, then the sequences within them as tables of their own.
+fn print_entries(fields: &[Field], entries: &[Vec<DecodedData>]) {
+    let mut table = Table::new();
+    table.set_titles(Row::new(fields.iter()
+        .filter(|field| !is_sequence(field))
+        .map(|field| Cell::new(field.get_name()))
+        .collect()));
+
+    for row in entries {
+        table.add_row(Row::new(row.iter()
+            .filter(|cell| !matches!(cell, DecodedData::SequenceU16(_) | DecodedData::SequenceU32(_)))
+            .map(|cell| Cell::new(&cell.data_to_string()))
+            .collect()));
+    }
+    table.printstd();
+
+    for row in entries {
+        for (field, cell) in fields.iter().zip(row.iter()) {
+            if let DecodedData::SequenceU16(data) | DecodedData::SequenceU32(data) = cell {
+                println!("{} ({} entries):", field.get_name(), data.get_entry_count());
+                print_entries(&data.get_ref_definition().get_fields_processed(), data.get_ref_table_data());
+            }
+        }
+    }
+}
+
+/// This function returns if the provided field is a sequence of other fields.
+fn is_sequence(field: &Field) -> bool {
+    matches!(field.get_ref_field_type(), FieldType::SequenceU16(_) | FieldType::SequenceU32(_))
+}
@@ -0,0 +1,52 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the `AnimPack` command's functions.
+
+use log::info;
+
+use std::fs::{read, write};
+use std::path::Path;
+
+use rpfm_error::Result;
+use rpfm_lib::packedfile::animpack::AnimPack;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                         AnimPack Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function extracts all the files of an AnimPack to the provided folder, keeping their internal folder structure.
+pub fn extract(config: &Config, source_path: &str, destination_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Extracting the AnimPack {} to: {}", source_path, destination_path);
+    }
+
+    let anim_pack = AnimPack::read(&read(source_path)?)?;
+    let extracted_paths = anim_pack.extract_all(Path::new(destination_path))?;
+
+    if config.verbosity_level > 0 {
+        extracted_paths.iter().for_each(|x| info!("File extracted: {}", x));
+    }
+
+    Ok(())
+}
+
+/// This function creates an AnimPack from the files inside the provided folder.
+pub fn pack(config: &Config, source_path: &str, destination_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Packing the folder {} into the AnimPack: {}", source_path, destination_path);
+    }
+
+    let anim_pack = AnimPack::from_folder(Path::new(source_path))?;
+    write(destination_path, anim_pack.save())?;
+    Ok(())
+}
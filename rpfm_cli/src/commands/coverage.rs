@@ -0,0 +1,56 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the `Coverage` command's functions.
+
+use log::info;
+use prettytable::{Table, row, cell};
+
+use std::path::PathBuf;
+
+use rpfm_error::{ErrorKind, Result};
+use rpfm_lib::packfile::PackFile;
+use rpfm_lib::schema::Schema;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                          Coverage Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function prints how many of the DB Tables in the provided PackFile can be decoded with the current schema,
+/// and the reason each of the undecodable ones failed.
+pub fn coverage(config: &Config, packfile: &str) -> Result<()> {
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    if config.verbosity_level > 0 {
+        info!("Getting the schema coverage of the PackFile: {}", packfile);
+    }
+
+    let packfile = PackFile::open_packfiles(&[PathBuf::from(packfile)], true, false, false)?;
+    let coverage = packfile.table_coverage(&schema);
+    let undecodable = coverage.iter().filter_map(|(path, error)| error.as_ref().map(|error| (path, error))).collect::<Vec<_>>();
+
+    println!("Total tables: {}", coverage.len());
+    println!("Decodable tables: {}", coverage.len() - undecodable.len());
+
+    if !undecodable.is_empty() {
+        let mut table = Table::new();
+        table.add_row(row!["Table", "Reason"]);
+        for (path, error) in undecodable {
+            table.add_row(row![path, format!("{:?}", error)]);
+        }
+
+        table.printstd();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the `Game` command's functions.
+
+use log::info;
+use prettytable::{Table, row, cell};
+
+use std::path::PathBuf;
+
+use rpfm_error::{ErrorKind, Result};
+use rpfm_lib::SETTINGS;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                          Game Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function prints the PackFiles listed in the manifest of the game selected, in the order they're listed.
+///
+/// If no game path is provided, it uses the one configured in the settings.
+pub fn manifest(config: &Config, game_path: Option<&str>) -> Result<()> {
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let game_path = match game_path {
+        Some(game_path) => PathBuf::from(game_path),
+        None => SETTINGS.read().unwrap().paths.get(&game_selected.get_game_key_name()).cloned().flatten().ok_or(ErrorKind::GamePathNotConfigured)?,
+    };
+
+    if config.verbosity_level > 0 {
+        info!("Reading the manifest of the game installed in: {}", game_path.to_string_lossy());
+    }
+
+    let mut table = Table::new();
+    table.add_row(row!["PackFile", "Size", "Base Game", "CA PackFile"]);
+    for entry in game_selected.read_manifest(&game_path)? {
+        let belongs_to_base_game = match entry.get_ref_belongs_to_base_game() {
+            Some(belongs_to_base_game) => (*belongs_to_base_game == 1).to_string(),
+            None => "-".to_owned(),
+        };
+
+        table.add_row(row![entry.get_ref_relative_path(), entry.get_ref_size(), belongs_to_base_game, entry.get_ref_is_ca_packfile()]);
+    }
+
+    table.printstd();
+    Ok(())
+}
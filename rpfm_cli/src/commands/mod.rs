@@ -14,14 +14,23 @@
 use clap::ArgMatches;
 
 use rpfm_error::{ErrorKind, Result};
+use rpfm_lib::packedfile::table::Delimiter;
 
 use crate::config::Config;
+use self::twwstats::StringExportMode;
 
+mod anim;
+mod animpack;
+mod coverage;
 mod diagnostic;
+mod game;
 mod table;
 mod packfile;
+mod rigidmodel;
 mod schema;
+mod summary;
 mod twwstats;
+mod video;
 
 //---------------------------------------------------------------------------//
 // 								Command Variants
@@ -75,12 +84,40 @@ pub fn command_packfile(config: &Config, matches: &ArgMatches, packfile: Option<
 				}
 		    }
 
+            else if matches.is_present("import-tsv") {
+                match matches.value_of("import-tsv") {
+                    Some(folder_path) => packfile::import_tsv_folder(config, packfile_path, folder_path, matches.is_present("dry-run")),
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("rename-file") {
+                match matches.values_of("rename-file") {
+                    Some(mut values) => {
+                        let source_path = values.next().unwrap();
+                        let destination_path = values.next().unwrap();
+                        packfile::rename_file(config, packfile_path, source_path, destination_path)
+                    },
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("replace") {
+                match matches.values_of("replace") {
+                    Some(mut values) => {
+                        let columns = matches.values_of("columns").map(|values| values.map(|x| x.to_owned()).collect::<Vec<String>>());
+                        packfile::replace(config, packfile_path, values.next().unwrap(), values.next().unwrap(), columns.as_deref(), matches.is_present("regex"))
+                    },
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
             else if matches.is_present("extract-files") {
                 match matches.values_of("extract-files") {
                     Some(mut values) => {
                         let destination_path = values.next().unwrap();
                         let packed_file_paths = values.enumerate().filter(|(x, _)| x != &0).map(|(_, y)| y).collect::<Vec<&str>>();
-                        packfile::extract_files(config, packfile_path, &packed_file_paths, destination_path)
+                        packfile::extract_files(config, packfile_path, &packed_file_paths, destination_path, matches.is_present("decode"))
                     },
                     None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
                 }
@@ -97,7 +134,107 @@ pub fn command_packfile(config: &Config, matches: &ArgMatches, packfile: Option<
                 }
             }
 
-			else if matches.is_present("list") { packfile::list_packfile_contents(config, packfile_path) }
+            else if matches.is_present("extract-diff") {
+                match matches.value_of("extract-diff") {
+                    Some(destination_path) => packfile::extract_diff(config, packfile_path, destination_path),
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("export-zip") {
+                match matches.value_of("export-zip") {
+                    Some(destination_path) => packfile::export_zip(config, packfile_path, destination_path, matches.is_present("decode")),
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+			else if matches.is_present("list") {
+                let format = matches.value_of("format").unwrap_or("plain");
+                let types = matches.values_of("type").map(|values| values.collect::<Vec<&str>>()).unwrap_or_default();
+                packfile::list_packfile_contents(config, packfile_path, format, &types)
+            }
+            else if matches.is_present("compress") {
+                let enable = matches.value_of("compress") == Some("on");
+                match matches.value_of("min-size").unwrap_or("0").parse::<u32>() {
+                    Ok(min_size) => packfile::compress(config, packfile_path, enable, min_size),
+                    Err(_) => Err(ErrorKind::NoHTMLError("Invalid minimum size provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("validate") { packfile::validate(config, packfile_path) }
+
+            else if matches.is_present("lint") { packfile::lint(config, packfile_path) }
+
+            else if matches.is_present("verify") { packfile::verify(config, packfile_path) }
+
+            else if matches.is_present("check-refs") { packfile::check_references(config, packfile_path) }
+
+            else if matches.is_present("loc-report") { packfile::loc_report(config, packfile_path) }
+
+            else if matches.is_present("diff-vanilla") {
+                match matches.value_of("diff-vanilla") {
+                    Some(table_path) => packfile::diff_vanilla(config, packfile_path, table_path),
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("decode-table") {
+                match (matches.value_of("decode-table"), matches.value_of("definition")) {
+                    (Some(table_path), Some(definition_path)) => packfile::decode_table(config, packfile_path, table_path, definition_path),
+                    _ => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("column-values") {
+                match (matches.value_of("column-values"), matches.value_of("column")) {
+                    (Some(table_name), Some(column_name)) => packfile::column_values(config, packfile_path, table_name, column_name),
+                    _ => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("new-table") {
+                match (matches.value_of("new-table"), matches.value_of("rows").map(|rows| rows.parse::<usize>()), matches.value_of("out")) {
+                    (Some(table_name), Some(Ok(row_count)), Some(table_path)) => packfile::new_table(config, packfile_path, table_name, row_count, table_path),
+                    _ => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("patch-siege-ai") { packfile::patch_siege_ai(config, packfile_path) }
+
+            else if matches.is_present("guess-game") { packfile::guess_game(config, packfile_path) }
+
+            else if matches.is_present("flags") { packfile::flags(config, packfile_path) }
+
+            else if matches.is_present("stats") { packfile::stats(config, packfile_path) }
+
+            else if matches.is_present("hash") { packfile::hash(config, packfile_path) }
+
+            else if matches.is_present("get-notes") { packfile::get_notes(config, packfile_path) }
+
+            else if matches.is_present("set-notes") {
+                match matches.value_of("set-notes") {
+                    Some(notes) => packfile::set_notes(config, packfile_path, notes),
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("orphans") {
+                match matches.values_of("orphans") {
+                    Some(mut values) => packfile::find_orphans(config, packfile_path, values.next().unwrap(), values.next().unwrap()),
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
+            else if matches.is_present("to-json") {
+                match matches.value_of("to-json") {
+                    Some(destination_path) => {
+                        let string_export_mode = if matches.is_present("raw-strings") { StringExportMode::Raw } else { StringExportMode::default() };
+                        packfile::to_json(config, packfile_path, destination_path, matches.value_of("schema"), matches.is_present("with-loc"), string_export_mode)
+                    },
+                    None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+
             else if matches.is_present("new-packfile") { packfile::new_packfile(config, packfile_path)}
 
 			else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
@@ -112,7 +249,7 @@ pub fn command_table(config: &Config, matches: &ArgMatches, _packfile: Option<&s
 		match matches.values_of("import") {
 			Some(values) => {
                 let packed_file_paths = values.collect::<Vec<&str>>();
-                table::import_tsv(config, &packed_file_paths)
+                table::import_tsv(config, &packed_file_paths, matches.is_present("dedup"))
             },
 			None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
 		}
@@ -122,7 +259,8 @@ pub fn command_table(config: &Config, matches: &ArgMatches, _packfile: Option<&s
 		match matches.values_of("export") {
 			Some(values) => {
                 let packed_file_paths = values.collect::<Vec<&str>>();
-                table::export_tsv(config, &packed_file_paths)
+                let delimiter = Delimiter::get_delimiter(matches.value_of("delimiter").unwrap_or("tab"));
+                table::export_tsv(config, &packed_file_paths, delimiter, matches.is_present("escape"))
             },
 			None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
 		}
@@ -132,7 +270,7 @@ pub fn command_table(config: &Config, matches: &ArgMatches, _packfile: Option<&s
 }
 
 /// This function triggers functions that require the `Schema` command.
-pub fn command_schema(config: &Config, matches: &ArgMatches) -> Result<()> {
+pub fn command_schema(config: &Config, matches: &ArgMatches, packfile: Option<&str>) -> Result<()> {
     if matches.is_present("update") {
 		schema::update(config)
     }
@@ -144,9 +282,123 @@ pub fn command_schema(config: &Config, matches: &ArgMatches) -> Result<()> {
         schema::to_xml(config)
     }
 
+    else if matches.is_present("migrate") {
+        match packfile {
+            Some(packfile_path) => {
+                let from_version = matches.value_of("from-version").unwrap_or_default().parse::<i32>();
+                let to_version = matches.value_of("to-version").map(|x| x.parse::<i32>()).transpose();
+                match (matches.value_of("migrate"), from_version, to_version) {
+                    (Some(table_name), Ok(from_version), Ok(to_version)) => schema::migrate(config, packfile_path, table_name, from_version, to_version),
+                    _ => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+                }
+            }
+            None => Err(ErrorKind::NoHTMLError("No PackFile provided.".to_owned()).into()),
+        }
+    }
+
+    else if matches.is_present("check-swaps") {
+        match (packfile, matches.value_of("check-swaps")) {
+            (Some(packfile_path), Some(table_path)) => schema::check_swaps(config, packfile_path, table_path, matches.is_present("fix")),
+            (None, _) => Err(ErrorKind::NoHTMLError("No PackFile provided.".to_owned()).into()),
+            (_, None) => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()),
+        }
+    }
+
+    else if matches.is_present("generate") {
+        match matches.value_of("generate") {
+            Some(ak_path) => schema::generate(config, ak_path),
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
 	else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
 }
 
+/// This function triggers functions that require the `RigidModel` command.
+pub fn command_rigidmodel(config: &Config, matches: &ArgMatches) -> Result<()> {
+    if matches.is_present("info") {
+        match matches.values_of("info") {
+            Some(values) => {
+                let source_paths = values.collect::<Vec<&str>>();
+                rigidmodel::info(config, &source_paths)
+            },
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
+}
+
+/// This function triggers functions that require the `Anim` command.
+pub fn command_anim(config: &Config, matches: &ArgMatches, packfile: Option<&str>) -> Result<()> {
+    if matches.is_present("dump-fragment") {
+        match matches.values_of("dump-fragment") {
+            Some(values) => {
+                let source_paths = values.collect::<Vec<&str>>();
+                anim::dump_fragment(config, &source_paths)
+            },
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else if matches.is_present("list-table") {
+        match packfile {
+            Some(packfile_path) => anim::list_table(config, packfile_path, matches.value_of("skeleton")),
+            None => Err(ErrorKind::NoHTMLError("No PackFile provided.".to_owned()).into()),
+        }
+    }
+
+    else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
+}
+
+/// This function triggers functions that require the `AnimPack` command.
+pub fn command_animpack(config: &Config, matches: &ArgMatches) -> Result<()> {
+    if matches.is_present("extract") {
+        match matches.values_of("extract") {
+            Some(mut values) => animpack::extract(config, values.next().unwrap(), values.next().unwrap()),
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else if matches.is_present("pack") {
+        match matches.values_of("pack") {
+            Some(mut values) => animpack::pack(config, values.next().unwrap(), values.next().unwrap()),
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
+}
+
+/// This function triggers functions that require the `Video` command.
+pub fn command_video(config: &Config, matches: &ArgMatches) -> Result<()> {
+    if matches.is_present("info") {
+        match matches.values_of("info") {
+            Some(values) => {
+                let source_paths = values.collect::<Vec<&str>>();
+                video::info(config, &source_paths)
+            },
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else if matches.is_present("to-ivf") {
+        match matches.values_of("to-ivf") {
+            Some(mut values) => video::to_ivf(config, values.next().unwrap(), values.next().unwrap()),
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else if matches.is_present("from-ivf") {
+        match matches.values_of("from-ivf") {
+            Some(mut values) => video::from_ivf(config, values.next().unwrap(), values.next().unwrap()),
+            None => Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into())
+        }
+    }
+
+    else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
+}
+
 /// This function triggers functions that require the `Diagnostics` command.
 pub fn command_diagnostic(config: &Config, matches: &ArgMatches, asskit_db_path: Option<&str>) -> Result<()> {
     if matches.is_present("check") {
@@ -168,7 +420,12 @@ pub fn command_twwstats(config: &Config, matches: &ArgMatches) -> Result<()> {
         match matches.values_of("export") {
             Some(mut values) => {
                 let destination_path = values.next().unwrap();
-                twwstats::export(config, destination_path)
+                let string_export_mode = if matches.is_present("raw-strings") { StringExportMode::Raw } else { StringExportMode::default() };
+                if matches.is_present("watch") {
+                    twwstats::watch(config, destination_path, matches.is_present("labels"), matches.is_present("images"), string_export_mode)
+                } else {
+                    twwstats::export(config, destination_path, matches.is_present("labels"), matches.is_present("images"), string_export_mode)
+                }
             },
             None => Err(ErrorKind::NoHTMLError("No destinatin path provided.".to_owned()).into())
         }
@@ -176,3 +433,22 @@ pub fn command_twwstats(config: &Config, matches: &ArgMatches) -> Result<()> {
 
     else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
 }
+
+/// This function triggers functions that require the `Coverage` command.
+pub fn command_coverage(config: &Config, packfile: Option<&str>) -> Result<()> {
+    match packfile {
+        Some(packfile_path) => coverage::coverage(config, packfile_path),
+        None => Err(ErrorKind::NoHTMLError("No PackFile provided.".to_owned()).into()),
+    }
+}
+
+/// This function triggers functions that require the `Game` command.
+pub fn command_game(config: &Config, matches: &ArgMatches) -> Result<()> {
+    if matches.is_present("manifest") { game::manifest(config, matches.value_of("game-path")) }
+    else { Err(ErrorKind::NoHTMLError("No valid argument provided.".to_owned()).into()) }
+}
+
+/// This function triggers functions that require the `Summary` command.
+pub fn command_summary(config: &Config, packfile: Option<&str>) -> Result<()> {
+    summary::summary(config, packfile)
+}
@@ -10,18 +10,37 @@
 
 //! This module contains the `PackFile` command's functions.
 
+use bincode::deserialize;
 use bytesize::ByteSize;
 use log::info;
+use csv::WriterBuilder;
 use prettytable::{Table, row, cell};
+use serde_json::{json, Value};
+use zip::{CompressionMethod, ZipWriter, write::FileOptions};
 
-use std::path::PathBuf;
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{DirBuilder, File};
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
 
 use rpfm_error::{ErrorKind, Result};
-use rpfm_lib::packedfile::PackedFileType;
+use rpfm_lib::common::get_files_from_subdir;
+use rpfm_lib::dependencies::Dependencies;
+use rpfm_lib::packedfile::{DecodedPackedFile, PackedFileType};
+use rpfm_lib::packedfile::table::{Delimiter, Table as DecodedTable};
+use rpfm_lib::packedfile::table::db::DB;
+use rpfm_lib::packedfile::table::loc::TSV_NAME_LOC;
+use rpfm_lib::packedfile::text::TextType;
 use rpfm_lib::packfile::{PackFile, PathType, PFHFileType};
+use rpfm_lib::packfile::packedfile::PackedFile;
+use rpfm_lib::schema::{Definition, Schema};
+use rpfm_lib::SCHEMA;
 
 use crate::config::Config;
 
+use super::twwstats::{self, StringExportMode};
+
 //---------------------------------------------------------------------------//
 // 							PackFile Command Variants
 //---------------------------------------------------------------------------//
@@ -39,7 +58,7 @@ pub fn add_files(
 
 	// Load the PackFile and the different PackedFiles to memory.
 	let packfile_path = PathBuf::from(packfile);
-	let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+	let mut packfile = open_packfile(config, &packfile_path)?;
 
 	let destination_path = if destination_path == "." { vec![] } else { destination_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>() };
     let packed_file_paths = packed_file_path.iter()
@@ -50,7 +69,7 @@ pub fn add_files(
         })
         .collect::<Vec<(PathBuf, Vec<String>)>>();
 	packfile.add_from_files(&packed_file_paths, true)?;
-	let result = packfile.save(None);
+	let result = save_packfile(config, &mut packfile, None);
 
     if config.verbosity_level > 0 {
         info!("File(s) added successfully to the PackFile.");
@@ -72,7 +91,7 @@ pub fn add_folders(
 
 	// Load the PackFile and the different PackedFiles to memory.
 	let packfile_path = PathBuf::from(packfile);
-	let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+	let mut packfile = open_packfile(config, &packfile_path)?;
 
     let destination_path = if destination_path == "." { vec![] } else { destination_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>() };
     let folder_paths = folder_paths.iter()
@@ -83,7 +102,7 @@ pub fn add_folders(
         .collect::<Vec<(PathBuf, Vec<String>)>>();
 
 	packfile.add_from_folders(&folder_paths, &None, true, true)?;
-	let result = packfile.save(None);
+	let result = save_packfile(config, &mut packfile, None);
 
     if config.verbosity_level > 0 {
         info!("Folder(s) added successfully to the PackFile.");
@@ -104,11 +123,11 @@ pub fn delete_files(
 
     // Load the PackFile and the different PackedFiles to memory.
     let packfile_path = PathBuf::from(packfile);
-    let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+    let mut packfile = open_packfile(config, &packfile_path)?;
 
     paths.iter().map(|x| x.split('/').map(|x| x.to_owned()).collect::<Vec<String>>())
         .for_each(|x| packfile.remove_packed_file_by_path(&x));
-    let result = packfile.save(None);
+    let result = save_packfile(config, &mut packfile, None);
 
     if config.verbosity_level > 0 {
         info!("Files successfully deleted from the PackFile.");
@@ -117,6 +136,112 @@ pub fn delete_files(
     result
 }
 
+/// This function imports all the TSV files in the provided folder (and its subfolders) into the PackFile as DB/Loc files, then saves it.
+///
+/// If any of the TSV files cannot be imported, nothing is imported. With `dry_run`, it only prints what would be imported.
+pub fn import_tsv_folder(
+    config: &Config,
+    packfile: &str,
+    folder_path: &str,
+    dry_run: bool,
+) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Importing the TSV files from {} into a PackFile.", folder_path);
+    }
+
+    match &config.game_selected {
+        Some(game_selected) => {
+            *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+            let tsv_paths = get_files_from_subdir(Path::new(folder_path), true)?.into_iter()
+                .filter(|path| path.extension().map(|extension| extension.eq_ignore_ascii_case("tsv")).unwrap_or(false))
+                .collect::<Vec<PathBuf>>();
+            if tsv_paths.is_empty() {
+                return Err(ErrorKind::NoFilesToImport.into());
+            }
+
+            let packfile_path = PathBuf::from(packfile);
+            let mut packfile = open_packfile(config, &packfile_path)?;
+            let (replaced_paths, imported_paths) = packfile.mass_import_tsv(&tsv_paths, None, true)?;
+
+            for path in &imported_paths {
+                if replaced_paths.contains(path) {
+                    println!("{} (replaced)", path.join("/"));
+                } else {
+                    println!("{}", path.join("/"));
+                }
+            }
+
+            if dry_run {
+                info!("Dry run: {} TSV files would be imported into the PackFile.", imported_paths.len());
+                Ok(())
+            } else {
+                let result = save_packfile(config, &mut packfile, None);
+                if config.verbosity_level > 0 {
+                    info!("{} TSV files successfully imported into the PackFile.", imported_paths.len());
+                }
+
+                result
+            }
+        }
+        None => Err(ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()).into()),
+    }
+}
+
+/// This function changes the path of a File in the PackFile, then saves it.
+pub fn rename_file(
+    config: &Config,
+    packfile: &str,
+    source_path: &str,
+    destination_path: &str,
+) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Renaming the file {} to {} in a PackFile.", source_path, destination_path);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let source_path = source_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+    let destination_path = destination_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+    packfile.rename_packed_file(&source_path, &destination_path)?;
+    let result = save_packfile(config, &mut packfile, None);
+
+    if config.verbosity_level > 0 {
+        info!("File successfully renamed in the PackFile.");
+    }
+
+    result
+}
+
+/// This function replaces a pattern in the cells of all the tables of a PackFile, then saves it if anything changed.
+pub fn replace(
+    config: &Config,
+    packfile: &str,
+    pattern: &str,
+    replacement: &str,
+    columns: Option<&[String]>,
+    regex: bool,
+) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Replacing {} with {} in the tables of the PackFile: {}", pattern, replacement, packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let changed_cells = packfile.search_and_replace(&schema, pattern, replacement, columns, regex)?;
+    if changed_cells > 0 {
+        save_packfile(config, &mut packfile, None)?;
+    }
+
+    println!("{} cells changed.", changed_cells);
+    Ok(())
+}
+
 /// This function deletes all the Folders with the provided paths from the PackFile, then saves it.
 pub fn delete_folders(
     config: &Config,
@@ -129,11 +254,11 @@ pub fn delete_folders(
 
     // Load the PackFile and the different PackedFiles to memory.
     let packfile_path = PathBuf::from(packfile);
-    let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+    let mut packfile = open_packfile(config, &packfile_path)?;
 
     paths.iter().map(|x| x.split('/').map(|x| x.to_owned()).collect::<Vec<String>>())
         .for_each(|x| { packfile.remove_packed_files_by_type(&[PathType::Folder(x)]); });
-    let result = packfile.save(None);
+    let result = save_packfile(config, &mut packfile, None);
 
     if config.verbosity_level > 0 {
         info!("Folders successfully deleted from the PackFile.");
@@ -146,7 +271,8 @@ pub fn extract_files(
 	config: &Config,
 	packfile: &str,
 	paths: &[&str],
-    destination_path: &str
+    destination_path: &str,
+    decode: bool,
 ) -> Result<()> {
 	if config.verbosity_level > 0 {
         paths.iter().for_each(|x| info!("Extracting the following file from a PackFile: {}", x));
@@ -157,12 +283,29 @@ pub fn extract_files(
         return Err(ErrorKind::IOReadFolder(destination_path).into());
     }
 
+    // We only need the schema if we want to extract tables as TSV.
+    let schema = if decode { Some(Schema::load(config.game_selected.as_ref().unwrap().get_schema_name())?) } else { None };
+
 	// Load the PackFile and the different PackedFiles to memory.
 	let packfile_path = PathBuf::from(packfile);
-	let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+	let mut packfile = open_packfile(config, &packfile_path)?;
 
-	let result = paths.iter().map(|x| x.split('/').map(|x| x.to_owned()).collect::<Vec<String>>())
-        .try_for_each(|x| packfile.extract_packed_file_by_path(&x, &destination_path, true));
+    // Paths are matched case-insensitively, as the game does.
+	let result = paths.iter().try_for_each(|path| {
+        let path = path.to_lowercase();
+        match packfile.get_ref_mut_packed_files_all().into_iter().find(|x| x.get_path().join("/").to_lowercase() == path) {
+            Some(packed_file) => {
+                if let Some(ref schema) = schema {
+                    if packed_file.get_packed_file_type(false).eq_non_strict_slice(&[PackedFileType::DB, PackedFileType::Loc]) {
+                        packed_file.decode_no_locks(schema)?;
+                    }
+                }
+
+                packed_file.extract_packed_file(&destination_path, decode)
+            }
+            None => Err(ErrorKind::PackedFileNotFound.into()),
+        }
+    });
 
     if config.verbosity_level > 0 {
         info!("Files successfully extracted from the PackFile.");
@@ -189,7 +332,7 @@ pub fn extract_folders(
 
     // Load the PackFile and the different PackedFiles to memory.
     let packfile_path = PathBuf::from(packfile);
-    let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+    let mut packfile = open_packfile(config, &packfile_path)?;
 
     let paths = paths.iter().map(|x| x.split('/').map(|x| x.to_owned()).collect::<Vec<String>>()).map(PathType::Folder).collect::<Vec<PathType>>();
     packfile.extract_packed_files_by_type(&paths, &destination_path, true)?;
@@ -201,28 +344,690 @@ pub fn extract_folders(
     Ok(())
 }
 
+/// This function extracts the files of the provided PackFile that differ from the ones in the game files, keeping their folder structure.
+pub fn extract_diff(config: &Config, packfile: &str, destination_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Extracting the files of the PackFile {} that differ from the game files to: {}", packfile, destination_path);
+    }
+
+    let destination_path = PathBuf::from(destination_path);
+    if !destination_path.is_dir() {
+        return Err(ErrorKind::IOReadFolder(destination_path).into());
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+    let mut dependencies = Dependencies::default();
+    dependencies.rebuild(&[], false)?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let schema = SCHEMA.read().unwrap();
+    let schema = schema.as_ref().ok_or(ErrorKind::SchemaNotFound)?;
+    let extracted_paths = packfile.extract_modified_only(&dependencies, schema, &destination_path)?;
+    extracted_paths.iter().for_each(|path| println!("{}", path));
+
+    if config.verbosity_level > 0 {
+        info!("{} files extracted from the PackFile.", extracted_paths.len());
+    }
+
+    Ok(())
+}
+
+/// This function extracts all the files of the provided PackFile into a ZIP archive, keeping their folder structure.
+///
+/// If `decode` is true, DB and Loc tables are stored as TSV files. Tables that fail to decode are stored as binary files.
+/// Files are written to the archive one by one, so the memory usage doesn't depend on the size of the PackFile.
+pub fn export_zip(config: &Config, packfile: &str, destination_path: &str, decode: bool) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Exporting the PackFile {} to {}.", packfile, destination_path);
+    }
+
+    let schema = if decode { Some(Schema::load(config.game_selected.as_ref().unwrap().get_schema_name())?) } else { None };
+
+    let packfile_path = PathBuf::from(packfile);
+    let packfile = open_packfile(config, &packfile_path)?;
+
+    let mut packed_files = packfile.get_ref_packed_files_all();
+    packed_files.sort_unstable_by_key(|x| x.get_path().join("/").to_lowercase());
+
+    let mut zip = ZipWriter::new(File::create(destination_path)?);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for packed_file in &packed_files {
+        let path = packed_file.get_path().join("/");
+
+        if let Some(ref schema) = schema {
+            if packed_file.get_packed_file_type(false).eq_non_strict_slice(&[PackedFileType::DB, PackedFileType::Loc]) {
+                match packed_file.decode_with_schema(schema).as_deref() {
+                    Ok(DecodedPackedFile::DB(table)) => {
+                        zip.start_file(format!("{}.tsv", path), options).map_err(|error| ErrorKind::NoHTMLError(error.to_string()))?;
+                        table.export_tsv_to_writer(&mut zip, table.get_ref_table_name(), packed_file.get_path(), Delimiter::Tab, false)?;
+                        continue;
+                    }
+                    Ok(DecodedPackedFile::Loc(table)) => {
+                        zip.start_file(format!("{}.tsv", path), options).map_err(|error| ErrorKind::NoHTMLError(error.to_string()))?;
+                        table.export_tsv_to_writer(&mut zip, TSV_NAME_LOC, packed_file.get_path(), Delimiter::Tab, false)?;
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        zip.start_file(path, options).map_err(|error| ErrorKind::NoHTMLError(error.to_string()))?;
+        zip.write_all(&packed_file.get_ref_raw().get_data()?)?;
+    }
+
+    zip.finish().map_err(|error| ErrorKind::NoHTMLError(error.to_string()))?;
+
+    if config.verbosity_level > 0 {
+        info!("{} files exported successfully.", packed_files.len());
+    }
+
+    Ok(())
+}
+
 /// This function list the contents of the provided Packfile.
-pub fn list_packfile_contents(config: &Config, packfile: &str) -> Result<()> {
+///
+/// Only the index data of the PackFile is used, so the data of the PackedFiles is never loaded.
+/// The output `format` can be `plain` (a table), `json` or `csv`.
+pub fn list_packfile_contents(config: &Config, packfile: &str, format: &str, types: &[&str]) -> Result<()> {
 	if config.verbosity_level > 0 {
 		info!("Listing PackFile Contents.");
 	}
 	let packfile_path = PathBuf::from(packfile);
-	let packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+	let packfile = open_packfile(config, &packfile_path)?;
+
+    let types = types.iter().map(|x| get_packed_file_type_from_name(x)).collect::<Result<Vec<PackedFileType>>>()?;
+    let mut packed_files = packfile.get_ref_packed_files_all();
+    if !types.is_empty() {
+        packed_files.retain(|file| PackedFileType::get_packed_file_type(file.get_ref_raw(), true).eq_non_strict_slice(&types));
+    }
+    packed_files.sort_unstable_by_key(|x| x.get_path().join("/").to_lowercase());
 
-	let mut table = Table::new();
-    table.add_row(row!["PackedFile Path", "Type", "Size"]);
-    for file in packfile.get_ref_packed_files_all() {
-    	let packedfile_type = PackedFileType::get_packed_file_type(file.get_ref_raw(), true);
-    	let size = ByteSize::kib((file.get_raw_data_size() / 1024).into());
-        if packedfile_type == PackedFileType::DB {
-            table.add_row(row![file.get_path().join("/"), packedfile_type, size]);
+    match format {
+        "json" => {
+            let list = packed_files.iter().map(|file| {
+                let raw = file.get_ref_raw();
+                json!({
+                    "path": raw.get_path().join("/"),
+                    "size": raw.get_size(),
+                    "type": PackedFileType::get_packed_file_type(raw, true).to_string(),
+                    "is_compressed": raw.get_compression_state(),
+                    "is_encrypted": raw.get_encryption_state(),
+                    "timestamp": raw.get_timestamp(),
+                })
+            }).collect::<Vec<Value>>();
+
+            println!("{}", serde_json::to_string_pretty(&list)?);
+        }
+
+        "csv" => {
+            let mut writer = WriterBuilder::new().from_writer(stdout());
+            writer.write_record(["path", "size", "type", "is_compressed", "is_encrypted", "timestamp"])?;
+            for file in &packed_files {
+                let raw = file.get_ref_raw();
+                writer.serialize((
+                    raw.get_path().join("/"),
+                    raw.get_size(),
+                    PackedFileType::get_packed_file_type(raw, true).to_string(),
+                    raw.get_compression_state(),
+                    raw.get_encryption_state(),
+                    raw.get_timestamp(),
+                ))?;
+            }
+            writer.flush()?;
+        }
+
+        _ => {
+            let mut table = Table::new();
+            table.add_row(row!["PackedFile Path", "Type", "Size", "Compressed", "Encrypted", "Timestamp"]);
+            for file in &packed_files {
+                let raw = file.get_ref_raw();
+                let packedfile_type = PackedFileType::get_packed_file_type(raw, true);
+                let size = ByteSize::b(raw.get_size().into());
+                table.add_row(row![raw.get_path().join("/"), packedfile_type, size, raw.get_compression_state(), raw.get_encryption_state(), raw.get_timestamp()]);
+            }
+
+            table.printstd();
         }
     }
 
-	table.printstd();
 	Ok(())
 }
 
+/// This function returns the `PackedFileType` corresponding to the name used to refer to it in the CLI.
+///
+/// Text PackedFiles are matched regardless of their subtype.
+fn get_packed_file_type_from_name(name: &str) -> Result<PackedFileType> {
+    match name {
+        "anim" => Ok(PackedFileType::Anim),
+        "animfragment" => Ok(PackedFileType::AnimFragment),
+        "animpack" => Ok(PackedFileType::AnimPack),
+        "animtable" => Ok(PackedFileType::AnimTable),
+        "ca_vp8" => Ok(PackedFileType::CaVp8),
+        "db" => Ok(PackedFileType::DB),
+        "esf" => Ok(PackedFileType::ESF),
+        "image" => Ok(PackedFileType::Image),
+        "loc" => Ok(PackedFileType::Loc),
+        "matchedcombat" => Ok(PackedFileType::MatchedCombat),
+        "rigidmodel" => Ok(PackedFileType::RigidModel),
+        "text" => Ok(PackedFileType::Text(TextType::Plain)),
+        "uic" => Ok(PackedFileType::UIC),
+        "unitvariant" => Ok(PackedFileType::UnitVariant),
+        "unknown" => Ok(PackedFileType::Unknown),
+        _ => Err(ErrorKind::NoHTMLError(format!("Invalid PackedFile type: {}", name)).into()),
+    }
+}
+
+/// This function enables/disables compression on the files of the provided PackFile bigger than `min_size`, then saves it.
+pub fn compress(config: &Config, packfile: &str, enable: bool, min_size: u32) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("{} compression on the PackFile: {}", if enable { "Enabling" } else { "Disabling" }, packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let paths = packfile.toggle_compression_by_size(enable, min_size)?;
+    let result = save_packfile(config, &mut packfile, None);
+
+    if config.verbosity_level > 0 {
+        paths.iter().for_each(|x| info!("Compression {} for: {}", if enable { "enabled" } else { "disabled" }, x.join("/")));
+        info!("Compression changed successfully on {} file(s).", paths.len());
+    }
+
+    result
+}
+
+/// This function checks the provided PackFile for problems, printing all the ones found.
+///
+/// If any problem is found, it returns an error.
+pub fn validate(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Validating the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let packfile = open_packfile(config, &packfile_path)?;
+
+    let issues = packfile.validate();
+    issues.iter().for_each(|x| println!("{}", x));
+
+    if issues.is_empty() {
+        if config.verbosity_level > 0 {
+            info!("No problems found in the PackFile.");
+        }
+        Ok(())
+    } else {
+        Err(ErrorKind::NoHTMLError(format!("{} problem(s) found in the PackFile.", issues.len())).into())
+    }
+}
+
+/// This function checks the DB Tables of the provided PackFile for rows breaking the constraints of their definitions.
+pub fn lint(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Linting the PackFile: {}", packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let mut table = Table::new();
+    table.add_row(row!["File", "Row", "Column", "Problem"]);
+    let mut violation_count = 0;
+    for packed_file in packfile.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+        let path = packed_file.get_path().join("/");
+        if let Ok(DecodedPackedFile::DB(db)) = packed_file.decode_return_ref_no_locks(&schema) {
+            for violation in db.check_constraints() {
+                table.add_row(row![path, violation.get_ref_row(), violation.get_ref_column(), violation.get_ref_reason()]);
+                violation_count += 1;
+            }
+        }
+    }
+
+    if violation_count == 0 {
+        if config.verbosity_level > 0 {
+            info!("No problems found in the tables of the PackFile.");
+        }
+        Ok(())
+    } else {
+        table.printstd();
+        Err(ErrorKind::NoHTMLError(format!("{} problem(s) found in the tables of the PackFile.", violation_count)).into())
+    }
+}
+
+/// This function checks the integrity of the data of every PackedFile in the provided PackFile.
+pub fn verify(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Verifying the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let packfile = open_packfile(config, &packfile_path)?;
+
+    let failed = packfile.verify_all_checksums();
+    failed.iter().for_each(|x| println!("{}", x.join("/")));
+
+    if failed.is_empty() {
+        if config.verbosity_level > 0 {
+            info!("No corrupted PackedFiles found in the PackFile.");
+        }
+        Ok(())
+    } else {
+        Err(ErrorKind::NoHTMLError(format!("{} corrupted PackedFile(s) found in the PackFile.", failed.len())).into())
+    }
+}
+
+/// This function patches the SiegeAI of the maps in the provided PackFile, removes their useless xml files, then saves it.
+pub fn patch_siege_ai(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Patching the SiegeAI of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let (message, deleted_paths) = packfile.patch_siege_ai()?;
+    let result = save_packfile(config, &mut packfile, None);
+
+    if config.verbosity_level > 0 {
+        deleted_paths.iter().for_each(|x| info!("Deleted: {}", x.join("/")));
+        info!("{}", message);
+    }
+
+    result
+}
+
+/// This function prints the keys of the games the provided PackFile may belong to, from most to least likely.
+pub fn guess_game(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Guessing the game of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let packfile = open_packfile(config, &packfile_path)?;
+
+    let games = packfile.guess_game();
+    games.iter().for_each(|x| println!("{}", x));
+
+    if games.is_empty() {
+        Err(ErrorKind::NoHTMLError("No supported game uses the version of this PackFile.".to_owned()).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// This function prints the flags enabled in the bitmask of the provided PackFile, one per line.
+pub fn flags(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Getting the flags of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let packfile = open_packfile(config, &packfile_path)?;
+
+    let names = packfile.get_bitmask().get_names();
+    names.iter().for_each(|x| println!("{}", x));
+
+    if names.is_empty() && config.verbosity_level > 0 {
+        info!("The PackFile has no flags enabled.");
+    }
+
+    Ok(())
+}
+
+/// This function prints some statistics of the provided PackFile: its header info, the amount of files of each type,
+/// the sizes of its files, and its biggest files.
+pub fn stats(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Getting the statistics of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+    let pfh_version = packfile.get_pfh_version();
+    let flags = packfile.get_bitmask().get_names().join(", ");
+
+    // Sizes are (path, stored size, decompressed size).
+    let mut files_by_type = BTreeMap::new();
+    let mut sizes = vec![];
+    for file in packfile.get_ref_mut_packed_files_all() {
+        let packedfile_type = PackedFileType::get_packed_file_type(file.get_ref_raw(), true);
+        *files_by_type.entry(packedfile_type.to_string()).or_insert(0) += 1;
+        sizes.push((file.get_path().join("/"), u64::from(file.get_ref_raw().get_size()), file.get_decompressed_size()?));
+    }
+
+    let compressed_size = sizes.iter().map(|(_, size, _)| size).sum::<u64>();
+    let uncompressed_size = sizes.iter().map(|(_, _, size)| size).sum::<u64>();
+    let compression_ratio = if uncompressed_size > 0 { compressed_size as f64 / uncompressed_size as f64 } else { 1.0 };
+
+    let mut table = Table::new();
+    table.add_row(row!["PFH Version", pfh_version]);
+    table.add_row(row!["Flags", flags]);
+    table.add_row(row!["Files", sizes.len()]);
+    table.add_row(row!["Uncompressed Size", ByteSize::b(uncompressed_size)]);
+    table.add_row(row!["Compressed Size", ByteSize::b(compressed_size)]);
+    table.add_row(row!["Compression Ratio", format!("{:.2}", compression_ratio)]);
+    table.printstd();
+
+    let mut table = Table::new();
+    table.add_row(row!["Type", "Files"]);
+    files_by_type.iter().for_each(|(packedfile_type, count)| { table.add_row(row![packedfile_type, count]); });
+    table.printstd();
+
+    sizes.sort_by(|x, y| y.2.cmp(&x.2).then_with(|| x.0.cmp(&y.0)));
+    let mut table = Table::new();
+    table.add_row(row!["Largest PackedFiles", "Size", "Compressed Size"]);
+    sizes.iter().take(10).for_each(|(path, compressed_size, uncompressed_size)| { table.add_row(row![path, ByteSize::b(*uncompressed_size), ByteSize::b(*compressed_size)]); });
+    table.printstd();
+
+    Ok(())
+}
+
+/// This function prints the hash of the contents of the provided PackFile.
+pub fn hash(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Getting the content hash of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    println!("{}", packfile.content_hash()?);
+    Ok(())
+}
+
+/// This function prints the notes of the provided PackFile, if it has any.
+pub fn get_notes(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Getting the notes of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let packfile = open_packfile(config, &packfile_path)?;
+
+    match packfile.get_notes() {
+        Some(notes) => println!("{}", notes),
+        None => if config.verbosity_level > 0 {
+            info!("The PackFile has no notes.");
+        }
+    }
+
+    Ok(())
+}
+
+/// This function replaces the notes of the provided PackFile, then saves it. An empty text removes the notes.
+pub fn set_notes(config: &Config, packfile: &str, notes: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Setting the notes of the PackFile: {}", packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    packfile.set_notes(if notes.is_empty() { None } else { Some(notes.to_owned()) });
+    save_packfile(config, &mut packfile, None)
+}
+
+/// This function checks the references of all the DB tables in the provided PackFile, and prints the ones pointing to missing data.
+///
+/// It fails if any missing reference is found, so it can be used to validate mods in CI.
+pub fn check_references(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Checking the references of the PackFile: {}", packfile);
+    }
+
+    match &config.game_selected {
+        Some(game_selected) => {
+            *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+            let packfile_path = PathBuf::from(packfile);
+            let mut packfile = open_packfile(config, &packfile_path)?;
+
+            // Load the PackFile's dependencies too, so references to its parent mods are not reported as missing.
+            let mut dependencies = Dependencies::default();
+            dependencies.rebuild(packfile.get_packfiles_list(), false)?;
+
+            let missing_references = match *SCHEMA.read().unwrap() {
+                Some(ref schema) => dependencies.check_references(&mut packfile, schema),
+                None => return Err(ErrorKind::SchemaNotFound.into()),
+            };
+            missing_references.iter().for_each(|x| println!("{}: row \"{}\", column \"{}\": \"{}\" not found in {}/{}.",
+                x.get_ref_path().join("/"),
+                x.get_ref_row_key().join("|"),
+                x.get_ref_column(),
+                x.get_ref_value(),
+                x.get_ref_referenced_table(),
+                x.get_ref_referenced_column()
+            ));
+
+            if missing_references.is_empty() {
+                if config.verbosity_level > 0 {
+                    info!("No missing references found in the PackFile.");
+                }
+                Ok(())
+            } else {
+                Err(ErrorKind::NoHTMLError(format!("{} missing reference(s) found in the PackFile.", missing_references.len())).into())
+            }
+        }
+        None => Err(ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()).into()),
+    }
+}
+
+/// This function prints, for each Loc file of the provided PackFile, the vanilla keys it overrides and the new keys it adds.
+pub fn loc_report(config: &Config, packfile: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Comparing the Loc files of the PackFile {} with the game files.", packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+    let mut dependencies = Dependencies::default();
+    dependencies.rebuild(&[], false)?;
+    let vanilla_loc = dependencies.get_vanilla_loc()?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let schema = SCHEMA.read().unwrap();
+    let schema = schema.as_ref().ok_or(ErrorKind::SchemaNotFound)?;
+    for packed_file in packfile.get_ref_mut_packed_files_by_type(PackedFileType::Loc, false) {
+        let path = packed_file.get_path().join("/");
+        if let Ok(DecodedPackedFile::Loc(loc)) = packed_file.decode_return_ref_no_locks(schema) {
+            let (overridden_keys, new_keys) = loc.classify_against(&vanilla_loc);
+            println!("{}: {} overridden, {} new.", path, overridden_keys.len(), new_keys.len());
+            overridden_keys.iter().for_each(|key| println!("~ {}", key));
+            new_keys.iter().for_each(|key| println!("+ {}", key));
+        }
+    }
+
+    Ok(())
+}
+
+/// This function decodes a DB table of the provided PackFile with the definition in the provided JSON file, and prints it as TSV.
+///
+/// If the table doesn't fully decode, the rows decoded before the failure are printed instead.
+pub fn decode_table(config: &Config, packfile: &str, table_path: &str, definition_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Decoding the table {} of the PackFile {} with the definition: {}", table_path, packfile, definition_path);
+    }
+
+    let definition: Definition = serde_json::from_reader(File::open(definition_path)?)?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let table_path = table_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+    let table_name = match table_path.get(1) {
+        Some(table_name) if table_path.len() == 3 && table_path[0] == "db" => table_name.to_owned(),
+        _ => return Err(ErrorKind::DBTableIsNotADBTable.into()),
+    };
+
+    let packed_file = packfile.get_ref_mut_packed_file_by_path(&table_path).ok_or(ErrorKind::PackedFileNotFound)?;
+    match DB::decode_with_definition(&packed_file.get_raw_data()?, &table_name, &definition) {
+        Ok(table) => table.export_tsv_to_writer(stdout(), &table_name, &table_path, Delimiter::Tab, false),
+        Err(error) => {
+            if let ErrorKind::TableIncompleteError(_, data) = error.kind() {
+                let table: DecodedTable = deserialize(data)?;
+                println!("{} row(s) decoded before the error:", table.get_ref_table_data().len());
+                table.get_ref_table_data().iter().for_each(|row| println!("{}", row.iter().map(|x| x.data_to_string()).collect::<Vec<String>>().join("\t")));
+            }
+            Err(error)
+        }
+    }
+}
+
+/// This function adds a new DB table with the provided amount of empty rows to the provided PackFile, then saves it.
+///
+/// The table uses the last version of its definition in the schema.
+pub fn new_table(config: &Config, packfile: &str, table_name: &str, row_count: usize, table_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Adding a new {} table with {} rows to the PackFile {} as {}.", table_name, row_count, packfile, table_path);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{}_tables", table_name) };
+    let table_path = table_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+    if table_path.len() != 3 || table_path[0] != "db" || table_path[1] != table_name {
+        return Err(ErrorKind::DBTableIsNotADBTable.into());
+    }
+
+    let definition = schema.get_ref_last_definition_db_from_schema(&table_name)?;
+    let table = DB::new_with_rows(&table_name, definition, row_count);
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+    packfile.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &table_path), true)?;
+    save_packfile(config, &mut packfile, None)
+}
+
+/// This function prints the distinct values of a column in all the DB tables of the provided type in the provided PackFile.
+pub fn column_values(config: &Config, packfile: &str, table_name: &str, column_name: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Getting the values of the column {} of the {} tables in the PackFile {}.", column_name, table_name, packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let table_folder = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{}_tables", table_name) };
+    let mut values = BTreeSet::new();
+    for packed_file in packfile.get_ref_mut_packed_files_by_path_start(&["db".to_owned(), table_folder]) {
+        if let DecodedPackedFile::DB(table) = packed_file.decode_return_ref_no_locks(&schema)? {
+            values.extend(table.column_values(column_name)?);
+        }
+    }
+
+    values.iter().for_each(|value| println!("{}", value));
+    Ok(())
+}
+
+/// This function prints the differences between a DB table of the provided PackFile and the same table in the game files.
+pub fn diff_vanilla(config: &Config, packfile: &str, table_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Comparing the table {} of the PackFile {} with the game files.", table_path, packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+    let mut dependencies = Dependencies::default();
+    dependencies.rebuild(&[], false)?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let table_path = table_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+    let packed_file = packfile.get_ref_mut_packed_file_by_path(&table_path).ok_or(ErrorKind::PackedFileNotFound)?;
+
+    let schema = SCHEMA.read().unwrap();
+    let schema = schema.as_ref().ok_or(ErrorKind::SchemaNotFound)?;
+    let table = match packed_file.decode_return_ref_no_locks(schema)? {
+        DecodedPackedFile::DB(table) => table,
+        _ => return Err(ErrorKind::DBTableIsNotADBTable.into()),
+    };
+
+    let diff = dependencies.compare_with_vanilla(table, schema)?;
+    let key_columns = table.get_key_column_indexes();
+    diff.get_ref_added_rows().iter().for_each(|row| println!("+ {}", DB::get_row_key_by_indexes(row, &key_columns).join("|")));
+    diff.get_ref_removed_rows().iter().for_each(|row| println!("- {}", DB::get_row_key_by_indexes(row, &key_columns).join("|")));
+    diff.get_ref_changed_cells().iter().for_each(|(key, column, old, new)| println!("~ {}: {}: {} -> {}", key.join("|"), column, old.data_to_string(), new.data_to_string()));
+
+    Ok(())
+}
+
+/// This function exports all the DB tables of the provided PackFile as JSON files, one per table.
+///
+/// If no schema is provided, the one of the game selected is used.
+pub fn to_json(config: &Config, packfile: &str, destination_path: &str, schema_name: Option<&str>, with_loc: bool, string_export_mode: StringExportMode) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Exporting the tables of the PackFile {} as JSON to: {}", packfile, destination_path);
+    }
+
+    let schema = match schema_name {
+        Some(schema_name) => Schema::load(schema_name)?,
+        None => match &config.game_selected {
+            Some(game_selected) => Schema::load(game_selected.get_schema_name())?,
+            None => return Err(ErrorKind::NoHTMLError("No Game Selected or Schema provided.".to_owned()).into()),
+        }
+    };
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = open_packfile(config, &packfile_path)?;
+
+    let destination_path = PathBuf::from(destination_path);
+    DirBuilder::new().recursive(true).create(&destination_path)?;
+
+    let exported = twwstats::export_tables(&mut packfile, &schema, &destination_path, with_loc, false, string_export_mode)?;
+    if config.verbosity_level > 0 {
+        info!("{} table(s) exported.", exported);
+    }
+
+    Ok(())
+}
+
+/// This function prints the values of a column of a table in the provided PackFile that no table references.
+///
+/// References are searched in the PackFile and its dependencies.
+pub fn find_orphans(config: &Config, packfile: &str, table: &str, column: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Finding the unreferenced values of {}/{} in the PackFile: {}", table, column, packfile);
+    }
+
+    match &config.game_selected {
+        Some(game_selected) => {
+            let schema = Schema::load(game_selected.get_schema_name())?;
+
+            let mut dependencies = Dependencies::default();
+            dependencies.rebuild(&[], false)?;
+
+            let packfile_path = PathBuf::from(packfile);
+            let mut packfile = open_packfile(config, &packfile_path)?;
+
+            let orphans = dependencies.find_orphans(&mut packfile, &schema, table, column)?;
+            orphans.iter().for_each(|x| println!("{}", x));
+
+            if config.verbosity_level > 0 {
+                info!("{} unreferenced value(s) found.", orphans.len());
+            }
+            Ok(())
+        }
+        None => Err(ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()).into()),
+    }
+}
+
 /// This function creates a new packfile with the provided path.
 pub fn new_packfile(config: &Config, packfile: &str) -> Result<()> {
     if config.verbosity_level > 0 {
@@ -233,8 +1038,38 @@ pub fn new_packfile(config: &Config, packfile: &str) -> Result<()> {
         Some(game_selected) => {
             let packfile_path = PathBuf::from(packfile);
             let mut packfile = PackFile::new_with_name("x", game_selected.get_pfh_version_by_file_type(PFHFileType::Mod));
-            packfile.save(Some(packfile_path))
+            save_packfile(config, &mut packfile, Some(packfile_path))
         }
         None => Err(ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()).into()),
     }
 }
+
+//---------------------------------------------------------------------------//
+//                          PackFile Command Helpers
+//---------------------------------------------------------------------------//
+
+/// This function opens the PackFile in the provided path, logging the progress of the read if the verbosity level is high enough.
+fn open_packfile(config: &Config, packfile_path: &Path) -> Result<PackFile> {
+    let progress = progress_logger(config, "Reading");
+    PackFile::read_with_progress(packfile_path, true, progress.as_ref().map(|x| x as &dyn Fn(u64, u64)))
+}
+
+/// This function saves the provided PackFile, logging the progress of the save if the verbosity level is high enough.
+fn save_packfile(config: &Config, packfile: &mut PackFile, new_path: Option<PathBuf>) -> Result<()> {
+    let progress = progress_logger(config, "Saving");
+    packfile.save_with_progress(new_path, progress.as_ref().map(|x| x as &dyn Fn(u64, u64)))
+}
+
+/// This function returns a progress callback that logs every 10% of progress, or `None` if the verbosity level is too low for it.
+fn progress_logger(config: &Config, action: &'static str) -> Option<impl Fn(u64, u64)> {
+    if config.verbosity_level > 1 {
+        let last_step = Cell::new(None);
+        Some(move |done: u64, total: u64| {
+            let step = (done * 10).checked_div(total).unwrap_or(10);
+            if last_step.get() != Some(step) {
+                last_step.set(Some(step));
+                info!("{} PackFile: {}%", action, step * 10);
+            }
+        })
+    } else { None }
+}
@@ -0,0 +1,46 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the `RigidModel` command's functions.
+
+use log::info;
+use prettytable::{Table, row, cell};
+
+use std::fs::read;
+
+use rpfm_error::{Error, Result};
+use rpfm_lib::packedfile::rigidmodel::RigidModel;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                        RigidModel Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function prints the signature, version, LOD count and skeleton of each of the provided RigidModel files.
+///
+/// Files that are not RigidModels are reported with the reason, instead of stopping the command.
+pub fn info(config: &Config, source_paths: &[&str]) -> Result<()> {
+    if config.verbosity_level > 0 {
+        source_paths.iter().for_each(|x| info!("Reading the header of the RigidModel: {}", x));
+    }
+
+    let mut table = Table::new();
+    table.add_row(row!["File", "Signature", "Version", "LODs", "Skeleton"]);
+    for path in source_paths {
+        match read(path).map_err(Error::from).and_then(|data| RigidModel::read_header(&data)) {
+            Ok(header) => table.add_row(row![path, header.get_ref_signature(), header.get_ref_version(), header.get_ref_lod_count(), header.get_ref_skeleton_id()]),
+            Err(error) => table.add_row(row![path, error.to_terminal(), "", "", ""]),
+        };
+    }
+
+    table.printstd();
+    Ok(())
+}
@@ -10,8 +10,14 @@
 
 use log::info;
 
-use rpfm_error::Result;
-use rpfm_lib::schema::Schema;
+use std::path::{Path, PathBuf};
+
+use rpfm_error::{ErrorKind, Result};
+use rpfm_lib::dependencies::Dependencies;
+use rpfm_lib::packedfile::DecodedPackedFile;
+use rpfm_lib::packfile::PackFile;
+use rpfm_lib::schema::{Schema, VersionedFile};
+use rpfm_lib::SCHEMA;
 
 use crate::config::Config;
 
@@ -56,3 +62,106 @@ pub fn to_xml(config: &Config) -> Result<()> {
     }
     result
 }
+
+/// This function migrates the tables of the provided type in a PackFile from one version to another, then saves the PackFile.
+///
+/// If no version to migrate to is provided, the newest version in the schema is used.
+pub fn migrate(config: &Config, packfile: &str, table_name: &str, from_version: i32, to_version: Option<i32>) -> Result<()> {
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    let to_version = match to_version {
+        Some(to_version) => to_version,
+        None => {
+            let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{}_tables", table_name) };
+            schema.get_ref_last_definition_db_from_schema(&table_name)?.get_version()
+        }
+    };
+
+    if config.verbosity_level > 0 {
+        info!("Migrating {} tables from version {} to version {} in the PackFile: {}", table_name, from_version, to_version, packfile);
+    }
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+
+    let migrated = packfile.migrate_db_tables(&schema, table_name, from_version, to_version)?;
+    if migrated > 0 {
+        packfile.save(None)?;
+    }
+
+    if config.verbosity_level > 0 {
+        info!("{} tables migrated.", migrated);
+    }
+
+    Ok(())
+}
+
+/// This function checks if the definition of the provided table has columns swapped, comparing its data with the vanilla files using other versions of its definition.
+///
+/// If `fix` is true, the swapped columns are fixed in the definition, and the schema is saved.
+pub fn check_swaps(config: &Config, packfile: &str, table_path: &str, fix: bool) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Checking for swapped columns in the table {} of the PackFile: {}", table_path, packfile);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    *SCHEMA.write().unwrap() = Some(Schema::load(game_selected.get_schema_name())?);
+
+    let mut dependencies = Dependencies::default();
+    dependencies.rebuild(&[], false)?;
+
+    let packfile_path = PathBuf::from(packfile);
+    let mut packfile = PackFile::open_packfiles(&[packfile_path], true, false, false)?;
+
+    let table_path = table_path.split('/').map(|x| x.to_owned()).collect::<Vec<String>>();
+    let packed_file = packfile.get_ref_mut_packed_file_by_path(&table_path).ok_or(ErrorKind::PackedFileNotFound)?;
+
+    let (table_name, version, swaps) = {
+        let schema = SCHEMA.read().unwrap();
+        let schema = schema.as_ref().ok_or(ErrorKind::SchemaNotFound)?;
+        let table = match packed_file.decode_return_ref_no_locks(schema)? {
+            DecodedPackedFile::DB(table) => table,
+            _ => return Err(ErrorKind::DBTableIsNotADBTable.into()),
+        };
+
+        let fields = table.get_ref_definition().get_ref_fields();
+        let swaps = schema.detect_swapped_columns(table, &dependencies);
+        swaps.iter().for_each(|(a, b)| println!("{} <-> {}", fields[*a].get_name(), fields[*b].get_name()));
+        (table.get_table_name(), table.get_ref_definition().get_version(), swaps)
+    };
+
+    if fix && !swaps.is_empty() {
+        let mut schema = SCHEMA.write().unwrap();
+        let schema = schema.as_mut().ok_or(ErrorKind::SchemaNotFound)?;
+        if let VersionedFile::DB(_, definitions) = schema.get_ref_mut_versioned_file_db(&table_name)? {
+            let definition = definitions.iter_mut().find(|x| x.get_version() == version).ok_or(ErrorKind::SchemaDefinitionNotFound)?;
+            swaps.iter().for_each(|(a, b)| definition.swap_columns(*a, *b));
+        }
+
+        schema.save(game_selected.get_schema_name())?;
+    }
+
+    if config.verbosity_level > 0 {
+        info!("{} swapped column pairs found.", swaps.len());
+    }
+
+    Ok(())
+}
+
+/// This function generates the schema of the game selected from the raw DB files of the provided Assembly Kit, then saves it.
+pub fn generate(config: &Config, ak_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Generating schema from the Assembly Kit at: {}", ak_path);
+    }
+
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let mut schema = Schema::generate_from_assembly_kit(Path::new(ak_path), game_selected)?;
+    schema.save(game_selected.get_schema_name())?;
+
+    if config.verbosity_level > 0 {
+        info!("Schema generated.");
+    }
+
+    Ok(())
+}
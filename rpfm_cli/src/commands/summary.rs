@@ -0,0 +1,58 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the `Summary` command's functions.
+
+use log::info;
+use prettytable::{Table, row, cell};
+
+use std::path::PathBuf;
+
+use rpfm_error::{ErrorKind, Result};
+use rpfm_lib::packfile::PackFile;
+use rpfm_lib::schema::Schema;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                          Summary Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function prints the name, version and amount of rows of each DB Table in the provided PackFile.
+///
+/// If no PackFile is provided, it uses all the CA PackFiles of the game selected.
+pub fn summary(config: &Config, packfile: Option<&str>) -> Result<()> {
+    let game_selected = config.game_selected.as_ref().ok_or_else(|| ErrorKind::NoHTMLError("No Game Selected provided.".to_owned()))?;
+    let schema = Schema::load(game_selected.get_schema_name())?;
+
+    let packfile = match packfile {
+        Some(packfile) => {
+            if config.verbosity_level > 0 {
+                info!("Getting the table summary of the PackFile: {}", packfile);
+            }
+            PackFile::open_packfiles(&[PathBuf::from(packfile)], true, false, false)?
+        }
+        None => {
+            if config.verbosity_level > 0 {
+                info!("Getting the table summary of the game files of: {}", game_selected.get_display_name());
+            }
+            PackFile::open_packfiles(&game_selected.get_all_ca_packfiles_paths()?, true, true, true)?
+        }
+    };
+
+    let mut table = Table::new();
+    table.add_row(row!["Table", "Version", "Rows"]);
+    for (table_name, version, entry_count) in packfile.table_summary(&schema) {
+        table.add_row(row![table_name, version, entry_count]);
+    }
+
+    table.printstd();
+    Ok(())
+}
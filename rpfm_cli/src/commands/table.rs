@@ -13,6 +13,7 @@ use std::path::PathBuf;
 
 use rpfm_error::{ErrorKind, Result};
 
+use rpfm_lib::packedfile::table::Delimiter;
 use rpfm_lib::packedfile::table::db::DB;
 use rpfm_lib::schema::Schema;
 
@@ -25,9 +26,12 @@ use crate::config::Config;
 /// This function imports a TSV file into a binary DB/Loc file.
 ///
 /// If no destination path was provided, it leaves the DB/Loc File in the same place as the tsv file, with the same name.
+///
+/// If `dedup` is true, only the last row of each key is kept in DB Tables.
 pub fn import_tsv(
     config: &Config,
     source_paths: &[&str],
+    dedup: bool,
 ) -> Result<()> {
 
 	if config.verbosity_level > 0 {
@@ -38,7 +42,7 @@ pub fn import_tsv(
         Some(game_selected) => {
             let schema = Schema::load(game_selected.get_schema_name())?;
         	let source_paths = source_paths.iter().map(PathBuf::from).collect::<Vec<PathBuf>>();
-        	let result = DB::import_tsv_to_binary_file(&schema, &source_paths);
+        	let result = DB::import_tsv_to_binary_file(&schema, &source_paths, dedup);
             info!("All TSV files imported to binary.");
             result
         },
@@ -46,12 +50,14 @@ pub fn import_tsv(
     }
 }
 
-/// This function imports a TSV file into a binary DB/Loc file.
+/// This function exports a binary DB/Loc file into a TSV file (or a CSV one, depending on the delimiter).
 ///
-/// If no destination path was provided, it leaves the DB/Loc File in the same place as the tsv file, with the same name.
+/// It leaves the TSV file in the same place as the DB/Loc file, with the same name.
 pub fn export_tsv(
     config: &Config,
     source_paths: &[&str],
+    delimiter: Delimiter,
+    escape: bool,
 ) -> Result<()> {
 	if config.verbosity_level > 0 {
 		source_paths.iter().for_each(|x| info!("Export Binary File as TSV: {}", x));
@@ -61,7 +67,7 @@ pub fn export_tsv(
         Some(game_selected) => {
             let schema = Schema::load(game_selected.get_schema_name())?;
             let source_paths = source_paths.iter().map(PathBuf::from).collect::<Vec<PathBuf>>();
-            let result = DB::export_tsv_from_binary_file(&schema, &source_paths);
+            let result = DB::export_tsv_from_binary_file(&schema, &source_paths, delimiter, escape);
             info!("All binary files exported to TSV.");
             result
         },
@@ -19,7 +19,12 @@ use rpfm_lib::schema::Field;
 use std::path::PathBuf;
 use std::vec;
 
-use rpfm_error::Result;
+#[cfg(feature = "watch")] use notify::{DebouncedEvent, RecursiveMode, Watcher, watcher};
+#[cfg(feature = "watch")] use std::collections::BTreeSet;
+#[cfg(feature = "watch")] use std::sync::mpsc::channel;
+#[cfg(feature = "watch")] use std::time::Duration;
+
+use rpfm_error::{ErrorKind, Result};
 use rpfm_lib::packedfile::PackedFileType;
 use rpfm_lib::packedfile::DecodedPackedFile;
 use rpfm_lib::packfile::*;
@@ -42,8 +47,27 @@ pub const EXTENSIONS: [&str; 3] = [
     ".png",
 ];
 
-fn process_string(field: &Field, data: &String) -> String {
-    if !data.is_empty() {
+/// How strings are written when exporting tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringExportMode {
+
+    /// Strings are exported as they are in the tables. Use it if you need to import the exported data back into the game,
+    /// as the game expects the path separators of filename fields as they are.
+    Raw,
+
+    /// Filename fields use `/` as path separator and have no trailing `/`, so they can be used directly in urls.
+    Normalized,
+}
+
+/// Default implementation for `StringExportMode`.
+impl Default for StringExportMode {
+    fn default() -> Self {
+        Self::Normalized
+    }
+}
+
+fn process_string(field: &Field, data: &String, string_export_mode: StringExportMode) -> String {
+    if !data.is_empty() && string_export_mode == StringExportMode::Normalized {
         if field.get_is_filename() {
             let mut path = data.replace('\\', "/");
 
@@ -59,7 +83,68 @@ fn process_string(field: &Field, data: &String) -> String {
     return data.clone();
 }
 
-pub fn export(config: &Config, destination: &str) -> Result<()> {
+/// This function converts a cell into its JSON representation.
+///
+/// Sequences are exported as nested arrays of rows, using the field names of their own definition.
+fn decoded_data_to_json(field: &Field, data: &DecodedData, string_export_mode: StringExportMode) -> serde_json::Value {
+    match data {
+        DecodedData::Boolean(data) => json!(data),
+        DecodedData::F32(data) => json!(data),
+        DecodedData::F64(data) => json!(data),
+        DecodedData::I16(data) => json!(data),
+        DecodedData::I32(data) => json!(data),
+        DecodedData::I64(data) => json!(data),
+        DecodedData::OptionalI32(data) => json!(data),
+        DecodedData::StringU8(data) => json!(process_string(field, data, string_export_mode)),
+        DecodedData::StringU16(data) => json!(process_string(field, data, string_export_mode)),
+        DecodedData::OptionalStringU8(data) => json!(process_string(field, data, string_export_mode)),
+        DecodedData::OptionalStringU16(data) => json!(process_string(field, data, string_export_mode)),
+        // Special case: we need to convert this into the hex representation of its bytes.
+        DecodedData::ColourRGB(data) => {
+            let mut encoded = Vec::with_capacity(4);
+            encoded.encode_integer_colour_rgb(*data);
+            match encoded.decode_string_colour_rgb(0) {
+                Ok(data) => json!(data),
+                Err(_) => json!("000000"),
+            }
+        },
+        DecodedData::ColourRGBA(data) => {
+            let mut encoded = Vec::with_capacity(4);
+            encoded.encode_integer_colour_rgba(*data);
+            match encoded.decode_string_colour_rgba(0) {
+                Ok(data) => json!(data),
+                Err(_) => json!("00000000"),
+            }
+        },
+        DecodedData::SequenceU16(table) |
+        DecodedData::SequenceU32(table) => {
+            let fields = table.columns();
+            let rows = table.get_ref_table_data().iter().map(|cells| {
+                let mut json_map = serde_json::Map::new();
+                for (column, field) in fields.iter().enumerate() {
+                    json_map.insert(field.get_name().to_owned(), decoded_data_to_json(field, &cells[column], string_export_mode));
+                }
+                serde_json::Value::Object(json_map)
+            }).collect::<Vec<serde_json::Value>>();
+            json!(rows)
+        },
+    }
+}
+
+/// This function returns the localised field used as display name of the rows of a table, if any.
+fn get_label_field(localised_fields: &[Field]) -> Option<String> {
+    ["onscreen_name", "name"].iter()
+        .find_map(|name| localised_fields.iter().find(|field| field.get_name() == *name))
+        .or_else(|| localised_fields.first())
+        .map(|field| field.get_name().to_owned())
+}
+
+/// This function exports all the tables of the game selected as JSON, with their localised fields.
+///
+/// If `with_labels` is true, reference columns get an extra `{column}_label` column. See `export_tables` for details.
+///
+/// If `with_images` is true, the images under `ui` are also extracted to the destination folder, keeping their folder structure.
+pub fn export(config: &Config, destination: &str, with_labels: bool, with_images: bool, string_export_mode: StringExportMode) -> Result<()> {
     info!("Exporting tables as JSON files to {}...", destination);
 
     let game_selected = config.game_selected.as_ref().unwrap();
@@ -67,125 +152,215 @@ pub fn export(config: &Config, destination: &str) -> Result<()> {
     let schema = Schema::load(game_selected.get_schema_name())?;
 
     info!("Opening packfiles for {}...", game_selected.get_display_name());
-    let mut packfile = PackFile::open_all_ca_packfiles().unwrap();
-
-    info!("Building locs HashMap...");
-    let locs: HashMap<_, _> = packfile.get_ref_mut_packed_files_by_type(PackedFileType::Loc, false).iter_mut().filter_map(|file| {
-        let name = file.get_path().last().unwrap().to_string().replace("__.loc", "");
-        warn!("Loc file found: {}", name);
-        if let Ok(DecodedPackedFile::Loc(table)) = file.decode_return_ref_no_locks(&schema) {
-            Some((name, table.clone()))
+    // We only need tables (and images, if requested), so don't even add the rest of the PackedFiles to the PackFile.
+    let mut types_to_load = vec![PackedFileType::DB, PackedFileType::Loc];
+    if with_images {
+        types_to_load.push(PackedFileType::Image);
+    }
+    let mut packfile = PackFile::open_all_ca_packfiles_with_types_for_game(game_selected, &Some(types_to_load))?;
+
+    export_tables(&mut packfile, &schema, Path::new(&destination), true, with_labels, string_export_mode)?;
+
+    if with_images {
+        info!("Exporting images...");
+
+        // Only export the formats the site can show.
+        let ui_path = [String::from("ui")];
+        let unsupported_images = packfile.get_ref_packed_files_by_path_start(&ui_path).iter()
+            .filter(|file| file.get_path().last().map(|name| !EXTENSIONS.iter().any(|x| name.to_lowercase().ends_with(x))).unwrap_or(true))
+            .map(|file| file.get_path().to_vec())
+            .collect::<Vec<Vec<String>>>();
+        unsupported_images.iter().for_each(|path| packfile.remove_packed_file_by_path(path));
+
+        let extracted = packfile.extract_by_path_start(&ui_path, Path::new(&destination), true)?;
+        info!("{} images exported.", extracted.len());
+    }
+
+	let result = Ok(());
+    if config.verbosity_level > 0 {
+        info!("Export completed!");
+    }
+    result
+}
+
+/// This function exports all the DB tables of the provided PackFile as JSON, one file per table.
+///
+/// If `with_loc` is true, the localised fields of each row are filled with their loc strings, if found in the PackFile.
+///
+/// If `with_labels` is true, for each reference column an extra `{column}_label` column is added with the localised
+/// display name of the referenced row, if found. The raw keys are kept in their original columns.
+///
+/// Strings are written following `string_export_mode`. See `StringExportMode` for details.
+///
+/// It returns the amount of tables exported.
+pub fn export_tables(packfile: &mut PackFile, schema: &Schema, destination: &Path, with_loc: bool, with_labels: bool, string_export_mode: StringExportMode) -> Result<usize> {
+
+    // Loc keys are `{table}_{localised field}_{row key}`, so we can just put all of them together.
+    let mut loc_strings = HashMap::<String, String>::new();
+    if with_loc || with_labels {
+        info!("Building locs HashMap...");
+        for file in packfile.get_ref_mut_packed_files_by_type(PackedFileType::Loc, false) {
+            if let Ok(DecodedPackedFile::Loc(table)) = file.decode_return_ref_no_locks(schema) {
+                table.get_ref_table_data().iter().for_each(|cells| {
+                    if let (DecodedData::StringU16(key), DecodedData::StringU16(value)) = (&cells[0], &cells[1]) {
+                        loc_strings.insert(key.to_owned(), value.to_owned());
+                    }
+                });
+            }
+        }
+    }
+
+    // For reference labels we need the localised field to use as label for each table.
+    let mut label_fields = HashMap::<String, String>::new();
+    if with_labels {
+        info!("Building reference labels...");
+        for file in packfile.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
+            if let Ok(DecodedPackedFile::DB(table)) = file.decode_return_ref_no_locks(schema) {
+                if let Some(field) = get_label_field(table.get_ref_definition().get_localised_fields()) {
+                    label_fields.insert(table.get_table_name_without_tables(), field);
+                }
+            }
         }
-        else { None }
-    }).collect();
+    }
 
     info!("Building DB tables...");
+    let mut exported = 0;
     for file in packfile.get_ref_mut_packed_files_by_type(PackedFileType::DB, false) {
-        if let Ok(DecodedPackedFile::DB(table)) = file.decode_return_ref_no_locks(&schema) {
+        if let Ok(DecodedPackedFile::DB(table)) = file.decode_return_ref_no_locks(schema) {
             let definition = table.get_ref_definition();
-
-            let fields_processed = definition.get_fields_processed();
-
+            let fields_processed = table.columns();
             let localised_fields = definition.get_localised_fields();
-
-            let table_name_without_tables = &table.get_table_name_without_tables();
-
-            // FOR DEBUGGING PURPOSES
-            // if table_name_without_tables != "land_units" {
-            //     continue;
-            // }
-
-            let mut loc_strings = HashMap::<String, String>::new();
-            if let Some(loc_table) = locs.get(table_name_without_tables) {
-                loc_table.get_ref_table_data().iter().for_each(|cells| {
-                    let mut prefix_to_remove = table_name_without_tables.clone();
-                    prefix_to_remove.push('_');
-                    let key = match &cells[0] {
-                        DecodedData::StringU16(data) => data.to_string().strip_prefix(&prefix_to_remove).unwrap().to_string(),
-                        _ => String::from("")
-                    };
-                    let value = match &cells[1] {
-                        DecodedData::StringU16(data) => data.to_string(),
-                        _ => String::from("")
-                    };
-                    loc_strings.insert(key, value);
-                });
-            };
+            let table_name_without_tables = table.get_table_name_without_tables();
 
             warn!("DB Table: {}", table.get_table_name());
             let json_data: Vec<serde_json::Map<String, serde_json::value::Value>> = table.get_ref_table_data().iter().map(|cells| {
                 let mut json_map = serde_json::Map::new();
-                let primary_key_col = fields_processed.iter().position(|f| f.get_is_key()).unwrap();
-                let primary_key = match &cells[primary_key_col] {
-                    DecodedData::StringU8(data) => data.to_string(),
-                    DecodedData::StringU16(data) => data.to_string(),
-                    _ => String::from("")
-                };
                 for (column, field) in fields_processed.iter().enumerate() {
                     let json_key = field.get_name().to_string();
 
-                    match &cells[column] {
-                        DecodedData::Boolean(data) => json_map.insert(json_key, json!(data)),
-                        DecodedData::F32(data) => json_map.insert(json_key, json!(data)),
-                        DecodedData::F64(data) => json_map.insert(json_key, json!(data)),
-                        DecodedData::I16(data) => json_map.insert(json_key, json!(data)),
-                        DecodedData::I32(data) => json_map.insert(json_key, json!(data)),
-                        DecodedData::I64(data) => json_map.insert(json_key, json!(data)),
-                        DecodedData::StringU8(data) => json_map.insert(json_key, json!(process_string(field, data))),
-                        DecodedData::StringU16(data) => json_map.insert(json_key, json!(process_string(field, data))),
-                        DecodedData::OptionalStringU8(data) => json_map.insert(json_key, json!(process_string(field, data))),
-                        DecodedData::OptionalStringU16(data) => json_map.insert(json_key, json!(process_string(field, data))),
-                        // Special case: we need to convert this into the hex representation of its bytes.
-                        DecodedData::ColourRGB(data) => {
-                            let mut encoded = Vec::with_capacity(4);
-                            encoded.encode_integer_colour_rgb(*data);
-                            match encoded.decode_string_colour_rgb(0) {
-                                Ok(data) => json_map.insert(json_key, json!(data)),
-                                Err(_) => json_map.insert(json_key, json!("000000")),
+                    json_map.insert(json_key, decoded_data_to_json(field, &cells[column], string_export_mode));
+                }
+
+                // Reference labels
+                if with_labels {
+                    for (column, field) in fields_processed.iter().enumerate() {
+                        if let Some((ref_table, _)) = field.get_is_reference() {
+                            if let Some(label_field) = label_fields.get(ref_table) {
+                                let ref_key = match &cells[column] {
+                                    DecodedData::StringU8(data) |
+                                    DecodedData::StringU16(data) |
+                                    DecodedData::OptionalStringU8(data) |
+                                    DecodedData::OptionalStringU16(data) => data.to_string(),
+                                    DecodedData::I16(data) => data.to_string(),
+                                    DecodedData::I32(data) => data.to_string(),
+                                    DecodedData::I64(data) => data.to_string(),
+                                    DecodedData::OptionalI32(Some(data)) => data.to_string(),
+                                    _ => continue,
+                                };
+
+                                if let Some(label) = loc_strings.get(&format!("{}_{}_{}", ref_table, label_field, ref_key)) {
+                                    json_map.insert(format!("{}_label", field.get_name()), json!(label));
+                                }
                             }
-                        },
-                        DecodedData::SequenceU16(_) => json_map.insert(json_key, json!("SequenceU16")),
-                        DecodedData::SequenceU32(_) => json_map.insert(json_key, json!("SequenceU32")),
-                    };
+                        }
+                    }
                 }
 
                 // Localised fields
-                localised_fields.iter().for_each(|field| {
-                    let json_key = field.get_name().to_string();
-                    let mut loc_key = field.get_name().to_string().clone();
-                    loc_key.push('_');
-                    loc_key.push_str(&primary_key);
-                    if let Some(value) = loc_strings.get(&loc_key) {
-                        json_map.insert(json_key, json!(value.clone()));
+                if with_loc {
+                    if let Some(primary_key_col) = fields_processed.iter().position(|f| f.get_is_key()) {
+                        let primary_key = match &cells[primary_key_col] {
+                            DecodedData::StringU8(data) => data.to_string(),
+                            DecodedData::StringU16(data) => data.to_string(),
+                            _ => String::from("")
+                        };
+
+                        localised_fields.iter().for_each(|field| {
+                            let loc_key = format!("{}_{}_{}", table_name_without_tables, field.get_name(), primary_key);
+                            if let Some(value) = loc_strings.get(&loc_key) {
+                                json_map.insert(field.get_name().to_string(), json!(value.clone()));
+                            }
+                        });
                     }
-                });
+                }
 
                 // Return the row
                 json_map
             }).collect();
 
-            let mut out_path = Path::new(&destination).join(table.get_table_name_without_tables());
+            let mut out_path = destination.join(&table_name_without_tables);
             out_path.set_extension("json");
             warn!("Saving {:?}...", out_path);
             ::serde_json::to_writer(&File::create(out_path)?, &json_data)?;
+            exported += 1;
         }
     }
 
-    // info!("Exporting images...");
-    // for file in packfile.get_ref_mut_packed_files_by_path_start(&[String::from("ui")]) {
-    //     if let Some(packedfile_name) = file.get_path().last() {
-    //         let packedfile_name = packedfile_name.to_lowercase();
+    Ok(exported)
+}
 
-    //         if EXTENSIONS.iter().any(|x| packedfile_name.ends_with(x)) {
-    //             let out_path = Path::new(&destination);
-    //             file.extract_packed_file(out_path, false);
-    //         }
-    //     }
-    // }
+/// Time we wait after a change before re-exporting, so a PackFile being written in multiple steps only triggers one export.
+#[cfg(feature = "watch")]
+const WATCH_DEBOUNCE_TIME: Duration = Duration::from_secs(2);
 
-	let result = Ok(());
-    if config.verbosity_level > 0 {
-        info!("Export completed!");
+/// This function exports all the tables of the game selected as JSON, then keeps watching the game's PackFiles and exports them again on change.
+///
+/// As any PackFile can overwrite any table of the ones before it, every change triggers a full export.
+///
+/// The folders containing the PackFiles are watched instead of the PackFiles themselves, so PackFiles replaced
+/// by a new file (like when they're updated) keep being watched. Changes to other files in these folders are ignored.
+#[cfg(feature = "watch")]
+pub fn watch(config: &Config, destination: &str, with_labels: bool, with_images: bool, string_export_mode: StringExportMode) -> Result<()> {
+    export(config, destination, with_labels, with_images, string_export_mode)?;
+
+    let game_selected = config.game_selected.as_ref().unwrap();
+    let paths = game_selected.get_all_ca_packfiles_paths()?.iter().filter_map(|path| normalize_watched_path(path)).collect::<BTreeSet<PathBuf>>();
+    let folders = paths.iter().filter_map(|path| path.parent().map(|parent| parent.to_path_buf())).collect::<BTreeSet<PathBuf>>();
+
+    let (sender, receiver) = channel();
+    let mut watcher = watcher(sender, WATCH_DEBOUNCE_TIME).map_err(|error| ErrorKind::NoHTMLError(error.to_string()))?;
+    for folder in &folders {
+        watcher.watch(folder, RecursiveMode::NonRecursive).map_err(|error| ErrorKind::NoHTMLError(error.to_string()))?;
     }
-    result
+
+    let is_watched = |path: &Path| normalize_watched_path(path).map_or(false, |path| paths.contains(&path));
+
+    info!("Watching {} PackFiles for changes. Press Ctrl+C to stop.", paths.len());
+    loop {
+        let changed_path = match receiver.recv() {
+            Ok(DebouncedEvent::Create(path)) |
+            Ok(DebouncedEvent::Write(path)) |
+            Ok(DebouncedEvent::Remove(path)) => if is_watched(&path) { Some(path) } else { None },
+            Ok(DebouncedEvent::Rename(old_path, new_path)) => if is_watched(&new_path) { Some(new_path) } else if is_watched(&old_path) { Some(old_path) } else { None },
+            Ok(DebouncedEvent::Error(error, _)) => {
+                warn!("Error while watching the PackFiles: {}", error);
+                None
+            }
+            Ok(_) => None,
+            Err(error) => return Err(ErrorKind::NoHTMLError(error.to_string()).into()),
+        };
+
+        if let Some(path) = changed_path {
+            info!("Change detected in {}. Exporting again...", path.to_string_lossy());
+
+            // Don't stop watching because one export failed. The PackFile may still be being written.
+            if let Err(error) = export(config, destination, with_labels, with_images, string_export_mode) {
+                error!("{}", error.to_terminal());
+            }
+        }
+    }
+}
+
+/// This function returns the provided path with its folder canonicalized, so paths to the same file can be compared
+/// even if the file itself no longer exists, like when it has been removed or renamed.
+#[cfg(feature = "watch")]
+fn normalize_watched_path(path: &Path) -> Option<PathBuf> {
+    let folder = path.parent()?.canonicalize().ok()?;
+    Some(folder.join(path.file_name()?))
+}
+
+/// Fallback for when the CLI has been built without the `watch` feature.
+#[cfg(not(feature = "watch"))]
+pub fn watch(_config: &Config, _destination: &str, _with_labels: bool, _with_images: bool, _string_export_mode: StringExportMode) -> Result<()> {
+    Err(ErrorKind::NoHTMLError("Watch mode is not available. Build rpfm_cli with the 'watch' feature to enable it.".to_owned()).into())
 }
@@ -0,0 +1,77 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the `Video` command's functions.
+
+use log::info;
+use prettytable::{Table, row, cell};
+
+use std::fs::{read, write};
+
+use rpfm_error::{Error, Result};
+use rpfm_lib::packedfile::ca_vp8::CaVp8;
+
+use crate::config::Config;
+
+//---------------------------------------------------------------------------//
+//                          Video Command Variants
+//---------------------------------------------------------------------------//
+
+/// This function prints the format, codec, dimensions, framerate, frame count and duration of each of the provided CA_VP8 files.
+///
+/// Files that are not videos are reported with the reason, instead of stopping the command.
+pub fn info(config: &Config, source_paths: &[&str]) -> Result<()> {
+    if config.verbosity_level > 0 {
+        source_paths.iter().for_each(|x| info!("Reading the header of the video: {}", x));
+    }
+
+    let mut table = Table::new();
+    table.add_row(row!["File", "Format", "Codec", "Width", "Height", "Framerate", "Frames", "Duration"]);
+    for path in source_paths {
+        match read(path).map_err(Error::from).and_then(|data| CaVp8::read_info(&data)) {
+            Ok(info) => table.add_row(row![
+                path,
+                format!("{:?}", info.get_ref_format()),
+                info.get_ref_codec_four_cc(),
+                info.get_ref_width(),
+                info.get_ref_height(),
+                format!("{:.3} fps", info.get_ref_framerate()),
+                info.get_ref_num_frames(),
+                format!("{:.3} s", info.get_ref_duration())
+            ]),
+            Err(error) => table.add_row(row![path, error.to_terminal(), "", "", "", "", "", ""]),
+        };
+    }
+
+    table.printstd();
+    Ok(())
+}
+
+/// This function converts a CA_VP8 video into a standard IVF video, so it can be played by external players.
+pub fn to_ivf(config: &Config, source_path: &str, destination_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Converting the video {} to IVF: {}", source_path, destination_path);
+    }
+
+    let video = CaVp8::read(read(source_path)?)?;
+    write(destination_path, video.to_ivf()?)?;
+    Ok(())
+}
+
+/// This function converts a standard IVF video into a CA_VP8 video, so it can be imported into a PackFile.
+pub fn from_ivf(config: &Config, source_path: &str, destination_path: &str) -> Result<()> {
+    if config.verbosity_level > 0 {
+        info!("Converting the IVF video {} to CA_VP8: {}", source_path, destination_path);
+    }
+
+    let video = CaVp8::from_ivf(&read(source_path)?)?;
+    write(destination_path, video.save())?;
+    Ok(())
+}
@@ -69,8 +69,15 @@ fn main() {
         Some(("diagnostic", matches)) => commands::command_diagnostic(&config, matches, asskit_db_path),
         Some(("packfile", matches)) => commands::command_packfile(&config, matches, packfile),
         Some(("table", matches)) => commands::command_table(&config, matches, packfile),
-        Some(("schema", matches)) => commands::command_schema(&config, matches),
+        Some(("schema", matches)) => commands::command_schema(&config, matches, packfile),
+        Some(("rigidmodel", matches)) => commands::command_rigidmodel(&config, matches),
+        Some(("anim", matches)) => commands::command_anim(&config, matches, packfile),
+        Some(("animpack", matches)) => commands::command_animpack(&config, matches),
+        Some(("video", matches)) => commands::command_video(&config, matches),
         Some(("twwstats", matches)) => commands::command_twwstats(&config, matches),
+        Some(("game", matches)) => commands::command_game(&config, matches),
+        Some(("summary", _)) => commands::command_summary(&config, packfile),
+        Some(("coverage", _)) => commands::command_coverage(&config, packfile),
         _ => { Ok(()) }
     };
 
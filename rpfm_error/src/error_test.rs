@@ -0,0 +1,42 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Error` type.
+!*/
+
+use std::path::PathBuf;
+
+use crate::{Error, ErrorKind};
+
+/// Test to make sure the ids of the variants of `ErrorKind` are stable, and don't depend on their data.
+#[test]
+fn test_error_kind_variant_id() {
+    assert_eq!(ErrorKind::PackedFileNotFound.get_variant_id(), "packed_file_not_found");
+    assert_eq!(ErrorKind::IOGenericCopy(PathBuf::from("a")).get_variant_id(), "io_generic_copy");
+    assert_eq!(ErrorKind::IOGenericCopy(PathBuf::from("b")).get_variant_id(), "io_generic_copy");
+}
+
+/// Test to make sure errors are translated with the keys in the locale files, and fall back to english otherwise.
+#[test]
+fn test_error_to_localized() {
+    let locale = include_str!("../../locale/Russian_ru.ftl");
+
+    let error = Error::from(ErrorKind::OperationCancelled);
+    assert_eq!(error.to_localized(locale), "<p>Операция была отменена.</p>");
+
+    let error = Error::from(ErrorKind::IOGenericCopy(PathBuf::from("a")));
+    assert_eq!(error.to_localized(locale), error.to_string());
+
+    // Broken entries in the locale must not break the valid ones.
+    let error = Error::from(ErrorKind::OperationCancelled);
+    assert_eq!(error.to_localized("broken entry\nerror_operation_cancelled = Cancelled"), "Cancelled");
+    assert_eq!(error.to_localized(""), error.to_string());
+}
@@ -17,10 +17,11 @@ for `rpfm-ui` and `rpfm-cli`. As such, **this lib is not intended to be standalo
 If you need a custom `From` implementation for any error of any lib, add it here.
 !*/
 
-use fluent::{FluentError, FluentResource};
+use fluent::{FluentBundle, FluentError, FluentResource};
 use fluent_syntax::parser::ParserError;
 use log::SetLoggerError;
 use serde_json::error::Category;
+use unic_langid::LanguageIdentifier;
 
 use std::boxed::Box;
 use std::{fmt, fmt::Display};
@@ -30,6 +31,11 @@ use std::path::PathBuf;
 use std::result;
 use std::string;
 
+use rpfm_macros::GetVariantId;
+
+#[cfg(test)]
+mod error_test;
+
 /// Alias for handling errors more easily.
 pub type Result<T> = result::Result<T, Error>;
 
@@ -50,7 +56,7 @@ pub struct Error {
 ///
 /// This type implements the `Display` trait to return a meaningful, user-readable error message.
 /// Most of the messages contain HTML tags for formatting. If you don't want the HTML tags, use the `Error::to_terminal()` function to remove them.
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug, GetVariantId)]
 pub enum ErrorKind {
 
     //-----------------------------------------------------//
@@ -93,6 +99,9 @@ pub enum ErrorKind {
     /// Error for when the version of a TSV file is not the one we're trying to import to.
     ImportTSVWrongVersion,
 
+    /// Error for when a table cannot be migrated to another version because some columns of the new version cannot be filled.
+    TableMigrationUnmappedColumns(String),
+
     /// Generic TSV import/export error.
     TSVErrorGeneric,
 
@@ -210,6 +219,33 @@ pub enum ErrorKind {
     /// Error for when trying to copy a Packfile to the data folder, while it's already in the data folder.
     PackFileIsAlreadyInDataFolder,
 
+    /// Error for when we try to compress the PackedFiles of a PackFile which version doesn't support compression. Contains the version of the PackFile.
+    PackFileCompressionNotSupported(String),
+
+    /// Error for when a PackFile is too big to be saved in its format. Contains the version of the PackFile and its size.
+    PackFileTooLargeForFormat(String, u64),
+
+    /// Error for when we try to change a flag of a PackFile we cannot save with that flag changed. Contains the flag and the version of the PackFile.
+    PackFileFlagNotEditable(String, String),
+
+    /// Error for when a PackFile fails the validation done before saving it. Contains the problems found.
+    PackFileValidationFailed(Vec<String>),
+
+    /// Error for when we try to open a split PackFile without providing any part.
+    PackFileSplitNoParts,
+
+    /// Error for when one of the parts of a split PackFile is missing. Contains the path of the part.
+    PackFileSplitPartNotFound(PathBuf),
+
+    /// Error for when a requested CA PackFile is not in the game's data folder. Contains the name of the PackFile.
+    CAPackFileNotFound(String),
+
+    /// Error for when the parts of a split PackFile are not in order. Contains the path of the first misplaced part.
+    PackFileSplitPartOutOfOrder(PathBuf),
+
+    /// Error for when the parts of a split PackFile don't add up to the size the PackFile expects. Contains both, the combined size and the expected size.
+    PackFileSplitSizeMismatch(u64, u64),
+
     //--------------------------------//
     // Schema Errors
     //--------------------------------//
@@ -316,6 +352,15 @@ pub enum ErrorKind {
     /// Error for when we are trying to use "Search&Replace" to place invalid data into a cell.
     DBTableReplaceInvalidData,
 
+    /// Error for when we are trying to use "Search&Replace" with an invalid regex. Contains the error returned by the regex parser.
+    DBTableReplaceInvalidRegex(String),
+
+    /// Error for when we try to rename an entity with a different amount of keys than the table has. Contains the amount we expected, and the amount we got.
+    DBTableRenameWrongKeyCount(u32, u32),
+
+    /// Error for when we try to rename an entity with a composite key in a table with localised fields.
+    DBTableRenameCompositeKeyLocalised,
+
     /// Error for when a DB Table fails to decode. Contains the error returned by the decoding process.
     DBTableDecode(String),
 
@@ -406,6 +451,9 @@ pub enum ErrorKind {
     /// Error for when an AnimPack PackedFile fails to decode. Contains the error message.
     AnimPackDecode(String),
 
+    /// Error for when a file inside an AnimPack has a path that cannot be safely extracted to disk. Contains the path.
+    AnimPackInvalidPath(String),
+
     //--------------------------------//
     // AnimTable Errors
     //--------------------------------//
@@ -444,6 +492,16 @@ pub enum ErrorKind {
     /// Error for when we try to decode a PackedFile that's not an Unit Variant as an Unit Variant.
     PackedFileIsNotUnitVariant,
 
+    //--------------------------------//
+    // Variant Mesh Definition Errors
+    //--------------------------------//
+
+    /// Error for when a Variant Mesh Definition is not well-formed XML. Contains the line, column and error message.
+    VariantMeshDefinitionInvalidXML(usize, usize, String),
+
+    /// Error for when a Variant Mesh Definition contains an unknown element. Contains the line, column and element name.
+    VariantMeshDefinitionUnknownElement(usize, usize, String),
+
     //--------------------------------//
     // ESF Errors
     //--------------------------------//
@@ -591,6 +649,9 @@ pub enum ErrorKind {
     /// Error for just passing a message along.
     GenericHTMLError(String),
 
+    /// Error for when a long-running operation has been cancelled by the user.
+    OperationCancelled,
+
     /// Error for when we're trying add/rename/whatever a file with a reserved path.
     ReservedFiles,
 
@@ -741,6 +802,35 @@ impl Error {
             .replace("<i>", "")         // Replace start of italics.
             .replace("</i>", "")        // Replace end of italics.
     }
+
+    /// This function returns the error message translated using the provided fluent (`.ftl`) locale source.
+    ///
+    /// Each `ErrorKind` is looked up by a stable key: `error_` followed by its name in snake_case (`PackedFileNotFound` => `error_packed_file_not_found`),
+    /// no matter the data it contains. If the key is not in the locale, it returns the english message.
+    pub fn to_localized(&self, locale: &str) -> String {
+        let fallback = self.to_string();
+
+        // Broken entries in the locale don't stop us from using the valid ones.
+        let resource = match FluentResource::try_new(locale.to_owned()) {
+            Ok(resource) => resource,
+            Err((resource, _)) => resource,
+        };
+
+        let mut bundle = FluentBundle::new(vec![LanguageIdentifier::default()]);
+        bundle.set_use_isolating(false);
+        if bundle.add_resource(resource).is_err() {
+            return fallback;
+        }
+
+        let key = format!("error_{}", self.kind.get_variant_id());
+        match bundle.get_message(&key).and_then(|message| message.value()) {
+            Some(pattern) => {
+                let mut errors = vec![];
+                bundle.format_pattern(pattern, None, &mut errors).to_string()
+            }
+            None => fallback,
+        }
+    }
 }
 
 //------------------------------------------------------------//
@@ -774,7 +864,8 @@ impl Display for ErrorKind {
             ErrorKind::JsonErrorEOF => write!(f,"<p>Error while trying to read JSON data:</p><p>Unexpected EOF found.</p>"),
             ErrorKind::ImportTSVIncorrectRow(row, column) => write!(f, "<p>This TSV file has an error in the <b>row <i>{}</i></b>, <b>field <i>{}</i></b> (both starting at 1). Please, check it and make sure the value in that field is a valid value for that column.</p>", row + 1, column + 1),
             ErrorKind::ImportTSVWrongTypeTable => write!(f, "<p>This TSV file either belongs to another table, to a localisation PackedFile, it's broken or it's incompatible with RPFM.</p>"),
-            ErrorKind::ImportTSVWrongVersion => write!(f, "<p>This TSV file belongs to another version of this table. If you want to use it, consider creating a new empty table, fill it with enough empty rows, open this file in a TSV editor, like Excel or LibreOffice, and copy column by column.</p><p>A more automatic solution is on the way, but not yet there.</p>"),
+            ErrorKind::ImportTSVWrongVersion => write!(f, "<p>This TSV file belongs to another version of this table. If you want to use it, consider creating a new empty table, fill it with enough empty rows, open this file in a TSV editor, like Excel or LibreOffice, and copy column by column.</p><p>RPFM-CLI can create the table with the empty rows for you, with the <i>packfile --new-table</i> option.</p>"),
+            ErrorKind::TableMigrationUnmappedColumns(columns) => write!(f, "<p>This table cannot be migrated to the new version, because the following columns of the new version cannot be filled from the old one:</p><ul>{}</ul><p>If you want to migrate it, consider creating a new empty table, fill it with enough empty rows, and copy column by column.</p>", columns),
             ErrorKind::ImportTSVInvalidVersion => write!(f, "<p>This TSV file has an invalid version value at line 1.</p>"),
             ErrorKind::TSVErrorGeneric => write!(f, "<p>Error while trying to import/export a TSV file.</p>"),
             ErrorKind::UnicLangIdParsingError => write!(f, "<p>Error while trying to parse a language id.</p>"),
@@ -842,6 +933,15 @@ impl Display for ErrorKind {
             ErrorKind::PackFileSizeIsNotWhatWeExpect(reported_size, expected_size) => write!(f, "<p>This PackFile's reported size is <i><b>{}</b></i> bytes, but we expected it to be <i><b>{}</b></i> bytes. This means that either the decoding logic in RPFM is broken for this PackFile, or this PackFile is corrupted.</p>", reported_size, expected_size),
             ErrorKind::NewDataIsNotDecodeableTheSameWayAsOldDAta => write!(f, "<p>The PackedFile you added is not the same type as the one you had before. So… the view showing it will get closed.</p>"),
             ErrorKind::PackFileIsAlreadyInDataFolder => write!(f, "<p>This PackFile is already being edited from the data folder of the game. You cannot install/uninstall it.</p>"),
+            ErrorKind::PackFileCompressionNotSupported(version) => write!(f, "<p>This PackFile is a <b><i>{}</i></b> PackFile, and compression is only supported on <b><i>PFH5</i></b> PackFiles.</p>", version),
+            ErrorKind::PackFileFlagNotEditable(flag, version) => write!(f, "<p>The flag <b><i>{}</i></b> cannot be changed in <b><i>{}</i></b> PackFiles. Only <b><i>HAS_INDEX_WITH_TIMESTAMPS</i></b> can be changed, and only in PackFiles newer than <b><i>PFH0</i></b>.</p>", flag, version),
+            ErrorKind::PackFileTooLargeForFormat(version, size) => write!(f, "<p>This PackFile is <i><b>{}</b></i> bytes big, but <b><i>{}</i></b> PackFiles cannot be bigger than 4GB. Split its contents into more PackFiles and try again.</p>", size, version),
+            ErrorKind::PackFileValidationFailed(issues) => write!(f, "<p>The PackFile has not been saved because it has the following problems:<ul>{}</ul></p>", issues.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>()),
+            ErrorKind::PackFileSplitNoParts => write!(f, "<p>No parts were provided for the split PackFile.</p>"),
+            ErrorKind::PackFileSplitPartNotFound(path) => write!(f, "<p>The following part of the split PackFile is missing: <i>{}</i>.</p>", path.display()),
+            ErrorKind::CAPackFileNotFound(name) => write!(f, "<p>The following CA PackFile has not been found in the game's data folder: <i>{}</i>.</p>", name),
+            ErrorKind::PackFileSplitPartOutOfOrder(path) => write!(f, "<p>The parts of the split PackFile are not in order. The first misplaced part is: <i>{}</i>.</p>", path.display()),
+            ErrorKind::PackFileSplitSizeMismatch(combined_size, expected_size) => write!(f, "<p>The parts of the split PackFile add up to <i><b>{}</b></i> bytes, but we expected <i><b>{}</b></i> bytes. This means that one or more parts are missing, out of order or corrupted.</p>", combined_size, expected_size),
 
             //-----------------------------------------------------//
             //                Schema Errors
@@ -887,6 +987,9 @@ impl Display for ErrorKind {
             ErrorKind::DBTableIsNotADBTable => write!(f, "<p>This is either not a DB Table, or it's a DB Table but it's corrupted.</p>"),
             ErrorKind::DBTableContainsListField => write!(f, "<p>This specific table version uses a currently unimplemented type (List), so it cannot be decoded for now.</p>"),
             ErrorKind::DBTableReplaceInvalidData => write!(f, "<p>Error while trying to replace the data of a Cell.</p><p>This means you tried to replace a number cell with text, or used a too big, too low or invalid number. Don't do it. It wont end well.</p>"),
+            ErrorKind::DBTableReplaceInvalidRegex(cause) => write!(f, "<p>The pattern to search is not a valid regex:</p><p>{}</p>", cause),
+            ErrorKind::DBTableRenameWrongKeyCount(expected, real) => write!(f, "<p>Error while trying to rename an entity:</p><p>The table has \"{}\" key columns, but we got \"{}\" keys instead.</p>", expected, real),
+            ErrorKind::DBTableRenameCompositeKeyLocalised => write!(f, "<p>Entities with more than one key cannot be renamed in tables with localised fields, as there is no way to know how their loc keys are built.</p>"),
             ErrorKind::DBTableDecode(cause) => write!(f, "<p>Error while trying to decode the DB Table:</p><p>{}</p><p>Before anything else, please check your game selected is really the one this PackFile is for! If it isn't, change your game selected and try again.</p>", cause),
             ErrorKind::DBMissingReferences(references) => write!(f, "<p>The currently open PackFile has reference errors in the following tables:<ul>{}</ul></p>", references.iter().map(|x| format!("<li>{}<li>", x)).collect::<String>()),
             ErrorKind::NoDefinitionUpdateAvailable => write!(f, "<p>This table already has the newer definition available.</p>"),
@@ -933,6 +1036,7 @@ impl Display for ErrorKind {
             // AnimPack Errors
             //--------------------------------//
             ErrorKind::AnimPackDecode(cause) => write!(f, "<p>Error while trying to decode the AnimPack PackedFile:</p><p>{}</p>", cause),
+            ErrorKind::AnimPackInvalidPath(path) => write!(f, "<p>The following file inside the AnimPack has an empty or invalid path, so it cannot be extracted:</p><p>{}</p>", path),
 
             //--------------------------------//
             // AnimTable Errors
@@ -970,6 +1074,12 @@ impl Display for ErrorKind {
             ErrorKind::UnitVariantDecode(cause) => write!(f, "<p>Error while trying to decode the Unit Variant PackedFile:</p><p>{}</p>", cause),
             ErrorKind::PackedFileIsNotUnitVariant => write!(f, "<p>This PackedFile is not an Unit Variant</p>"),
 
+            //--------------------------------//
+            // Variant Mesh Definition Errors
+            //--------------------------------//
+            ErrorKind::VariantMeshDefinitionInvalidXML(line, column, cause) => write!(f, "<p>Invalid XML in the Variant Mesh Definition at line {}, column {}:</p><p>{}</p>", line, column, cause),
+            ErrorKind::VariantMeshDefinitionUnknownElement(line, column, name) => write!(f, "<p>Unknown element <i>{}</i> in the Variant Mesh Definition at line {}, column {}.</p>", name, line, column),
+
             //--------------------------------//
             // PAK File Errors
             //--------------------------------//
@@ -1034,6 +1144,7 @@ impl Display for ErrorKind {
             ErrorKind::Generic => write!(f, "<p>Generic error. You should never read this.</p>"),
             ErrorKind::NoHTMLError(error) => write!(f,"{}", error),
             ErrorKind::GenericHTMLError(error) => write!(f,"{}", error),
+            ErrorKind::OperationCancelled => write!(f, "<p>The operation has been cancelled.</p>"),
             ErrorKind::ReservedFiles => write!(f, "<p>One or more of the files you're trying to add/create/rename to have a reserved name. Those names are reserved for internal use in RPFM. Please, try again with another name.</p>"),
             ErrorKind::NonExistentFile => write!(f, "<p>The file you tried to… use doesn't exist. This is a bug, because if everything worked properly, you'll never see this message.</p>"),
             ErrorKind::InvalidFilesForMerging => write!(f, "<p>The files you selected are not all LOCs, neither DB Tables of the same type and version.</p>"),
@@ -0,0 +1,52 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Annotations` module.
+!*/
+
+use std::path::Path;
+
+use super::Annotations;
+
+#[test]
+fn test_annotations_set_and_get() {
+    let mut annotations = Annotations::default();
+    let packfile_a = Path::new("/data/mod_a.pack");
+    let packfile_b = Path::new("/data/mod_b.pack");
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+
+    annotations.set_annotation(packfile_a, &path, "unit_1", "needs review", Some(0xFF0000));
+    annotations.set_annotation(packfile_a, &path, "unit_2", "", Some(0x00FF00));
+
+    // Annotations of a table must not leak into the same table of another PackFile.
+    let table_annotations = annotations.get_annotations(packfile_a, &path);
+    assert_eq!(table_annotations.len(), 2);
+    assert_eq!(table_annotations["unit_1"].get_ref_note(), "needs review");
+    assert_eq!(table_annotations["unit_1"].get_ref_colour(), &Some(0xFF0000));
+    assert!(annotations.get_annotations(packfile_b, &path).is_empty());
+
+    // An empty note without colour removes the annotation, and the empty PackFile entries with it.
+    annotations.set_annotation(packfile_a, &path, "unit_1", "", None);
+    annotations.set_annotation(packfile_a, &path, "unit_2", "", None);
+    assert!(annotations.get_annotations(packfile_a, &path).is_empty());
+    assert_eq!(annotations, Annotations::default());
+}
+
+#[test]
+fn test_annotations_serialization() {
+    let mut annotations = Annotations::default();
+    let path = vec!["db".to_owned(), "units_tables".to_owned(), "test".to_owned()];
+    annotations.set_annotation(Path::new("/data/mod_a.pack"), &path, "unit_1", "needs review", None);
+
+    let serialized = serde_json::to_string_pretty(&annotations).unwrap();
+    let deserialized: Annotations = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, annotations);
+}
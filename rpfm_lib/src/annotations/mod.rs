@@ -0,0 +1,160 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code to deal with row annotations.
+
+Annotations are notes (and optionally a colour) attached to individual rows of a table, meant for reviewing tables.
+They're stored in the config folder, never in the PackFile, so they don't alter the game data. Each game has its own
+annotations file, with the annotations of each PackFile kept by the PackFile's path.
+!*/
+
+use serde_derive::{Serialize, Deserialize};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use rpfm_error::{Error, Result};
+use rpfm_macros::*;
+
+use crate::games::GameInfo;
+use crate::settings::get_config_path;
+
+#[cfg(test)] mod annotations_test;
+
+/// Name of the folder containing the annotations.
+const ANNOTATIONS_FOLDER: &str = "annotations";
+
+/// Extension of the files containing the annotations. Their name is the key of the game they belong to.
+const ANNOTATIONS_EXTENSION: &str = "json";
+
+/// Current structural version of the Annotations file, for compatibility purposes.
+const CURRENT_STRUCTURAL_VERSION: u16 = 0;
+
+//---------------------------------------------------------------------------//
+//                              Enum & Structs
+//---------------------------------------------------------------------------//
+
+/// This struct represents the Annotations in memory.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Annotations {
+
+    /// Version of the file's structure.
+    version: u16,
+
+    /// Annotations split per PackFile path, then per path of the table within the PackFile, then per row key.
+    annotations: BTreeMap<PathBuf, BTreeMap<String, HashMap<String, Annotation>>>,
+}
+
+/// Individual annotation of a row.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize, GetRef, Set)]
+pub struct Annotation {
+
+    /// Note attached to the row.
+    note: String,
+
+    /// Colour to tint the row with, as an RGB value.
+    colour: Option<u32>,
+}
+
+//---------------------------------------------------------------------------//
+//                       Enum & Structs Implementations
+//---------------------------------------------------------------------------//
+
+/// Implementation of Annotations.
+impl Annotations {
+
+    /// This function loads the Annotations of the provided game to memory from the `annotations/` folder.
+    ///
+    /// If there is no annotations file for the game yet, it returns an empty set of annotations.
+    pub fn load(game: &GameInfo) -> Result<Self> {
+        let file_path = Self::get_file_path(game)?;
+        if !file_path.is_file() {
+            return Ok(Self::default());
+        }
+
+        let file = BufReader::new(File::open(&file_path)?);
+        serde_json::from_reader(file).map_err(Error::from)
+    }
+
+    /// This function saves the Annotations from memory to the file of the provided game in the `annotations/` folder.
+    pub fn save(&self, game: &GameInfo) -> Result<()> {
+        let file_path = Self::get_file_path(game)?;
+
+        // Make sure the path exists to avoid problems when saving.
+        if let Some(folder) = file_path.parent() {
+            DirBuilder::new().recursive(true).create(folder)?;
+        }
+
+        let mut file = File::create(&file_path)?;
+        file.write_all(serde_json::to_string_pretty(&self)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// This function returns the path of the annotations file of the provided game.
+    fn get_file_path(game: &GameInfo) -> Result<PathBuf> {
+        Ok(get_config_path()?.join(ANNOTATIONS_FOLDER).join(game.get_game_key_name()).with_extension(ANNOTATIONS_EXTENSION))
+    }
+
+    /// This function returns all the annotations of the table with the provided path, in the PackFile with the provided path, by row key.
+    pub fn get_annotations(&self, packfile_path: &Path, path: &[String]) -> HashMap<String, Annotation> {
+        self.annotations.get(packfile_path)
+            .and_then(|packfile_annotations| packfile_annotations.get(&path.join("/")))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// This function sets the annotation of the row with the provided key, in the table with the provided path,
+    /// in the PackFile with the provided path.
+    ///
+    /// Setting an empty note without colour removes the annotation.
+    pub fn set_annotation(&mut self, packfile_path: &Path, path: &[String], key: &str, note: &str, colour: Option<u32>) {
+        let path = path.join("/");
+
+        if note.is_empty() && colour.is_none() {
+            if let Some(packfile_annotations) = self.annotations.get_mut(packfile_path) {
+                if let Some(annotations) = packfile_annotations.get_mut(&path) {
+                    annotations.remove(key);
+                    if annotations.is_empty() {
+                        packfile_annotations.remove(&path);
+                    }
+                }
+
+                if packfile_annotations.is_empty() {
+                    self.annotations.remove(packfile_path);
+                }
+            }
+        } else {
+            let annotation = Annotation {
+                note: note.to_owned(),
+                colour,
+            };
+
+            self.annotations.entry(packfile_path.to_path_buf())
+                .or_default()
+                .entry(path)
+                .or_default()
+                .insert(key.to_owned(), annotation);
+        }
+    }
+}
+
+/// Default implementation for Annotations.
+impl Default for Annotations {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_STRUCTURAL_VERSION,
+            annotations: BTreeMap::new(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `AssemblyKit` module.
+!*/
+
+use std::env::temp_dir;
+use std::fs::{create_dir_all, remove_dir_all, remove_file, write};
+
+use crate::packedfile::table::DecodedData;
+
+use super::import_raw_db;
+
+#[test]
+fn test_import_raw_db() {
+    let raw_db_path = temp_dir().join("rpfm_test_import_raw_db");
+    let _ = remove_dir_all(&raw_db_path);
+    create_dir_all(&raw_db_path).unwrap();
+
+    write(raw_db_path.join("TWaD_units.xml"), "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<root>
+    <field><primary_key>1</primary_key><name>key</name><field_type>text</field_type><required>1</required></field>
+    <field><primary_key>0</primary_key><name>name</name><field_type>text</field_type><required>1</required></field>
+    <field><primary_key>0</primary_key><name>value</name><field_type>integer</field_type><required>1</required></field>
+</root>").unwrap();
+
+    write(raw_db_path.join("units.xml"), "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\r
+<dataroot>\r
+<units record_uuid=\"1\">\r
+<key>unit_1</key>\r
+<name>Unit 1</name>\r
+<value>5</value>\r
+</units>\r
+</dataroot>").unwrap();
+
+    let localisable_fields_path = raw_db_path.join("TExc_LocalisableFields.xml");
+    write(&localisable_fields_path, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<dataroot>
+    <TExc_LocalisableFields><table_name>units</table_name><field>name</field></TExc_LocalisableFields>
+</dataroot>").unwrap();
+
+    // Localisable fields must be removed from the tables.
+    let tables = import_raw_db(&raw_db_path, 2);
+
+    // Without a localisable fields file, all the fields are kept.
+    remove_file(&localisable_fields_path).unwrap();
+    let tables_without_localisable_fields = import_raw_db(&raw_db_path, 2);
+
+    // A localisable fields file that cannot be read must not be treated as a missing one.
+    write(&localisable_fields_path, "not xml").unwrap();
+    let tables_with_broken_localisable_fields = import_raw_db(&raw_db_path, 2);
+    let tables_with_wrong_version = import_raw_db(&raw_db_path, 0);
+
+    remove_dir_all(&raw_db_path).unwrap();
+
+    let tables = tables.unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].get_table_name(), "units_tables");
+    assert_eq!(tables[0].get_ref_definition().get_ref_fields().iter().map(|x| x.get_name()).collect::<Vec<_>>(), vec!["key", "value"]);
+    assert_eq!(tables[0].get_ref_table_data(), &[vec![DecodedData::StringU8("unit_1".to_owned()), DecodedData::I32(5)]]);
+
+    let tables = tables_without_localisable_fields.unwrap();
+    assert_eq!(tables[0].get_ref_definition().get_ref_fields().iter().map(|x| x.get_name()).collect::<Vec<_>>(), vec!["key", "name", "value"]);
+
+    assert!(tables_with_broken_localisable_fields.is_err());
+    assert!(tables_with_wrong_version.is_err());
+}
@@ -28,6 +28,7 @@ use std::path::{Path, PathBuf};
 
 use rpfm_error::{Result, ErrorKind};
 
+use crate::assembly_kit::table_data::RawTable;
 use crate::assembly_kit::table_definition::RawDefinition;
 use crate::assembly_kit::localisable_fields::RawLocalisableFields;
 use crate::{GAME_SELECTED, SCHEMA};
@@ -40,6 +41,8 @@ pub mod localisable_fields;
 pub mod table_data;
 pub mod table_definition;
 
+#[cfg(test)] mod assembly_kit_test;
+
 const LOCALISABLE_FILES_FILE_NAME_V2: &str = "TExc_LocalisableFields";
 
 const RAW_DEFINITION_NAME_PREFIX_V2: &str = "TWaD_";
@@ -57,7 +60,7 @@ const RAW_DEFINITION_IGNORED_FILES_V2: [&str; 5] = [
 const RAW_DEFINITION_EXTENSION_V0: &str = ".xsd";
 //const RAW_DATA_EXTENSION_V0: &str = RAW_DATA_EXTENSION_V2;
 
-const BLACKLISTED_TABLES: [&str; 1] = ["translated_texts.xml"];
+pub(crate) const BLACKLISTED_TABLES: [&str; 1] = ["translated_texts.xml"];
 
 //---------------------------------------------------------------------------//
 // Functions to process the Raw DB Tables from the Assembly Kit.
@@ -129,6 +132,51 @@ pub fn update_schema_from_raw_files(ass_kit_path: Option<PathBuf>, dependencies:
     else { Err(ErrorKind::SchemaNotFound.into()) }
 }
 
+/// This function parses all the raw DB Tables in the provided folder into decoded `DB` tables.
+///
+/// Some notes:
+/// - The folder must be the one with both, the definitions and the data of the tables (`raw_data/db` in the Assembly Kit).
+/// - Localisable fields are removed from the tables, as they go to the Loc files. If there is no `Localisable Fields` file, all fields are kept.
+///   If there is one but it cannot be read, this fails.
+/// - Blacklisted tables are skipped. Any other table that fails to be parsed makes this fail.
+/// - Definitions of the resulting tables have version -100, as the Assembly Kit doesn't tell us what version the game uses.
+pub fn import_raw_db(raw_db_path: &Path, version: i16) -> Result<Vec<DB>> {
+    match version {
+        2 | 1 => {
+            let raw_localisable_fields = match RawLocalisableFields::read(raw_db_path, version) {
+                Ok(raw_localisable_fields) => raw_localisable_fields.fields,
+                Err(error) if error.kind() == &ErrorKind::AssemblyKitLocalisableFieldsNotFound => vec![],
+                Err(error) => return Err(error),
+            };
+            get_raw_definition_paths(raw_db_path, version)?.par_iter().filter_map(|raw_definition_path| {
+                let raw_definition = match RawDefinition::read(raw_definition_path, version) {
+                    Ok(raw_definition) => raw_definition,
+                    Err(error) => return Some(Err(error)),
+                };
+
+                if BLACKLISTED_TABLES.contains(&raw_definition.name.as_deref().unwrap_or_default()) {
+                    return None;
+                }
+
+                let raw_table = match RawTable::read(&raw_definition, raw_db_path, version) {
+                    Ok(raw_table) => raw_table,
+                    Err(error) => return Some(Err(error)),
+                };
+
+                let mut definition = Definition::from(&raw_definition);
+                definition.update_from_raw_localisable_fields(&raw_definition, &raw_localisable_fields);
+                let localised_fields_names = definition.get_localised_fields().iter().map(|x| x.get_name().to_owned()).collect::<Vec<String>>();
+                definition.get_ref_mut_fields().retain(|x| !localised_fields_names.iter().any(|y| y == x.get_name()));
+
+                let mut table = DB::from(&raw_table);
+                table.set_definition(&definition);
+                Some(Ok(table))
+            }).collect()
+        }
+        _ => Err(ErrorKind::AssemblyKitUnsupportedVersion(version).into())
+    }
+}
+
 //---------------------------------------------------------------------------//
 // Utility functions to process raw files from the Assembly Kit.
 //---------------------------------------------------------------------------//
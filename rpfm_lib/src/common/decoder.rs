@@ -15,10 +15,13 @@ This module contains the `Decoder` trait and his implementation for `&[u8]`. Thi
 to safely (yes, it covers your `index-out-of-bounds` bugs) decode any type of data contained within
 a PackFile/PackedFile.
 
+All the numbers are decoded as little-endian, except when using the `_be` variants. Most of the formats used by the games are little-endian,
+but some fields are big-endian, like the optimized record info of ESF files. Use the `_be` variants only for those fields.
+
 Note: If you change anything from here, remember to update the `decoder_test.rs` file for it.
 !*/
 
-use byteorder::{ByteOrder, LittleEndian};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
 use encoding::{Encoding, DecoderTrap};
 use encoding::all::ISO_8859_1;
 
@@ -81,9 +84,36 @@ pub trait Decoder {
     /// This function allows us to decode a f64 float from raw data.
     fn decode_float_f64(&self, offset: usize) -> Result<f64>;
 
+    /// This function allows us to decode a big-endian u16 integer from raw data.
+    fn decode_integer_u16_be(&self, offset: usize) -> Result<u16>;
+
+    /// This function allows us to decode a big-endian u32 integer from raw data.
+    fn decode_integer_u32_be(&self, offset: usize) -> Result<u32>;
+
+    /// This function allows us to decode a big-endian u64 integer from raw data.
+    fn decode_integer_u64_be(&self, offset: usize) -> Result<u64>;
+
+    /// This function allows us to decode a big-endian i16 integer from raw data.
+    fn decode_integer_i16_be(&self, offset: usize) -> Result<i16>;
+
+    /// This function allows us to decode a big-endian i32 integer from raw data.
+    fn decode_integer_i32_be(&self, offset: usize) -> Result<i32>;
+
+    /// This function allows us to decode a big-endian i64 integer from raw data.
+    fn decode_integer_i64_be(&self, offset: usize) -> Result<i64>;
+
+    /// This function allows us to decode a big-endian f32 float from raw data.
+    fn decode_float_f32_be(&self, offset: usize) -> Result<f32>;
+
+    /// This function allows us to decode a big-endian f64 float from raw data.
+    fn decode_float_f64_be(&self, offset: usize) -> Result<f64>;
+
     /// This function allows us to decode a u32 encoded colour from raw data.
     fn decode_integer_colour_rgb(&self, offset: usize) -> Result<u32>;
 
+    /// This function allows us to decode a u32 encoded colour with alpha from raw data.
+    fn decode_integer_colour_rgba(&self, offset: usize) -> Result<u32>;
+
     /// This function allows us to decode an UTF-8 String  from raw data.
     fn decode_string_u8(&self, offset: usize, size: usize) -> Result<String>;
 
@@ -114,6 +144,9 @@ pub trait Decoder {
     /// This function allows us to decode an encoded RGB colour as a String from raw data.
     fn decode_string_colour_rgb(&self, offset: usize) -> Result<String>;
 
+    /// This function allows us to decode an encoded RGBA colour as a String from raw data.
+    fn decode_string_colour_rgba(&self, offset: usize) -> Result<String>;
+
     /// This function allows us to decode a boolean from a byte, moving the provided index to the byte where the next data starts.
     fn decode_packedfile_bool(&self, offset: usize, index: &mut usize) -> Result<bool>;
 
@@ -159,6 +192,9 @@ pub trait Decoder {
     /// This function allows us to decode an u32 encoded colour from raw data, moving the provided index to the byte where the next data starts.
     fn decode_packedfile_integer_colour_rgb(&self, offset: usize, index: &mut usize) -> Result<u32>;
 
+    /// This function allows us to decode an u32 encoded colour with alpha from raw data, moving the provided index to the byte where the next data starts.
+    fn decode_packedfile_integer_colour_rgba(&self, offset: usize, index: &mut usize) -> Result<u32>;
+
     /// This function allows us to decode an UTF-8 encoded String from raw data, moving the provided index to the byte where the next data starts.
     fn decode_packedfile_string_u8(&self, offset: usize, index: &mut usize) -> Result<String>;
 
@@ -180,8 +216,17 @@ pub trait Decoder {
     /// If true, there is a normal UTF-16 encoded String after that byte.
     fn decode_packedfile_optional_string_u16(&self, offset: usize, index: &mut usize) -> Result<String>;
 
+    /// This function allows us to decode an optional i32 encoded integer from raw data, moving the provided index to the byte where the next data starts.
+    ///
+    /// These integers's first byte it's a boolean that indicates if the integer is there. If false, the integer it's just that byte.
+    /// If true, there is a normal i32 encoded integer after that byte.
+    fn decode_packedfile_optional_integer_i32(&self, offset: usize, index: &mut usize) -> Result<Option<i32>>;
+
     /// This function allows us to decode an encoded RGB colour as a String from raw data, moving the provided index to the byte where the next data starts.
     fn decode_packedfile_string_colour_rgb(&self, offset: usize, index: &mut usize) -> Result<String>;
+
+    /// This function allows us to decode an encoded RGBA colour as a String from raw data, moving the provided index to the byte where the next data starts.
+    fn decode_packedfile_string_colour_rgba(&self, offset: usize, index: &mut usize) -> Result<String>;
 }
 
 /// Implementation of trait `Decoder` for `&[u8]`.
@@ -287,6 +332,11 @@ impl Decoder for [u8] {
         else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode an RGB colour:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
     }
 
+    fn decode_integer_colour_rgba(&self, offset: usize) -> Result<u32> {
+        if self.len() >= offset + 4 { Ok(LittleEndian::read_u32(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode an RGBA colour:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
     fn decode_string_u8_iso_8859_1(&self, offset: usize, size: usize) -> Result<String> {
         if self.len() >= offset + size {
             ISO_8859_1.decode(&self[offset..offset + size], DecoderTrap::Replace).map_err(|_| Error::from(ErrorKind::HelperDecodingEncodingError("<p>Error trying to decode an UTF-8 String.</p>".to_owned())))
@@ -340,6 +390,60 @@ impl Decoder for [u8] {
         else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode an RGB colour:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
     }
 
+    fn decode_string_colour_rgba(&self, offset: usize) -> Result<String> {
+        if self.len() >= offset + 4 {
+
+            // Unlike RGB colours, here all 8 digits are meaningful, so always keep them.
+            let value = format!("{:08X?}", LittleEndian::read_u32(&self[offset..]));
+            Ok(value)
+        }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode an RGBA colour:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    //---------------------------------------------------------------------------//
+    //                          Big-Endian Decoders
+    //---------------------------------------------------------------------------//
+
+    fn decode_integer_u16_be(&self, offset: usize) -> Result<u16> {
+        if self.len() >= offset + 2 { Ok(BigEndian::read_u16(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian u16 number:</p><ul><li>Required bytes: 2.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_integer_u32_be(&self, offset: usize) -> Result<u32> {
+        if self.len() >= offset + 4 { Ok(BigEndian::read_u32(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian u32 number:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_integer_u64_be(&self, offset: usize) -> Result<u64> {
+        if self.len() >= offset + 8 { Ok(BigEndian::read_u64(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian u64 number:</p><ul><li>Required bytes: 8.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_integer_i16_be(&self, offset: usize) -> Result<i16> {
+        if self.len() >= offset + 2 { Ok(BigEndian::read_i16(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian i16 number:</p><ul><li>Required bytes: 2.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_integer_i32_be(&self, offset: usize) -> Result<i32> {
+        if self.len() >= offset + 4 { Ok(BigEndian::read_i32(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian i32 number:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_integer_i64_be(&self, offset: usize) -> Result<i64> {
+        if self.len() >= offset + 8 { Ok(BigEndian::read_i64(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian i64 number:</p><ul><li>Required bytes: 8.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_float_f32_be(&self, offset: usize) -> Result<f32> {
+        if self.len() >= offset + 4 { Ok(BigEndian::read_f32(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian f32 number:</p><ul><li>Required bytes: 4.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
+    fn decode_float_f64_be(&self, offset: usize) -> Result<f64> {
+        if self.len() >= offset + 8 { Ok(BigEndian::read_f64(&self[offset..])) }
+        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode a big-endian f64 number:</p><ul><li>Required bytes: 8.</li><li>Provided bytes: {:?}.</li></ul>", offset.checked_sub(self.len()))).into()) }
+    }
+
     //---------------------------------------------------------------------------//
     //                              Indexed Decoders
     //---------------------------------------------------------------------------//
@@ -452,6 +556,12 @@ impl Decoder for [u8] {
         result
     }
 
+    fn decode_packedfile_integer_colour_rgba(&self, offset: usize, index: &mut usize) -> Result<u32> {
+        let result = self.decode_integer_colour_rgba(offset);
+        if result.is_ok() { *index += 4; }
+        result
+    }
+
     fn decode_packedfile_string_u8(&self, offset: usize, mut index: &mut usize) -> Result<String> {
         if let Ok(size) = self.decode_packedfile_integer_u16(offset, &mut index) {
             let result = self.decode_string_u8(offset + 2, size as usize);
@@ -506,9 +616,26 @@ impl Decoder for [u8] {
         else { Err(ErrorKind::HelperDecodingEncodingError("<p>Error trying to decode an UTF-16 Optional String:</p><p>The first byte is not a boolean.</p>".to_owned()).into()) }
     }
 
+    fn decode_packedfile_optional_integer_i32(&self, offset: usize, index: &mut usize) -> Result<Option<i32>> {
+        if let Ok(is) = self.decode_packedfile_bool(offset, index) {
+            if is {
+                let result = self.decode_packedfile_integer_i32(offset + 1, index);
+                if result.is_err() { *index -= 1 };
+                result.map(Some)
+            } else { Ok(None) }
+        }
+        else { Err(ErrorKind::HelperDecodingEncodingError("<p>Error trying to decode an Optional I32:</p><p>The first byte is not a boolean.</p>".to_owned()).into()) }
+    }
+
     fn decode_packedfile_string_colour_rgb(&self, offset: usize, index: &mut usize) -> Result<String> {
         let result = self.decode_string_colour_rgb(offset);
         if result.is_ok() { *index += 4; }
         result
     }
+
+    fn decode_packedfile_string_colour_rgba(&self, offset: usize, index: &mut usize) -> Result<String> {
+        let result = self.decode_string_colour_rgba(offset);
+        if result.is_ok() { *index += 4; }
+        result
+    }
 }
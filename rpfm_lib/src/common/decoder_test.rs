@@ -279,6 +279,18 @@ fn test_decode_string_colour_rgb() {
     assert_eq!(Decoder::decode_string_colour_rgb([0x87, 0x97].as_ref(), 0).is_err(), true);
 }
 
+/// Test to make sure the rgba colour decoder (`decode_string_colour_rgba()`) works and fails properly.
+#[test]
+fn test_decode_string_colour_rgba() {
+
+    // Check the decoding works for a proper encoded string, keeping the alpha digits.
+    assert_eq!(Decoder::decode_string_colour_rgba([0xFF, 0x04, 0x05, 0x80].as_ref(), 0).unwrap(), "800504FF");
+    assert_eq!(Decoder::decode_string_colour_rgba([0xFF, 0x04, 0x05, 0x00].as_ref(), 0).unwrap(), "000504FF");
+
+    // Check the decoder returns an error for a slice shorter than expected.
+    assert!(Decoder::decode_string_colour_rgba([0x87, 0x97].as_ref(), 0).is_err());
+}
+
 //---------------------------------------------------------------------------//
 //                          Indexed Decoders
 //---------------------------------------------------------------------------//
@@ -724,6 +736,33 @@ fn test_decode_packedfile_optional_string_u16() {
     }
 }
 
+/// Test to make sure the optional i32 specific decoder (`decode_packedfile_optional_integer_i32()`)
+/// works and fails properly.
+#[test]
+fn test_decode_packedfile_optional_integer_i32() {
+
+    // Check the decoding works for a nonexistent integer.
+    {
+        let mut index = 0;
+        assert_eq!(Decoder::decode_packedfile_optional_integer_i32([0].as_ref(), 0, &mut index).unwrap(), None);
+        assert_eq!(index, 1);
+    }
+
+    // Check the decoding works for a proper encoded integer.
+    {
+        let mut index = 0;
+        assert_eq!(Decoder::decode_packedfile_optional_integer_i32([1, 10, 0, 0, 0].as_ref(), 0, &mut index).unwrap(), Some(10));
+        assert_eq!(index, 5);
+    }
+
+    // Check the decoder returns an error for a slice shorter than the integer.
+    {
+        let mut index = 0;
+        assert!(Decoder::decode_packedfile_optional_integer_i32([1, 10, 0].as_ref(), 0, &mut index).is_err());
+        assert_eq!(index, 0);
+    }
+}
+
 /// Test to make sure the RGB decoder (`decode_packedfile_string_colour_rgb()`) works and fails properly.
 #[test]
 fn test_decode_packedfile_string_colour_rgb() {
@@ -15,10 +15,13 @@ This module contains the `Encoder` trait and his implementation for `Vec<u8>`. T
 to encode any type of data contained within a PackFile/PackedFile, so it can be saved to disk and
 read by the games.
 
+All the numbers are encoded in little-endian, except when using the `_be` variants. Most of the formats used by the games are little-endian,
+but some fields are big-endian, like the optimized record info of ESF files. Use the `_be` variants only for those fields.
+
 Note: If you change anything from here, remember to update the `encoder_test.rs` file for it.
 !*/
 
-use byteorder::{LittleEndian, WriteBytesExt};
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
 use encoding::all::ISO_8859_1;
 use encoding::types::{Encoding, EncoderTrap};
 
@@ -70,9 +73,36 @@ pub trait Encoder {
     /// This function allows us to encode a f64 float into the provided `Vec<u8>`.
     fn encode_float_f64(&mut self, float: f64);
 
+    /// This function allows us to encode a big-endian u16 integer into the provided `Vec<u8>`.
+    fn encode_integer_u16_be(&mut self, integer: u16);
+
+    /// This function allows us to encode a big-endian u32 integer into the provided `Vec<u8>`.
+    fn encode_integer_u32_be(&mut self, integer: u32);
+
+    /// This function allows us to encode a big-endian u64 integer into the provided `Vec<u8>`.
+    fn encode_integer_u64_be(&mut self, integer: u64);
+
+    /// This function allows us to encode a big-endian i16 integer into the provided `Vec<u8>`.
+    fn encode_integer_i16_be(&mut self, integer: i16);
+
+    /// This function allows us to encode a big-endian i32 integer into the provided `Vec<u8>`.
+    fn encode_integer_i32_be(&mut self, integer: i32);
+
+    /// This function allows us to encode a big-endian i64 integer into the provided `Vec<u8>`.
+    fn encode_integer_i64_be(&mut self, integer: i64);
+
+    /// This function allows us to encode a big-endian f32 float into the provided `Vec<u8>`.
+    fn encode_float_f32_be(&mut self, float: f32);
+
+    /// This function allows us to encode a big-endian f64 float into the provided `Vec<u8>`.
+    fn encode_float_f64_be(&mut self, float: f64);
+
     /// This function allows us to encode colour in integer format into the provided `Vec<u8>`.
     fn encode_integer_colour_rgb(&mut self, integer: u32);
 
+    /// This function allows us to encode colour with alpha in integer format into the provided `Vec<u8>`.
+    fn encode_integer_colour_rgba(&mut self, integer: u32);
+
     /// This function allows us to encode an UTF-8 String into the provided `Vec<u8>`.
     fn encode_string_u8(&mut self, string: &str);
 
@@ -107,6 +137,9 @@ pub trait Encoder {
 
     /// This function allows us to encode an UTF-16 Optional String into the provided `Vec<u8>`.
     fn encode_packedfile_optional_string_u16(&mut self, string: &str);
+
+    /// This function allows us to encode an Optional i32 integer into the provided `Vec<u8>`.
+    fn encode_packedfile_optional_integer_i32(&mut self, integer: Option<i32>);
 }
 
 /// Implementation of trait `Encoder` for `Vec<u8>`.
@@ -194,6 +227,10 @@ impl Encoder for Vec<u8> {
         self.write_u32::<LittleEndian>(integer).unwrap();
     }
 
+    fn encode_integer_colour_rgba(&mut self, integer: u32) {
+        self.write_u32::<LittleEndian>(integer).unwrap();
+    }
+
     fn encode_string_u8(&mut self, string: &str) {
         self.extend_from_slice(string.as_bytes());
     }
@@ -226,6 +263,42 @@ impl Encoder for Vec<u8> {
         }
     }
 
+    //---------------------------------------------------------------------------//
+    //                          Big-Endian Encoders
+    //---------------------------------------------------------------------------//
+
+    fn encode_integer_u16_be(&mut self, integer: u16) {
+        self.write_u16::<BigEndian>(integer).unwrap();
+    }
+
+    fn encode_integer_u32_be(&mut self, integer: u32) {
+        self.write_u32::<BigEndian>(integer).unwrap();
+    }
+
+    fn encode_integer_u64_be(&mut self, integer: u64) {
+        self.write_u64::<BigEndian>(integer).unwrap();
+    }
+
+    fn encode_integer_i16_be(&mut self, integer: i16) {
+        self.write_i16::<BigEndian>(integer).unwrap();
+    }
+
+    fn encode_integer_i32_be(&mut self, integer: i32) {
+        self.write_i32::<BigEndian>(integer).unwrap();
+    }
+
+    fn encode_integer_i64_be(&mut self, integer: i64) {
+        self.write_i64::<BigEndian>(integer).unwrap();
+    }
+
+    fn encode_float_f32_be(&mut self, float: f32) {
+        self.write_f32::<BigEndian>(float).unwrap();
+    }
+
+    fn encode_float_f64_be(&mut self, float: f64) {
+        self.write_f64::<BigEndian>(float).unwrap();
+    }
+
     //---------------------------------------------------------------------------//
     //                          Indexed Encoders
     //---------------------------------------------------------------------------//
@@ -261,4 +334,14 @@ impl Encoder for Vec<u8> {
             string.encode_utf16().for_each(|character| self.encode_integer_u16(character));
         }
     }
+
+    fn encode_packedfile_optional_integer_i32(&mut self, integer: Option<i32>) {
+        match integer {
+            Some(integer) => {
+                self.encode_bool(true);
+                self.encode_integer_i32(integer);
+            }
+            None => self.encode_bool(false),
+        }
+    }
 }
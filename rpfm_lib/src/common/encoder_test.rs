@@ -162,6 +162,16 @@ fn test_encode_integer_colour_rgb() {
     assert_eq!(data, vec![0x80, 0x26, 0x01, 0x00]);
 }
 
+/// Test to make sure the u32 integer encoder (`encode_integer_colour_rgba()`) works properly.
+#[test]
+fn test_encode_integer_colour_rgba() {
+
+    // Check the encoder works properly.
+    let mut data = vec![];
+    data.encode_integer_colour_rgba(0x80012680);
+    assert_eq!(data, vec![0x80, 0x26, 0x01, 0x80]);
+}
+
 /// Test to make sure the u8 string encoder (`encode_string_u8()`) works properly.
 #[test]
 fn test_encode_string_u8() {
@@ -265,3 +275,18 @@ fn test_encode_packedfile_optional_string_u16() {
     data.encode_packedfile_optional_string_u16("");
     assert_eq!(data, vec![0]);
 }
+
+/// Test to make sure the optional i32 specific encoder (`encode_packedfile_optional_integer_i32()`)
+/// works properly.
+#[test]
+fn test_encode_packedfile_optional_integer_i32() {
+
+    // Check the encoder works for a proper encoded integer.
+    let mut data = vec![];
+    data.encode_packedfile_optional_integer_i32(Some(10));
+    assert_eq!(data, vec![1, 10, 0, 0, 0]);
+
+    let mut data = vec![];
+    data.encode_packedfile_optional_integer_i32(None);
+    assert_eq!(data, vec![0]);
+}
@@ -0,0 +1,86 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing round-trip tests for the little-endian and big-endian numeric `Encoder` and `Decoder` implementations.
+!*/
+
+use crate::common::decoder::Decoder;
+use crate::common::encoder::Encoder;
+
+/// Test to make sure all the integer types survive an encode/decode cycle in both endiannesses.
+#[test]
+fn test_round_trip_integers() {
+
+    // Little-endian.
+    let mut data = vec![];
+    data.encode_integer_u16(0x1234);
+    data.encode_integer_u32(0x1234_5678);
+    data.encode_integer_u64(0x1234_5678_9ABC_DEF0);
+    data.encode_integer_i16(-0x1234);
+    data.encode_integer_i32(-0x1234_5678);
+    data.encode_integer_i64(-0x1234_5678_9ABC_DEF0);
+
+    assert_eq!(data[0..2], 0x1234u16.to_le_bytes());
+    assert_eq!(data.decode_integer_u16(0).unwrap(), 0x1234);
+    assert_eq!(data.decode_integer_u32(2).unwrap(), 0x1234_5678);
+    assert_eq!(data.decode_integer_u64(6).unwrap(), 0x1234_5678_9ABC_DEF0);
+    assert_eq!(data.decode_integer_i16(14).unwrap(), -0x1234);
+    assert_eq!(data.decode_integer_i32(16).unwrap(), -0x1234_5678);
+    assert_eq!(data.decode_integer_i64(20).unwrap(), -0x1234_5678_9ABC_DEF0);
+
+    // Big-endian.
+    let mut data = vec![];
+    data.encode_integer_u16_be(0x1234);
+    data.encode_integer_u32_be(0x1234_5678);
+    data.encode_integer_u64_be(0x1234_5678_9ABC_DEF0);
+    data.encode_integer_i16_be(-0x1234);
+    data.encode_integer_i32_be(-0x1234_5678);
+    data.encode_integer_i64_be(-0x1234_5678_9ABC_DEF0);
+
+    assert_eq!(data[0..2], 0x1234u16.to_be_bytes());
+    assert_eq!(data[2..6], 0x1234_5678u32.to_be_bytes());
+    assert_eq!(data.decode_integer_u16_be(0).unwrap(), 0x1234);
+    assert_eq!(data.decode_integer_u32_be(2).unwrap(), 0x1234_5678);
+    assert_eq!(data.decode_integer_u64_be(6).unwrap(), 0x1234_5678_9ABC_DEF0);
+    assert_eq!(data.decode_integer_i16_be(14).unwrap(), -0x1234);
+    assert_eq!(data.decode_integer_i32_be(16).unwrap(), -0x1234_5678);
+    assert_eq!(data.decode_integer_i64_be(20).unwrap(), -0x1234_5678_9ABC_DEF0);
+
+    // Check the decoders return an error when there are not enough bytes.
+    assert!(data.decode_integer_u64_be(22).is_err());
+}
+
+/// Test to make sure all the float types survive an encode/decode cycle in both endiannesses.
+#[test]
+fn test_round_trip_floats() {
+
+    // Little-endian.
+    let mut data = vec![];
+    data.encode_float_f32(-10.5);
+    data.encode_float_f64(1234.125);
+
+    assert_eq!(data[0..4], (-10.5f32).to_le_bytes());
+    assert_eq!(data.decode_float_f32(0).unwrap(), -10.5);
+    assert_eq!(data.decode_float_f64(4).unwrap(), 1234.125);
+
+    // Big-endian.
+    let mut data = vec![];
+    data.encode_float_f32_be(-10.5);
+    data.encode_float_f64_be(1234.125);
+
+    assert_eq!(data[0..4], (-10.5f32).to_be_bytes());
+    assert_eq!(data[4..12], 1234.125f64.to_be_bytes());
+    assert_eq!(data.decode_float_f32_be(0).unwrap(), -10.5);
+    assert_eq!(data.decode_float_f64_be(4).unwrap(), 1234.125);
+
+    // Check the decoders return an error when there are not enough bytes.
+    assert!(data.decode_float_f32_be(10).is_err());
+}
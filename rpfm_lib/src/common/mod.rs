@@ -45,6 +45,9 @@ mod decoder_test;
 #[cfg(test)]
 mod encoder_test;
 
+#[cfg(test)]
+mod endianness_test;
+
 /// These consts are used for dealing with Time-related operations.
 pub const WINDOWS_TICK: i64 = 10_000_000;
 pub const SEC_TO_UNIX_EPOCH: i64 = 11_644_473_600;
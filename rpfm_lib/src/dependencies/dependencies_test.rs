@@ -0,0 +1,72 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Dependencies` module.
+!*/
+
+use rpfm_error::ErrorKind;
+
+use crate::packedfile::DecodedPackedFile;
+use crate::packedfile::table::DecodedData;
+use crate::packfile::{PackFile, PFHVersion};
+use crate::packfile::packedfile::PackedFile;
+use crate::schema::{FieldType, Schema, VersionedFile};
+use crate::test_helpers::*;
+
+use super::Dependencies;
+
+#[test]
+fn test_find_orphans_without_cache() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true)]);
+    let schema = schema_with_db(&definition);
+    let table = db(&definition, &[vec![DecodedData::StringU8("orphan".to_owned())]]);
+
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    let path = vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), "test".to_owned()];
+    pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &path), true).unwrap();
+
+    // Without a dependencies cache we cannot know what the game references, so we must not report everything as orphaned.
+    assert!(Dependencies::default().find_orphans(&mut pack_file, &schema, TEST_TABLE_NAME, "key").is_err());
+}
+
+#[test]
+fn test_compare_with_vanilla() {
+    let definition_v1 = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let definition_v2 = definition(2, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false), field("extra", FieldType::Boolean, false)]);
+    let mut schema = Schema::default();
+    schema.add_versioned_file(&VersionedFile::DB(TEST_TABLE_NAME.to_owned(), vec![definition_v2.clone(), definition_v1.clone()]));
+
+    let row_v1 = |key: &str, value| vec![DecodedData::StringU8(key.to_owned()), DecodedData::I32(value)];
+    let row_v2 = |key: &str, value, extra| vec![DecodedData::StringU8(key.to_owned()), DecodedData::I32(value), DecodedData::Boolean(extra)];
+
+    // Vanilla files can be of an older version than the mod table, and they're all merged before comparing.
+    let vanilla_packed_files = [
+        ("data__", db(&definition_v1, &[row_v1("changed", 1), row_v1("removed", 2), row_v1("same", 3)])),
+        ("patch__", db(&definition_v2, &[row_v2("patched", 4, true)])),
+    ].iter().map(|(name, table)| {
+        let mut packed_file = PackedFile::new_from_decoded(&DecodedPackedFile::DB(table.clone()), &["db".to_owned(), TEST_TABLE_NAME.to_owned(), name.to_string()]);
+        packed_file.encode_and_clean_cache().unwrap();
+        packed_file
+    }).collect::<Vec<PackedFile>>();
+
+    let table = db(&definition_v2, &[row_v2("changed", 10, false), row_v2("same", 3, false), row_v2("patched", 4, true), row_v2("added", 5, false)]);
+    let vanilla_tables = Dependencies::decode_vanilla_tables(vanilla_packed_files, TEST_TABLE_NAME, &schema).unwrap();
+    assert_eq!(vanilla_tables.len(), 2);
+
+    let diff = Dependencies::merge_vanilla_tables(&table, vanilla_tables).unwrap().diff(&table).unwrap();
+    assert_eq!(diff.get_ref_added_rows(), &vec![row_v2("added", 5, false)]);
+    assert_eq!(diff.get_ref_removed_rows(), &vec![row_v2("removed", 2, false)]);
+    assert_eq!(diff.get_ref_changed_cells(), &vec![(vec!["changed".to_owned()], "value".to_owned(), DecodedData::I32(1), DecodedData::I32(10))]);
+
+    // No vanilla files means there is nothing to compare with, and without a dependencies cache there are no vanilla files to begin with.
+    assert_eq!(Dependencies::decode_vanilla_tables(vec![], TEST_TABLE_NAME, &schema).unwrap_err().kind(), &ErrorKind::NoTableInGameFilesToCompare);
+    assert!(Dependencies::default().compare_with_vanilla(&table, &schema).is_err());
+}
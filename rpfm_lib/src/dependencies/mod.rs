@@ -40,9 +40,15 @@ use crate::packfile::packedfile::PackedFileInfo;
 use crate::packfile::packedfile::CachedPackedFile;
 use crate::packedfile::{DecodedPackedFile, PackedFileType};
 use crate::packedfile::table::DependencyData;
+use crate::packedfile::table::db::TableDiff;
+use crate::packedfile::table::loc::Loc;
+use crate::schema::{FieldType, Schema};
 use crate::SCHEMA;
 use crate::settings::get_config_path;
 
+#[cfg(test)]
+mod dependencies_test;
+
 const BINARY_EXTENSION: &str = "pak2";
 const DEPENDENCIES_FOLDER: &str = "dependencies";
 
@@ -123,6 +129,32 @@ pub struct Dependencies {
     asskit_only_db_tables: Vec<DB>,
 }
 
+/// This struct represents a value in a DB table referencing data that doesn't exist in its referenced table.
+#[derive(Debug, Clone, PartialEq, Eq, GetRef, Serialize, Deserialize)]
+pub struct MissingReference {
+
+    /// Path of the table containing the reference.
+    path: Vec<String>,
+
+    /// Name of the table containing the reference.
+    table_name: String,
+
+    /// Key of the row containing the reference.
+    row_key: Vec<String>,
+
+    /// Name of the column containing the reference.
+    column: String,
+
+    /// Name of the referenced table.
+    referenced_table: String,
+
+    /// Name of the referenced column.
+    referenced_column: String,
+
+    /// The referenced value that could not be found.
+    value: String,
+}
+
 /// This struct contains the minimal data needed (mainly paths), to know what we have loaded in out dependencies.
 #[derive(Debug, Clone, GetRef, GetRefMut)]
 pub struct DependenciesInfo {
@@ -840,6 +872,190 @@ impl Dependencies {
         }
     }
 
+    /// This function checks the references of all the DB tables in the provided `PackFile`, returning the ones pointing to missing data.
+    ///
+    /// References are checked against the `PackFile` itself and the dependencies. Columns whose referenced data is localised,
+    /// only in the Assembly Kit, or not found at all are skipped, as we cannot tell if their values are valid.
+    pub fn check_references(&self, pack_file: &mut PackFile, schema: &Schema) -> Vec<MissingReference> {
+
+        // Tables need to be decoded to be checked. The ones that fail to decode are skipped.
+        pack_file.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).into_par_iter().for_each(|packed_file| {
+            let _ = packed_file.decode_no_locks(schema);
+        });
+
+        let vanilla_dependencies = self.get_db_and_loc_tables_from_cache(true, false, true, true).unwrap_or_default();
+        let mut missing_references = vec![];
+
+        for packed_file in pack_file.get_ref_packed_files_by_type(PackedFileType::DB, false) {
+            if let DecodedPackedFile::DB(table) = packed_file.get_ref_decoded() {
+                let dependency_data = DB::get_dependency_data(
+                    pack_file,
+                    table.get_ref_table_name(),
+                    table.get_ref_definition(),
+                    &vanilla_dependencies,
+                    self.get_ref_asskit_only_db_tables(),
+                    self,
+                    &[],
+                );
+
+                let fields = table.get_ref_definition().get_fields_processed();
+                let key_columns = table.get_key_column_indexes();
+                for row in table.get_ref_table_data() {
+                    for (column, field) in fields.iter().enumerate() {
+                        if let Some((referenced_table, referenced_column)) = field.get_is_reference() {
+                            if let Some(ref_data) = dependency_data.get(&(column as i32)) {
+                                if ref_data.referenced_column_is_localised || ref_data.referenced_table_is_ak_only || ref_data.data.is_empty() {
+                                    continue;
+                                }
+
+                                // Numeric cells with 0 are "empty" references and should not be checked.
+                                let value = row[column].data_to_string();
+                                let is_number = matches!(field.get_field_type(), FieldType::I32 | FieldType::I64);
+                                if value.is_empty() || (is_number && value == "0") || ref_data.data.contains_key(&value) {
+                                    continue;
+                                }
+
+                                missing_references.push(MissingReference {
+                                    path: packed_file.get_path().to_vec(),
+                                    table_name: table.get_ref_table_name().to_owned(),
+                                    row_key: DB::get_row_key_by_indexes(row, &key_columns),
+                                    column: field.get_name().to_owned(),
+                                    referenced_table: referenced_table.to_owned(),
+                                    referenced_column: referenced_column.to_owned(),
+                                    value,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        missing_references
+    }
+
+    /// This function returns the values of the provided column of a table in the provided `PackFile` that no table references.
+    ///
+    /// References are searched in the DB tables of the `PackFile` and its dependencies, vanilla and parent mods included,
+    /// using the reference data of their columns. The table name can be provided with or without the `_tables` suffix.
+    ///
+    /// If the dependencies cache is not generated or is out of date, this returns an error instead of ignoring the dependencies.
+    pub fn find_orphans(&self, pack_file: &mut PackFile, schema: &Schema, table: &str, column: &str) -> Result<Vec<String>> {
+        let table_name = table.strip_suffix("_tables").unwrap_or(table);
+        let table_name_tables = format!("{}_tables", table_name);
+
+        // Tables need to be decoded to be checked. The ones that fail to decode are skipped.
+        pack_file.get_ref_mut_packed_files_by_type(PackedFileType::DB, false).into_par_iter().for_each(|packed_file| {
+            let _ = packed_file.decode_no_locks(schema);
+        });
+
+        let dependencies = self.get_db_and_loc_tables_from_cache(true, false, true, true)?;
+        let tables = pack_file.get_ref_packed_files_by_type(PackedFileType::DB, false).into_iter()
+            .filter_map(|packed_file| if let DecodedPackedFile::DB(db) = packed_file.get_ref_decoded() { Some(db) } else { None })
+            .chain(dependencies.iter().filter_map(|packed_file| if let Ok(DecodedPackedFile::DB(db)) = packed_file.get_decoded_from_memory() { Some(db) } else { None }))
+            .collect::<Vec<&DB>>();
+
+        // Get the values of the column in the PackFile's tables, then remove the ones referenced anywhere.
+        let mut values = HashSet::new();
+        for packed_file in pack_file.get_ref_packed_files_by_type(PackedFileType::DB, false) {
+            if let DecodedPackedFile::DB(db) = packed_file.get_ref_decoded() {
+                if db.get_ref_table_name() == table_name_tables {
+                    let position = db.get_column_position_by_name(column)?;
+                    values.extend(db.get_ref_table_data().iter().map(|row| row[position].data_to_string()));
+                }
+            }
+        }
+
+        for db in tables {
+            for (position, field) in db.columns().iter().enumerate() {
+                if let Some((ref_table, ref_column)) = field.get_is_reference() {
+                    if ref_table == table_name && ref_column == column {
+                        db.get_ref_table_data().iter().for_each(|row| { values.remove(&row[position].data_to_string()); });
+                    }
+                }
+            }
+        }
+
+        let mut orphans = values.into_iter().collect::<Vec<String>>();
+        orphans.sort();
+        Ok(orphans)
+    }
+
+    /// This function returns all the vanilla Loc Tables of the game merged into a single one.
+    ///
+    /// In case of duplicated keys, the Loc Tables later in path order win. It returns `NoTableInGameFilesToCompare` if there are no vanilla Loc Tables.
+    pub fn get_vanilla_loc(&self) -> Result<Loc> {
+        let mut packed_files = self.get_db_and_loc_tables_from_cache(false, true, true, false)?;
+        packed_files.sort_by(|x, y| x.get_path().cmp(y.get_path()));
+
+        let locs = packed_files.iter()
+            .filter_map(|packed_file| if let Ok(DecodedPackedFile::Loc(loc)) = packed_file.get_decoded_from_memory() { Some((loc, 0)) } else { None })
+            .collect::<Vec<(&Loc, u32)>>();
+
+        if locs.is_empty() {
+            Err(ErrorKind::NoTableInGameFilesToCompare.into())
+        } else {
+            Ok(Loc::merge_with_priority(&locs))
+        }
+    }
+
+    /// This function returns the differences between the vanilla version of the provided table and the provided table.
+    ///
+    /// Rows are matched by their keys. See `get_vanilla_table` for how the vanilla table is built.
+    pub fn compare_with_vanilla(&self, table: &DB, schema: &Schema) -> Result<TableDiff> {
+        self.get_vanilla_table(table, schema)?.diff(table)
+    }
+
+    /// This function returns the vanilla version of the provided table, using the definition of the provided table.
+    ///
+    /// All the vanilla files of the table are merged. If they use a different version than the provided table, their data is converted to its definition first.
+    pub fn get_vanilla_table(&self, table: &DB, schema: &Schema) -> Result<DB> {
+        Self::merge_vanilla_tables(table, self.get_vanilla_tables(table.get_ref_table_name(), schema)?)
+    }
+
+    /// This function merges the provided vanilla tables into one, using the definition of the provided table.
+    fn merge_vanilla_tables(table: &DB, vanilla_tables: Vec<DB>) -> Result<DB> {
+        let mut entries = vec![];
+        for mut vanilla_table in vanilla_tables {
+            if vanilla_table.get_ref_definition() != table.get_ref_definition() {
+                vanilla_table.set_definition(table.get_ref_definition());
+            }
+
+            entries.extend_from_slice(vanilla_table.get_ref_table_data());
+        }
+
+        let mut vanilla_table = DB::new(table.get_ref_table_name(), None, table.get_ref_definition());
+        vanilla_table.set_table_data(&entries)?;
+        Ok(vanilla_table)
+    }
+
+    /// This function returns the vanilla files of the provided table, sorted by path, each one decoded with the definition of its own version.
+    pub fn get_vanilla_tables(&self, table_name: &str, schema: &Schema) -> Result<Vec<DB>> {
+        let folder = vec!["db".to_owned(), table_name.to_owned()];
+        let (packed_files, _) = self.get_packedfiles_from_game_files(&[PathType::Folder(folder)])?;
+        Self::decode_vanilla_tables(packed_files, table_name, schema)
+    }
+
+    /// This function decodes the tables with the provided name from the provided vanilla `PackedFiles`, sorted by path.
+    fn decode_vanilla_tables(mut packed_files: Vec<PackedFile>, table_name: &str, schema: &Schema) -> Result<Vec<DB>> {
+        packed_files.sort_by(|x, y| x.get_path().cmp(y.get_path()));
+
+        let mut tables = vec![];
+        for packed_file in &mut packed_files {
+            if let Ok(DecodedPackedFile::DB(vanilla_table)) = packed_file.decode_return_ref_no_locks(schema) {
+                if vanilla_table.get_ref_table_name() == table_name {
+                    tables.push(vanilla_table.clone());
+                }
+            }
+        }
+
+        if tables.is_empty() {
+            Err(ErrorKind::NoTableInGameFilesToCompare.into())
+        } else {
+            Ok(tables)
+        }
+    }
+
     pub fn get_most_relevant_files_by_paths(&self, paths: &[PathType]) -> Vec<PackedFile> {
         let mut packed_files = vec![];
 
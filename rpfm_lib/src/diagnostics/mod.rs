@@ -34,7 +34,7 @@ use crate::dependencies::Dependencies;
 use crate::games::VanillaDBTableNameLogic;
 use crate::GAME_SELECTED;
 use crate::packfile::{PackFile, PathType};
-use crate::packedfile::{table::{DecodedData, DependencyData}, DecodedPackedFile, PackedFileType};
+use crate::packedfile::{table::{DecodedData, DependencyData, db::ConstraintViolationReason}, DecodedPackedFile, PackedFileType};
 use crate::packfile::packedfile::{PackedFile, PackedFileInfo};
 use crate::schema::FieldType;
 use crate::SCHEMA;
@@ -377,7 +377,7 @@ impl Diagnostics {
             let mut columns_without_reference_table = vec![];
             let mut columns_with_reference_table_and_no_column = vec![];
             let mut keys: HashMap<String, Vec<(i32, i32)>> = HashMap::new();
-            let mut duplicated_combined_keys_already_marked = vec![];
+            let mut duplicated_combined_keys_already_marked = HashSet::new();
 
             // Before anything else, check if the table is outdated.
             if !Self::ignore_diagnostic(None, Some("OutdatedTable"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
@@ -598,28 +598,6 @@ impl Diagnostics {
                         row_keys_are_empty = false;
                     }
 
-                    if !Self::ignore_diagnostic(Some(field.get_name()), Some("EmptyKeyField"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
-                        if field.get_is_key() && field.get_field_type() != FieldType::OptionalStringU8 && field.get_field_type() != FieldType::Boolean && (cell_data.is_empty() || cell_data == "false") {
-                            diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
-                                cells_affected: vec![(row as i32, column as i32)],
-                                message: format!("Empty key for column \"{}\".", field.get_name()),
-                                report_type: TableDiagnosticReportType::EmptyKeyField,
-                                level: DiagnosticLevel::Warning,
-                            });
-                        }
-                    }
-
-                    if !Self::ignore_diagnostic(Some(field.get_name()), Some("ValueCannotBeEmpty"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
-                        if field.get_cannot_be_empty(Some(table.get_ref_table_name())) && cell_data.is_empty() {
-                            diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
-                                cells_affected: vec![(row as i32, column as i32)],
-                                message: format!("Empty value for column \"{}\".", field.get_name()),
-                                report_type: TableDiagnosticReportType::ValueCannotBeEmpty,
-                                level: DiagnosticLevel::Error,
-                            });
-                        }
-                    }
-
                     if field.get_is_key() {
                         row_keys.insert(column as i32, cell_data);
                     }
@@ -647,36 +625,57 @@ impl Diagnostics {
                     }
                 }
 
-                if !Self::ignore_diagnostic(None, Some("DuplicatedCombinedKeys"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                keys.insert(row_keys.values().join("| |"), row_keys.keys().map(|x| (row as i32, *x)).collect());
+            }
 
-                    // If this returns something, it means there is a duplicate.
-                    let combined_keys = row_keys.values().join("| |");
-                    if let Some(old_position) = keys.insert(combined_keys.to_owned(), row_keys.keys().map(|x| (row as i32, *x)).collect()) {
-                        if let Some(old_pos) = old_position.get(0) {
+            // Empty keys, empty values and duplicated keys are checked by the table itself.
+            for violation in table.check_constraints() {
+                let row = *violation.get_ref_row();
+                let column_name = violation.get_ref_column();
+                let cells_affected = violation.get_ref_columns().iter().map(|column| (row as i32, *column as i32)).collect::<Vec<(i32, i32)>>();
+                match violation.get_ref_reason() {
+                    ConstraintViolationReason::EmptyKey => {
+                        if !Self::ignore_diagnostic(Some(column_name), Some("EmptyKeyField"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
+                                cells_affected,
+                                message: format!("Empty key for column \"{}\".", column_name),
+                                report_type: TableDiagnosticReportType::EmptyKeyField,
+                                level: DiagnosticLevel::Warning,
+                            });
+                        }
+                    }
 
-                            // Mark previous row, if not yet marked.
-                            if !duplicated_combined_keys_already_marked.contains(&old_pos.0) {
-                                diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
-                                    cells_affected: old_position.to_vec(),
-                                    message: format!("Duplicated combined keys: {}.", &combined_keys),
-                                    report_type: TableDiagnosticReportType::DuplicatedCombinedKeys,
-                                    level: DiagnosticLevel::Error,
-                                });
+                    ConstraintViolationReason::EmptyValue => {
+                        if !Self::ignore_diagnostic(Some(column_name), Some("ValueCannotBeEmpty"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
+                                cells_affected,
+                                message: format!("Empty value for column \"{}\".", column_name),
+                                report_type: TableDiagnosticReportType::ValueCannotBeEmpty,
+                                level: DiagnosticLevel::Error,
+                            });
+                        }
+                    }
 
-                                duplicated_combined_keys_already_marked.push(old_pos.0);
-                            }
+                    ConstraintViolationReason::DuplicatedKey(first_row) => {
+                        if !Self::ignore_diagnostic(None, Some("DuplicatedCombinedKeys"), ignored_fields, ignored_diagnostics, ignored_diagnostics_for_fields) {
+                            let combined_keys = DB::get_row_key_by_indexes(&table.get_ref_table_data()[row], violation.get_ref_columns()).join("| |");
 
-                            // Mark current row, if not yet marked.
-                            if !duplicated_combined_keys_already_marked.contains(&(row as i32)) {
+                            // Mark the first row with these keys, if not yet marked.
+                            if duplicated_combined_keys_already_marked.insert(*first_row) {
                                 diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
-                                    cells_affected: row_keys.keys().map(|column| (row as i32, *column)).collect::<Vec<(i32, i32)>>(),
+                                    cells_affected: violation.get_ref_columns().iter().map(|column| (*first_row as i32, *column as i32)).collect(),
                                     message: format!("Duplicated combined keys: {}.", &combined_keys),
                                     report_type: TableDiagnosticReportType::DuplicatedCombinedKeys,
                                     level: DiagnosticLevel::Error,
                                 });
-
-                                duplicated_combined_keys_already_marked.push(row as i32);
                             }
+
+                            diagnostic.get_ref_mut_result().push(TableDiagnosticReport {
+                                cells_affected,
+                                message: format!("Duplicated combined keys: {}.", &combined_keys),
+                                report_type: TableDiagnosticReportType::DuplicatedCombinedKeys,
+                                level: DiagnosticLevel::Error,
+                            });
                         }
                     }
                 }
@@ -1029,6 +1028,9 @@ impl Diagnostics {
     /// This means that, as long as you change any `PackedFile` in the `PackFile`, you should trigger this. That way, the `Diagnostics`
     /// will always be up-to-date in an efficient way.
     ///
+    /// Tables referencing the tables in the provided paths are also re-checked, so their reference diagnostics are kept in sync.
+    /// The diagnostics of the rest of the `PackedFiles` are kept from the previous check.
+    ///
     /// If you passed the entire `PackFile` to this and it crashed, it's not an error. I forced that crash. If you want to do that,
     /// use the normal check function, because it's a lot more efficient than this one.
     pub fn update(&mut self, pack_file: &PackFile, updated_paths: &[PathType], dependencies: &Dependencies) {
@@ -1078,6 +1080,25 @@ impl Diagnostics {
             }
         }
 
+        // Diagnostics of deleted files need to go too, so remove them here using the paths we received.
+        for path_type in updated_paths {
+            match path_type {
+                PathType::File(path) => self.get_ref_mut_diagnostics().retain(|x| x.get_path() != path),
+                PathType::Folder(path) => self.get_ref_mut_diagnostics().retain(|x| !x.get_path().starts_with(path) || x.get_path().is_empty()),
+                _ => {}
+            }
+        }
+
+        // Tables referencing the updated ones need to be re-checked too, as their references may have been broken or fixed.
+        // We use the received paths for this, so we also catch references to deleted tables.
+        let updated_table_names = updated_paths.iter()
+            .filter_map(|path_type| match path_type {
+                PathType::File(path) | PathType::Folder(path) => if path.len() > 1 && path[0].to_lowercase() == "db" { Some(path[1].to_owned()) } else { None },
+                _ => None,
+            })
+            .collect::<HashSet<String>>();
+        packed_files.append(&mut Self::get_tables_referencing(pack_file, &updated_table_names));
+
         let mut packed_files_complete: Vec<&PackedFile> = vec![];
         let mut locs_added = false;
         for packed_file in &packed_files {
@@ -1267,6 +1288,28 @@ impl Diagnostics {
         });
     }
 
+    /// This function returns the DB `PackedFiles` of the `PackFile` with references to any of the provided tables.
+    ///
+    /// Only decoded tables are checked, as those are the only ones the diagnostics can use.
+    fn get_tables_referencing<'a>(pack_file: &'a PackFile, table_names: &HashSet<String>) -> Vec<&'a PackedFile> {
+        if table_names.is_empty() {
+            return vec![];
+        }
+
+        let short_table_names = table_names.iter()
+            .map(|table_name| table_name.strip_suffix("_tables").unwrap_or(table_name))
+            .collect::<HashSet<&str>>();
+
+        pack_file.get_ref_packed_files_by_type(PackedFileType::DB, false).into_iter()
+            .filter(|packed_file| {
+                if let DecodedPackedFile::DB(table) = packed_file.get_ref_decoded() {
+                    !table_names.contains(table.get_ref_table_name()) && table.get_ref_definition().get_fields_processed().iter()
+                        .any(|field| matches!(field.get_is_reference(), Some((ref_table, _)) if short_table_names.contains(&**ref_table)))
+                } else { false }
+            })
+            .collect()
+    }
+
     /// This function returns the PackedFileInfo for all the PackedFiles with the provided paths.
     pub fn get_update_paths_packed_file_info(&self, pack_file: &PackFile, paths: &[PathType]) -> Vec<PackedFileInfo> {
         let paths = paths.iter().filter_map(|x| if let PathType::File(path) = x { Some(&**path) } else { None }).collect();
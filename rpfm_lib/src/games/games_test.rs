@@ -0,0 +1,77 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Games` module.
+!*/
+
+use std::env::temp_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+
+use rpfm_error::ErrorKind;
+
+use crate::packfile::{PackFile, PFHFileType, PFHVersion};
+use crate::SUPPORTED_GAMES;
+
+use super::supported_games::{KEY_TROY, KEY_WARHAMMER_2};
+
+#[test]
+fn test_troy_game_info() {
+    let game = SUPPORTED_GAMES.get_supported_game_from_key(KEY_TROY).unwrap();
+    assert_eq!(game.get_game_key_name(), KEY_TROY);
+    assert_eq!(game.get_schema_name(), "schema_troy.ron");
+    assert_eq!(game.get_dependencies_cache_file_name(), "troy.pak2");
+    assert_eq!(game.get_pfh_version_by_file_type(PFHFileType::Mod), PFHVersion::PFH6);
+    assert_eq!(game.get_pfh_version_by_file_type(PFHFileType::Release), PFHVersion::PFH5);
+
+    // Mods for Troy are PFH6, so they must be readable.
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/PFH6_test.pack"), false).unwrap();
+    assert_eq!(pack_file.get_pfh_version(), game.get_pfh_version_by_file_type(PFHFileType::Mod));
+}
+
+#[test]
+fn test_read_manifest() {
+    let game = SUPPORTED_GAMES.get_supported_game_from_key(KEY_TROY).unwrap();
+    let game_path = temp_dir().join("rpfm_test_read_manifest");
+    let data_path = game_path.join("data");
+    let _ = remove_dir_all(&game_path);
+    create_dir_all(&data_path).unwrap();
+
+    // One CA PackFile, one PackFile replaced by a mod, and one PackFile missing from the data folder.
+    // Only the header is read, so that's all we need to write.
+    for (name, pfh_file_type) in [("data.pack", PFHFileType::Release), ("movies.pack", PFHFileType::Mod)] {
+        write(data_path.join(name), [&b"PFH5"[..], &pfh_file_type.get_value().to_le_bytes()].concat()).unwrap();
+    }
+
+    write(data_path.join("manifest.txt"), "data.pack\t1024\t1\nmovies.pack\t2048\t0\nmissing.pack\t4096\t1\nvideo/intro.ca_vp8\t512\t1\n").unwrap();
+    let manifest = game.read_manifest(&game_path);
+
+    write(data_path.join("manifest.txt"), "data.pack\tnot_a_size\n").unwrap();
+    let manifest_malformed = game.read_manifest(&game_path);
+
+    // Clean up before checking anything, so a failed check doesn't leave the folder behind.
+    remove_dir_all(&game_path).unwrap();
+
+    let manifest = manifest.unwrap();
+    assert_eq!(manifest.iter().map(|x| x.get_ref_relative_path().as_str()).collect::<Vec<&str>>(), vec!["data.pack", "movies.pack", "missing.pack"]);
+    assert_eq!(*manifest[1].get_ref_size(), 2048);
+    assert_eq!(*manifest[1].get_ref_belongs_to_base_game(), Some(0));
+    assert_eq!(manifest.iter().map(|x| *x.get_ref_is_ca_packfile()).collect::<Vec<bool>>(), vec![true, false, false]);
+
+    assert!(matches!(manifest_malformed.unwrap_err().kind(), ErrorKind::ManifestError));
+}
+
+#[test]
+fn test_games_using_schema() {
+    assert_eq!(SUPPORTED_GAMES.games_using_schema("schema_troy.ron"), vec![KEY_TROY]);
+    assert_eq!(SUPPORTED_GAMES.games_using_schema("schema_wh2.ron"), vec![KEY_WARHAMMER_2]);
+    assert!(SUPPORTED_GAMES.games_using_schema("schema_unknown.ron").is_empty());
+}
@@ -16,18 +16,21 @@ Module that contains the GameInfo definition and stuff related with it.
 use std::collections::HashMap;
 use std::fs::{DirBuilder, File};
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rpfm_error::{Result, Error, ErrorKind};
 use rpfm_macros::*;
 
 use crate::common::get_files_from_subdir;
 use crate::settings::get_config_path;
-use crate::packfile::{Manifest, PFHFileType, PFHVersion};
+use crate::packfile::{Manifest, ManifestEntry, PFHFileType, PFHVersion};
 use crate::SETTINGS;
 
 pub mod supported_games;
 
+#[cfg(test)]
+mod games_test;
+
 const BRAZILIAN: &str = "br";
 const SIMPLIFIED_CHINESE: &str = "cn";
 const CZECH: &str = "cz";
@@ -386,6 +389,27 @@ impl GameInfo {
         Some(path)
     }
 
+    /// This function returns the entries of the `manifest.txt` of the game installed in the provided path that are PackFiles, in the order they're listed.
+    ///
+    /// The manifest only lists files released by CA, so all the entries returned are vanilla PackFiles. If the manifest has malformed lines, this returns a `ManifestError`.
+    /// Each entry is marked as a CA PackFile if the file in the data folder has one of the types CA uses. Missing or unreadable files are not marked.
+    pub fn read_manifest(&self, game_path: &Path) -> Result<Vec<ManifestEntry>> {
+        let data_path = self.install_data.values()
+            .map(|install_data| game_path.join(install_data.get_ref_data_path()))
+            .find(|data_path| data_path.join("manifest.txt").is_file())
+            .ok_or(ErrorKind::GameManifestNotFound)?;
+
+        let manifest = Manifest::read_from_folder(&data_path)?;
+        Ok(manifest.0.into_iter()
+            .filter(|entry| entry.get_ref_relative_path().ends_with(".pack"))
+            .map(|mut entry| {
+                let is_ca_packfile = PFHFileType::read_from_path(&data_path.join(entry.get_ref_relative_path())).map_or(false, |pfh_file_type| pfh_file_type.is_ca_type());
+                entry.set_is_ca_packfile(is_ca_packfile);
+                entry
+            })
+            .collect())
+    }
+
     /// This function returns if we should use the manifest of the game (if found) to get the vanilla PackFiles, or if we should get them from out hardcoded list.
     pub fn use_manifest(&self) -> Result<bool> {
         let install_type = self.get_install_type()?;
@@ -411,7 +435,7 @@ impl GameInfo {
         } else {
 
             // Try to get the manifest, if exists.
-            match Manifest::read_from_game_selected() {
+            match Manifest::read_from_game(self) {
                 Ok(manifest) => {
                     let pack_file_names = manifest.0.iter().filter_map(|x|
                         if x.get_ref_relative_path().ends_with(".pack") {
@@ -1316,4 +1316,19 @@ impl SupportedGames {
     pub fn get_games(&self) -> Vec<&GameInfo> {
         self.games.values().collect::<Vec<&GameInfo>>()
     }
+
+    /// This function returns the full list of supported games, along with their keys.
+    pub fn get_games_with_keys(&self) -> Vec<(&'static str, &GameInfo)> {
+        self.games.iter().map(|(key, game)| (*key, game)).collect()
+    }
+
+    /// This function returns the keys of all the games using the provided schema file.
+    ///
+    /// Useful to know which games are affected by a change in a schema.
+    pub fn games_using_schema(&self, schema_name: &str) -> Vec<&'static str> {
+        self.games.iter()
+            .filter(|(_, game)| game.get_schema_name() == schema_name)
+            .map(|(key, _)| *key)
+            .collect()
+    }
 }
@@ -558,11 +558,21 @@ impl GlobalSearch {
                         self.replace_match(&mut string, matching_mode);
                         *field = string.parse::<i64>()?;
                     }
+                    DecodedData::OptionalI32(ref mut field) => {
+                        let mut string = field.map(|field| field.to_string()).unwrap_or_default();
+                        self.replace_match(&mut string, matching_mode);
+                        *field = if string.is_empty() { None } else { Some(string.parse::<i32>()?) };
+                    }
                     DecodedData::ColourRGB(ref mut field) => {
                         let mut string = field.to_string();
                         self.replace_match(&mut string, matching_mode);
                         *field = string.parse::<u32>()?;
                     }
+                    DecodedData::ColourRGBA(ref mut field) => {
+                        let mut string = field.to_string();
+                        self.replace_match(&mut string, matching_mode);
+                        *field = string.parse::<u32>()?;
+                    }
                     DecodedData::StringU8(ref mut field) |
                     DecodedData::StringU16(ref mut field) |
                     DecodedData::OptionalStringU8(ref mut field) |
@@ -619,7 +629,9 @@ impl GlobalSearch {
                     DecodedData::I16(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
                     DecodedData::I32(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
                     DecodedData::I64(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
+                    DecodedData::OptionalI32(ref data) => if let Some(data) = data { self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64) },
                     DecodedData::ColourRGB(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
+                    DecodedData::ColourRGBA(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
 
                     DecodedData::StringU8(ref data) |
                     DecodedData::StringU16(ref data) |
@@ -649,7 +661,9 @@ impl GlobalSearch {
                     DecodedData::I16(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
                     DecodedData::I32(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
                     DecodedData::I64(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
+                    DecodedData::OptionalI32(ref data) => if let Some(data) = data { self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64) },
                     DecodedData::ColourRGB(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
+                    DecodedData::ColourRGBA(ref data) => self.match_decoded_data(&data.to_string(), matching_mode, &mut matches.matches, table_data.get_ref_definition(), column_number as u32, row_number as i64),
 
                     DecodedData::StringU8(ref data) |
                     DecodedData::StringU16(ref data) |
@@ -33,6 +33,7 @@ use crate::schema::patch::SchemaPatches;
 use crate::schema::Schema;
 use crate::settings::Settings;
 
+pub mod annotations;
 pub mod assembly_kit;
 pub mod common;
 pub mod dependencies;
@@ -47,6 +48,9 @@ pub mod settings;
 pub mod tips;
 pub mod updater;
 
+#[cfg(test)]
+mod test_helpers;
+
 // Statics, so we don't need to pass them everywhere to use them.
 lazy_static! {
 
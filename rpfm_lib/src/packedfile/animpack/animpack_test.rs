@@ -0,0 +1,71 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `AnimPack` module.
+!*/
+
+use std::env::temp_dir;
+use std::fs::{create_dir_all, read, remove_dir_all, write};
+
+use rpfm_error::ErrorKind;
+
+use crate::common::encoder::Encoder;
+
+use super::AnimPack;
+
+#[test]
+fn test_animpack_round_trip() {
+    let source = temp_dir().join("rpfm_test_animpack_round_trip_source");
+    let _ = remove_dir_all(&source);
+    create_dir_all(source.join("animations/animation_tables")).unwrap();
+    write(source.join("animations/animation_tables/test.bin"), [1, 2, 3, 4]).unwrap();
+    write(source.join("animations/test.frg"), [5, 6]).unwrap();
+
+    let anim_pack = AnimPack::from_folder(&source).unwrap();
+    assert_eq!(anim_pack.get_file_list(), vec!["animations/animation_tables/test.bin".to_owned(), "animations/test.frg".to_owned()]);
+
+    let destination = temp_dir().join("rpfm_test_animpack_round_trip_destination");
+    let _ = remove_dir_all(&destination);
+    let extracted = anim_pack.extract_all(&destination).unwrap();
+    let extracted_data = read(destination.join("animations/test.frg"));
+    let repacked = AnimPack::from_folder(&destination);
+
+    // Clean up before checking anything, so a failed check doesn't leave the folders behind.
+    remove_dir_all(&source).unwrap();
+    remove_dir_all(&destination).unwrap();
+
+    assert_eq!(extracted, anim_pack.get_file_list());
+    assert_eq!(extracted_data.unwrap(), vec![5, 6]);
+
+    // Repacking the extracted files must give us back the same AnimPack.
+    assert_eq!(AnimPack::read(&anim_pack.save()).unwrap(), repacked.unwrap());
+}
+
+#[test]
+fn test_animpack_extract_all_invalid_paths() {
+    let destination = temp_dir().join("rpfm_test_animpack_extract_all_invalid_paths");
+    for invalid_path in ["../escaped.bin", "/absolute.bin", "animations//test.bin", "animations/./test.bin", "animations/"] {
+        let mut data = vec![];
+        data.encode_integer_i32(2);
+        data.encode_packedfile_string_u8("animations/valid.bin");
+        data.encode_integer_i32(1);
+        data.push(1);
+        data.encode_packedfile_string_u8(invalid_path);
+        data.encode_integer_i32(1);
+        data.push(2);
+
+        // An invalid path anywhere in the AnimPack must fail before anything is written.
+        let _ = remove_dir_all(&destination);
+        let anim_pack = AnimPack::read(&data).unwrap();
+        assert!(matches!(anim_pack.extract_all(&destination).unwrap_err().kind(), ErrorKind::AnimPackInvalidPath(_)));
+        assert!(!destination.exists());
+    }
+}
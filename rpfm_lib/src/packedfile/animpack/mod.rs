@@ -25,13 +25,18 @@ use rayon::prelude::*;
 use serde_derive::{Serialize, Deserialize};
 
 use std::convert::TryFrom;
+use std::fs::{create_dir_all, read, remove_file, write};
+use std::path::{Component, Path};
 
-use rpfm_error::{Error, Result};
+use rpfm_error::{Error, ErrorKind, Result};
 
-use crate::common::{decoder::Decoder, encoder::Encoder};
+use crate::common::{decoder::Decoder, encoder::Encoder, get_files_from_subdir};
 use crate::packfile::{PackFileInfo, PathType};
 use crate::packfile::packedfile::{PackedFile, PackedFileInfo};
 
+#[cfg(test)]
+mod animpack_test;
+
 pub const EXTENSION: &str = ".animpack";
 
 //---------------------------------------------------------------------------//
@@ -101,6 +106,72 @@ impl AnimPack {
         data
     }
 
+    /// This function writes every file of the AnimPack to disk under `dest`, recreating their internal folder structure.
+    ///
+    /// Paths are validated before writing anything, and if writing a file fails, the files already written are removed.
+    ///
+    /// It returns the internal paths of the extracted files.
+    pub fn extract_all(&self, dest: &Path) -> Result<Vec<String>> {
+
+        // Do not let a malformed AnimPack write outside the destination folder. Each part of a path must be a single, normal component.
+        for packed_file in &self.packed_files {
+            if packed_file.path.is_empty() || !packed_file.path.iter().all(|x| Self::is_valid_path_component(x)) {
+                return Err(ErrorKind::AnimPackInvalidPath(packed_file.path.join("/")).into());
+            }
+        }
+
+        let mut written_paths = vec![];
+        for packed_file in &self.packed_files {
+            let mut path = dest.to_path_buf();
+            packed_file.path.iter().for_each(|x| path.push(x));
+
+            let result = match path.parent() {
+                Some(parent) => create_dir_all(parent).and_then(|_| write(&path, &packed_file.data)),
+                None => write(&path, &packed_file.data),
+            };
+
+            if let Err(error) = result {
+                written_paths.iter().for_each(|path| { let _ = remove_file(path); });
+                return Err(error.into());
+            }
+
+            written_paths.push(path);
+        }
+
+        Ok(self.packed_files.iter().map(|x| x.path.join("/")).collect())
+    }
+
+    /// This function checks if the provided part of an internal path is exactly one normal path component.
+    fn is_valid_path_component(component: &str) -> bool {
+        let mut components = Path::new(component).components();
+        matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+    }
+
+    /// This function creates an `AnimPack` from the files inside the provided folder, using their paths relative to it as internal paths.
+    pub fn from_folder(src: &Path) -> Result<Self> {
+        let mut file_paths = get_files_from_subdir(src, true)?;
+        file_paths.sort();
+
+        let mut packed_files = vec![];
+        for file_path in &file_paths {
+            let path = file_path.strip_prefix(src)
+                .map_err(|_| ErrorKind::IOReadFile(file_path.to_path_buf()))?
+                .components()
+                .map(|x| x.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>();
+
+            let data = read(file_path)?;
+            packed_files.push(AnimPacked {
+                path,
+                data,
+            });
+        }
+
+        Ok(Self {
+            packed_files,
+        })
+    }
+
     /// This function returns the entire list of paths contained within the provided AnimPack.
     pub fn get_file_list(&self) -> Vec<String> {
         self.packed_files.iter()
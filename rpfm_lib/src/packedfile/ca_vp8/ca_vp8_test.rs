@@ -0,0 +1,94 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `CaVp8` module.
+!*/
+
+use rpfm_error::ErrorKind;
+
+use crate::common::encoder::Encoder;
+
+use super::{CaVp8, SupportedFormats, KEY_FRAME_MARKER};
+
+/// This function builds a 640x360 IVF video at 25 fps, with a key frame followed by a normal frame.
+fn ivf_video(codec_four_cc: &str) -> Vec<u8> {
+    let frames = [[&[0x10, 0x02, 0x00][..], KEY_FRAME_MARKER, &[0x80, 0x02, 0x68, 0x01]].concat(), vec![0x31, 0x01, 0x00, 0x00, 0x00, 0x00]];
+
+    let mut data = vec![];
+    data.encode_string_u8("DKIF");
+    data.encode_integer_i16(0);
+    data.encode_integer_u16(32);
+    data.encode_string_u8(codec_four_cc);
+    data.encode_integer_u16(640);
+    data.encode_integer_u16(360);
+    data.encode_integer_u32(25);
+    data.encode_integer_u32(1);
+    data.encode_integer_u32(frames.len() as u32);
+    data.encode_integer_u32(0);
+
+    for (index, frame) in frames.iter().enumerate() {
+        data.encode_integer_u32(frame.len() as u32);
+        data.encode_integer_u64(index as u64);
+        data.extend_from_slice(frame);
+    }
+    data
+}
+
+#[test]
+fn test_ca_vp8_read_info() {
+    let ivf = ivf_video("VP80");
+    let info = CaVp8::read_info(&ivf).unwrap();
+    assert_eq!(*info.get_ref_format(), SupportedFormats::Ivf);
+    assert_eq!(*info.get_ref_version(), 0);
+    assert_eq!(info.get_ref_codec_four_cc(), "VP80");
+    assert_eq!((*info.get_ref_width(), *info.get_ref_height()), (640, 360));
+    assert_eq!(*info.get_ref_framerate(), 25.0);
+    assert_eq!(*info.get_ref_num_frames(), 2);
+    assert!((info.get_ref_duration() - 2.0 / 25.0).abs() < f32::EPSILON);
+
+    // Only the header is needed, so it must work even if the frames are missing.
+    assert_eq!(CaVp8::read_info(&ivf[..32]).unwrap(), info);
+
+    let camv = CaVp8::from_ivf(&ivf).unwrap().save();
+    let info = CaVp8::read_info(&camv).unwrap();
+    assert_eq!(*info.get_ref_format(), SupportedFormats::Camv);
+    assert_eq!(*info.get_ref_version(), 1);
+    assert_eq!((*info.get_ref_width(), *info.get_ref_height()), (640, 360));
+    assert_eq!(*info.get_ref_framerate(), 25.0);
+    assert_eq!(*info.get_ref_num_frames(), 2);
+
+    let error = CaVp8::read_info(b"RIFF0000AVI LIST0000hdrl").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::CaVp8Decode(_)));
+}
+
+#[test]
+fn test_ca_vp8_ivf_conversion() {
+    let ivf = ivf_video("VP80");
+
+    // IVF -> CA_VP8 -> IVF must give back the same video. 25 fps is 40ms per frame, so the framerate survives CAMV headers as it is.
+    let video = CaVp8::from_ivf(&ivf).unwrap();
+    assert_eq!(video.get_format(), SupportedFormats::Camv);
+    assert_eq!(video.get_version(), 1);
+    assert_eq!(video.get_ref_frame_table().len(), 2);
+
+    let camv = video.save();
+    assert_eq!(CaVp8::read(camv.to_vec()).unwrap().to_ivf().unwrap(), ivf);
+
+    // CAMV videos with no milliseconds per frame have no valid framerate.
+    let mut broken_camv = camv;
+    broken_camv[16..20].copy_from_slice(&0f32.to_le_bytes());
+    assert!(CaVp8::read(broken_camv).unwrap().to_ivf().is_err());
+
+    // Only complete VP8 IVF videos can be converted.
+    assert!(CaVp8::from_ivf(&CaVp8::from_ivf(&ivf).unwrap().save()).is_err());
+    assert!(CaVp8::from_ivf(&ivf_video("VP90")).is_err());
+    assert!(CaVp8::from_ivf(&ivf[..ivf.len() - 1]).is_err());
+}
@@ -25,9 +25,13 @@ use serde_derive::{Serialize, Deserialize};
 use fraction::GenericFraction;
 
 use rpfm_error::{ErrorKind, Result};
+use rpfm_macros::*;
 
 use crate::common::{decoder::Decoder, encoder::Encoder};
 
+#[cfg(test)]
+mod ca_vp8_test;
+
 /// Extensions used by CA_VP8 PackedFiles.
 pub const EXTENSION: &str = ".ca_vp8";
 
@@ -37,6 +41,12 @@ pub const SIGNATURE_IVF: &str = "DKIF";
 /// Signature/Magic Numbers/Whatever of a CAMV video file.
 pub const SIGNATURE_CAMV: &str = "CAMV";
 
+/// FourCC of the only codec supported in CA_VP8 videos.
+pub const CODEC_FOUR_CC_VP8: &str = "VP80";
+
+/// Version used by CA in CAMV videos.
+const VERSION_CAMV: i16 = 1;
+
 /// Key frame marker of a frame in IVF format.
 pub const KEY_FRAME_MARKER: &[u8; 3] = &[0x9D, 0x01, 0x2A];
 
@@ -82,6 +92,35 @@ pub struct CaVp8 {
     frame_data: Vec<u8>,
 }
 
+/// This holds the metadata of a CA_VP8 PackedFile, decoded from its header without touching the frames.
+#[derive(PartialEq, Clone, Debug, GetRef, Serialize, Deserialize)]
+pub struct CaVp8Info {
+
+    /// Format of the file.
+    format: SupportedFormats,
+
+    /// Version of the file.
+    version: i16,
+
+    /// Codec FourCC (usually 'VP80').
+    codec_four_cc: String,
+
+    /// Width of the video in pixels.
+    width: u16,
+
+    /// Height of the video in pixels.
+    height: u16,
+
+    /// Framerate of the video.
+    framerate: f32,
+
+    /// Number of frames on the video.
+    num_frames: u32,
+
+    /// Total duration of the video, in seconds.
+    duration: f32,
+}
+
 /// This enum contains the list of formats RPFM supports.
 #[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum SupportedFormats {
@@ -131,6 +170,38 @@ impl CaVp8 {
         }
     }
 
+    /// This function decodes the metadata of a video from its header, without reading the frame data.
+    ///
+    /// Useful to check the dimensions and framerate of big videos quickly.
+    pub fn read_info(packed_file_data: &[u8]) -> Result<CaVp8Info> {
+        let (format, framerate) = match &*packed_file_data.decode_string_u8(0, 4)? {
+            SIGNATURE_CAMV => (SupportedFormats::Camv, 1_000f32 / packed_file_data.decode_float_f32(16)?),
+            SIGNATURE_IVF => {
+                let timebase_denominator = packed_file_data.decode_integer_u32(16)?;
+                let timebase_numerator = packed_file_data.decode_integer_u32(20)?;
+                (SupportedFormats::Ivf, timebase_denominator as f32 / timebase_numerator as f32)
+            }
+            _ => return Err(ErrorKind::CaVp8Decode("Unknown video format.".to_string()).into()),
+        };
+
+        // The frame count is in a different place depending on the format.
+        let num_frames = match format {
+            SupportedFormats::Camv => packed_file_data.decode_integer_u32(32)?,
+            SupportedFormats::Ivf => packed_file_data.decode_integer_u32(24)?,
+        };
+
+        Ok(CaVp8Info {
+            format,
+            version: packed_file_data.decode_integer_i16(4)?,
+            codec_four_cc: packed_file_data.decode_string_u8(8, 4)?,
+            width: packed_file_data.decode_integer_u16(12)?,
+            height: packed_file_data.decode_integer_u16(14)?,
+            framerate,
+            num_frames,
+            duration: if framerate > 0.0 { num_frames as f32 / framerate } else { 0.0 },
+        })
+    }
+
     /// This function takes a `CaVp8` and encodes it to `Vec<u8>`.
     pub fn save(&self) -> Vec<u8> {
         match self.format {
@@ -139,6 +210,35 @@ impl CaVp8 {
         }
     }
 
+    /// This function rewraps the frames of the video into a standard IVF container, so it can be played by external players.
+    pub fn to_ivf(&self) -> Result<Vec<u8>> {
+        if !self.framerate.is_normal() || self.framerate < 0.0 {
+            return Err(ErrorKind::CaVp8Decode(format!("Invalid framerate: {}.", self.framerate)).into());
+        }
+
+        Ok(self.save_ivf())
+    }
+
+    /// This function creates a `CaVp8` in CA's format from a standard IVF video, so it can be imported back into a PackFile.
+    ///
+    /// Codec, dimensions and framerate are kept from the IVF header. The rest of the header is filled with the values CA uses.
+    /// Only VP8 videos are supported.
+    pub fn from_ivf(data: &[u8]) -> Result<Self> {
+        if data.decode_string_u8(0, 4).ok().as_deref() != Some(SIGNATURE_IVF) {
+            return Err(ErrorKind::CaVp8Decode("The file is not an IVF video.".to_string()).into());
+        }
+
+        let codec_four_cc = data.decode_string_u8(8, 4)?;
+        if codec_four_cc != CODEC_FOUR_CC_VP8 {
+            return Err(ErrorKind::CaVp8Decode(format!("Unsupported codec \"{}\". Only VP8 ({}) videos can be converted to CA_VP8.", codec_four_cc, CODEC_FOUR_CC_VP8)).into());
+        }
+
+        let mut video = Self::read_ivf(data.to_vec())?;
+        video.format = SupportedFormats::Camv;
+        video.version = VERSION_CAMV;
+        Ok(video)
+    }
+
     /// This function creates a `CaVp8` from a `Vec<u8>` containing a video of CAMV format.
     ///
     /// NOTE: this takes a whole vector, not a reference. The reason is this vector can by enormous and this way
@@ -233,6 +333,10 @@ impl CaVp8 {
                 offset: frame_offset,
                 size,
             };
+            if offset + frame.size as usize > packed_file_data.len() {
+                return Err(ErrorKind::CaVp8Decode("Incorrect/Unknown Frame size.".to_string()).into());
+            }
+
             frame_data.extend_from_slice(&packed_file_data[offset..offset + frame.size as usize]);
             offset += frame.size as usize;
             frame_offset += frame.size;
@@ -256,7 +360,7 @@ impl CaVp8 {
     fn save_camv(&self) -> Vec<u8> {
         let mut packed_file = vec![];
         packed_file.encode_string_u8(SIGNATURE_CAMV);
-        packed_file.encode_integer_i16(1);
+        packed_file.encode_integer_i16(VERSION_CAMV);
         packed_file.encode_integer_u16(HEADER_LENGTH_CAMV);
         packed_file.encode_string_u8(&self.codec_four_cc);
         packed_file.encode_integer_u16(self.width);
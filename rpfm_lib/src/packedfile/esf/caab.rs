@@ -1122,7 +1122,7 @@ impl ESF {
                     info |= (value.version as u16) << 9;
                     info |= record_names.iter().position(|x| x == &value.name).unwrap() as u16;
 
-                    data.encode_integer_u16(info.swap_bytes());
+                    data.encode_integer_u16_be(info);
                 }
 
                 let mut children_data = vec![];
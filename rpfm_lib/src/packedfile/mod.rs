@@ -47,6 +47,7 @@ pub mod table;
 pub mod text;
 pub mod uic;
 pub mod unit_variant;
+pub mod variant_mesh_definition;
 
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
@@ -97,6 +98,9 @@ pub enum PackedFileType {
     UIC,
     UnitVariant,
 
+    /// Variant Mesh Definitions are XML Text PackedFiles, but they're kept apart so they can be validated.
+    VariantMeshDefinition,
+
     /// This one is an exception, as it contains the MimeType of the Text PackedFile, so we can do things depending on the type.
     Text(TextType),
 
@@ -216,6 +220,20 @@ impl DecodedPackedFile {
                 let data = raw_packed_file.get_data_and_keep_it()?;
                 let mut packed_file = Text::read(&data)?;
                 packed_file.set_text_type(text_type);
+
+                // If we couldn't get a specific type from the path, try with the contents.
+                if text_type == TextType::Plain {
+                    packed_file.set_text_type(packed_file.guess_text_type());
+                }
+
+                Ok(DecodedPackedFile::Text(packed_file))
+            }
+
+            // These are edited as text, so we decode them as XML Text PackedFiles.
+            PackedFileType::VariantMeshDefinition => {
+                let data = raw_packed_file.get_data_and_keep_it()?;
+                let mut packed_file = Text::read(&data)?;
+                packed_file.set_text_type(TextType::Xml);
                 Ok(DecodedPackedFile::Text(packed_file))
             }
 
@@ -288,6 +306,7 @@ impl DecodedPackedFile {
             PackedFileType::RigidModel => Self::decode(raw_packed_file),
 
             PackedFileType::Text(_) => Self::decode(raw_packed_file),
+            PackedFileType::VariantMeshDefinition => Self::decode(raw_packed_file),
 
             #[cfg(feature = "support_uic")]
             PackedFileType::UIC => {
@@ -384,6 +403,7 @@ impl Display for PackedFileType {
             PackedFileType::RigidModel => write!(f, "RigidModel"),
             PackedFileType::UIC => write!(f, "UI Component"),
             PackedFileType::UnitVariant => write!(f, "Unit Variant"),
+            PackedFileType::VariantMeshDefinition => write!(f, "Variant Mesh Definition"),
             PackedFileType::Text(text_type) => write!(f, "Text, type: {:?}", text_type),
             PackedFileType::PackFileSettings => write!(f, "PackFile Settings"),
             PackedFileType::Unknown => write!(f, "Unknown"),
@@ -434,6 +454,10 @@ impl PackedFileType {
                 return Self::Image;
             }
 
+            if packedfile_name.ends_with(variant_mesh_definition::EXTENSION) {
+                return Self::VariantMeshDefinition;
+            }
+
             if let Some((_, text_type)) = text::EXTENSIONS.iter().find(|(x, _)| packedfile_name.ends_with(x)) {
                 return Self::Text(*text_type);
             }
@@ -530,6 +554,10 @@ impl PackedFileType {
             return Self::Image;
         }
 
+        if path.ends_with(variant_mesh_definition::EXTENSION) {
+            return Self::VariantMeshDefinition;
+        }
+
         if let Some((_, text_type)) = text::EXTENSIONS.iter().find(|(x, _)| path.ends_with(x)) {
             return Self::Text(*text_type);
         }
@@ -582,6 +610,7 @@ impl PackedFileType {
     ///
     /// It performs an equality check between both provided types, ignoring the subtypes. This means,
     /// a Text PackedFile with subtype XML and one with subtype LUA will return true, because both are Text PackedFiles.
+    /// Variant Mesh Definitions are also considered Text PackedFiles here.
     pub fn eq_non_strict(self, other: Self) -> bool {
         match self {
             Self::Anim |
@@ -603,7 +632,8 @@ impl PackedFileType {
             Self::UIC |
             Self::UnitVariant |
             Self::Unknown => self == other,
-            Self::Text(_) => matches!(other, Self::Text(_)),
+            Self::Text(_) |
+            Self::VariantMeshDefinition => matches!(other, Self::Text(_) | Self::VariantMeshDefinition),
         }
     }
 
@@ -611,6 +641,7 @@ impl PackedFileType {
     ///
     /// It performs an equality check between both provided types, ignoring the subtypes. This means,
     /// a Text PackedFile with subtype XML and one with subtype LUA will return true, because both are Text PackedFiles.
+    /// Variant Mesh Definitions are also considered Text PackedFiles here.
     pub fn eq_non_strict_slice(self, others: &[Self]) -> bool {
         match self {
             Self::Anim |
@@ -632,7 +663,8 @@ impl PackedFileType {
             Self::UIC |
             Self::UnitVariant |
             Self::Unknown => others.contains(&self),
-            Self::Text(_) => others.iter().any(|x| matches!(x, Self::Text(_))),
+            Self::Text(_) |
+            Self::VariantMeshDefinition => others.iter().any(|x| matches!(x, Self::Text(_) | Self::VariantMeshDefinition)),
         }
     }
 }
@@ -16,10 +16,20 @@ This is really a dummy module, as all the logic for this is done in the view thr
 
 use serde_derive::{Serialize, Deserialize};
 
+use rpfm_error::{ErrorKind, Result};
+use rpfm_macros::*;
+
+use crate::common::decoder::Decoder;
+
+#[cfg(test)]
+mod rigidmodel_test;
+
 /// This represents the value that every RigidModel PackedFile has in their 0-4 bytes. A.k.a it's signature or preamble.
-#[allow(dead_code)]
 const PACKED_FILE_TYPE: &str = "RMV2";
 
+/// Size of the skeleton id in the header, 00-padded.
+const SKELETON_ID_SIZE: usize = 128;
+
 /// Extension used by RigidModel PackedFiles.
 pub const EXTENSION: &str = ".rigid_model_v2";
 
@@ -33,6 +43,23 @@ pub struct RigidModel {
     pub data: Vec<u8>,
 }
 
+/// This struct contains the header of a RigidModel, decoded without touching the rest of the file.
+#[derive(Clone, Debug, PartialEq, Eq, GetRef, Serialize, Deserialize)]
+pub struct RigidModelHeader {
+
+    /// Signature of the file. Always `RMV2`.
+    signature: String,
+
+    /// Version of the RigidModel format used by the file.
+    version: u32,
+
+    /// Amount of LODs in the file.
+    lod_count: u32,
+
+    /// Skeleton used by the model. Empty for static models.
+    skeleton_id: String,
+}
+
 //---------------------------------------------------------------------------//
 //                              Implementations
 //---------------------------------------------------------------------------//
@@ -49,4 +76,19 @@ impl RigidModel {
     pub fn save(&self) -> Vec<u8> {
         self.data.to_vec()
     }
+
+    /// This function decodes the header of a RigidModel, without decoding its geometry.
+    pub fn read_header(packed_file_data: &[u8]) -> Result<RigidModelHeader> {
+        let signature = packed_file_data.decode_string_u8(0, 4)?;
+        if signature != PACKED_FILE_TYPE {
+            return Err(ErrorKind::RigidModelNotSupportedFile.into());
+        }
+
+        Ok(RigidModelHeader {
+            signature,
+            version: packed_file_data.decode_integer_u32(4)?,
+            lod_count: packed_file_data.decode_integer_u32(8)?,
+            skeleton_id: packed_file_data.decode_string_u8_0padded(12, SKELETON_ID_SIZE)?.0,
+        })
+    }
 }
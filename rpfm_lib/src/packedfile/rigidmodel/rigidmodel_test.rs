@@ -0,0 +1,47 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `RigidModel` module.
+!*/
+
+use rpfm_error::ErrorKind;
+
+use super::{RigidModel, SKELETON_ID_SIZE};
+
+/// This function builds the header of a RigidModel, followed by some junk to stand for the geometry.
+fn rigid_model_data(signature: &[u8], skeleton_id: &str) -> Vec<u8> {
+    let mut data = signature.to_vec();
+    data.extend_from_slice(&7u32.to_le_bytes());
+    data.extend_from_slice(&4u32.to_le_bytes());
+    data.extend_from_slice(skeleton_id.as_bytes());
+    data.resize(data.len() + SKELETON_ID_SIZE - skeleton_id.len(), 0);
+    data.extend_from_slice(&[0xFF; 16]);
+    data
+}
+
+#[test]
+fn test_rigidmodel_read_header() {
+    let header = RigidModel::read_header(&rigid_model_data(b"RMV2", "humanoid01")).unwrap();
+    assert_eq!(header.get_ref_signature(), "RMV2");
+    assert_eq!(*header.get_ref_version(), 7);
+    assert_eq!(*header.get_ref_lod_count(), 4);
+    assert_eq!(header.get_ref_skeleton_id(), "humanoid01");
+
+    // Static models have no skeleton.
+    let header = RigidModel::read_header(&rigid_model_data(b"RMV2", "")).unwrap();
+    assert!(header.get_ref_skeleton_id().is_empty());
+
+    let error = RigidModel::read_header(&rigid_model_data(b"RMV3", "humanoid01")).unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::RigidModelNotSupportedFile);
+
+    // Headers cut before the skeleton id ends are an error too.
+    assert!(RigidModel::read_header(&rigid_model_data(b"RMV2", "humanoid01")[..40]).is_err());
+}
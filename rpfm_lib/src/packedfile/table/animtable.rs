@@ -79,6 +79,26 @@ impl AnimTable {
         self.table.get_ref_table_data()
     }
 
+    /// This function returns an iterator over the rows of this AnimTable, without cloning them.
+    pub fn rows(&self) -> impl Iterator<Item = &[DecodedData]> {
+        self.get_ref_table_data().iter().map(|row| &row[..])
+    }
+
+    /// This function returns the rows of this AnimTable that use the provided skeleton.
+    ///
+    /// The skeleton is checked against every column with `skeleton` in its name, ignoring case.
+    pub fn filter_by_skeleton(&self, skeleton: &str) -> Vec<&[DecodedData]> {
+        let skeleton_columns = self.get_ref_definition().get_fields_processed().iter()
+            .enumerate()
+            .filter(|(_, field)| field.get_name().to_lowercase().contains("skeleton"))
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+
+        self.rows()
+            .filter(|row| skeleton_columns.iter().any(|column| row.get(*column).map_or(false, |cell| cell.data_to_string().eq_ignore_ascii_case(skeleton))))
+            .collect()
+    }
+
     /// This function returns the amount of entries in this AnimTable Table.
     pub fn get_entry_count(&self) -> usize {
         self.table.get_entry_count()
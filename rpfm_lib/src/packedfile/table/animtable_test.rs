@@ -0,0 +1,52 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `AnimTable` module.
+!*/
+
+use crate::schema::FieldType;
+use crate::test_helpers::*;
+
+use super::DecodedData;
+use super::animtable::AnimTable;
+
+#[test]
+fn test_animtable_filter_by_skeleton() {
+    let definition = definition(2, vec![
+        field("table_name", FieldType::StringU8, true),
+        field("skeleton_type", FieldType::StringU8, false),
+        field("mount_table_name", FieldType::StringU8, false),
+        field("Skeleton_Name_Mount", FieldType::StringU8, false),
+    ]);
+    let row = |name: &str, skeleton: &str, mount: &str| vec![
+        DecodedData::StringU8(name.to_owned()),
+        DecodedData::StringU8(skeleton.to_owned()),
+        DecodedData::StringU8(format!("{}_table", mount)),
+        DecodedData::StringU8(mount.to_owned()),
+    ];
+
+    let mut table = AnimTable::new(&definition);
+    table.set_table_data(&[
+        row("hu1_sword", "humanoid01", ""),
+        row("hu1_horse", "humanoid01", "horse01"),
+        row("horse", "horse01", ""),
+        row("hu2_spear", "humanoid02", ""),
+    ]).unwrap();
+
+    assert_eq!(table.rows().count(), 4);
+
+    // Every column with "skeleton" in its name is checked, ignoring case. Other columns are not, even if they contain the skeleton.
+    let names = |skeleton: &str| table.filter_by_skeleton(skeleton).iter().map(|row| row[0].data_to_string()).collect::<Vec<String>>();
+    assert_eq!(names("humanoid01"), vec!["hu1_sword", "hu1_horse"]);
+    assert_eq!(names("HORSE01"), vec!["hu1_horse", "horse"]);
+    assert!(names("horse01_table").is_empty());
+    assert!(names("dragon01").is_empty());
+}
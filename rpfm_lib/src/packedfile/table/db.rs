@@ -15,15 +15,17 @@ DB Tables are the files which controls a lot of the parameters used in game, lik
 effects data, projectile parameters.... It's what modders use the most.
 !*/
 
-use bincode::deserialize;
+use bincode::{deserialize, serialize};
+use itertools::Itertools;
 use rayon::prelude::*;
 use serde_derive::{Serialize, Deserialize};
 use uuid::Uuid;
 
 use std::cmp::Ordering;
-use std::collections::{HashSet, BTreeMap};
+use std::collections::{HashMap, HashSet, BTreeMap, BTreeSet};
+use std::{fmt, fmt::Display};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
 use rpfm_error::{ErrorKind, Result};
@@ -32,6 +34,7 @@ use rpfm_macros::*;
 use crate::assembly_kit::table_data::RawTable;
 use crate::common::{decoder::Decoder, encoder::Encoder};
 use crate::GAME_SELECTED;
+use crate::games::GameInfo;
 use crate::packedfile::DecodedPackedFile;
 use crate::packedfile::Dependencies;
 use crate::packedfile::PackedFileType;
@@ -40,7 +43,8 @@ use crate::packfile::PackFile;
 use crate::schema::*;
 use crate::SETTINGS;
 use crate::SCHEMA;
-use super::{DecodedData, Table, DependencyData};
+use super::{DecodedData, Delimiter, Table, DependencyData};
+use super::loc::TSV_NAME_LOC;
 
 /// If this sequence is found, the DB Table has a GUID after it.
 const GUID_MARKER: &[u8] = &[253, 254, 252, 255];
@@ -48,6 +52,10 @@ const GUID_MARKER: &[u8] = &[253, 254, 252, 255];
 /// If this sequence is found, the DB Table has a version number after it.
 const VERSION_MARKER: &[u8] = &[252, 253, 254, 255];
 
+/// Size of the biggest header a DB Table can have with a normal GUID: GUID marker and GUID (4 + 74), version marker and version (4 + 4),
+/// mysterious byte (1) and entry count (4). For reading only the header of a table.
+pub const HEADER_MAX_SIZE: u32 = 91;
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -89,6 +97,68 @@ pub struct CascadeEdition {
     data_changes: BTreeMap<u32, Vec<(String, String)>>,
 }
 
+/// This holds the differences between two versions of the same DB Table.
+#[derive(Clone, Debug, Default, PartialEq, GetRef, Serialize, Deserialize)]
+pub struct TableDiff {
+
+    /// Rows only present in the new table.
+    added_rows: Vec<Vec<DecodedData>>,
+
+    /// Rows only present in the old table.
+    removed_rows: Vec<Vec<DecodedData>>,
+
+    /// Cells that changed between both tables, as (row key, column name, old value, new value).
+    changed_cells: Vec<(Vec<String>, String, DecodedData, DecodedData)>,
+}
+
+/// This holds a row that has been changed differently on both sides of a three-way merge.
+#[derive(Clone, Debug, Default, PartialEq, GetRef, Serialize, Deserialize)]
+pub struct Conflict {
+
+    /// Key of the conflicting row.
+    key: Vec<String>,
+
+    /// Row in the common ancestor table, if it was there.
+    base: Option<Vec<DecodedData>>,
+
+    /// Row in our table, if it's still there.
+    ours: Option<Vec<DecodedData>>,
+
+    /// Row in their table, if it's still there.
+    theirs: Option<Vec<DecodedData>>,
+}
+
+/// This holds a row that breaks one of the constraints of its table.
+#[derive(Clone, Debug, PartialEq, Eq, GetRef, Serialize, Deserialize)]
+pub struct ConstraintViolation {
+
+    /// Index of the row breaking the constraint.
+    row: usize,
+
+    /// Name of the column breaking the constraint. For duplicated keys, these are the names of all the key columns, joined with `,`.
+    column: String,
+
+    /// Indexes of the columns breaking the constraint. For duplicated keys, these are the indexes of all the key columns.
+    columns: Vec<usize>,
+
+    /// The constraint the row breaks.
+    reason: ConstraintViolationReason,
+}
+
+/// This enum represents the constraints a row of a table can break.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstraintViolationReason {
+
+    /// The row has the same keys as a previous row. Contains the index of the first row with those keys.
+    DuplicatedKey(usize),
+
+    /// The row has an empty key column.
+    EmptyKey,
+
+    /// The row has an empty value in a column marked as "not empty" in the schema patches.
+    EmptyValue,
+}
+
 //---------------------------------------------------------------------------//
 //                           Implementation of DB
 //---------------------------------------------------------------------------//
@@ -110,6 +180,24 @@ impl DB {
         }
     }
 
+    /// This function creates a new `DB` with the provided amount of empty rows, like the ones returned by `get_new_row`.
+    ///
+    /// Useful to create a table to copy the data of another version of it into, column by column.
+    pub fn new_with_rows(table_name: &str, definition: &Definition, row_count: usize) -> Self {
+        let mut table = Self::new(table_name, None, definition);
+        table.table.entries = vec![table.get_new_row(); row_count];
+        table
+    }
+
+    /// This function creates a `DB` from an array of JSON objects keyed by field name, like the ones exported by RPFM-CLI.
+    ///
+    /// Fields missing from an object get their default value. Values of the wrong type return a `TableWrongFieldType` error.
+    pub fn from_json(name: &str, json: &serde_json::Value, definition: &Definition) -> Result<Self> {
+        let mut db = Self::new(name, None, definition);
+        db.table = Table::from_json(json, definition, Some(name))?;
+        Ok(db)
+    }
+
     /// This function returns a copy of the name of this DB Table.
     pub fn get_table_name(&self) -> String {
         self.name.to_owned()
@@ -140,6 +228,11 @@ impl DB {
         self.table.get_ref_definition()
     }
 
+    /// This function returns a reference to the processed fields of the definition of this DB Table, without cloning them.
+    pub fn columns(&self) -> &[Field] {
+        self.table.columns()
+    }
+
     /// This function returns a reference to the underlying table.
     pub fn get_ref_table(&self) -> &Table {
         &self.table
@@ -155,11 +248,55 @@ impl DB {
         self.table.get_ref_table_data()
     }
 
+    /// This function returns an iterator over the rows of this DB Table, without cloning them.
+    pub fn rows(&self) -> impl Iterator<Item = &[DecodedData]> {
+        self.get_ref_table_data().iter().map(|row| &row[..])
+    }
+
+    /// This function returns a mutable iterator over the rows of this DB Table.
+    ///
+    /// Rows are meant to be edited in place. Changing their length or the type of their fields will break the table on save.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut Vec<DecodedData>> {
+        self.table.get_ref_mut_table_data().iter_mut()
+    }
+
+    /// This function returns the first row of this DB Table with the provided key, if any.
+    ///
+    /// The key is the one returned by `get_row_key`, so for tables with multiple key columns it contains the value of each one of them.
+    pub fn row_by_key(&self, key: &[String]) -> Option<&[DecodedData]> {
+        let key_columns = self.get_key_column_indexes();
+        self.rows().find(|row| Self::get_row_key_by_indexes(row, &key_columns) == key)
+    }
+
+    /// This function returns the first row of this DB Table with the provided single-column key, if any.
+    ///
+    /// Meant for tables with only one key column. For tables with multiple key columns use `row_by_key`.
+    pub fn row_by_single_key(&self, key: &str) -> Option<&[DecodedData]> {
+        self.row_by_key(&[key.to_owned()])
+    }
+
     /// This function returns the position of a column in a definition, or an error if the column is not found.
     pub fn get_column_position_by_name(&self, column_name: &str) -> Result<usize> {
         self.table.get_column_position_by_name(column_name)
     }
 
+    /// This function returns the distinct values of the column with the provided name, as strings and sorted.
+    ///
+    /// Sequence columns cannot be represented as a single value, so they return an error.
+    pub fn column_values(&self, column_name: &str) -> Result<Vec<String>> {
+        let column = self.columns().iter().position(|field| field.get_name() == column_name).ok_or(ErrorKind::ToolTableColumnNotFound)?;
+        let field_type = self.columns()[column].get_ref_field_type();
+        if let FieldType::SequenceU16(_) | FieldType::SequenceU32(_) = field_type {
+            return Err(ErrorKind::TableWrongFieldType(column_name.to_owned(), field_type.to_string()).into());
+        }
+
+        Ok(self.rows()
+            .map(|row| row[column].data_to_string())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect())
+    }
+
     /// This function returns the amount of entries in this DB Table.
     pub fn get_entry_count(&self) -> usize {
         self.table.get_entry_count()
@@ -172,6 +309,30 @@ impl DB {
         self.table.set_definition(new_definition);
     }
 
+    /// This function migrates this table to the provided definition, mapping the columns by name.
+    ///
+    /// New columns are filled with their default value, and removed columns are dropped. If any new key column has no default value,
+    /// or a column changed from or to a sequence, the table is left untouched and an error is returned.
+    pub fn migrate(&mut self, new_definition: &Definition) -> Result<()> {
+        let old_fields_processed = self.get_ref_definition().get_fields_processed();
+        let unmapped_columns = new_definition.get_fields_processed().iter().filter(|new_field| {
+            match old_fields_processed.iter().find(|old_field| old_field.get_name() == new_field.get_name()) {
+                Some(old_field) => old_field.get_ref_field_type() != new_field.get_ref_field_type() && (
+                    matches!(old_field.get_ref_field_type(), FieldType::SequenceU16(_) | FieldType::SequenceU32(_)) ||
+                    matches!(new_field.get_ref_field_type(), FieldType::SequenceU16(_) | FieldType::SequenceU32(_))
+                ),
+                None => new_field.get_is_key() && new_field.get_default_value(None).map_or(true, |default_value| default_value.is_empty()),
+            }
+        }).map(|field| format!("<li>{}</li>", field.get_name())).collect::<Vec<String>>();
+
+        if !unmapped_columns.is_empty() {
+            return Err(ErrorKind::TableMigrationUnmappedColumns(unmapped_columns.join("")).into());
+        }
+
+        self.set_definition(new_definition);
+        Ok(())
+    }
+
     /// This function replaces the data of this table with the one provided.
     ///
     /// This can (and will) fail if the data is not of the format defined by the definition of the table.
@@ -283,6 +444,36 @@ impl DB {
         })
     }
 
+    /// This function creates a `DB` from a `Vec<u8>` using the provided definition, instead of the one in the schema.
+    ///
+    /// Meant to test custom definitions. If the data doesn't fully decode, it returns `TableIncompleteError`, with the data decoded so far.
+    pub fn decode_with_definition(
+        packed_file_data: &[u8],
+        name: &str,
+        definition: &Definition,
+    ) -> Result<Self> {
+
+        // Get the header of the `DB`.
+        let (_, mysterious_byte, uuid, entry_count, mut index) = Self::read_header(packed_file_data)?;
+
+        // Then try to decode all the entries.
+        let mut table = Table::new(definition);
+        table.decode(packed_file_data, entry_count, &mut index, true)?;
+
+        // If we are not in the last byte, the definition is missing fields at the end of the rows.
+        if index != packed_file_data.len() {
+            let error = ErrorKind::PackedFileSizeIsNotWhatWeExpect(packed_file_data.len(), index);
+            return Err(ErrorKind::TableIncompleteError(error.to_string(), serialize(&table)?).into())
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            mysterious_byte,
+            uuid,
+            table,
+        })
+    }
+
     /// This function takes a `DB` and encodes it to `Vec<u8>`.
     pub fn save(&self) -> Result<Vec<u8>> {
         let mut packed_file: Vec<u8> = vec![];
@@ -376,6 +567,32 @@ impl DB {
         db_files
     }
 
+    /// This function loads the PAK file of the provided game into memory, as a list of decoded DB Tables.
+    ///
+    /// Unlike `read_pak_file`, errors are reported instead of returning an empty list. If the schema has a definition for the version
+    /// of a table, that definition is used instead of the one stored in the PAK file.
+    pub fn load_pak_tables(game: &GameInfo, schema: &Schema) -> Result<Vec<Self>> {
+        if game.get_dependencies_cache_file_name().is_empty() {
+            return Err(ErrorKind::PAKFileNotSupportedForThisGame.into());
+        }
+
+        let mut pak_file = BufReader::new(File::open(game.get_dependencies_cache_file()?)?);
+        let mut data = vec![];
+        pak_file.read_to_end(&mut data)?;
+
+        let mut db_files: Vec<Self> = deserialize(&data)?;
+        for table in &mut db_files {
+            let version = table.get_ref_definition().get_version();
+            if let Ok(definition) = schema.get_ref_versioned_file_db(&table.name).and_then(|versioned_file| versioned_file.get_version(version)) {
+                if definition != table.get_ref_definition() {
+                    table.set_definition(definition);
+                }
+            }
+        }
+
+        Ok(db_files)
+    }
+
     /// This function is used to optimize the size of a DB Table.
     ///
     /// It scans every line to check if it's a vanilla line, and remove it in that case. Also, if the entire
@@ -449,6 +666,270 @@ impl DB {
         self.table.get_ref_table_data().is_empty()
     }
 
+    /// This function removes the rows that are exact duplicates of a previous row, keeping the first one in its position.
+    ///
+    /// Floats are compared with the precision we show them with, and sequences by their contents. It returns the amount of rows removed.
+    pub fn dedup_rows(&mut self) -> usize {
+        let columns = (0..self.columns().len()).collect::<Vec<usize>>();
+        let mut entries = self.get_ref_table_data().to_vec();
+        let entry_count = entries.len();
+
+        let mut rows_found = HashSet::new();
+        entries.retain(|entry| rows_found.insert(Self::get_row_key_by_indexes(entry, &columns)));
+
+        let removed = entry_count - entries.len();
+        if removed > 0 {
+            let _ = self.table.set_table_data(&entries);
+        }
+
+        removed
+    }
+
+    /// This function removes the rows with the same keys as a later row, keeping the last one in its position.
+    ///
+    /// Composite keys are supported. It returns the amount of rows removed.
+    pub fn dedup_by_key(&mut self) -> usize {
+        let key_columns = self.get_key_column_indexes();
+        let entries = self.get_ref_table_data();
+        let entry_count = entries.len();
+
+        // Go from the end, so the first time we see a key is its last occurrence.
+        let mut keys_found = HashSet::new();
+        let mut entries = entries.iter().rev()
+            .filter(|entry| keys_found.insert(Self::get_row_key_by_indexes(entry, &key_columns)))
+            .cloned()
+            .collect::<Vec<Vec<DecodedData>>>();
+        entries.reverse();
+
+        let removed = entry_count - entries.len();
+        if removed > 0 {
+            let _ = self.table.set_table_data(&entries);
+        }
+
+        removed
+    }
+
+    /// This function returns the differences between this table (the old one) and the provided one (the new one).
+    ///
+    /// Rows are matched by their key columns. If the table has no key columns, the full row is used as key,
+    /// so any change in a row shows up as a removed row and an added row.
+    ///
+    /// Both tables must be of the same type and version. Otherwise, this returns an error.
+    pub fn diff(&self, other: &DB) -> Result<TableDiff> {
+        if self.name != other.name || self.get_ref_definition() != other.get_ref_definition() {
+            return Err(ErrorKind::InvalidFilesForMerging.into());
+        }
+
+        let fields = self.columns();
+        let key_columns = self.get_key_column_indexes();
+        let get_key = |row: &[DecodedData]| Self::get_row_key_by_indexes(row, &key_columns);
+
+        let old_rows = self.get_ref_table_data().iter().map(|row| (get_key(row), row)).collect::<HashMap<Vec<String>, &Vec<DecodedData>>>();
+        let new_rows = other.get_ref_table_data().iter().map(|row| (get_key(row), row)).collect::<HashMap<Vec<String>, &Vec<DecodedData>>>();
+
+        let mut diff = TableDiff::default();
+        for row in self.get_ref_table_data() {
+            if !new_rows.contains_key(&get_key(row)) {
+                diff.removed_rows.push(row.to_vec());
+            }
+        }
+
+        for new_row in other.get_ref_table_data() {
+            let key = get_key(new_row);
+            match old_rows.get(&key) {
+                Some(old_row) => {
+                    for (column, field) in fields.iter().enumerate() {
+
+                        // Compare them by value, so edits inside sequences are not missed.
+                        if old_row[column] != new_row[column] {
+                            diff.changed_cells.push((key.to_owned(), field.get_name().to_owned(), old_row[column].clone(), new_row[column].clone()));
+                        }
+                    }
+                }
+                None => diff.added_rows.push(new_row.to_vec()),
+            }
+        }
+
+        Ok(diff)
+    }
+
+    /// This function merges two tables derived from a common ancestor, using the row keys to match rows between them.
+    ///
+    /// Changes done only in one side are merged automatically, cell by cell. Rows with the same cell changed differently on both sides,
+    /// or deleted on one side and changed on the other, are returned as conflicts, keeping our version of the row in the merged table.
+    ///
+    /// Rows with duplicated keys (or duplicated rows, in tables without key columns) are not collapsed: the first row with a key
+    /// in each table is matched with the first one with the same key in the others, the second with the second, and so on.
+    pub fn merge_three_way(base: &DB, ours: &DB, theirs: &DB) -> Result<(DB, Vec<Conflict>)> {
+        if [ours, theirs].iter().any(|table| table.name != base.name || table.get_ref_definition() != base.get_ref_definition()) {
+            return Err(ErrorKind::InvalidFilesForMerging.into());
+        }
+
+        // Compare them by value, so edits inside sequences are not missed.
+        let same_row = |a: Option<&Vec<DecodedData>>, b: Option<&Vec<DecodedData>>| match (a, b) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => true,
+            _ => false,
+        };
+
+        // All three tables share the same definition, so they share the same key columns.
+        let key_columns = base.get_key_column_indexes();
+
+        let base_keys = base.get_row_keys_with_occurrences(&key_columns);
+        let our_keys = ours.get_row_keys_with_occurrences(&key_columns);
+        let their_keys = theirs.get_row_keys_with_occurrences(&key_columns);
+
+        let base_rows = base_keys.iter().cloned().collect::<HashMap<(Vec<String>, usize), &Vec<DecodedData>>>();
+        let our_rows = our_keys.iter().cloned().collect::<HashMap<(Vec<String>, usize), &Vec<DecodedData>>>();
+        let their_rows = their_keys.iter().cloned().collect::<HashMap<(Vec<String>, usize), &Vec<DecodedData>>>();
+
+        // Keep our order, then their new rows, then the rows deleted by us, in case they're needed for conflicts.
+        let keys = our_keys.iter()
+            .chain(their_keys.iter())
+            .chain(base_keys.iter())
+            .map(|(key, _)| key)
+            .unique()
+            .collect::<Vec<&(Vec<String>, usize)>>();
+
+        let mut entries = vec![];
+        let mut conflicts = vec![];
+        for key in keys {
+            let base_row = base_rows.get(key).copied();
+            let our_row = our_rows.get(key).copied();
+            let their_row = their_rows.get(key).copied();
+
+            let merged_row = if same_row(our_row, their_row) || same_row(base_row, their_row) { our_row.cloned() }
+            else if same_row(base_row, our_row) { their_row.cloned() }
+
+            // Changed on both sides. If both still have the row and it existed before, try to merge it cell by cell.
+            else {
+                let merged_cells = match (base_row, our_row, their_row) {
+                    (Some(base_row), Some(our_row), Some(their_row)) => base_row.iter().zip(our_row.iter()).zip(their_row.iter())
+                        .map(|((base_cell, our_cell), their_cell)| {
+                            if our_cell == their_cell || base_cell == their_cell { Some(our_cell.clone()) }
+                            else if base_cell == our_cell { Some(their_cell.clone()) }
+                            else { None }
+                        })
+                        .collect::<Option<Vec<DecodedData>>>(),
+                    _ => None,
+                };
+
+                if merged_cells.is_none() {
+                    conflicts.push(Conflict {
+                        key: key.0.to_vec(),
+                        base: base_row.cloned(),
+                        ours: our_row.cloned(),
+                        theirs: their_row.cloned(),
+                    });
+                }
+
+                merged_cells.or_else(|| our_row.cloned())
+            };
+
+            if let Some(row) = merged_row {
+                entries.push(row);
+            }
+        }
+
+        let mut merged = ours.clone();
+        merged.set_table_data(&entries)?;
+        Ok((merged, conflicts))
+    }
+
+    /// This function returns the rows of this table breaking the constraints of its definition.
+    ///
+    /// It checks for empty and duplicated keys, and for empty values in columns marked as "not empty" in the schema patches.
+    /// Optional fields and booleans can be keys while empty, so they're not reported as empty keys.
+    pub fn check_constraints(&self) -> Vec<ConstraintViolation> {
+        let fields = self.columns();
+        let key_column_names = fields.iter().filter(|field| field.get_is_key()).map(|field| field.get_name()).join(",");
+        let key_columns = self.get_key_column_indexes();
+
+        let mut keys_found = HashMap::new();
+        let mut violations = vec![];
+        for (row, entry) in self.get_ref_table_data().iter().enumerate() {
+            for (column, (field, cell)) in fields.iter().zip(entry.iter()).enumerate() {
+                if !cell.data_to_string().is_empty() {
+                    continue;
+                }
+
+                let can_be_empty_key = matches!(field.get_ref_field_type(), FieldType::OptionalStringU8 | FieldType::OptionalStringU16 | FieldType::OptionalI32 | FieldType::Boolean);
+                let reason = if field.get_is_key() && !can_be_empty_key {
+                    ConstraintViolationReason::EmptyKey
+                } else if field.get_cannot_be_empty(Some(&self.name)) {
+                    ConstraintViolationReason::EmptyValue
+                } else {
+                    continue;
+                };
+
+                violations.push(ConstraintViolation {
+                    row,
+                    column: field.get_name().to_owned(),
+                    columns: vec![column],
+                    reason,
+                });
+            }
+
+            if !key_column_names.is_empty() {
+                let first_row = *keys_found.entry(Self::get_row_key_by_indexes(entry, &key_columns)).or_insert(row);
+                if first_row != row {
+                    violations.push(ConstraintViolation {
+                        row,
+                        column: key_column_names.to_owned(),
+                        columns: key_columns.to_vec(),
+                        reason: ConstraintViolationReason::DuplicatedKey(first_row),
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// This function returns the key identifying the provided row of this table.
+    ///
+    /// The key is made of the values of the key columns of the row. If the table has no key columns, the whole row is used.
+    /// If you need the keys of many rows, get the key columns once with `get_key_column_indexes` and use `get_row_key_by_indexes` instead.
+    pub fn get_row_key(&self, row: &[DecodedData]) -> Vec<String> {
+        Self::get_row_key_by_indexes(row, &self.get_key_column_indexes())
+    }
+
+    /// This function returns the rows of this table with their keys, made of the values of the provided columns.
+    ///
+    /// Each key goes with the amount of previous rows with the same key, so rows with duplicated keys can be told apart.
+    fn get_row_keys_with_occurrences(&self, key_columns: &[usize]) -> Vec<((Vec<String>, usize), &Vec<DecodedData>)> {
+        let mut occurrences: HashMap<Vec<String>, usize> = HashMap::new();
+        self.get_ref_table_data().iter().map(|row| {
+            let key = Self::get_row_key_by_indexes(row, key_columns);
+            let occurrence = occurrences.entry(key.to_vec()).or_default();
+            *occurrence += 1;
+            ((key, *occurrence - 1), row)
+        }).collect()
+    }
+
+    /// This function returns the indexes of the columns making up the keys returned by `get_row_key`.
+    ///
+    /// These are the key columns of the table or, if it has none, all of its columns.
+    pub fn get_key_column_indexes(&self) -> Vec<usize> {
+        let fields = self.columns();
+        let key_columns = fields.iter().enumerate().filter(|(_, field)| field.get_is_key()).map(|(column, _)| column).collect::<Vec<usize>>();
+        if key_columns.is_empty() {
+            (0..fields.len()).collect()
+        } else {
+            key_columns
+        }
+    }
+
+    /// This function returns the key of the provided row, made of the values of the provided columns.
+    ///
+    /// Sequences are serialized whole, so rows with different sequences get different keys.
+    pub fn get_row_key_by_indexes(row: &[DecodedData], key_columns: &[usize]) -> Vec<String> {
+        key_columns.iter().map(|column| match &row[*column] {
+            DecodedData::SequenceU16(data) | DecodedData::SequenceU32(data) => serde_json::to_string(data).unwrap_or_default(),
+            data => data.data_to_string(),
+        }).collect()
+    }
+
     /// This function returns the dependency/lookup data of a column from the dependency database.
     ///
     /// Returns true if anything was found. Otherwise returns false.
@@ -881,6 +1362,20 @@ impl DB {
         Ok((db, file_path))
     }
 
+    /// This function creates a `DB` from a TSV already in memory, like the ones we export.
+    ///
+    /// The name and version of the table are taken from the metadata of the TSV, and its definition from the provided schema.
+    pub fn from_tsv_str(tsv: &str, schema: &Schema) -> Result<Self> {
+        let (table, table_type) = Table::import_tsv_from_str(schema, tsv)?;
+        if table_type == TSV_NAME_LOC {
+            return Err(ErrorKind::ImportTSVWrongTypeTable.into());
+        }
+
+        let mut db = DB::from(table);
+        db.name = table_type;
+        Ok(db)
+    }
+
     /// This function exports the provided data to a TSV file.
     pub fn export_tsv(
         &self,
@@ -891,35 +1386,68 @@ impl DB {
         self.table.export_tsv(path, table_name, file_path)
     }
 
+    /// This function exports the provided data as TSV to the provided writer, instead of to a file.
+    ///
+    /// The delimiter can be changed to export it as comma-delimited instead. If `escape` is true, tabs, newlines
+    /// and backslashes in strings are escaped, and restored on import.
+    pub fn export_tsv_to_writer<W: Write>(
+        &self,
+        writer: W,
+        table_name: &str,
+        file_path: &[String],
+        delimiter: Delimiter,
+        escape: bool,
+    ) -> Result<()> {
+        self.table.export_tsv_to_writer(writer, table_name, file_path, delimiter, escape)
+    }
+
     /// This function imports a TSV file into a binary file on disk.
+    ///
+    /// If `dedup` is true, rows with duplicated keys are removed from DB Tables, keeping the last one.
     pub fn import_tsv_to_binary_file(
         schema: &Schema,
         source_paths: &[PathBuf],
+        dedup: bool,
     ) -> Result<()> {
         for path in source_paths {
             let mut destination = path.clone();
             destination.set_extension("");
-            Table::import_tsv_to_binary_file(schema, path, &destination)?;
+            Table::import_tsv_to_binary_file(schema, path, &destination, dedup)?;
         }
 
         Ok(())
     }
 
-    /// This function exports to TSV a binary file on disk.
+    /// This function exports to TSV (or any other supported delimiter) a binary file on disk.
+    ///
+    /// If `escape` is true, tabs, newlines and backslashes in strings are escaped, and restored on import.
     pub fn export_tsv_from_binary_file(
         schema: &Schema,
         source_paths: &[PathBuf],
+        delimiter: Delimiter,
+        escape: bool,
     ) -> Result<()> {
         for path in source_paths {
             let mut destination = path.clone();
-            destination.set_extension("tsv");
-            Table::export_tsv_from_binary_file(schema, path, &destination)?;
+            destination.set_extension(delimiter.get_extension());
+            Table::export_tsv_from_binary_file(schema, path, &destination, delimiter, escape)?;
         }
 
         Ok(())
     }
 }
 
+/// Display implementation of `ConstraintViolationReason`.
+impl Display for ConstraintViolationReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(match self {
+            Self::DuplicatedKey(_) => "Duplicated key",
+            Self::EmptyKey => "Empty key",
+            Self::EmptyValue => "Empty value",
+        }, f)
+    }
+}
+
 /// Implementation to create a `DB` from a `Table`.
 impl From<Table> for DB {
     fn from(table: Table) -> Self {
@@ -0,0 +1,422 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `DB` module.
+!*/
+
+use std::env::temp_dir;
+use std::fs::{remove_file, write};
+use std::path::PathBuf;
+
+use rpfm_error::ErrorKind;
+
+use crate::common::encoder::Encoder;
+use crate::packfile::PackFile;
+use crate::schema::{Definition, FieldType};
+use crate::test_helpers::*;
+
+use super::{DecodedData, Delimiter, Table};
+use super::db::{ConstraintViolationReason, DB};
+
+#[test]
+fn test_db_sequence_round_trip() {
+    let sub_definition = definition(0, vec![field("key", FieldType::StringU8, false), field("value", FieldType::I32, false)]);
+    let definition = definition(1, vec![field("key", FieldType::StringU8, false), field("entries", FieldType::SequenceU32(sub_definition.clone()), false)]);
+
+    let mut sub_table = Table::new(&sub_definition);
+    sub_table.set_table_data(&[
+        vec![DecodedData::StringU8("first".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("second".to_owned()), DecodedData::I32(2)],
+    ]).unwrap();
+
+    let db = db(&definition, &[
+        vec![DecodedData::StringU8("with_entries".to_owned()), DecodedData::SequenceU32(sub_table)],
+        vec![DecodedData::StringU8("without_entries".to_owned()), DecodedData::SequenceU32(Table::new(&sub_definition))],
+    ]);
+
+    // Nested sequences must decode back to the same data, and re-encode to the same bytes.
+    let data = db.save().unwrap();
+    let db_decoded = DB::read_with_fields(&data, TEST_TABLE_NAME, definition.get_ref_fields(), false).unwrap();
+    assert_eq!(db_decoded.get_ref_table_data(), db.get_ref_table_data());
+    assert_eq!(db_decoded.save().unwrap(), data);
+}
+
+#[test]
+fn test_db_decode_with_definition() {
+    let mut definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let db = db(&definition, &[
+        vec![DecodedData::StringU8("key_1".to_owned()), DecodedData::I32(10)],
+        vec![DecodedData::StringU8("key_2".to_owned()), DecodedData::I32(20)],
+    ]);
+
+    let data = db.save().unwrap();
+    assert_eq!(DB::decode_with_definition(&data, TEST_TABLE_NAME, &definition).unwrap().get_ref_table_data(), db.get_ref_table_data());
+
+    // A definition with missing fields must fail, returning what it decoded.
+    definition.get_ref_mut_fields().pop();
+    let error = DB::decode_with_definition(&data, TEST_TABLE_NAME, &definition).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::TableIncompleteError(_, _)));
+}
+
+#[test]
+fn test_db_optional_i32() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::OptionalI32, false)]);
+    let db = db(&definition, &[
+        vec![DecodedData::StringU8("key_1".to_owned()), DecodedData::OptionalI32(Some(10))],
+        vec![DecodedData::StringU8("key_2".to_owned()), DecodedData::OptionalI32(None)],
+    ]);
+
+    // Absent values must be encoded as just the presence byte, and survive a round-trip.
+    let data = db.save().unwrap();
+    assert_eq!(&data[data.len() - 1..], &[0]);
+    let decoded = DB::decode_with_definition(&data, TEST_TABLE_NAME, &definition).unwrap();
+    assert_eq!(decoded.get_ref_table_data(), db.get_ref_table_data());
+    assert_eq!(decoded.save().unwrap(), data);
+}
+
+#[test]
+fn test_db_optional_i32_with_packfile_rows() {
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/example_list_tables.pack"), false).unwrap();
+    let table_name = "_kv_battle_ai_ability_usage_variables_tables";
+    let data = pack_file.get_ref_packed_file_by_path(&["db".to_owned(), table_name.to_owned(), "data__".to_owned()]).unwrap().get_raw_data().unwrap();
+
+    let mut definition = definition(0, vec![field("key", FieldType::StringU8, true), field("value", FieldType::F32, false)]);
+    let db = DB::decode_with_definition(&data, table_name, &definition).unwrap();
+
+    // Rebuild the real rows with an extra OptionalI32 column, mixing present and absent values.
+    definition.get_ref_mut_fields().push(field("optional_value", FieldType::OptionalI32, false));
+    let mut rows = vec![];
+    let mut rows_data = vec![];
+    for (row, value) in db.get_ref_table_data().iter().zip([Some(5), None, Some(-1)]) {
+        match (&row[0], &row[1]) {
+            (DecodedData::StringU8(key), DecodedData::F32(float)) => {
+                rows_data.encode_packedfile_string_u8(key);
+                rows_data.encode_float_f32(*float);
+                rows_data.encode_packedfile_optional_integer_i32(value);
+            }
+            _ => unreachable!(),
+        }
+
+        let mut row = row.to_vec();
+        row.push(DecodedData::OptionalI32(value));
+        rows.push(row);
+    }
+    assert_eq!(rows.len(), 3);
+
+    // The header is the same. Only the rows change.
+    let rows_len = rows.iter().map(|row| if let DecodedData::StringU8(key) = &row[0] { 2 + key.len() + 4 } else { unreachable!() }).sum::<usize>();
+    let mut data_optional = data[..data.len() - rows_len].to_vec();
+    data_optional.extend_from_slice(&rows_data);
+
+    let decoded = DB::decode_with_definition(&data_optional, table_name, &definition).unwrap();
+    assert_eq!(decoded.get_ref_table_data(), &rows);
+
+    // The GUID may be regenerated on save, so only compare the rows.
+    let saved = decoded.save().unwrap();
+    assert!(saved.ends_with(&rows_data));
+}
+
+#[test]
+fn test_db_row_key() {
+    let definition = definition(1, vec![field("key_1", FieldType::StringU8, true), field("key_2", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+
+    // Keys with the separator of the old string keys in them must not collide.
+    let mut db = db(&definition, &[
+        vec![DecodedData::StringU8("a|b".to_owned()), DecodedData::StringU8("c".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::StringU8("b|c".to_owned()), DecodedData::I32(2)],
+    ]);
+
+    assert_eq!(db.get_key_column_indexes(), vec![0, 1]);
+    assert_eq!(db.get_row_key(&db.get_ref_table_data()[0]), vec!["a|b".to_owned(), "c".to_owned()]);
+    assert_eq!(db.dedup_by_key(), 0);
+    assert!(db.check_constraints().is_empty());
+    assert_eq!(db.row_by_key(&["a".to_owned(), "b|c".to_owned()]).unwrap()[2], DecodedData::I32(2));
+}
+
+#[test]
+fn test_db_diff() {
+    let sub_definition = definition(0, vec![field("value", FieldType::I32, false)]);
+    let sequence = |values: &[i32]| {
+        let mut table = Table::new(&sub_definition);
+        table.set_table_data(&values.iter().map(|value| vec![DecodedData::I32(*value)]).collect::<Vec<_>>()).unwrap();
+        DecodedData::SequenceU32(table)
+    };
+
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::F32, false), field("entries", FieldType::SequenceU32(sub_definition.clone()), false)]);
+    let old = db(&definition, &[
+        vec![DecodedData::StringU8("same".to_owned()), DecodedData::F32(1.0), sequence(&[1, 2])],
+        vec![DecodedData::StringU8("edited".to_owned()), DecodedData::F32(2.0), sequence(&[1, 2])],
+        vec![DecodedData::StringU8("removed".to_owned()), DecodedData::F32(3.0), sequence(&[])],
+    ]);
+    let new = db(&definition, &[
+        vec![DecodedData::StringU8("same".to_owned()), DecodedData::F32(1.00001), sequence(&[1, 2])],
+        vec![DecodedData::StringU8("edited".to_owned()), DecodedData::F32(2.0), sequence(&[1, 3])],
+        vec![DecodedData::StringU8("added".to_owned()), DecodedData::F32(4.0), sequence(&[])],
+    ]);
+
+    // Edits inside a sequence must show up, while float noise must not.
+    let diff = old.diff(&new).unwrap();
+    assert_eq!(diff.get_ref_added_rows(), &[new.get_ref_table_data()[2].to_vec()]);
+    assert_eq!(diff.get_ref_removed_rows(), &[old.get_ref_table_data()[2].to_vec()]);
+    assert_eq!(diff.get_ref_changed_cells(), &[(vec!["edited".to_owned()], "entries".to_owned(), sequence(&[1, 2]), sequence(&[1, 3]))]);
+
+    let other_definition = crate::test_helpers::definition(2, vec![field("key", FieldType::StringU8, true)]);
+    assert!(matches!(old.diff(&db(&other_definition, &[])).unwrap_err().kind(), ErrorKind::InvalidFilesForMerging));
+}
+
+#[test]
+fn test_db_merge_three_way() {
+    let definition_keyed = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false), field("other", FieldType::I32, false)]);
+    let table = |rows: &[(&str, i32, i32)]| db(&definition_keyed, &rows.iter().map(|(key, value, other)| vec![DecodedData::StringU8(key.to_string()), DecodedData::I32(*value), DecodedData::I32(*other)]).collect::<Vec<_>>());
+
+    // `a` is changed in different cells on each side, `b` deleted by them, `c` deleted by us and changed by them,
+    // `d` changed in the same cell on both sides, `f` added by us and `g` added by them.
+    let base = table(&[("a", 1, 1), ("b", 2, 2), ("c", 3, 3), ("d", 4, 4), ("e", 5, 5)]);
+    let ours = table(&[("a", 10, 1), ("b", 2, 2), ("d", 40, 4), ("e", 5, 5), ("f", 6, 6)]);
+    let theirs = table(&[("a", 1, 11), ("c", 30, 3), ("d", 41, 4), ("e", 5, 5), ("g", 7, 7)]);
+
+    let (merged, conflicts) = DB::merge_three_way(&base, &ours, &theirs).unwrap();
+    assert_eq!(merged.get_ref_table_data(), table(&[("a", 10, 11), ("d", 40, 4), ("e", 5, 5), ("f", 6, 6), ("g", 7, 7)]).get_ref_table_data());
+    assert_eq!(conflicts.iter().map(|x| x.get_ref_key().to_vec()).collect::<Vec<Vec<String>>>(), vec![vec!["d".to_owned()], vec!["c".to_owned()]]);
+    assert_eq!(conflicts[1].get_ref_ours(), &None);
+    assert_eq!(conflicts[1].get_ref_theirs().as_ref().unwrap()[1], DecodedData::I32(30));
+
+    // Identical rows in tables without keys must not be collapsed: we added a third `x`, and they deleted the second one.
+    let definition_keyless = definition(1, vec![field("value", FieldType::StringU8, false)]);
+    let table = |rows: &[&str]| db(&definition_keyless, &rows.iter().map(|value| vec![DecodedData::StringU8(value.to_string())]).collect::<Vec<_>>());
+
+    let (merged, conflicts) = DB::merge_three_way(&table(&["x", "x", "y"]), &table(&["x", "x", "y", "x"]), &table(&["x", "y"])).unwrap();
+    assert_eq!(merged.get_ref_table_data(), table(&["x", "y", "x"]).get_ref_table_data());
+    assert!(conflicts.is_empty());
+
+    // Edits inside a sequence must be merged like any other edit.
+    let sub_definition = definition(0, vec![field("value", FieldType::I32, false)]);
+    let sequence = |values: &[i32]| {
+        let mut table = Table::new(&sub_definition);
+        table.set_table_data(&values.iter().map(|value| vec![DecodedData::I32(*value)]).collect::<Vec<_>>()).unwrap();
+        DecodedData::SequenceU32(table)
+    };
+
+    let definition_sequence = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false), field("entries", FieldType::SequenceU32(sub_definition.clone()), false)]);
+    let table = |value: i32, entries: &[i32]| db(&definition_sequence, &[vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(value), sequence(entries)]]);
+
+    let (merged, conflicts) = DB::merge_three_way(&table(1, &[1, 2]), &table(10, &[1, 2]), &table(1, &[1, 3])).unwrap();
+    assert_eq!(merged.get_ref_table_data(), table(10, &[1, 3]).get_ref_table_data());
+    assert!(conflicts.is_empty());
+
+    let (_, conflicts) = DB::merge_three_way(&table(1, &[1, 2]), &table(1, &[1, 4]), &table(1, &[1, 3])).unwrap();
+    assert_eq!(conflicts.len(), 1);
+}
+
+#[test]
+fn test_db_rows() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let mut db = db(&definition, &[
+        vec![DecodedData::StringU8("first".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("second".to_owned()), DecodedData::I32(2)],
+    ]);
+
+    assert_eq!(db.rows().count(), 2);
+    assert_eq!(db.row_by_key(&["second".to_owned()]), Some(&[DecodedData::StringU8("second".to_owned()), DecodedData::I32(2)][..]));
+    assert_eq!(db.row_by_key(&["third".to_owned()]), None);
+    assert_eq!(db.row_by_single_key("second"), Some(&[DecodedData::StringU8("second".to_owned()), DecodedData::I32(2)][..]));
+    assert_eq!(db.row_by_single_key("third"), None);
+
+    db.rows_mut().for_each(|row| row[1] = DecodedData::I32(0));
+    assert!(db.rows().all(|row| row[1] == DecodedData::I32(0)));
+}
+
+#[test]
+fn test_db_new_with_rows() {
+    let definition = definition(1, vec![field_with_default("key", FieldType::StringU8, true, "default_key"), field("value", FieldType::I32, false)]);
+
+    let db = DB::new_with_rows(TEST_TABLE_NAME, &definition, 50);
+    assert_eq!(db.get_entry_count(), 50);
+    assert!(db.rows().all(|row| row == &db.get_new_row()[..]));
+    assert_eq!(db.rows().next().unwrap()[0], DecodedData::StringU8("default_key".to_owned()));
+}
+
+#[test]
+fn test_db_column_values() {
+    let definition = definition(1, vec![
+        field("key", FieldType::StringU8, true),
+        field("category", FieldType::StringU8, false),
+        field("sequence", FieldType::SequenceU32(Definition::new(-1)), false),
+    ]);
+
+    let db = db(&definition, &[
+        vec![DecodedData::StringU8("first".to_owned()), DecodedData::StringU8("inf_melee".to_owned()), DecodedData::SequenceU32(Table::new(&Definition::new(-1)))],
+        vec![DecodedData::StringU8("second".to_owned()), DecodedData::StringU8("cavalry".to_owned()), DecodedData::SequenceU32(Table::new(&Definition::new(-1)))],
+        vec![DecodedData::StringU8("third".to_owned()), DecodedData::StringU8("inf_melee".to_owned()), DecodedData::SequenceU32(Table::new(&Definition::new(-1)))],
+    ]);
+
+    assert_eq!(db.column_values("category").unwrap(), vec!["cavalry".to_owned(), "inf_melee".to_owned()]);
+    assert!(matches!(db.column_values("missing").unwrap_err().kind(), ErrorKind::ToolTableColumnNotFound));
+    assert!(matches!(db.column_values("sequence").unwrap_err().kind(), ErrorKind::TableWrongFieldType(_, _)));
+}
+
+#[test]
+fn test_db_dedup() {
+    let definition_keys = definition(1, vec![field("key", FieldType::StringU8, true), field("key_2", FieldType::I32, true), field("value", FieldType::I32, false)]);
+    let mut db_keys = db(&definition_keys, &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(2), DecodedData::I32(2)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1), DecodedData::I32(3)],
+    ]);
+
+    // Only the full composite key counts, and the last occurrence is the one kept.
+    assert_eq!(db_keys.dedup_by_key(), 1);
+    assert_eq!(db_keys.get_ref_table_data(), &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(2), DecodedData::I32(2)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1), DecodedData::I32(3)],
+    ]);
+
+    // Comparing full rows, floats are compared with the precision we show them with, and sequences by their contents.
+    let sub_definition = definition(0, vec![field("value", FieldType::I32, false)]);
+    let sequence = |value: i32| {
+        let mut table = Table::new(&sub_definition);
+        table.set_table_data(&[vec![DecodedData::I32(value)]]).unwrap();
+        DecodedData::SequenceU32(table)
+    };
+
+    let definition_rows = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::F32, false), field("entries", FieldType::SequenceU32(sub_definition.clone()), false)]);
+    let mut db_rows = db(&definition_rows, &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::F32(1.00001), sequence(1)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::F32(1.00002), sequence(2)],
+        vec![DecodedData::StringU8("b".to_owned()), DecodedData::F32(2.0), sequence(1)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::F32(1.00002), sequence(1)],
+    ]);
+
+    // The first copy of each row is the one kept, in its original position.
+    assert_eq!(db_rows.dedup_rows(), 1);
+    assert_eq!(db_rows.get_ref_table_data().iter().map(|row| (row[0].data_to_string(), row[1].clone(), row[2].clone())).collect::<Vec<_>>(), vec![
+        ("a".to_owned(), DecodedData::F32(1.00001), sequence(1)),
+        ("a".to_owned(), DecodedData::F32(1.00002), sequence(2)),
+        ("b".to_owned(), DecodedData::F32(2.0), sequence(1)),
+    ]);
+}
+
+#[test]
+fn test_db_from_json() {
+    let definition = definition(1, vec![
+        field("key", FieldType::StringU8, true),
+        field_with_default("value", FieldType::I32, false, "7"),
+        field("colour", FieldType::ColourRGB, false),
+    ]);
+
+    // Missing fields must get their default value, and colours are parsed from their hex representation.
+    let json = serde_json::json!([{ "key": "first", "colour": "FF0080" }, { "key": "second", "value": 2, "colour": "000000" }]);
+    let db = DB::from_json(TEST_TABLE_NAME, &json, &definition).unwrap();
+    assert_eq!(db.get_ref_table_data(), &[
+        vec![DecodedData::StringU8("first".to_owned()), DecodedData::I32(7), DecodedData::ColourRGB(0xFF0080)],
+        vec![DecodedData::StringU8("second".to_owned()), DecodedData::I32(2), DecodedData::ColourRGB(0)],
+    ]);
+
+    let json = serde_json::json!([{ "key": "first", "value": "two" }]);
+    assert!(DB::from_json(TEST_TABLE_NAME, &json, &definition).is_err());
+}
+
+#[test]
+fn test_db_export_csv_quoting() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::StringU8, false)]);
+    let schema = schema_with_db(&definition);
+    let db = db(&definition, &[
+        vec![DecodedData::StringU8("key_1".to_owned()), DecodedData::StringU8("one, two; \"three\"".to_owned())],
+        vec![DecodedData::StringU8("key_2".to_owned()), DecodedData::StringU8("four".to_owned())],
+    ]);
+
+    // Fields containing the delimiter or quotes are quoted, but the metadata line is never quoted, so it always starts with `#`.
+    let file_path = vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), "test, 2".to_owned()];
+    let mut data = vec![];
+    db.export_tsv_to_writer(&mut data, TEST_TABLE_NAME, &file_path, Delimiter::Comma, false).unwrap();
+    assert_eq!(String::from_utf8(data.to_vec()).unwrap(), "key,value\n#test_tables;1;db/test_tables/test, 2,\nkey_1,\"one, two; \"\"three\"\"\"\nkey_2,four\n");
+
+    // And it must import back the same from a `.csv` file.
+    let path = temp_dir().join("rpfm_test_db_export_csv_quoting.csv");
+    write(&path, &data).unwrap();
+    let (imported, imported_path) = DB::import_tsv(&schema, &path).unwrap();
+    assert_eq!(imported_path, Some(file_path.to_vec()));
+    assert_eq!(imported.get_table_data(), db.get_table_data());
+
+    // Same with semicolons, which are detected from the column names when importing.
+    let mut data = vec![];
+    db.export_tsv_to_writer(&mut data, TEST_TABLE_NAME, &file_path, Delimiter::Semicolon, false).unwrap();
+    assert_eq!(String::from_utf8(data.to_vec()).unwrap(), "key;value\n#test_tables;1;db/test_tables/test, 2;\nkey_1;\"one, two; \"\"three\"\"\"\nkey_2;four\n");
+
+    write(&path, &data).unwrap();
+    let (imported, imported_path) = DB::import_tsv(&schema, &path).unwrap();
+    remove_file(&path).unwrap();
+
+    assert_eq!(imported_path, Some(file_path.to_vec()));
+    assert_eq!(imported.get_table_data(), db.get_table_data());
+}
+
+#[test]
+fn test_db_from_tsv_str() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let schema = schema_with_db(&definition);
+    let db = db(&definition, &[vec![DecodedData::StringU8("key_1".to_owned()), DecodedData::I32(10)]]);
+
+    let mut data = vec![];
+    db.export_tsv_to_writer(&mut data, TEST_TABLE_NAME, &["db".to_owned(), TEST_TABLE_NAME.to_owned(), "test".to_owned()], Delimiter::Tab, false).unwrap();
+    let tsv = String::from_utf8(data).unwrap();
+
+    let imported = DB::from_tsv_str(&tsv, &schema).unwrap();
+    assert_eq!(imported.get_ref_table_name(), TEST_TABLE_NAME);
+    assert_eq!(imported.get_ref_table_data(), db.get_ref_table_data());
+
+    // Invalid cells must report their position.
+    let error = DB::from_tsv_str(&tsv.replace("\t10", "\tten"), &schema).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::ImportTSVIncorrectRow(1, 1)));
+}
+
+#[test]
+fn test_db_migrate() {
+    let definition_old = definition(1, vec![field("key", FieldType::StringU8, true), field("removed", FieldType::I32, false), field("value", FieldType::I32, false)]);
+    let definition_new = definition(2, vec![field("key", FieldType::StringU8, true), field_with_default("new", FieldType::I32, false, "5"), field("value", FieldType::I32, false)]);
+    let definition_new_key = definition(3, vec![field("key", FieldType::StringU8, true), field("new_key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+
+    let mut db = db(&definition_old, &[vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1), DecodedData::I32(2)]]);
+
+    // New key columns without default value cannot be filled, so the table must not change.
+    assert!(db.migrate(&definition_new_key).is_err());
+    assert_eq!(db.get_ref_definition().get_version(), 1);
+
+    db.migrate(&definition_new).unwrap();
+    assert_eq!(db.get_ref_definition().get_version(), 2);
+    assert_eq!(db.get_ref_table_data(), &[vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(5), DecodedData::I32(2)]]);
+}
+
+#[test]
+fn test_db_check_constraints() {
+    let definition_required = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let db_required = db(&definition_required, &[
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(1)],
+        vec![DecodedData::StringU8("".to_owned()), DecodedData::I32(2)],
+        vec![DecodedData::StringU8("a".to_owned()), DecodedData::I32(3)],
+    ]);
+
+    let violations = db_required.check_constraints();
+    assert_eq!(violations.len(), 2);
+    assert_eq!((*violations[0].get_ref_row(), violations[0].get_ref_column().as_str(), *violations[0].get_ref_reason()), (1, "key", ConstraintViolationReason::EmptyKey));
+    assert_eq!((*violations[1].get_ref_row(), violations[1].get_ref_column().as_str(), *violations[1].get_ref_reason()), (2, "key", ConstraintViolationReason::DuplicatedKey(0)));
+
+    // Optional fields can be empty keys, and every duplicate points to the first row with its keys.
+    let definition_optional = definition(1, vec![field("key", FieldType::StringU8, true), field("key_2", FieldType::OptionalStringU16, true), field("key_3", FieldType::OptionalI32, true)]);
+    let row = || vec![DecodedData::StringU8("a".to_owned()), DecodedData::OptionalStringU16(String::new()), DecodedData::OptionalI32(None)];
+    let db_optional = db(&definition_optional, &[row(), row(), row()]);
+
+    let violations = db_optional.check_constraints();
+    assert_eq!(violations.iter().map(|x| (*x.get_ref_row(), x.get_ref_columns().to_vec(), *x.get_ref_reason())).collect::<Vec<_>>(), vec![
+        (1, vec![0, 1, 2], ConstraintViolationReason::DuplicatedKey(0)),
+        (2, vec![0, 1, 2], ConstraintViolationReason::DuplicatedKey(0)),
+    ]);
+}
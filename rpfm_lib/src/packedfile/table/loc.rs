@@ -18,7 +18,8 @@ They're just tables with a key, a text, and a boolean column.
 use rayon::prelude::*;
 
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::path::Path;
 
 use rpfm_error::{ErrorKind, Result};
@@ -26,6 +27,7 @@ use rpfm_error::{ErrorKind, Result};
 use crate::common::{decoder::Decoder, encoder::Encoder};
 use crate::packedfile::Dependencies;
 use super::DecodedData;
+use super::Delimiter;
 use super::Table;
 
 use crate::SCHEMA;
@@ -227,6 +229,128 @@ impl Loc {
         self.table.get_ref_table_data().is_empty()
     }
 
+    /// This function renames the entries with the provided key, keeping them in the same position within the table.
+    ///
+    /// Returns true if any entry has been renamed. Otherwise returns false.
+    pub fn rename_key(&mut self, old_key: &str, new_key: &str) -> bool {
+        let key_column = self.get_column_position_by_name("key").unwrap_or(0);
+        let mut renamed = false;
+
+        for row in &mut self.table.entries {
+            if let Some(DecodedData::StringU8(key)) |
+                Some(DecodedData::StringU16(key)) |
+                Some(DecodedData::OptionalStringU8(key)) |
+                Some(DecodedData::OptionalStringU16(key)) = row.get_mut(key_column) {
+                if key == old_key {
+                    *key = new_key.to_owned();
+                    renamed = true;
+                }
+            }
+        }
+
+        renamed
+    }
+
+    /// This function merges the provided Loc Tables into a new one, using the priority of each one to decide which row wins in case of duplicated keys.
+    ///
+    /// Higher priorities override lower ones and, between equal priorities, the last Loc wins. Rows keep the position where their key first appeared.
+    /// The definition of the new Loc is the one of the first Loc provided.
+    pub fn merge_with_priority(locs: &[(&Loc, u32)]) -> Loc {
+        let definition = locs.first().map(|(loc, _)| loc.get_definition()).unwrap_or_default();
+        let mut entries: Vec<Vec<DecodedData>> = vec![];
+        let mut keys: HashMap<String, (usize, u32)> = HashMap::new();
+
+        for (loc, priority) in locs {
+            let key_column = loc.get_column_position_by_name("key").unwrap_or(0);
+            for row in loc.get_ref_table_data() {
+                let key = row[key_column].data_to_string();
+                match keys.get_mut(&key) {
+                    Some((position, current_priority)) => {
+                        if priority >= current_priority {
+                            entries[*position] = row.to_vec();
+                            *current_priority = *priority;
+                        }
+                    }
+                    None => {
+                        keys.insert(key, (entries.len(), *priority));
+                        entries.push(row.to_vec());
+                    }
+                }
+            }
+        }
+
+        let mut merged = Self::new(&definition);
+        merged.table.entries = entries;
+        merged
+    }
+
+    /// This function merges the provided Loc Tables into a new one. In case of duplicated keys, the last Loc wins.
+    ///
+    /// Along with the merged Loc, it returns the keys that got overridden by a later Loc, in the order they were first overridden.
+    /// Keys duplicated within a single Loc are not reported. All the Locs must share the same definition version.
+    pub fn merge(tables: &[Loc]) -> Result<(Loc, Vec<String>)> {
+        let version = match tables.first() {
+            Some(table) => table.get_ref_definition().get_version(),
+            None => return Err(ErrorKind::InvalidFilesForMerging.into()),
+        };
+
+        if tables.iter().any(|table| table.get_ref_definition().get_version() != version) {
+            return Err(ErrorKind::InvalidFilesForMerging.into());
+        }
+
+        // Keep track of the last table each key came from, so duplicates within the same table are not reported as overridden.
+        let mut keys: HashMap<String, usize> = HashMap::new();
+        let mut overridden_keys_set = HashSet::new();
+        let mut overridden_keys = vec![];
+        for (index, table) in tables.iter().enumerate() {
+            let key_column = table.get_column_position_by_name("key").unwrap_or(0);
+            for row in table.get_ref_table_data() {
+                let key = row[key_column].data_to_string();
+                match keys.get_mut(&key) {
+                    Some(source) => if *source != index {
+                        *source = index;
+                        if overridden_keys_set.insert(key.to_owned()) {
+                            overridden_keys.push(key);
+                        }
+                    }
+                    None => { keys.insert(key, index); }
+                }
+            }
+        }
+
+        let locs = tables.iter().map(|table| (table, 0)).collect::<Vec<(&Loc, u32)>>();
+        Ok((Self::merge_with_priority(&locs), overridden_keys))
+    }
+
+    /// This function splits the keys of this Loc Table between the ones already present in the provided base Loc, and the new ones.
+    ///
+    /// It returns (overridden keys, new keys), each one in the order their keys first appear in this table, without duplicates.
+    pub fn classify_against(&self, base: &Loc) -> (Vec<String>, Vec<String>) {
+        let base_key_column = base.get_column_position_by_name("key").unwrap_or(0);
+        let base_keys = base.get_ref_table_data().iter()
+            .map(|row| row[base_key_column].data_to_string())
+            .collect::<HashSet<String>>();
+
+        let key_column = self.get_column_position_by_name("key").unwrap_or(0);
+        let mut keys = HashSet::new();
+        let mut overridden_keys = vec![];
+        let mut new_keys = vec![];
+        for row in self.get_ref_table_data() {
+            let key = row[key_column].data_to_string();
+            if !keys.insert(key.to_owned()) {
+                continue;
+            }
+
+            if base_keys.contains(&key) {
+                overridden_keys.push(key);
+            } else {
+                new_keys.push(key);
+            }
+        }
+
+        (overridden_keys, new_keys)
+    }
+
     /// This function returns the table/column/key from the provided key, if it exists in the current PackFile.
     ///
     /// We return the table without "_tables". Keep that in mind if you use this.
@@ -285,6 +409,21 @@ impl Loc {
     ) -> Result<()> {
         self.table.export_tsv(path, table_name, file_path)
     }
+
+    /// This function exports the provided data as TSV to the provided writer, instead of to a file.
+    ///
+    /// The delimiter can be changed to export it as comma-delimited instead. If `escape` is true, tabs, newlines
+    /// and backslashes in strings are escaped, and restored on import.
+    pub fn export_tsv_to_writer<W: Write>(
+        &self,
+        writer: W,
+        table_name: &str,
+        file_path: &[String],
+        delimiter: Delimiter,
+        escape: bool,
+    ) -> Result<()> {
+        self.table.export_tsv_to_writer(writer, table_name, file_path, delimiter, escape)
+    }
 }
 
 /// Implementation to create a `Loc` from a `Table`.
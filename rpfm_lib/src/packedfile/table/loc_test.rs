@@ -0,0 +1,127 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Loc` module.
+!*/
+
+use std::env::temp_dir;
+use std::fs::{remove_file, write};
+
+use crate::schema::{Definition, FieldType, Schema, VersionedFile};
+use crate::test_helpers::*;
+
+use super::{DecodedData, Delimiter};
+use super::loc::{Loc, TSV_NAME_LOC};
+
+/// This function returns the definition of a Loc PackedFile.
+fn loc_definition() -> Definition {
+    definition(1, vec![
+        field("key", FieldType::StringU16, true),
+        field("text", FieldType::StringU16, false),
+        field("tooltip", FieldType::Boolean, false),
+    ])
+}
+
+#[test]
+fn test_loc_export_tsv_escaped() {
+    let definition = loc_definition();
+    let mut schema = Schema::default();
+    schema.add_versioned_file(&VersionedFile::Loc(vec![definition.clone()]));
+
+    let mut loc = Loc::new(&definition);
+    loc.set_table_data(&[
+        vec![DecodedData::StringU16("multi_line".to_owned()), DecodedData::StringU16("First line\nSecond\tline \\ end".to_owned()), DecodedData::Boolean(true)],
+    ]).unwrap();
+
+    // Escaped, the row must be kept in a single line and restored exactly on import.
+    let path = temp_dir().join("rpfm_test_loc_export_tsv_escaped.tsv");
+    let mut data = vec![];
+    loc.export_tsv_to_writer(&mut data, TSV_NAME_LOC, &["text".to_owned(), "test.loc".to_owned()], Delimiter::Tab, true).unwrap();
+    assert_eq!(String::from_utf8(data.to_vec()).unwrap().lines().count(), 3);
+
+    write(&path, &data).unwrap();
+    let (loc_imported, _) = Loc::import_tsv(&schema, &path).unwrap();
+    remove_file(&path).unwrap();
+    assert_eq!(loc_imported.get_ref_table_data(), loc.get_ref_table_data());
+}
+
+#[test]
+fn test_loc_classify_against() {
+    let definition = loc_definition();
+    let row = |key: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(String::new()), DecodedData::Boolean(false)];
+
+    let mut base = Loc::new(&definition);
+    base.set_table_data(&[row("a"), row("b")]).unwrap();
+
+    let mut loc = Loc::new(&definition);
+    loc.set_table_data(&[row("c"), row("a"), row("c")]).unwrap();
+    assert_eq!(loc.classify_against(&base), (vec!["a".to_owned()], vec!["c".to_owned()]));
+}
+
+#[test]
+fn test_loc_merge() {
+    let definition = loc_definition();
+    let row = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(false)];
+
+    let mut loc_a = Loc::new(&definition);
+    loc_a.set_table_data(&[row("a", "1"), row("b", "1"), row("b", "2")]).unwrap();
+
+    let mut loc_b = Loc::new(&definition);
+    loc_b.set_table_data(&[row("c", "1"), row("c", "2"), row("a", "2")]).unwrap();
+
+    let mut loc_c = Loc::new(&definition);
+    loc_c.set_table_data(&[row("a", "3")]).unwrap();
+
+    // Only keys overridden by a later Loc are reported, once each. Duplicates within the same Loc are not.
+    let (merged, overridden_keys) = Loc::merge(&[loc_a, loc_b, loc_c]).unwrap();
+    assert_eq!(overridden_keys, vec!["a".to_owned()]);
+    assert_eq!(merged.get_ref_table_data(), &[row("a", "3"), row("b", "2"), row("c", "2")]);
+
+    assert!(Loc::merge(&[]).is_err());
+}
+
+#[test]
+fn test_loc_rename_key() {
+    let definition = loc_definition();
+    let row = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(false)];
+
+    let mut loc = Loc::new(&definition);
+    loc.set_table_data(&[row("a", "1"), row("b", "a"), row("a", "2")]).unwrap();
+
+    // Every row with the key must be renamed in place, and only the key column is checked.
+    assert!(loc.rename_key("a", "c"));
+    assert_eq!(loc.get_ref_table_data(), &[row("c", "1"), row("b", "a"), row("c", "2")]);
+
+    assert!(!loc.rename_key("a", "d"));
+    assert_eq!(loc.get_ref_table_data(), &[row("c", "1"), row("b", "a"), row("c", "2")]);
+}
+
+#[test]
+fn test_loc_merge_with_priority() {
+    let definition = loc_definition();
+    let row = |key: &str, text: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(text.to_owned()), DecodedData::Boolean(false)];
+
+    let mut loc_a = Loc::new(&definition);
+    loc_a.set_table_data(&[row("a", "high"), row("b", "a")]).unwrap();
+
+    let mut loc_b = Loc::new(&definition);
+    loc_b.set_table_data(&[row("c", "b"), row("a", "low"), row("b", "b")]).unwrap();
+
+    let mut loc_c = Loc::new(&definition);
+    loc_c.set_table_data(&[row("b", "c")]).unwrap();
+
+    // Lower priorities never override higher ones, equal priorities are overridden by the last Loc,
+    // and rows keep the position where their key first appeared.
+    let merged = Loc::merge_with_priority(&[(&loc_a, 2), (&loc_b, 1), (&loc_c, 2)]);
+    assert_eq!(merged.get_ref_table_data(), &[row("a", "high"), row("b", "c"), row("c", "b")]);
+
+    assert!(Loc::merge_with_priority(&[]).get_ref_table_data().is_empty());
+}
@@ -18,11 +18,12 @@ use bincode::serialize;
 use csv::{QuoteStyle, ReaderBuilder, WriterBuilder};
 use serde_derive::{Serialize, Deserialize};
 
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::{fmt, fmt::Display};
 use std::fs::{DirBuilder, File};
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
 use rpfm_error::{Error, ErrorKind, Result};
@@ -37,6 +38,18 @@ pub mod db;
 pub mod loc;
 pub mod matched_combat;
 
+#[cfg(test)]
+mod animtable_test;
+
+#[cfg(test)]
+mod db_test;
+
+#[cfg(test)]
+mod loc_test;
+
+/// Marker added to the metadata line of exported TSV files with their tabs, newlines and backslashes escaped.
+pub const TSV_METADATA_ESCAPED: &str = "escaped";
+
 //---------------------------------------------------------------------------//
 //                              Enum & Structs
 //---------------------------------------------------------------------------//
@@ -45,15 +58,27 @@ pub mod matched_combat;
 ///
 /// This is for internal use. If you need to interact with this in any way, do it through the PackedFile that contains it, not directly.
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(from = "SerializedTable")]
 pub struct Table {
 
     /// A copy of the `Definition` this table uses, so we don't have to check the schema everywhere.
     definition: Definition,
 
+    /// The processed fields of the definition, cached so we don't have to process them every time we iterate the table.
+    #[serde(skip_serializing)]
+    fields_processed: Vec<Field>,
+
     /// The decoded entries of the table. This list is a Vec(rows) of a Vec(fields of a row) of DecodedData (decoded field).
     entries: Vec<Vec<DecodedData>>,
 }
 
+/// This struct is the serialized form of a `Table`, without its cached data. Used to rebuild the cache on deserialization.
+#[derive(Deserialize)]
+struct SerializedTable {
+    definition: Definition,
+    entries: Vec<Vec<DecodedData>>,
+}
+
 /// This enum is used to store different types of data in a unified way. Used, for example, to store the data from each field in a DB Table.
 ///
 /// NOTE: `Sequence` it's a recursive type. A Sequence/List means you got a repeated sequence of fields
@@ -72,7 +97,11 @@ pub enum DecodedData {
     OptionalStringU8(String),
     OptionalStringU16(String),
     SequenceU16(Table),
-    SequenceU32(Table)
+    SequenceU32(Table),
+
+    // New variants go at the end, so bincode-serialized data from older versions keeps its discriminants.
+    ColourRGBA(u32),
+    OptionalI32(Option<i32>)
 }
 
 /// This holds the dependency data for a specific column of a table.
@@ -89,6 +118,101 @@ pub struct DependencyData {
     pub data: HashMap<String, String>,
 }
 
+/// This enum represents the delimiters we can use when exporting a table to a delimited text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+
+    /// Tab-delimited, without quotes. This is the default, as it's the TSV format we use everywhere.
+    Tab,
+
+    /// Comma-delimited, quoting fields when needed.
+    Comma,
+
+    /// Semicolon-delimited, quoting fields when needed. This is the CSV flavour used by spreadsheets in many locales.
+    Semicolon,
+}
+
+//----------------------------------------------------------------//
+// Implementations for `Delimiter`.
+//----------------------------------------------------------------//
+
+/// Implementation of `Delimiter`.
+impl Delimiter {
+
+    /// This function returns the `Delimiter` as the byte the csv writer expects.
+    pub fn get_value(&self) -> u8 {
+        match *self {
+            Delimiter::Tab => b'\t',
+            Delimiter::Comma => b',',
+            Delimiter::Semicolon => b';',
+        }
+    }
+
+    /// This function returns the `Delimiter` corresponding to the provided name, or the default one if the name is not valid.
+    pub fn get_delimiter(value: &str) -> Self {
+        match value {
+            "comma" => Delimiter::Comma,
+            "semicolon" => Delimiter::Semicolon,
+            _ => Delimiter::default(),
+        }
+    }
+
+    /// This function returns the file extension used for files exported with this `Delimiter`.
+    pub fn get_extension(&self) -> &str {
+        match *self {
+            Delimiter::Tab => "tsv",
+            Delimiter::Comma | Delimiter::Semicolon => "csv",
+        }
+    }
+
+    /// This function returns the `Delimiter` a file should be imported with, based on its extension.
+    ///
+    /// `.csv` files are comma-delimited, unless their first line (the column names) only uses semicolons. Anything else is treated as a TSV file.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => {
+                let mut column_names = String::new();
+                if let Ok(file) = File::open(path) {
+                    let _ = BufReader::new(file).read_line(&mut column_names);
+                }
+
+                if column_names.contains(';') && !column_names.contains(',') { Delimiter::Semicolon } else { Delimiter::Comma }
+            }
+            _ => Delimiter::Tab,
+        }
+    }
+
+    /// This function returns the quote style to use with this `Delimiter`.
+    ///
+    /// TSV files are never quoted, as Excel, Libreoffice and all the programs that edit this kind of files break them on save.
+    /// The rest are quoted when needed, so fields containing the delimiter survive a round-trip.
+    fn get_quote_style(&self) -> QuoteStyle {
+        match *self {
+            Delimiter::Tab => QuoteStyle::Never,
+            Delimiter::Comma | Delimiter::Semicolon => QuoteStyle::Necessary,
+        }
+    }
+
+    /// This function returns a csv reader builder ready to read a file exported with this `Delimiter`.
+    ///
+    /// TSV files are read without quotes, as we never write them. The rest are read honoring quotes.
+    fn get_reader_builder(&self) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder.delimiter(self.get_value())
+            .quoting(*self != Delimiter::Tab)
+            .has_headers(true)
+            .flexible(true);
+        builder
+    }
+}
+
+/// Default implementation of `Delimiter`.
+impl Default for Delimiter {
+    fn default() -> Self {
+        Delimiter::Tab
+    }
+}
+
 //----------------------------------------------------------------//
 // Implementations for `DecodedData`.
 //----------------------------------------------------------------//
@@ -104,10 +228,12 @@ impl Display for DecodedData {
             DecodedData::I32(_) => write!(f, "I32"),
             DecodedData::I64(_) => write!(f, "I64"),
             DecodedData::ColourRGB(_) => write!(f, "ColourRGB"),
+            DecodedData::ColourRGBA(_) => write!(f, "ColourRGBA"),
             DecodedData::StringU8(_) => write!(f, "StringU8"),
             DecodedData::StringU16(_) => write!(f, "StringU16"),
             DecodedData::OptionalStringU8(_) => write!(f, "OptionalStringU8"),
             DecodedData::OptionalStringU16(_) => write!(f, "OptionalStringU16"),
+            DecodedData::OptionalI32(_) => write!(f, "OptionalI32"),
             DecodedData::SequenceU16(_) => write!(f, "SequenceU16"),
             DecodedData::SequenceU32(_) => write!(f, "SequenceU32"),
         }
@@ -125,10 +251,12 @@ impl PartialEq for DecodedData {
             (DecodedData::I32(x), DecodedData::I32(y)) => x == y,
             (DecodedData::I64(x), DecodedData::I64(y)) => x == y,
             (DecodedData::ColourRGB(x), DecodedData::ColourRGB(y)) => x == y,
+            (DecodedData::ColourRGBA(x), DecodedData::ColourRGBA(y)) => x == y,
             (DecodedData::StringU8(x), DecodedData::StringU8(y)) => x == y,
             (DecodedData::StringU16(x), DecodedData::StringU16(y)) => x == y,
             (DecodedData::OptionalStringU8(x), DecodedData::OptionalStringU8(y)) => x == y,
             (DecodedData::OptionalStringU16(x), DecodedData::OptionalStringU16(y)) => x == y,
+            (DecodedData::OptionalI32(x), DecodedData::OptionalI32(y)) => x == y,
             (DecodedData::SequenceU16(x), DecodedData::SequenceU16(y)) => x == y,
             (DecodedData::SequenceU32(x), DecodedData::SequenceU32(y)) => x == y,
             _ => false
@@ -150,10 +278,12 @@ impl DecodedData {
                 FieldType::I32 => if let Ok(value) = default_value.parse::<i32>() { DecodedData::I32(value) } else { DecodedData::I32(0) },
                 FieldType::I64 => if let Ok(value) = default_value.parse::<i64>() { DecodedData::I64(value) } else { DecodedData::I64(0) },
                 FieldType::ColourRGB => if let Ok(value) = default_value.parse::<u32>() { DecodedData::ColourRGB(value) } else { DecodedData::ColourRGB(0) },
+                FieldType::ColourRGBA => if let Ok(value) = default_value.parse::<u32>() { DecodedData::ColourRGBA(value) } else { DecodedData::ColourRGBA(0) },
                 FieldType::StringU8 => DecodedData::StringU8(default_value.to_owned()),
                 FieldType::StringU16 => DecodedData::StringU16(default_value.to_owned()),
                 FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(default_value.to_owned()),
                 FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(default_value.to_owned()),
+                FieldType::OptionalI32 => DecodedData::OptionalI32(default_value.parse::<i32>().ok()),
 
                 // For these two ignore the default value.
                 FieldType::SequenceU16(definition) => DecodedData::SequenceU16(Table::new(definition)),
@@ -167,10 +297,12 @@ impl DecodedData {
                 FieldType::I32 => DecodedData::I32(0),
                 FieldType::I64 => DecodedData::I64(0),
                 FieldType::ColourRGB => DecodedData::ColourRGB(0),
+                FieldType::ColourRGBA => DecodedData::ColourRGBA(0),
                 FieldType::StringU8 => DecodedData::StringU8("".to_owned()),
                 FieldType::StringU16 => DecodedData::StringU16("".to_owned()),
                 FieldType::OptionalStringU8 => DecodedData::OptionalStringU8("".to_owned()),
                 FieldType::OptionalStringU16 => DecodedData::OptionalStringU16("".to_owned()),
+                FieldType::OptionalI32 => DecodedData::OptionalI32(None),
                 FieldType::SequenceU16(definition) => DecodedData::SequenceU16(Table::new(definition)),
                 FieldType::SequenceU32(definition) => DecodedData::SequenceU32(Table::new(definition)),
             }
@@ -187,10 +319,12 @@ impl DecodedData {
             DecodedData::I32(_) => field_type == &FieldType::I32,
             DecodedData::I64(_) => field_type == &FieldType::I64,
             DecodedData::ColourRGB(_) => field_type == &FieldType::ColourRGB,
+            DecodedData::ColourRGBA(_) => field_type == &FieldType::ColourRGBA,
             DecodedData::StringU8(_) => field_type == &FieldType::StringU8,
             DecodedData::StringU16(_) => field_type == &FieldType::StringU16,
             DecodedData::OptionalStringU8(_) => field_type == &FieldType::OptionalStringU8,
             DecodedData::OptionalStringU16(_) => field_type == &FieldType::OptionalStringU16,
+            DecodedData::OptionalI32(_) => field_type == &FieldType::OptionalI32,
             DecodedData::SequenceU16(_) => matches!(field_type, FieldType::SequenceU16(_)),
             DecodedData::SequenceU32(_) => matches!(field_type, FieldType::SequenceU32(_)),
         }
@@ -209,10 +343,12 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(if *data { 1 } else { 0 })),
                 FieldType::I64 => Ok(Self::I64(if *data { 1 } else { 0 })),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(if *data { 1 } else { 0 })),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(if *data { 1 } else { 0 })),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(if *data { 1 } else { 0 }))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -225,10 +361,12 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(*data as i32)),
                 FieldType::I64 => Ok(Self::I64(*data as i64)),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(*data as u32)),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(*data as u32)),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data as i32))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -241,10 +379,12 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(*data as i32)),
                 FieldType::I64 => Ok(Self::I64(*data as i64)),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(*data as u32)),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(*data as u32)),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data as i32))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -257,10 +397,12 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(*data as i32)),
                 FieldType::I64 => Ok(Self::I64(*data as i64)),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(*data as u32)),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(*data as u32)),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data as i32))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -273,10 +415,12 @@ impl DecodedData {
                 FieldType::I32 => Ok(self.clone()),
                 FieldType::I64 => Ok(Self::I64(*data as i64)),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(*data as u32)),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(*data as u32)),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -289,10 +433,12 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(*data as i32)),
                 FieldType::I64 => Ok(self.clone()),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(*data as u32)),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(*data as u32)),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data as i32))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -305,10 +451,30 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(*data as i32)),
                 FieldType::I64 => Ok(Self::I64(*data as i64)),
                 FieldType::ColourRGB => Ok(self.clone()),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(*data)),
                 FieldType::StringU8 => Ok(Self::StringU8(self.data_to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(self.data_to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(self.data_to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(self.data_to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data as i32))),
+                FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
+                FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
+            }
+
+            Self::ColourRGBA(ref data) => match new_field_type {
+                FieldType::Boolean => Ok(Self::Boolean(data > &1)),
+                FieldType::F32 => Ok(Self::F32(*data as f32)),
+                FieldType::F64 => Ok(Self::F64(*data as f64)),
+                FieldType::I16 => Ok(Self::I16(*data as i16)),
+                FieldType::I32 => Ok(Self::I32(*data as i32)),
+                FieldType::I64 => Ok(Self::I64(*data as i64)),
+                FieldType::ColourRGB => Ok(Self::ColourRGB(*data)),
+                FieldType::ColourRGBA => Ok(self.clone()),
+                FieldType::StringU8 => Ok(Self::StringU8(self.data_to_string())),
+                FieldType::StringU16 => Ok(Self::StringU16(self.data_to_string())),
+                FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(self.data_to_string())),
+                FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(self.data_to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(Some(*data as i32))),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
@@ -324,14 +490,25 @@ impl DecodedData {
                 FieldType::I32 => Ok(Self::I32(data.parse::<i32>()?)),
                 FieldType::I64 => Ok(Self::I64(data.parse::<i64>()?)),
                 FieldType::ColourRGB => Ok(Self::ColourRGB(u32::from_str_radix(data, 16)?)),
+                FieldType::ColourRGBA => Ok(Self::ColourRGBA(u32::from_str_radix(data, 16)?)),
                 FieldType::StringU8 => Ok(Self::StringU8(data.to_string())),
                 FieldType::StringU16 => Ok(Self::StringU16(data.to_string())),
                 FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(data.to_string())),
                 FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(data.to_string())),
+                FieldType::OptionalI32 => Ok(Self::OptionalI32(if data.is_empty() { None } else { Some(data.parse::<i32>()?) })),
                 FieldType::SequenceU16(_) => Err(ErrorKind::Generic.into()),
                 FieldType::SequenceU32(_) => Err(ErrorKind::Generic.into()),
             }
 
+            Self::OptionalI32(ref data) => match new_field_type {
+                FieldType::OptionalI32 => Ok(self.clone()),
+                FieldType::StringU8 => Ok(Self::StringU8(self.data_to_string())),
+                FieldType::StringU16 => Ok(Self::StringU16(self.data_to_string())),
+                FieldType::OptionalStringU8 => Ok(Self::OptionalStringU8(self.data_to_string())),
+                FieldType::OptionalStringU16 => Ok(Self::OptionalStringU16(self.data_to_string())),
+                _ => Self::I32(data.unwrap_or_default()).convert_between_types(new_field_type),
+            }
+
             /*
             Self::SequenceU16(ref data) => match new_field_type {
                 FieldType::SequenceU16(ref definition) => Ok(self.clone()),
@@ -367,10 +544,19 @@ impl DecodedData {
                     Err(_) => "000000".to_owned(),
                 }
             },
+            DecodedData::ColourRGBA(data) => {
+                let mut encoded = Vec::with_capacity(4);
+                encoded.encode_integer_colour_rgba(*data);
+                match encoded.decode_string_colour_rgba(0) {
+                    Ok(data) => data,
+                    Err(_) => "00000000".to_owned(),
+                }
+            },
             DecodedData::StringU8(data) |
             DecodedData::StringU16(data) |
             DecodedData::OptionalStringU8(data) |
             DecodedData::OptionalStringU16(data) => data.to_owned(),
+            DecodedData::OptionalI32(data) => data.map(|data| data.to_string()).unwrap_or_default(),
             DecodedData::SequenceU16(_) => "SequenceU16".to_owned(),
             DecodedData::SequenceU32(_) => "SequenceU32".to_owned(),
         }
@@ -388,6 +574,7 @@ impl Table {
     pub fn new(definition: &Definition) -> Self {
         Table {
             definition: definition.clone(),
+            fields_processed: definition.get_fields_processed(),
             entries: vec![],
         }
     }
@@ -402,6 +589,13 @@ impl Table {
         &self.definition
     }
 
+    /// This function returns a reference to the processed fields of the definition of this Table.
+    ///
+    /// Unlike `Definition::get_fields_processed`, this doesn't process nor clone anything, so it's safe to use it in hot loops.
+    pub fn columns(&self) -> &[Field] {
+        &self.fields_processed
+    }
+
     /// This function returns a copy of the entries of this Table.
     pub fn get_table_data(&self) -> Vec<Vec<DecodedData>> {
         self.entries.to_vec()
@@ -412,6 +606,13 @@ impl Table {
         &self.entries
     }
 
+    /// This function returns a mutable reference to the entries of this Table.
+    ///
+    /// It's up to the caller to keep the rows valid for the definition of the table.
+    pub(crate) fn get_ref_mut_table_data(&mut self) -> &mut [Vec<DecodedData>] {
+        &mut self.entries
+    }
+
     /// This function returns the position of a column in a definition, or an error if the column is not found.
     pub fn get_column_position_by_name(&self, column_name: &str) -> Result<usize> {
         self.get_ref_definition().get_column_position_by_name(column_name)
@@ -476,6 +677,7 @@ impl Table {
 
         // Then, we finally replace our definition and our data.
         self.definition = new_definition.clone();
+        self.fields_processed = new_definition.get_fields_processed();
         self.entries = new_entries;
     }
 
@@ -504,6 +706,48 @@ impl Table {
         Ok(())
     }
 
+    /// This function creates a `Table` from an array of JSON objects keyed by field name, like the ones exported by RPFM-CLI.
+    ///
+    /// Keys not in the definition are ignored, and fields missing from an object get their default value.
+    /// Sequences are expected as nested arrays of objects, and colours as hexadecimal strings.
+    pub fn from_json(json: &serde_json::Value, definition: &Definition, table_name: Option<&str>) -> Result<Self> {
+        let rows = json.as_array().ok_or_else(|| Error::from(ErrorKind::TableWrongFieldType(json.to_string(), "Array".to_owned())))?;
+        let fields_processed = definition.get_fields_processed();
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let row = row.as_object().ok_or_else(|| Error::from(ErrorKind::TableWrongFieldType(row.to_string(), "Object".to_owned())))?;
+            let mut entry = Self::get_new_row(definition, table_name);
+            for (column, field) in fields_processed.iter().enumerate() {
+                if let Some(value) = row.get(field.get_name()) {
+                    let wrong_type = || Error::from(ErrorKind::TableWrongFieldType(value.to_string(), format!("{}", field.get_ref_field_type())));
+                    entry[column] = match field.get_ref_field_type() {
+                        FieldType::Boolean => DecodedData::Boolean(value.as_bool().ok_or_else(wrong_type)?),
+                        FieldType::F32 => DecodedData::F32(value.as_f64().ok_or_else(wrong_type)? as f32),
+                        FieldType::F64 => DecodedData::F64(value.as_f64().ok_or_else(wrong_type)?),
+                        FieldType::I16 => DecodedData::I16(value.as_i64().and_then(|x| i16::try_from(x).ok()).ok_or_else(wrong_type)?),
+                        FieldType::I32 => DecodedData::I32(value.as_i64().and_then(|x| i32::try_from(x).ok()).ok_or_else(wrong_type)?),
+                        FieldType::I64 => DecodedData::I64(value.as_i64().ok_or_else(wrong_type)?),
+                        FieldType::ColourRGB => DecodedData::ColourRGB(value.as_str().and_then(|x| u32::from_str_radix(x, 16).ok()).ok_or_else(wrong_type)?),
+                        FieldType::ColourRGBA => DecodedData::ColourRGBA(value.as_str().and_then(|x| u32::from_str_radix(x, 16).ok()).ok_or_else(wrong_type)?),
+                        FieldType::StringU8 => DecodedData::StringU8(value.as_str().ok_or_else(wrong_type)?.to_owned()),
+                        FieldType::StringU16 => DecodedData::StringU16(value.as_str().ok_or_else(wrong_type)?.to_owned()),
+                        FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(value.as_str().ok_or_else(wrong_type)?.to_owned()),
+                        FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(value.as_str().ok_or_else(wrong_type)?.to_owned()),
+                        FieldType::OptionalI32 => DecodedData::OptionalI32(if value.is_null() { None } else { Some(value.as_i64().and_then(|x| i32::try_from(x).ok()).ok_or_else(wrong_type)?) }),
+                        FieldType::SequenceU16(definition) => DecodedData::SequenceU16(Self::from_json(value, definition, None)?),
+                        FieldType::SequenceU32(definition) => DecodedData::SequenceU32(Self::from_json(value, definition, None)?),
+                    };
+                }
+            }
+            entries.push(entry);
+        }
+
+        let mut table = Self::new(definition);
+        table.set_table_data(&entries)?;
+        Ok(table)
+    }
+
     /// This function decodes all the fields of a table from raw bytes.
     ///
     /// If return_incomplete == true, this function will return an error with the incompletely decoded table when it fails.
@@ -550,6 +794,10 @@ impl Table {
                         if let Ok(data) = data.decode_packedfile_integer_colour_rgb(*index, &mut index) { Ok(DecodedData::ColourRGB(data)) }
                         else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>Colour RGB</b></i> value: the value is not a valid RGB value, or there are insufficient bytes left to decode it as an RGB value.</p>", row + 1, column + 1))) }
                     }
+                    FieldType::ColourRGBA => {
+                        if let Ok(data) = data.decode_packedfile_integer_colour_rgba(*index, index) { Ok(DecodedData::ColourRGBA(data)) }
+                        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as a <b><i>Colour RGBA</b></i> value: the value is not a valid RGBA value, or there are insufficient bytes left to decode it as an RGBA value.</p>", row + 1, column + 1))) }
+                    }
                     FieldType::StringU8 => {
                         if let Ok(data) = data.decode_packedfile_string_u8(*index, &mut index) { Ok(DecodedData::StringU8(Self::escape_special_chars(&data))) }
                         else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>UTF-8 String</b></i> value: the value is not a valid UTF-8 String, or there are insufficient bytes left to decode it as an UTF-8 String.</p>", row + 1, column + 1))) }
@@ -566,6 +814,10 @@ impl Table {
                         if let Ok(data) = data.decode_packedfile_optional_string_u16(*index, &mut index) { Ok(DecodedData::OptionalStringU16(Self::escape_special_chars(&data))) }
                         else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>Optional UTF-16 String</b></i> value: the value is not a valid Optional UTF-16 String, or there are insufficient bytes left to decode it as an Optional UTF-16 String.</p>", row + 1, column + 1))) }
                     }
+                    FieldType::OptionalI32 => {
+                        if let Ok(data) = data.decode_packedfile_optional_integer_i32(*index, index) { Ok(DecodedData::OptionalI32(data)) }
+                        else { Err(ErrorKind::HelperDecodingEncodingError(format!("<p>Error trying to decode the <i><b>Row {}, Cell {}</b></i> as an <b><i>Optional I32</b></i> value: the value is not a valid Optional I32, or there are insufficient bytes left to decode it as an Optional I32 value.</p>", row + 1, column + 1))) }
+                    }
 
                     // This type is just a recursive type.
                     FieldType::SequenceU16(definition) => {
@@ -774,6 +1026,8 @@ impl Table {
                         DecodedData::I32(data) => packed_file.encode_integer_i32(data),
                         DecodedData::I64(data) => packed_file.encode_integer_i64(data),
                         DecodedData::ColourRGB(data) => packed_file.encode_integer_colour_rgb(data),
+                        DecodedData::ColourRGBA(data) => packed_file.encode_integer_colour_rgba(data),
+                        DecodedData::OptionalI32(data) => packed_file.encode_packedfile_optional_integer_i32(data),
                         DecodedData::StringU8(ref data) |
                         DecodedData::StringU16(ref data) |
                         DecodedData::OptionalStringU8(ref data) |
@@ -924,6 +1178,17 @@ impl Table {
                             vec![DecodedData::ColourRGB(0); 1]
                         }
                     },
+                    FieldType::ColourRGBA => {
+                        if let Some(default_value) = field.get_default_value(table_name) {
+                            if let Ok(default_value) = u32::from_str_radix(&default_value, 16) {
+                                vec![DecodedData::ColourRGBA(default_value); 1]
+                            } else {
+                                vec![DecodedData::ColourRGBA(0); 1]
+                            }
+                        } else {
+                            vec![DecodedData::ColourRGBA(0); 1]
+                        }
+                    },
                     FieldType::StringU8 => {
                         if let Some(default_value) = field.get_default_value(table_name) {
                             vec![DecodedData::StringU8(default_value.to_owned()); 1]
@@ -952,6 +1217,13 @@ impl Table {
                             vec![DecodedData::OptionalStringU16(String::new()); 1]
                         }
                     },
+                    FieldType::OptionalI32 => {
+                        if let Some(default_value) = field.get_default_value(table_name) {
+                            vec![DecodedData::OptionalI32(default_value.parse::<i32>().ok()); 1]
+                        } else {
+                            vec![DecodedData::OptionalI32(None); 1]
+                        }
+                    },
                     FieldType::SequenceU16(ref definition) => vec![DecodedData::SequenceU16(Table::new(definition)); 1],
                     FieldType::SequenceU32(ref definition) => vec![DecodedData::SequenceU32(Table::new(definition)); 1]
                 }
@@ -1048,20 +1320,28 @@ impl Table {
     // TSV Functions for PackedFiles.
     //----------------------------------------------------------------//
 
-    /// This function imports a TSV file into a decoded table.
+    /// This function imports a TSV file (or a CSV one, if it has the `.csv` extension) into a decoded table.
     fn import_tsv(
         schema: &Schema,
         path: &Path,
     ) -> Result<(Self, Option<Vec<String>>)> {
 
-        // We want the reader to have no quotes, tab as delimiter and custom headers, because otherwise
+        // We want TSV readers to have no quotes, tab as delimiter and custom headers, because otherwise
         // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
-        let mut reader = ReaderBuilder::new()
-            .delimiter(b'\t')
-            .quoting(false)
-            .has_headers(true)
-            .flexible(true)
-            .from_path(&path)?;
+        let delimiter = Delimiter::from_path(path);
+        let reader = delimiter.get_reader_builder().from_path(path)?;
+
+        Self::import_tsv_from_reader(schema, reader, delimiter).map(|(table, file_path, _)| (table, file_path))
+    }
+
+    /// This function imports a TSV from an already configured reader into a decoded table.
+    ///
+    /// Returns the table, the path in the metadata of the TSV, if any, and the table type (table name) in the metadata.
+    fn import_tsv_from_reader<R: Read>(
+        schema: &Schema,
+        mut reader: csv::Reader<R>,
+        delimiter: Delimiter,
+    ) -> Result<(Self, Option<Vec<String>>, String)> {
 
         // If we successfully load the TSV file into a reader, check the first line to get the column list.
         let field_order = reader.headers()?.iter().enumerate().map(|(x, y)| (x as u32, y.to_owned())).collect::<BTreeMap<u32, String>>();
@@ -1070,27 +1350,13 @@ impl Table {
         let mut definition = Definition::new(-1);
         let mut file_path = None;
         let mut table_type = String::new();
+        let mut escaped = false;
         for (row, record) in reader.records().enumerate() {
             if let Ok(record) = record {
 
                 // The second line contains the TSV metadata. It may have it split in three columns, or just one.
                 if row == 0 {
-                    let has_legacy_structure = if let Some(table_type) = record.get(1) { table_type != "" } else { false };
-
-                    // If we have at least 2 fields, use the legacy behavior.
-                    let record_data = if has_legacy_structure {
-                        record.iter().map(|x| x.to_owned()).collect::<Vec<String>>()
-                    }
-
-                    // Otherwise, use the new behavior.
-                    else if let Some(metadata) = record.get(0) {
-                        metadata.split(';').map(|x| x.to_owned()).collect::<Vec<String>>()
-                    }
-
-                    // Otherwise, is an error.
-                    else {
-                        return Err(ErrorKind::ImportTSVWrongTypeTable.into())
-                    };
+                    let record_data = Self::get_tsv_metadata(&record, delimiter)?;
 
                     // Get the type and version of the table, then the definition.
                     table_type = if let Some(table_type) = record_data.get(0) {
@@ -1101,6 +1367,7 @@ impl Table {
                         table_type
                     } else { return Err(ErrorKind::ImportTSVWrongTypeTable.into()) };
                     let table_version = if let Some(table_version) = record_data.get(1) { table_version.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVInvalidVersion))? } else { return Err(ErrorKind::ImportTSVInvalidVersion.into()) };
+                    escaped = record_data.get(3).map(|x| x == TSV_METADATA_ESCAPED).unwrap_or(false);
                     file_path = record_data.get(2).map(|x| x.split('/').map(|x| x.to_string()).collect::<Vec<String>>());
 
                     definition = if table_type == loc::TSV_NAME_LOC { schema.get_ref_versioned_file_loc()?.get_version(table_version)?.clone() }
@@ -1112,6 +1379,8 @@ impl Table {
                 else {
                     let mut entry = Self::get_new_row(&definition, Some(&table_type));
                     for (column, field) in record.iter().enumerate() {
+                        let field = if escaped { Cow::Owned(Self::unescape_tsv_string(field)) } else { Cow::Borrowed(field) };
+                        let field = field.as_ref();
 
                         // Get the column name from the header, and try to map it to a column in the table's.
                         if let Some(column_name) = field_order.get(&(column as u32)) {
@@ -1130,10 +1399,12 @@ impl Table {
                                     FieldType::I32 => DecodedData::I32(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
                                     FieldType::I64 => DecodedData::I64(field.parse::<i64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
                                     FieldType::ColourRGB => DecodedData::ColourRGB(u32::from_str_radix(field, 16).map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
+                                    FieldType::ColourRGBA => DecodedData::ColourRGBA(u32::from_str_radix(field, 16).map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
                                     FieldType::StringU8 => DecodedData::StringU8(field.to_owned()),
                                     FieldType::StringU16 => DecodedData::StringU16(field.to_owned()),
                                     FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(field.to_owned()),
                                     FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(field.to_owned()),
+                                    FieldType::OptionalI32 => DecodedData::OptionalI32(if field.is_empty() { None } else { Some(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?) }),
 
                                     // For now fail on Sequences. These are a bit special and I don't know if the're even possible in TSV.
                                     FieldType::SequenceU16(_) => return Err(ErrorKind::ImportTSVIncorrectRow(row, column).into()),
@@ -1151,24 +1422,33 @@ impl Table {
         // If we reached this point without errors, we replace the old data with the new one and return success.
         let mut table = Table::new(&definition);
         table.set_table_data(&entries)?;
-        Ok((table, file_path))
+        Ok((table, file_path, table_type))
     }
 
-    /// This function imports a TSV file into a new Table File.
+    /// This function imports a TSV already in memory into a decoded table.
+    ///
+    /// Returns the table and its table type (table name), from the metadata of the TSV.
+    fn import_tsv_from_str(
+        schema: &Schema,
+        tsv: &str,
+    ) -> Result<(Self, String)> {
+        let reader = Delimiter::Tab.get_reader_builder().from_reader(tsv.as_bytes());
+
+        Self::import_tsv_from_reader(schema, reader, Delimiter::Tab).map(|(table, _, table_type)| (table, table_type))
+    }
+
+    /// This function imports a TSV file (or a CSV one, if it has the `.csv` extension) into a new Table File.
     fn import_tsv_to_binary_file(
         schema: &Schema,
         source_path: &Path,
         destination_path: &Path,
+        dedup: bool,
     ) -> Result<()> {
 
-        // We want the reader to have no quotes, tab as delimiter and custom headers, because otherwise
+        // We want TSV readers to have no quotes, tab as delimiter and custom headers, because otherwise
         // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
-        let mut reader = ReaderBuilder::new()
-            .delimiter(b'\t')
-            .quoting(false)
-            .has_headers(true)
-            .flexible(true)
-            .from_path(&source_path)?;
+        let delimiter = Delimiter::from_path(source_path);
+        let mut reader = delimiter.get_reader_builder().from_path(source_path)?;
 
         // If we successfully load the TSV file into a reader, check the first line to get the column list.
         let field_order = reader.headers()?.iter().enumerate().map(|(x, y)| (x as u32, y.to_owned())).collect::<BTreeMap<u32, String>>();
@@ -1176,28 +1456,14 @@ impl Table {
         let mut fields_processed = vec![];
         let mut definition = Definition::new(-1);
         let mut table_type = String::new();
+        let mut escaped = false;
         for (row, record) in reader.records().enumerate() {
             if let Ok(record) = record {
 
                 // The second line contains the TSV metadata.
                 if row == 0 {
 
-                    let has_legacy_structure = if let Some(table_type) = record.get(1) { table_type != "" } else { false };
-
-                    // If we have at least 2 fields, use the legacy behavior.
-                    let record_data = if has_legacy_structure {
-                        record.iter().map(|x| x.to_owned()).collect::<Vec<String>>()
-                    }
-
-                    // Otherwise, use the new behavior.
-                    else if let Some(metadata) = record.get(0) {
-                        metadata.split(';').map(|x| x.to_owned()).collect::<Vec<String>>()
-                    }
-
-                    // Otherwise, is an error.
-                    else {
-                        return Err(ErrorKind::ImportTSVWrongTypeTable.into())
-                    };
+                    let record_data = Self::get_tsv_metadata(&record, delimiter)?;
 
                     // Get the type and version of the table, then the definition.
                     table_type = if let Some(table_type) = record_data.get(0) {
@@ -1208,6 +1474,7 @@ impl Table {
                         table_type
                     } else { return Err(ErrorKind::ImportTSVWrongTypeTable.into()) };
                     let table_version = if let Some(table_version) = record_data.get(1) { table_version.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVInvalidVersion))? } else { return Err(ErrorKind::ImportTSVInvalidVersion.into()) };
+                    escaped = record_data.get(3).map(|x| x == TSV_METADATA_ESCAPED).unwrap_or(false);
 
                     definition = if table_type == loc::TSV_NAME_LOC { schema.get_ref_versioned_file_loc()?.get_version(table_version)?.clone() }
                     else { schema.get_ref_versioned_file_db(&table_type)?.get_version(table_version)?.clone() };
@@ -1218,6 +1485,8 @@ impl Table {
 
                     let mut entry = Self::get_new_row(&definition, Some(&table_type));
                     for (column, field) in record.iter().enumerate() {
+                        let field = if escaped { Cow::Owned(Self::unescape_tsv_string(field)) } else { Cow::Borrowed(field) };
+                        let field = field.as_ref();
 
                         // Get the column name from the header, and try to map it to a column in the table's.
                         if let Some(column_name) = field_order.get(&(column as u32)) {
@@ -1236,10 +1505,12 @@ impl Table {
                                     FieldType::I32 => DecodedData::I32(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
                                     FieldType::I64 => DecodedData::I64(field.parse::<i64>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
                                     FieldType::ColourRGB => DecodedData::ColourRGB(u32::from_str_radix(field, 16).map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
+                                    FieldType::ColourRGBA => DecodedData::ColourRGBA(u32::from_str_radix(field, 16).map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?),
                                     FieldType::StringU8 => DecodedData::StringU8(field.to_owned()),
                                     FieldType::StringU16 => DecodedData::StringU16(field.to_owned()),
                                     FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(field.to_owned()),
                                     FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(field.to_owned()),
+                                    FieldType::OptionalI32 => DecodedData::OptionalI32(if field.is_empty() { None } else { Some(field.parse::<i32>().map_err(|_| Error::from(ErrorKind::ImportTSVIncorrectRow(row, column)))?) }),
 
                                     // For now fail on Sequences. These are a bit special and I don't know if the're even possible in TSV.
                                     FieldType::SequenceU16(_) => return Err(ErrorKind::ImportTSVIncorrectRow(row, column).into()),
@@ -1264,6 +1535,9 @@ impl Table {
         else {
             let mut file = db::DB::new(&table_type, None, &definition);
             file.set_table_data(&entries)?;
+            if dedup {
+                file.dedup_by_key();
+            }
             file.save()
         }?;
 
@@ -1288,53 +1562,139 @@ impl Table {
         folder_path.pop();
         DirBuilder::new().recursive(true).create(&folder_path)?;
 
-        // We want the writer to have no quotes, tab as delimiter and custom headers, because otherwise
-        // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
-        let mut writer = WriterBuilder::new()
-            .delimiter(b'\t')
-            .quote_style(QuoteStyle::Never)
-            .has_headers(false)
-            .flexible(true)
-            .from_path(path)?;
+        self.export_tsv_to_writer(File::create(path)?, table_name, file_path, Delimiter::Tab, false)
+    }
 
+    /// This function exports the provided data as TSV (or any other supported delimiter) to the provided writer.
+    ///
+    /// If `escape` is true, tabs, newlines and backslashes in strings are written as `\t`, `\n` and `\\`, and the file
+    /// is marked as escaped in its metadata, so they're restored on import.
+    fn export_tsv_to_writer<W: Write>(
+        &self,
+        writer: W,
+        table_name: &str,
+        file_path: &[String],
+        delimiter: Delimiter,
+        escape: bool,
+    ) -> Result<()> {
         let fields_sorted = self.definition.get_fields_sorted();
         let sorted_indexes = fields_sorted.iter()
-            .map(|field_sorted| self.definition.get_fields_processed().iter().position(|field| field == field_sorted).unwrap())
+            .map(|field_sorted| self.columns().iter().position(|field| field == field_sorted).unwrap())
             .collect::<Vec<usize>>();
 
         // We serialize the info of the table (name and version) in the first line, and the column names in the second one.
-        let metadata = ("#".to_owned() + table_name + ";" + &self.definition.get_version().to_string() + ";" + &file_path.join("/"), (0..sorted_indexes.len() - 1).map(|_| "".to_owned()).collect::<Vec<String>>());
-        writer.serialize(fields_sorted.iter().map(|x| x.get_name().to_owned()).collect::<Vec<String>>())?;
-        writer.serialize(metadata)?;
+        let mut metadata_info = "#".to_owned() + table_name + ";" + &self.definition.get_version().to_string() + ";" + &file_path.join("/");
+        if escape {
+            metadata_info.push(';');
+            metadata_info.push_str(TSV_METADATA_ESCAPED);
+        }
+        let metadata = (metadata_info, (0..sorted_indexes.len() - 1).map(|_| "".to_owned()).collect::<Vec<String>>());
+        let column_names = fields_sorted.iter().map(|x| x.get_name().to_owned()).collect::<Vec<String>>();
+        let mut writer = Self::export_tsv_header(writer, &column_names, &metadata, delimiter)?;
 
-        // Then we serialize each entry in the DB Table.
+        // Then we serialize each entry in the DB Table. Only colours (and strings, if escaping) need conversion,
+        // so we borrow everything else to avoid cloning the entire table while writing it.
         for entry in &self.entries {
             let sorted_entry = sorted_indexes.iter()
                 .map(|index| &entry[*index])
-                .map(|data| if let DecodedData::ColourRGB(_) = data { DecodedData::StringU8(data.data_to_string()) } else { data.clone() })
-                .collect::<Vec<DecodedData>>();
+                .map(|data| match data {
+                    DecodedData::ColourRGB(_) | DecodedData::ColourRGBA(_) => Cow::Owned(DecodedData::StringU8(data.data_to_string())),
+                    DecodedData::StringU8(value) if escape => Cow::Owned(DecodedData::StringU8(Self::escape_tsv_string(value))),
+                    DecodedData::StringU16(value) if escape => Cow::Owned(DecodedData::StringU16(Self::escape_tsv_string(value))),
+                    DecodedData::OptionalStringU8(value) if escape => Cow::Owned(DecodedData::OptionalStringU8(Self::escape_tsv_string(value))),
+                    DecodedData::OptionalStringU16(value) if escape => Cow::Owned(DecodedData::OptionalStringU16(Self::escape_tsv_string(value))),
+                    _ => Cow::Borrowed(data),
+                })
+                .collect::<Vec<Cow<DecodedData>>>();
             writer.serialize(&sorted_entry)?;
         }
 
         writer.flush().map_err(From::from)
     }
 
-    /// This function exports the provided file to a TSV file..
+    /// This function escapes the tabs, newlines and backslashes of a string, so it fits in a single TSV cell.
+    fn escape_tsv_string(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+    }
+
+    /// This function reverts the escaping done by `escape_tsv_string`.
+    fn unescape_tsv_string(value: &str) -> String {
+        let mut unescaped = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(character) = chars.next() {
+            if character == '\\' {
+                match chars.next() {
+                    Some('t') => unescaped.push('\t'),
+                    Some('n') => unescaped.push('\n'),
+                    Some('r') => unescaped.push('\r'),
+                    Some(other) => unescaped.push(other),
+                    None => unescaped.push(character),
+                }
+            } else {
+                unescaped.push(character);
+            }
+        }
+        unescaped
+    }
+
+    /// This function writes the column names and metadata lines of an exported table, and returns a writer ready for the rows.
+    ///
+    /// Column names never contain delimiters, and the metadata line is never quoted, so it always starts with `#`, which is what importers use to detect it.
+    fn export_tsv_header<W: Write>(
+        mut writer: W,
+        column_names: &[String],
+        metadata: &(String, Vec<String>),
+        delimiter: Delimiter,
+    ) -> Result<csv::Writer<W>> {
+        let separator = (delimiter.get_value() as char).to_string();
+        let mut header = column_names.join(&separator);
+        header.push('\n');
+        header.push_str(&metadata.0);
+        metadata.1.iter().for_each(|field| {
+            header.push_str(&separator);
+            header.push_str(field);
+        });
+        header.push('\n');
+        writer.write_all(header.as_bytes())?;
+
+        Ok(WriterBuilder::new()
+            .delimiter(delimiter.get_value())
+            .quote_style(delimiter.get_quote_style())
+            .has_headers(false)
+            .flexible(true)
+            .from_writer(writer))
+    }
+
+    /// This function returns the fields of the metadata line of an imported table: its type, version, path and escaping mark.
+    ///
+    /// The metadata may be in a single field, separated by `;`, or in one field per value in legacy files. As the metadata line
+    /// is never quoted, any delimiter within it has split it into multiple fields, so we join them back before splitting it.
+    fn get_tsv_metadata(record: &csv::StringRecord, delimiter: Delimiter) -> Result<Vec<String>> {
+        if record.is_empty() {
+            return Err(ErrorKind::ImportTSVWrongTypeTable.into())
+        }
+
+        let separator = delimiter.get_value() as char;
+        let metadata = record.iter().collect::<Vec<&str>>().join(&separator.to_string());
+        let metadata = metadata.trim_end_matches(separator);
+        if metadata.contains(';') {
+            Ok(metadata.split(';').map(|x| x.to_owned()).collect())
+        } else {
+            Ok(record.iter().map(|x| x.to_owned()).collect())
+        }
+    }
+
+    /// This function exports the provided file to a TSV file (or any other supported delimiter).
+    ///
+    /// If `escape` is true, strings are escaped like in `export_tsv_to_writer`.
     fn export_tsv_from_binary_file(
         schema: &Schema,
         source_path: &Path,
-        destination_path: &Path
+        destination_path: &Path,
+        delimiter: Delimiter,
+        escape: bool,
     ) -> Result<()> {
 
-        // We want the writer to have no quotes, tab as delimiter and custom headers, because otherwise
-        // Excel, Libreoffice and all the programs that edit this kind of files break them on save.
-        let mut writer = WriterBuilder::new()
-            .delimiter(b'\t')
-            .quote_style(QuoteStyle::Never)
-            .has_headers(false)
-            .flexible(true)
-            .from_path(destination_path)?;
-
         // We don't know what type this file is, so we try to decode it as a Loc. If that fails, we try
         // to decode it as a DB using the name of his parent folder. If that fails too, run before it explodes!
         let mut file = BufReader::new(File::open(source_path)?);
@@ -1359,15 +1719,27 @@ impl Table {
             .collect::<Vec<usize>>();
 
         // We serialize the info of the table (name and version) in the first line, and the column names in the second one.
-        let metadata = ("#".to_owned() + table_type + ";" + &version.to_string(), (0..sorted_indexes.len() - 1).map(|_| "".to_owned()).collect::<Vec<String>>());
-        writer.serialize(fields_sorted.iter().map(|x| x.get_name().to_owned()).collect::<Vec<String>>())?;
-        writer.serialize(metadata)?;
+        let mut metadata_info = "#".to_owned() + table_type + ";" + &version.to_string();
+        if escape {
+            metadata_info.push_str(";;");
+            metadata_info.push_str(TSV_METADATA_ESCAPED);
+        }
+        let metadata = (metadata_info, (0..sorted_indexes.len() - 1).map(|_| "".to_owned()).collect::<Vec<String>>());
+        let column_names = fields_sorted.iter().map(|x| x.get_name().to_owned()).collect::<Vec<String>>();
+        let mut writer = Self::export_tsv_header(BufWriter::new(File::create(destination_path)?), &column_names, &metadata, delimiter)?;
 
         // Then we serialize each entry in the DB Table.
         for entry in entries {
             let sorted_entry = sorted_indexes.iter()
                 .map(|index| &entry[*index])
-                .map(|data| if let DecodedData::ColourRGB(_) = data { DecodedData::StringU8(data.data_to_string()) } else { data.clone() })
+                .map(|data| match data {
+                    DecodedData::ColourRGB(_) | DecodedData::ColourRGBA(_) => DecodedData::StringU8(data.data_to_string()),
+                    DecodedData::StringU8(value) if escape => DecodedData::StringU8(Self::escape_tsv_string(value)),
+                    DecodedData::StringU16(value) if escape => DecodedData::StringU16(Self::escape_tsv_string(value)),
+                    DecodedData::OptionalStringU8(value) if escape => DecodedData::OptionalStringU8(Self::escape_tsv_string(value)),
+                    DecodedData::OptionalStringU16(value) if escape => DecodedData::OptionalStringU16(Self::escape_tsv_string(value)),
+                    _ => data.clone(),
+                })
                 .collect::<Vec<DecodedData>>();
             writer.serialize(&sorted_entry)?;
         }
@@ -1394,6 +1766,17 @@ impl Table {
     }
 }
 
+/// Implementation of `From<SerializedTable>` for `Table`.
+impl From<SerializedTable> for Table {
+    fn from(table: SerializedTable) -> Self {
+        Self {
+            fields_processed: table.definition.get_fields_processed(),
+            definition: table.definition,
+            entries: table.entries,
+        }
+    }
+}
+
 /// Implementation of `From<&RawTable>` for `Table`.
 impl From<&RawTable> for Table {
     fn from(raw_table: &RawTable) -> Self {
@@ -1417,10 +1800,12 @@ impl From<&RawTable> for Table {
                                 FieldType::I32 => DecodedData::I32(if let Ok(data) = field.field_data.parse::<i32>() { data } else { 0 }),
                                 FieldType::I64 => DecodedData::I64(if let Ok(data) = field.field_data.parse::<i64>() { data } else { 0 }),
                                 FieldType::ColourRGB => DecodedData::ColourRGB(if let Ok(data) = u32::from_str_radix(&field.field_data, 16) { data } else { 0 }),
+                                FieldType::ColourRGBA => DecodedData::ColourRGBA(u32::from_str_radix(&field.field_data, 16).unwrap_or_default()),
                                 FieldType::StringU8 => DecodedData::StringU8(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
                                 FieldType::StringU16 => DecodedData::StringU16(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
                                 FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
                                 FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(if field.field_data == "Frodo Best Waifu" { String::new() } else { field.field_data.to_string() }),
+                                FieldType::OptionalI32 => DecodedData::OptionalI32(field.field_data.parse::<i32>().ok()),
 
                                 // This type is not used in the raw tables so, if we find it, we skip it.
                                 FieldType::SequenceU16(_) | FieldType::SequenceU32(_) => continue,
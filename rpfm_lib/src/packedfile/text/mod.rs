@@ -17,15 +17,18 @@ The only thing to take into account is that this only work for UTF-8 encoded fil
 
 use serde_derive::{Serialize, Deserialize};
 
-use rpfm_error::{ErrorKind, Result};
+use rpfm_error::{Error, ErrorKind, Result};
 
 use crate::common::{decoder::Decoder, encoder::Encoder};
 
+#[cfg(test)]
+mod text_test;
+
 /// UTF-8 BOM (Byte Order Mark).
 const BOM_UTF_8: [u8;3] = [0xEF,0xBB,0xBF];
 
-// UTF-16 BOM (Byte Order Mark), Big Endian.
-//const BOM_UTF_16_BE: [u8;2] = [0xFE,0xFF];
+/// UTF-16 BOM (Byte Order Mark), Big Endian.
+const BOM_UTF_16_BE: [u8;2] = [0xFE,0xFF];
 
 /// UTF-16 BOM (Byte Order Mark), Little Endian.
 const BOM_UTF_16_LE: [u8;2] = [0xFF,0xFE];
@@ -45,8 +48,8 @@ pub const EXTENSIONS: [(&str, TextType); 23] = [
     (".benchmark", TextType::Xml),
     (".cindyscene", TextType::Xml),
     (".cindyscenemanager", TextType::Xml),
-    (".csv", TextType::Plain),
-    (".tsv", TextType::Plain),
+    (".csv", TextType::Csv),
+    (".tsv", TextType::Csv),
     (".tai", TextType::Plain),
     (".battle_speech_camera", TextType::Plain),
     (".bob", TextType::Plain),
@@ -79,7 +82,7 @@ pub struct Text {
 #[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum SupportedEncodings {
     Utf8,
-    //Utf16Be,
+    Utf16Be,
     Utf16Le,
     Iso8859_1,
     //Iso8859_15,
@@ -97,6 +100,7 @@ pub enum TextType {
     Markdown,
     Json,
     Plain,
+    Csv,
 }
 
 //---------------------------------------------------------------------------//
@@ -123,34 +127,39 @@ impl Text {
     }
 
     /// This function creates a `Text` from a `Vec<u8>`.
+    ///
+    /// The encoding is guessed with `Text::detect_encoding`. If it cannot be guessed, we assume ISO-8859-1, as it can decode anything.
     pub fn read(packed_file_data: &[u8]) -> Result<Self> {
+        let encoding = Self::detect_encoding(packed_file_data).unwrap_or(SupportedEncodings::Iso8859_1);
+        Self::decode_with_encoding(packed_file_data, encoding)
+    }
 
-        // First, check for BOMs. 2 bytes for UTF-16 BOMs, 3 for UTF-8. If no BOM is found, we assume UTF-8 or ISO5589-1.
-        let (packed_file_data, guessed_encoding) = if packed_file_data.is_empty() { (packed_file_data, SupportedEncodings::Utf8) }
-        else if packed_file_data.len() > 2 && packed_file_data[0..3] == BOM_UTF_8 { (&packed_file_data[3..], SupportedEncodings::Utf8) }
-        //else if packed_file_data.len() > 1 && packed_file_data[0..2] == BOM_UTF_16_BE { (&packed_file_data[2..], SupportedEncodings::UTF16_BE) }
-        else if packed_file_data.len() > 1 && packed_file_data[0..2] == BOM_UTF_16_LE { (&packed_file_data[2..], SupportedEncodings::Utf16Le) }
-        else { (packed_file_data, SupportedEncodings::Utf8) };
-
-        // This is simple: we try to decode it depending on what the guesser gave us. If all fails, return error.
-        let (encoding, contents) = match guessed_encoding {
-            SupportedEncodings::Utf8 | SupportedEncodings::Iso8859_1 => {
-                match packed_file_data.decode_string_u8(0, packed_file_data.len()) {
-                    Ok(string) => (SupportedEncodings::Utf8, string),
-                    Err(_) => match packed_file_data.decode_string_u8_iso_8859_1(0, packed_file_data.len()) {
-                        Ok(string) => (SupportedEncodings::Iso8859_1, string),
-                        Err(_) => return Err(ErrorKind::TextDecodeWrongEncodingOrNotATextFile.into()),
-                    }
-                }
+    /// This function creates a `Text` from a `Vec<u8>`, decoding it with the provided encoding.
+    ///
+    /// If the data starts with the BOM of the provided encoding, the BOM is skipped. If the data is not valid for the encoding, this returns an error.
+    pub fn decode_with_encoding(packed_file_data: &[u8], encoding: SupportedEncodings) -> Result<Self> {
+        let contents = match encoding {
+            SupportedEncodings::Utf8 => {
+                let packed_file_data = packed_file_data.strip_prefix(&BOM_UTF_8[..]).unwrap_or(packed_file_data);
+                packed_file_data.decode_string_u8(0, packed_file_data.len())
             }
-
+            SupportedEncodings::Iso8859_1 => packed_file_data.decode_string_u8_iso_8859_1(0, packed_file_data.len()),
             SupportedEncodings::Utf16Le => {
-                match packed_file_data.decode_string_u16(0, packed_file_data.len()) {
-                    Ok(string) => (SupportedEncodings::Utf16Le, string),
-                    Err(_) => return Err(ErrorKind::TextDecodeWrongEncodingOrNotATextFile.into()),
+                let packed_file_data = packed_file_data.strip_prefix(&BOM_UTF_16_LE[..]).unwrap_or(packed_file_data);
+                packed_file_data.decode_string_u16(0, packed_file_data.len())
+            }
+
+            // There is no helper for Big Endian UTF-16 in the decoder, as the game doesn't use it.
+            SupportedEncodings::Utf16Be => {
+                let packed_file_data = packed_file_data.strip_prefix(&BOM_UTF_16_BE[..]).unwrap_or(packed_file_data);
+                if packed_file_data.len() % 2 == 0 {
+                    let u16_characters = packed_file_data.chunks_exact(2).map(|x| u16::from_be_bytes([x[0], x[1]])).collect::<Vec<u16>>();
+                    String::from_utf16(&u16_characters).map_err(|_| Error::from(ErrorKind::TextDecodeWrongEncodingOrNotATextFile))
+                } else {
+                    Err(ErrorKind::TextDecodeWrongEncodingOrNotATextFile.into())
                 }
             }
-        };
+        }.map_err(|_| ErrorKind::TextDecodeWrongEncodingOrNotATextFile)?;
 
         // Without the path we can't know the text type, so we left it as plain, and overwrite it later.
         let text_type = TextType::Plain;
@@ -162,6 +171,36 @@ impl Text {
         })
     }
 
+    /// This function tries to guess the encoding of the provided data.
+    ///
+    /// It checks for BOMs first. If there are none, it checks if the data looks like UTF-16 (ASCII characters leave half of its bytes as zeros),
+    /// then if it's valid UTF-8 and, if it doesn't have zeros at all, it assumes ISO-8859-1. Otherwise, it returns `None`.
+    pub fn detect_encoding(data: &[u8]) -> Option<SupportedEncodings> {
+        if data.starts_with(&BOM_UTF_8) && std::str::from_utf8(&data[BOM_UTF_8.len()..]).is_ok() { return Some(SupportedEncodings::Utf8) }
+        if data.starts_with(&BOM_UTF_16_LE) { return Some(SupportedEncodings::Utf16Le) }
+        if data.starts_with(&BOM_UTF_16_BE) { return Some(SupportedEncodings::Utf16Be) }
+
+        if !data.is_empty() && data.len() % 2 == 0 {
+            let zeros_even = data.iter().step_by(2).filter(|x| **x == 0).count();
+            let zeros_odd = data.iter().skip(1).step_by(2).filter(|x| **x == 0).count();
+            let half_code_units = data.len() / 4;
+
+            let guessed_encoding = if zeros_odd > half_code_units && zeros_even == 0 { Some(SupportedEncodings::Utf16Le) }
+            else if zeros_even > half_code_units && zeros_odd == 0 { Some(SupportedEncodings::Utf16Be) }
+            else { None };
+
+            if let Some(encoding) = guessed_encoding {
+                if Self::decode_with_encoding(data, encoding).is_ok() {
+                    return Some(encoding);
+                }
+            }
+        }
+
+        if std::str::from_utf8(data).is_ok() { Some(SupportedEncodings::Utf8) }
+        else if !data.contains(&0) { Some(SupportedEncodings::Iso8859_1) }
+        else { None }
+    }
+
     /// This function takes a `Text` and encodes it to `Vec<u8>`.
     ///
     /// TODO: Make this save other than UTF-8.
@@ -176,6 +215,10 @@ impl Text {
                 data.append(&mut BOM_UTF_16_LE.to_vec());
                 data.encode_string_u16(&self.contents)
             },
+            SupportedEncodings::Utf16Be => {
+                data.append(&mut BOM_UTF_16_BE.to_vec());
+                self.contents.encode_utf16().for_each(|character| data.extend_from_slice(&character.to_be_bytes()));
+            },
         }
 
         Ok(data)
@@ -210,4 +253,23 @@ impl Text {
     pub fn set_text_type(&mut self, text_type: TextType) {
         self.text_type = text_type;
     }
+
+    /// This function tries to guess the type of the text file from its contents.
+    ///
+    /// Only formats that can be reliably identified are detected. Otherwise, this returns `TextType::Plain`.
+    pub fn guess_text_type(&self) -> TextType {
+        let contents = self.contents.trim();
+        if contents.starts_with("<?xml") || (contents.starts_with('<') && contents.ends_with('>')) {
+            TextType::Xml
+        }
+
+        else if ((contents.starts_with('{') && contents.ends_with('}')) || (contents.starts_with('[') && contents.ends_with(']'))) &&
+            serde_json::from_str::<serde_json::Value>(contents).is_ok() {
+            TextType::Json
+        }
+
+        else {
+            TextType::Plain
+        }
+    }
 }
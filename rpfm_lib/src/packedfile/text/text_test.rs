@@ -0,0 +1,57 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Text` module.
+!*/
+
+use super::{SupportedEncodings, Text, TextType};
+
+#[test]
+fn test_text_detect_encoding() {
+    let mut data = vec![0xFF, 0xFE];
+    "-- Script ñ".encode_utf16().for_each(|character| data.extend_from_slice(&character.to_le_bytes()));
+
+    assert_eq!(Text::detect_encoding(&data), Some(SupportedEncodings::Utf16Le));
+    let text = Text::read(&data).unwrap();
+    assert_eq!(text.get_encoding(), SupportedEncodings::Utf16Le);
+    assert_eq!(text.get_ref_contents(), "-- Script ñ");
+    assert_eq!(text.save().unwrap(), data);
+
+    // Without BOM, the zeros of the ASCII characters give it away.
+    let data_be = "-- Script".encode_utf16().flat_map(|character| character.to_be_bytes()).collect::<Vec<u8>>();
+    assert_eq!(Text::detect_encoding(&data_be), Some(SupportedEncodings::Utf16Be));
+    assert_eq!(Text::detect_encoding("-- Script ñ".as_bytes()), Some(SupportedEncodings::Utf8));
+    assert_eq!(Text::detect_encoding(&[0x2D, 0x2D, 0xF1]), Some(SupportedEncodings::Iso8859_1));
+    assert_eq!(Text::detect_encoding(&[0x00, 0x01, 0x02, 0x00, 0xFF, 0x80]), None);
+
+    // Forcing the wrong encoding must fail instead of returning garbage.
+    assert!(Text::decode_with_encoding(&data[1..], SupportedEncodings::Utf16Le).is_err());
+    assert!(Text::decode_with_encoding(&data, SupportedEncodings::Utf8).is_err());
+}
+
+#[test]
+fn test_text_guess_text_type() {
+    let text_type = |contents: &str| {
+        let mut text = Text::new();
+        text.set_contents(contents);
+        text.guess_text_type()
+    };
+
+    assert_eq!(text_type("<?xml version=\"1.0\"?>\n<root/>"), TextType::Xml);
+    assert_eq!(text_type("  <root><item/></root>\n"), TextType::Xml);
+    assert_eq!(text_type("{\"key\": [1, 2]}"), TextType::Json);
+    assert_eq!(text_type("[1, 2]"), TextType::Json);
+
+    // Text that only looks like JSON must not be detected as JSON.
+    assert_eq!(text_type("{ not json }"), TextType::Plain);
+    assert_eq!(text_type("key\tvalue\n"), TextType::Plain);
+    assert_eq!(text_type(""), TextType::Plain);
+}
@@ -0,0 +1,107 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module with all the code to interact with Variant Mesh Definitions.
+
+Variant Mesh Definitions are XML files describing which models a unit can use on each of its slots.
+They're decoded and edited as Text PackedFiles, but they can be validated, so typos don't make the game fail silently.
+!*/
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use rpfm_error::{ErrorKind, Result};
+
+#[cfg(test)]
+mod variant_mesh_definition_test;
+
+/// Extension used by Variant Mesh Definition PackedFiles.
+pub const EXTENSION: &str = ".variantmeshdefinition";
+
+/// List of element names the game understands in a Variant Mesh Definition.
+///
+/// `VARIANT_MESH` is both the root element and the element pointing to a model, `SLOT` groups the models that can
+/// be picked for an attach point, and `VARIANT_MESH_REFERENCE` includes another Variant Mesh Definition in a slot.
+pub const KNOWN_ELEMENTS: [&str; 3] = [
+    "VARIANT_MESH",
+    "SLOT",
+    "VARIANT_MESH_REFERENCE",
+];
+
+//---------------------------------------------------------------------------//
+//                              Functions
+//---------------------------------------------------------------------------//
+
+/// This function checks if the provided text is a valid Variant Mesh Definition.
+///
+/// It checks that the text is well-formed XML, and that it only contains known elements.
+/// In case of error, it returns the line and column (both starting at 1) where the problem was found.
+pub fn validate(contents: &str) -> Result<()> {
+    let mut reader = Reader::from_str(contents);
+    reader.trim_text(true);
+
+    let mut buffer = vec![];
+    let mut depth = 0;
+    let mut has_root = false;
+
+    loop {
+        let position = reader.buffer_position();
+        match reader.read_event(&mut buffer) {
+            Ok(Event::Start(ref element)) => {
+                check_element_name(contents, position, element.name())?;
+                has_root = true;
+                depth += 1;
+            }
+            Ok(Event::Empty(ref element)) => {
+                check_element_name(contents, position, element.name())?;
+                has_root = true;
+            }
+            Ok(Event::End(_)) => depth -= 1,
+            Ok(Event::Eof) => break,
+            Ok(_) => {},
+            Err(error) => {
+                let (line, column) = get_line_and_column(contents, reader.buffer_position());
+                return Err(ErrorKind::VariantMeshDefinitionInvalidXML(line, column, error.to_string()).into());
+            }
+        }
+
+        buffer.clear();
+    }
+
+    if depth != 0 || !has_root {
+        let (line, column) = get_line_and_column(contents, contents.len());
+        return Err(ErrorKind::VariantMeshDefinitionInvalidXML(line, column, "Unexpected end of file.".to_owned()).into());
+    }
+
+    Ok(())
+}
+
+/// This function checks if the provided element name is one of the known ones.
+fn check_element_name(contents: &str, position: usize, name: &[u8]) -> Result<()> {
+    let name = String::from_utf8_lossy(name);
+    if KNOWN_ELEMENTS.contains(&&*name) { Ok(()) }
+    else {
+
+        // The position we get is the one before the element, which may include whitespace that we need to skip.
+        let position = position + contents[position..].len() - contents[position..].trim_start().len();
+        let (line, column) = get_line_and_column(contents, position);
+        Err(ErrorKind::VariantMeshDefinitionUnknownElement(line, column, name.to_string()).into())
+    }
+}
+
+/// This function returns the line and column (both starting at 1) of the provided byte position in a text.
+fn get_line_and_column(contents: &str, position: usize) -> (usize, usize) {
+    let position = position.min(contents.len());
+    let before = &contents.as_bytes()[..position];
+    let line = before.iter().filter(|x| **x == b'\n').count() + 1;
+    let column = position - before.iter().rposition(|x| *x == b'\n').map(|x| x + 1).unwrap_or(0) + 1;
+    (line, column)
+}
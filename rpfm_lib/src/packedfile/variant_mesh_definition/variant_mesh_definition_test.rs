@@ -0,0 +1,41 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `VariantMeshDefinition` module.
+!*/
+
+use rpfm_error::ErrorKind;
+
+use super::validate;
+
+#[test]
+fn test_variant_mesh_definition_validate() {
+    let valid = r#"<?xml version="1.0" encoding="utf-8"?>
+<VARIANT_MESH>
+    <SLOT name="body" probability="100">
+        <VARIANT_MESH model="variantmeshes/wh_variantmodels/hu1/emp/emp_body_01.rigid_model_v2"/>
+    </SLOT>
+    <SLOT name="weapon_1" attach_point="be_prop_0">
+        <VARIANT_MESH_REFERENCE definition="variantmeshes/variantmeshdefinitions/emp_sword.variantmeshdefinition"/>
+    </SLOT>
+</VARIANT_MESH>
+"#;
+    assert!(validate(valid).is_ok());
+
+    // Typos in element names must be reported at the start of the element.
+    let unknown_element = "<VARIANT_MESH>\n    <SLOTT name=\"body\"/>\n</VARIANT_MESH>";
+    assert_eq!(validate(unknown_element).unwrap_err().kind(), &ErrorKind::VariantMeshDefinitionUnknownElement(2, 5, "SLOTT".to_owned()));
+
+    // Broken XML must be reported with its position, including files that end before closing all their elements.
+    assert!(matches!(validate("<VARIANT_MESH>\n    <SLOT name=\"body\">\n</VARIANT_MESH>").unwrap_err().kind(), ErrorKind::VariantMeshDefinitionInvalidXML(3, _, _)));
+    assert!(matches!(validate("<VARIANT_MESH>\n    <SLOT name=\"body\"/>").unwrap_err().kind(), ErrorKind::VariantMeshDefinitionInvalidXML(2, _, _)));
+    assert!(matches!(validate("").unwrap_err().kind(), ErrorKind::VariantMeshDefinitionInvalidXML(1, 1, _)));
+}
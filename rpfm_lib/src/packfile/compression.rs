@@ -31,31 +31,37 @@ use crate::settings::ZIP_PATH;
 pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
     if !data.is_empty() {
         if data.len() >= 9 {
-
-            // CA Tweaks their headers to remove 4 bytes per PackedFile, while losing +4GB File Compression Support.
-            // We need to fix their headers so the normal LZMA lib can read them.
-            let mut fixed_data: Vec<u8> = vec![];
-            fixed_data.extend_from_slice(&data[4..8]);
-            fixed_data.push(0);
-            fixed_data.extend_from_slice(&data[0..4]);
-            fixed_data.extend_from_slice(&[0; 4]);
-            fixed_data.extend_from_slice(&data[9..]);
-
-            // Vanilla compressed files are LZMA Alone (or legacy) level 3 compressed files, reproducible by compressing them
-            // with default settings with 7-Zip. This should do the trick to get them decoded.
-            let stream = Stream::new_lzma_decoder(u64::MAX).map_err(|_| Error::from(ErrorKind::PackedFileDataCouldNotBeDecompressed))?;
-            let mut encoder = XzDecoder::new_stream(&*fixed_data, stream);
-            let mut compress_data = vec![];
-            match encoder.read_to_end(&mut compress_data) {
-                Ok(_) => Ok(compress_data),
-                Err(_) => Err(ErrorKind::PackedFileDataCouldNotBeDecompressed.into())
-            }
+            let mut decompressed_data = vec![];
+            decompress_data_to_writer(data, &mut decompressed_data)?;
+            Ok(decompressed_data)
         }
         else { Err(ErrorKind::PackedFileDataCouldNotBeDecompressed.into()) }
     }
     else { Ok(vec![]) }
 }
 
+/// This function decompress the data of a PackedFile from the provided reader, writing the decompressed data to the provided writer as it goes.
+///
+/// Unlike `decompress_data`, this doesn't need to keep neither the compressed nor the decompressed data in memory. Returns the amount of bytes written.
+pub fn decompress_data_to_writer<R: Read, W: Write>(mut data: R, writer: &mut W) -> Result<u64> {
+    let mut header = [0; 9];
+    data.read_exact(&mut header).map_err(|_| Error::from(ErrorKind::PackedFileDataCouldNotBeDecompressed))?;
+
+    // CA Tweaks their headers to remove 4 bytes per PackedFile, while losing +4GB File Compression Support.
+    // We need to fix their headers so the normal LZMA lib can read them.
+    let mut fixed_header: Vec<u8> = vec![];
+    fixed_header.extend_from_slice(&header[4..8]);
+    fixed_header.push(0);
+    fixed_header.extend_from_slice(&header[0..4]);
+    fixed_header.extend_from_slice(&[0; 4]);
+
+    // Vanilla compressed files are LZMA Alone (or legacy) level 3 compressed files, reproducible by compressing them
+    // with default settings with 7-Zip. This should do the trick to get them decoded.
+    let stream = Stream::new_lzma_decoder(u64::MAX).map_err(|_| Error::from(ErrorKind::PackedFileDataCouldNotBeDecompressed))?;
+    let mut decoder = XzDecoder::new_stream((&fixed_header[..]).chain(data), stream);
+    std::io::copy(&mut decoder, writer).map_err(|_| Error::from(ErrorKind::PackedFileDataCouldNotBeDecompressed))
+}
+
 /// This function compress the data of a PackedFile, returning the compressed data.
 ///
 /// Now, some explanation: CA uses Non-Streamed LZMA1 (or LZMA Alone) compressed files.
@@ -21,18 +21,25 @@ so you don't have to worry about that.
 
 use bitflags::bitflags;
 use csv::ReaderBuilder;
+use fnv::FnvHasher;
 use itertools::{Itertools, Either};
+use memmap2::Mmap;
+use regex::Regex;
 use serde_derive::{Serialize, Deserialize};
 use serde_json::{from_slice, to_string_pretty};
 use rayon::prelude::*;
 use unicase::UniCase;
 
+use std::borrow::Borrow;
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::{fmt, fmt::Display};
 use std::fs::{DirBuilder, File};
-use std::io::{prelude::*, BufReader, BufWriter, SeekFrom, Read, Write};
+use std::hash::Hasher;
+use std::io::{prelude::*, BufReader, BufWriter, Cursor, SeekFrom, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use rpfm_error::{Error, ErrorKind, Result};
 use rpfm_macros::*;
@@ -40,15 +47,21 @@ use rpfm_macros::*;
 use crate::GAME_SELECTED;
 use crate::SCHEMA;
 use crate::SETTINGS;
+use crate::SUPPORTED_GAMES;
 use crate::common::{*, decoder::Decoder, encoder::Encoder};
 use crate::dependencies::Dependencies;
+use crate::games::GameInfo;
 use crate::packfile::compression::*;
 use crate::packfile::crypto::*;
 use crate::packfile::packedfile::*;
 use crate::packedfile::{DecodedPackedFile, PackedFileType};
-use crate::packedfile::table::db::DB;
+use crate::packedfile::table::DecodedData;
+use crate::packedfile::table::animtable::AnimTable;
+use crate::packedfile::table::db::{DB, HEADER_MAX_SIZE as DB_HEADER_MAX_SIZE, TableDiff};
 use crate::packedfile::table::loc::{Loc, TSV_NAME_LOC};
-use crate::packedfile::text::TextType;
+use crate::packedfile::text::{Text, TextType};
+use crate::packedfile::variant_mesh_definition;
+use crate::schema::Schema;
 
 mod compression;
 mod crypto;
@@ -144,6 +157,9 @@ pub struct PackFile {
     /// The timestamp of the last time the PackFile was saved.
     timestamp: i64,
 
+    /// If true, saving keeps the current `timestamp` instead of replacing it with the current time. Used for reproducible builds.
+    deterministic_timestamp: bool,
+
     /// Game version this mod is intended for. This usually triggers the "outdated mod" warning in the launcher if it doesn't match the current exe version.
     game_version: u32,
 
@@ -200,13 +216,11 @@ pub struct PackFileInfo {
 }
 
 /// This struct represents the entire **Manifest.txt** from the /data folder.
-///
-/// Private for now, because I see no public use for this.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Manifest(pub Vec<ManifestEntry>);
 
 /// This struct represents a Manifest Entry.
-#[derive(Default, Debug, GetRef, Serialize, Deserialize)]
+#[derive(Default, Debug, GetRef, Set, Serialize, Deserialize)]
 pub struct ManifestEntry {
 
     /// The path of the file, relative to /data.
@@ -217,6 +231,10 @@ pub struct ManifestEntry {
 
     /// If the file comes with the base game (1), or with one of its dlc (0). Not in all games.
     belongs_to_base_game: Option<u8>,
+
+    /// If the file is a PackFile of one of the CA types. Not part of the manifest: it's only filled when reading the manifest through `GameInfo::read_manifest`.
+    #[serde(default)]
+    is_ca_packfile: bool,
 }
 
 /// This enum represents the **Version** of a PackFile.
@@ -305,6 +323,40 @@ pub enum CompressionState {
     Disabled,
 }
 
+/// This enum represents the order in which the `PackedFiles` are written when saving a `PackFile`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+
+    /// Sorted by path, ignoring case. This is the default, as some people get crashes with unsorted PackFiles.
+    CaseInsensitivePath,
+
+    /// Sorted by path, taking case into account.
+    CaseSensitivePath,
+
+    /// In the order they were read or added to the `PackFile`.
+    Insertion,
+}
+
+/// This enum represents the problems `PackFile::validate` can find in a `PackFile` before saving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+
+    /// A PackedFile is using one of the reserved names. Contains the path of the PackedFile.
+    ReservedName(Vec<String>),
+
+    /// More than one PackedFile has the same path, ignoring case. Contains the path of the PackedFile.
+    DuplicatedPath(Vec<String>),
+
+    /// A PackedFile has an empty path, or a path with empty segments. Contains the path of the PackedFile.
+    EmptyPathSegment(Vec<String>),
+
+    /// The data of a PackedFile doesn't match the type of its path. Contains the path of the PackedFile and the type of its path.
+    TypeMismatch(Vec<String>, PackedFileType),
+
+    /// A Variant Mesh Definition is not valid. Contains the path of the PackedFile and the problem found.
+    InvalidVariantMeshDefinition(Vec<String>, ErrorKind),
+}
+
 /// This struct hold PackFile-specific settings.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PackFileSettings {
@@ -318,10 +370,63 @@ pub struct PackFileSettings {
     pub settings_number: BTreeMap<String, i32>,
 }
 
+/// This struct reads the parts of a split PackFile one after another, as if they were a single file.
+///
+/// It keeps the position where each part starts within the joined data, so it can seek between parts.
+struct SplitPackFileReader {
+
+    /// Parts of the PackFile, with the position within the joined data where they start.
+    parts: Vec<(u64, File)>,
+
+    /// Size of all the parts combined.
+    len: u64,
+
+    /// Position within the joined data.
+    position: u64,
+}
+
 //---------------------------------------------------------------------------//
 //                             Enum Implementations
 //---------------------------------------------------------------------------//
 
+/// Implementation of `SortMode`.
+impl SortMode {
+
+    /// This function returns the `SortMode` in `&str` format, as it's stored in the `PackFile` settings.
+    pub fn get_value(&self) -> &str {
+        match *self {
+            SortMode::CaseInsensitivePath => "case_insensitive_path",
+            SortMode::CaseSensitivePath => "case_sensitive_path",
+            SortMode::Insertion => "insertion",
+        }
+    }
+
+    /// This function returns the `SortMode` corresponding to the provided value, or the default one if the value is not valid.
+    pub fn get_sort_mode(value: &str) -> Self {
+        match value {
+            "case_sensitive_path" => SortMode::CaseSensitivePath,
+            "insertion" => SortMode::Insertion,
+            _ => SortMode::default(),
+        }
+    }
+
+    /// This function sorts the provided `PackedFiles` in the order they're written with this `SortMode`.
+    pub fn sort<T: Borrow<PackedFile>>(&self, packed_files: &mut [T]) {
+        match self {
+            SortMode::CaseInsensitivePath => packed_files.sort_unstable_by_key(|a| a.borrow().get_path().join("\\").to_lowercase()),
+            SortMode::CaseSensitivePath => packed_files.sort_unstable_by_key(|a| a.borrow().get_path().join("\\")),
+            SortMode::Insertion => {},
+        }
+    }
+}
+
+/// Default implementation of `SortMode`.
+impl Default for SortMode {
+    fn default() -> Self {
+        SortMode::CaseInsensitivePath
+    }
+}
+
 /// Implementation of `PFHFileType`.
 impl PFHFileType {
 
@@ -337,6 +442,19 @@ impl PFHFileType {
         }
     }
 
+    /// This function returns if the Type is one of the ones used by CA for the PackFiles they release.
+    pub fn is_ca_type(self) -> bool {
+        matches!(self, PFHFileType::Boot | PFHFileType::Release | PFHFileType::Patch | PFHFileType::Movie)
+    }
+
+    /// This function returns the Type of the PackFile in the provided path, reading only its header.
+    pub fn read_from_path(path: &Path) -> Result<Self> {
+        let mut buffer = vec![0; 8];
+        File::open(path)?.read_exact(&mut buffer)?;
+        PFHVersion::get_version(&buffer.decode_string_u8(0, 4)?)?;
+        Ok(Self::get_type(buffer.decode_integer_u32(4)? & 15))
+    }
+
     /// This function returns the PackFile's Type corresponding to the provided value.
     pub fn get_type(value: u32) -> Self {
         match value {
@@ -379,6 +497,11 @@ impl PFHVersion {
         }
     }
 
+    /// This function returns if the PackFiles of this version use 32-bit offsets, meaning they cannot be bigger than 4GB.
+    pub fn has_32_bit_offsets(&self) -> bool {
+        matches!(self, PFHVersion::PFH4 | PFHVersion::PFH3 | PFHVersion::PFH2 | PFHVersion::PFH0)
+    }
+
     /// This function returns the PackFile's `PFHVersion` corresponding to the provided value, or an error if the provided value is not a valid `PFHVersion`.
     pub fn get_version(value: &str) -> Result<Self> {
         match value {
@@ -407,6 +530,19 @@ impl Display for PFHVersion {
     }
 }
 
+/// Display implementation of `ValidationIssue`.
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationIssue::ReservedName(path) => write!(f, "Reserved name: {}", path.join("/")),
+            ValidationIssue::DuplicatedPath(path) => write!(f, "Duplicated path: {}", path.join("/")),
+            ValidationIssue::EmptyPathSegment(path) => write!(f, "Empty path segment: {}", path.join("/")),
+            ValidationIssue::TypeMismatch(path, packed_file_type) => write!(f, "Data doesn't match the type {}: {}", packed_file_type, path.join("/")),
+            ValidationIssue::InvalidVariantMeshDefinition(path, error) => write!(f, "Invalid Variant Mesh Definition: {}: {}", path.join("/"), Error::from(error.clone()).to_terminal().trim()),
+        }
+    }
+}
+
 /// Implementation of `PathType`.
 impl PathType {
 
@@ -500,6 +636,7 @@ impl PackFile {
             pfh_file_type: PFHFileType::Mod,
             bitmask: PFHFlags::empty(),
             timestamp: 0,
+            deterministic_timestamp: false,
             game_version: 0,
             build_number: 0,
             authoring_tool: AUTHORING_TOOL_RPFM.to_owned(),
@@ -521,6 +658,7 @@ impl PackFile {
             pfh_file_type: PFHFileType::Mod,
             bitmask: PFHFlags::empty(),
             timestamp: 0,
+            deterministic_timestamp: false,
             game_version: 0,
             build_number: 0,
             authoring_tool: AUTHORING_TOOL_RPFM.to_owned(),
@@ -571,6 +709,9 @@ impl PackFile {
     }
 
     /// This function returns the `PackFile List` of the provided `PackFile`.
+    ///
+    /// The list keeps the order of the PackFile's header, which is the order the game loads them in.
+    /// Entries are only names: the format has no per-entry flags to mark a dependency as optional.
     pub fn get_packfiles_list(&self) -> &[String] {
         &self.pack_files
     }
@@ -686,6 +827,13 @@ impl PackFile {
             self.packed_files.append(&mut packed_files_conflict);
         }
 
+        // If the PackFile has compression enabled, mark the new PackedFiles to be compressed on save.
+        if self.is_compression_enabled() {
+            self.packed_files.par_iter_mut()
+                .filter(|x| destination_paths.contains(&x.get_path().to_vec()))
+                .for_each(|x| x.get_ref_mut_raw().set_should_be_compressed(true));
+        }
+
         Ok(destination_paths)
     }
 
@@ -771,7 +919,7 @@ impl PackFile {
                             let mut file = BufReader::new(File::open(path_as_file)?);
                             let mut data = String::new();
                             file.read_to_string(&mut data)?;
-                            self.set_notes(&Some(data));
+                            self.set_notes(Some(data));
                             return Ok(vec![]);
                         }
                         else {
@@ -940,7 +1088,7 @@ impl PackFile {
                                             let mut file = BufReader::new(File::open(file_path)?);
                                             let mut data = String::new();
                                             file.read_to_string(&mut data)?;
-                                            self.set_notes(&Some(data));
+                                            self.set_notes(Some(data));
                                             return Ok(vec![]);
                                         }
                                         else {
@@ -1648,19 +1796,360 @@ impl PackFile {
         Ok(files_extracted)
     }
 
+    /// This function extracts all the `PackedFiles` whose path starts with the provided prefix to disk.
+    ///
+    /// If `keep_structure` is true, each `PackedFile` is extracted to `destination_path/path_to_packedfile/packed_file`.
+    /// Otherwise, all of them are extracted directly into `destination_path`, and files with the same name overwrite each other.
+    ///
+    /// It returns the list of extracted paths, relative to `destination_path`. If any of them failed, we return `Error` with a list of the paths that failed to get extracted.
+    pub fn extract_by_path_start(
+        &mut self,
+        prefix: &[String],
+        destination_path: &Path,
+        keep_structure: bool
+    ) -> Result<Vec<String>> {
+        let mut extracted_paths = vec![];
+        let mut error_files = vec![];
+
+        if !keep_structure {
+            DirBuilder::new().recursive(true).create(destination_path)?;
+        }
+
+        for packed_file in self.get_ref_mut_packed_files_by_path_start(prefix) {
+            let (relative_path, result) = if keep_structure {
+                (packed_file.get_path().join("/"), packed_file.extract_packed_file(destination_path, false))
+            } else {
+                let file_name = packed_file.get_path().last().cloned().unwrap_or_default();
+                let result = packed_file.encode_and_save_to_disk(&destination_path.join(&file_name));
+                (file_name, result)
+            };
+
+            match result {
+                Ok(_) => extracted_paths.push(relative_path),
+                Err(_) => error_files.push(format!("<li>{}</li>", packed_file.get_path().join("/"))),
+            }
+        }
+
+        // If there is any error in the list, report it.
+        if !error_files.is_empty() {
+            return Err(ErrorKind::ExtractError(error_files).into())
+        }
+
+        Ok(extracted_paths)
+    }
+
+    /// This function extracts to disk only the `PackedFiles` that differ from their vanilla counterpart, so the result only contains what the `PackFile` overrides.
+    ///
+    /// DB Tables are extracted if they add or change rows of the vanilla version of their table, and Loc Tables if they have any line not found in the vanilla Loc Tables.
+    /// The rest of `PackedFiles` are compared byte by byte with the file with the same path in the parent mods or, if not there, in the game files.
+    /// `PackedFiles` not found in either of them are always extracted.
+    ///
+    /// Each `PackedFile` is extracted to `destination_path/path_to_packedfile/packed_file`, and the list of extracted paths is returned.
+    /// If any of them failed, we return `Error` with a list of the paths that failed to get extracted.
+    pub fn extract_modified_only(
+        &mut self,
+        dependencies: &Dependencies,
+        schema: &Schema,
+        destination_path: &Path
+    ) -> Result<Vec<String>> {
+        self.extract_modified_only_with(
+            schema,
+            destination_path,
+            dependencies.get_vanilla_loc().ok(),
+            |table| dependencies.compare_with_vanilla(table, schema),
+            |path| dependencies.get_packedfile_from_parent_files(path).or_else(|_| dependencies.get_packedfile_from_game_files(path)),
+        )
+    }
+
+    /// This function does the actual work of `PackFile::extract_modified_only`, getting the vanilla data from the provided Loc and closures instead of from the dependencies.
+    fn extract_modified_only_with<D: Fn(&DB) -> Result<TableDiff>, F: Fn(&[String]) -> Result<PackedFile>>(
+        &mut self,
+        schema: &Schema,
+        destination_path: &Path,
+        vanilla_loc: Option<Loc>,
+        compare_with_vanilla: D,
+        get_vanilla_packed_file: F,
+    ) -> Result<Vec<String>> {
+        let row_to_strings = |row: &[DecodedData]| row.iter().map(|cell| cell.data_to_string()).collect::<Vec<String>>();
+        let vanilla_loc_rows = match vanilla_loc {
+            Some(vanilla_loc) => vanilla_loc.get_ref_table_data().iter().map(|row| row_to_strings(row)).collect::<HashSet<Vec<String>>>(),
+            None => HashSet::new(),
+        };
+
+        let mut extracted_paths = vec![];
+        let mut error_files = vec![];
+        for packed_file in self.get_ref_mut_packed_files_all() {
+            if packed_file.get_packed_file_type(false).eq_non_strict_slice(&[PackedFileType::DB, PackedFileType::Loc]) {
+                let _ = packed_file.decode_no_locks(schema);
+            }
+
+            let is_modified = match packed_file.get_ref_decoded() {
+
+                // Rows removed from the vanilla table don't count, as mod tables only override the rows they contain.
+                DecodedPackedFile::DB(table) => match compare_with_vanilla(table) {
+                    Ok(diff) => !diff.get_ref_added_rows().is_empty() || !diff.get_ref_changed_cells().is_empty(),
+                    Err(_) => true,
+                },
+                DecodedPackedFile::Loc(table) => table.get_ref_table_data().iter().any(|row| !vanilla_loc_rows.contains(&row_to_strings(row))),
+                _ => {
+                    let path = packed_file.get_path();
+                    match get_vanilla_packed_file(path) {
+                        Ok(vanilla_packed_file) => match (vanilla_packed_file.get_raw_data(), packed_file.get_raw_data()) {
+                            (Ok(vanilla_data), Ok(data)) => vanilla_data != data,
+                            _ => true,
+                        },
+                        Err(_) => true,
+                    }
+                }
+            };
+
+            if is_modified {
+                match packed_file.extract_packed_file(destination_path, false) {
+                    Ok(_) => extracted_paths.push(packed_file.get_path().join("/")),
+                    Err(_) => error_files.push(format!("<li>{}</li>", packed_file.get_path().join("/"))),
+                }
+            }
+        }
+
+        // If there is any error in the list, report it.
+        if !error_files.is_empty() {
+            return Err(ErrorKind::ExtractError(error_files).into())
+        }
+
+        Ok(extracted_paths)
+    }
+
+    /// This function returns a summary of the DB Tables in the `PackFile`, with the name, version and amount of rows of each table.
+    ///
+    /// Only the headers of the tables are read, so it's fast even with big PackFiles. Tables with the same name and version are counted together.
+    /// If the provided schema has no definition for a table's version, the version is marked as such.
+    pub fn table_summary(&self, schema: &Schema) -> Vec<(String, String, usize)> {
+        let mut summary: BTreeMap<(String, i32), usize> = BTreeMap::new();
+        for packed_file in self.get_ref_packed_files_by_type(PackedFileType::DB, false) {
+            if let Some(table_name) = packed_file.get_path().get(1) {
+
+                // Tables with unusually long GUIDs don't fit in the bytes we read, so we read them fully.
+                let header = packed_file.get_ref_raw().get_data_start(DB_HEADER_MAX_SIZE).and_then(|data| DB::read_header(&data))
+                    .or_else(|_| packed_file.get_ref_raw().get_data().and_then(|data| DB::read_header(&data)));
+
+                if let Ok((version, _, _, entry_count, _)) = header {
+                    *summary.entry((table_name.to_owned(), version)).or_default() += entry_count as usize;
+                }
+            }
+        }
+
+        summary.into_iter().map(|((table_name, version), entry_count)| {
+            let has_definition = schema.get_ref_versioned_file_db(&table_name).map_or(false, |x| x.get_version(version).is_ok());
+            let version = if has_definition { version.to_string() } else { format!("{} (no definition)", version) };
+            (table_name, version, entry_count)
+        }).collect()
+    }
+
+    /// This function tries to decode every DB Table in the `PackFile` with the provided schema.
+    ///
+    /// It returns the path of each DB Table, with the reason it failed to decode, if it failed.
+    pub fn table_coverage(&self, schema: &Schema) -> Vec<(String, Option<ErrorKind>)> {
+        let mut coverage = self.get_ref_packed_files_by_type(PackedFileType::DB, false).par_iter().map(|packed_file| {
+            let path = packed_file.get_path().join("/");
+            let result = match packed_file.get_path().get(1) {
+                Some(table_name) => packed_file.get_ref_raw().get_data().and_then(|data| DB::read(&data, table_name, schema, false)),
+                None => Err(ErrorKind::DBTableIsNotADBTable.into()),
+            };
+
+            (path, result.err().map(|error| error.kind().clone()))
+        }).collect::<Vec<(String, Option<ErrorKind>)>>();
+
+        coverage.sort_by(|(path_a, _), (path_b, _)| path_a.cmp(path_b));
+        coverage
+    }
+
+    /// This function migrates the DB Tables of the provided table in the `PackFile` from one version to another, mapping their columns by name.
+    ///
+    /// Only the tables with the version `from_version` are migrated. It returns the amount of migrated tables.
+    pub fn migrate_db_tables(&mut self, schema: &Schema, table_name: &str, from_version: i32, to_version: i32) -> Result<usize> {
+        let table_name = if table_name.ends_with("_tables") { table_name.to_owned() } else { format!("{}_tables", table_name) };
+        let new_definition = schema.get_ref_versioned_file_db(&table_name)?.get_version(to_version)?.clone();
+
+        let mut migrated = 0;
+        for packed_file in self.get_ref_mut_packed_files_by_path_start(&["db".to_owned(), table_name]) {
+            let (version, _, _, _, _) = DB::read_header(&packed_file.get_ref_raw().get_data()?)?;
+            if version != from_version {
+                continue;
+            }
+
+            if let DecodedPackedFile::DB(table) = packed_file.decode_return_ref_mut_no_locks(schema)? {
+                table.migrate(&new_definition)?;
+            }
+
+            packed_file.encode_and_clean_cache()?;
+            migrated += 1;
+        }
+
+        Ok(migrated)
+    }
+
+    /// This function replaces the provided pattern in the cells of all the DB and Loc Tables in the `PackFile`, returning the amount of cells changed.
+    ///
+    /// Only string cells are checked, so numbers, booleans, colours and sequences are never changed.
+    /// If `columns` is provided, only the columns with those names are checked.
+    pub fn search_and_replace(&mut self, schema: &Schema, pattern: &str, replacement: &str, columns: Option<&[String]>, regex: bool) -> Result<usize> {
+        if pattern.is_empty() {
+            return Ok(0);
+        }
+
+        let regex = if regex {
+            Some(Regex::new(pattern).map_err(|error| ErrorKind::DBTableReplaceInvalidRegex(error.to_string()))?)
+        } else { None };
+
+        // Do all the replacements before saving any of them, so a table failing to decode doesn't leave the PackFile half-changed.
+        let mut changed_cells = 0;
+        let mut changed_tables = vec![];
+        for packed_file in self.get_ref_mut_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false) {
+            let (fields, mut data) = match packed_file.decode_return_ref_no_locks(schema) {
+                Ok(DecodedPackedFile::DB(table)) => (table.get_ref_definition().get_fields_processed(), table.get_table_data()),
+                Ok(DecodedPackedFile::Loc(table)) => (table.get_ref_definition().get_fields_processed(), table.get_table_data()),
+                _ => continue,
+            };
+
+            let mut changed_cells_table = 0;
+            for row in &mut data {
+                for (field, cell) in fields.iter().zip(row.iter_mut()) {
+                    if columns.map_or(false, |columns| !columns.iter().any(|column| column == field.get_name())) {
+                        continue;
+                    }
+
+                    let text = match cell {
+                        DecodedData::StringU8(text) |
+                        DecodedData::StringU16(text) |
+                        DecodedData::OptionalStringU8(text) |
+                        DecodedData::OptionalStringU16(text) => text,
+                        _ => continue,
+                    };
+
+                    let replaced = match regex {
+                        Some(ref regex) => regex.replace_all(text, replacement).to_string(),
+                        None => text.replace(pattern, replacement),
+                    };
+
+                    if replaced != *text {
+                        *text = replaced;
+                        changed_cells_table += 1;
+                    }
+                }
+            }
+
+            if changed_cells_table > 0 {
+                changed_cells += changed_cells_table;
+                changed_tables.push((packed_file.get_path().to_vec(), data));
+            }
+        }
+
+        for (path, data) in &changed_tables {
+            if let Some(packed_file) = self.get_ref_mut_packed_file_by_path(path) {
+                match packed_file.decode_return_ref_mut_no_locks(schema)? {
+                    DecodedPackedFile::DB(table) => table.set_table_data(data)?,
+                    DecodedPackedFile::Loc(table) => table.set_table_data(data)?,
+                    _ => unreachable!(),
+                }
+
+                packed_file.encode_and_clean_cache()?;
+            }
+        }
+
+        Ok(changed_cells)
+    }
+
     /// This function enables/disables compression in all `PackedFiles` inside the `PackFile`. Partial compression is not supported.
     pub fn toggle_compression(&mut self, enable: bool) {
         self.packed_files.par_iter_mut().for_each(|x| x.get_ref_mut_raw().set_should_be_compressed(enable));
     }
 
+    /// This function enables/disables compression in all `PackedFiles` inside the `PackFile`, so the next save compresses/decompresses them.
+    ///
+    /// Unlike `toggle_compression`, this fails for `PackFiles` with a `PFHVersion` that doesn't support compression. Tables are never compressed, even if enabled here.
+    pub fn set_compression_for_all(&mut self, compress: bool) -> Result<()> {
+        if compress && self.pfh_version != PFHVersion::PFH5 {
+            return Err(ErrorKind::PackFileCompressionNotSupported(self.pfh_version.to_string()).into());
+        }
+
+        self.packed_files.par_iter_mut().for_each(|x| x.set_should_compress(compress));
+        Ok(())
+    }
+
+    /// This function returns if the `PackFile` has compression enabled for new `PackedFiles`.
+    ///
+    /// The PFH versions we support have no header flag for this, so the policy is kept in the `PackFile` settings.
+    pub fn is_compression_enabled(&self) -> bool {
+        self.pfh_version == PFHVersion::PFH5 && *self.settings.settings_bool.get("compress_new_files").unwrap_or(&false)
+    }
+
+    /// This function enables/disables compression for the `PackedFiles` added to the `PackFile` from now on.
+    ///
+    /// It doesn't change the compression of the `PackedFiles` already in the `PackFile`. For that, use `toggle_compression`.
+    /// As compression is only supported by `PFHVersion::PFH5` PackFiles, this fails for any other version.
+    pub fn set_compression_enabled(&mut self, enable: bool) -> Result<()> {
+        if self.pfh_version != PFHVersion::PFH5 {
+            return Err(ErrorKind::PackFileCompressionNotSupported(self.pfh_version.to_string()).into());
+        }
+
+        self.settings.settings_bool.insert("compress_new_files".to_owned(), enable);
+        Ok(())
+    }
+
+    /// This function returns the order in which the `PackedFiles` of this `PackFile` are written on save.
+    pub fn get_sort_mode(&self) -> SortMode {
+        self.settings.settings_string.get("sort_mode").map(|value| SortMode::get_sort_mode(value)).unwrap_or_default()
+    }
+
+    /// This function sets the order in which the `PackedFiles` of this `PackFile` are written on save.
+    ///
+    /// Like the rest of the `PackFile` settings, it's saved within the `PackFile`, so it persists between saves.
+    pub fn set_sort_mode(&mut self, sort_mode: SortMode) {
+        self.settings.settings_string.insert("sort_mode".to_owned(), sort_mode.get_value().to_owned());
+    }
+
+    /// This function enables/disables compression in the `PackedFiles` inside the `PackFile` with a size equal or bigger than `min_size`.
+    ///
+    /// When enabling it, `PackedFiles` that would end up bigger after compression are left uncompressed. Tables are never compressed.
+    /// As compression is only supported by `PFHVersion::PFH5` PackFiles, this fails for any other version.
+    ///
+    /// It returns the paths of the `PackedFiles` which compression state will change on save.
+    pub fn toggle_compression_by_size(&mut self, enable: bool, min_size: u32) -> Result<Vec<Vec<String>>> {
+        if self.pfh_version != PFHVersion::PFH5 {
+            return Err(ErrorKind::PackFileCompressionNotSupported(self.pfh_version.to_string()).into());
+        }
+
+        let paths = self.packed_files.par_iter_mut().map(|packed_file| {
+            let is_compressible = !matches!(PackedFileType::get_packed_file_type(packed_file.get_ref_raw(), false), PackedFileType::DB | PackedFileType::Loc);
+            let raw_packed_file = packed_file.get_ref_mut_raw();
+            let data = raw_packed_file.get_data()?;
+            if data.len() < min_size as usize {
+                return Ok(None);
+            }
+
+            let should_be_compressed = enable && is_compressible && compress_data(&data)?.len() < data.len();
+            raw_packed_file.set_should_be_compressed(should_be_compressed);
+
+            if should_be_compressed != raw_packed_file.get_compression_state() {
+                Ok(Some(raw_packed_file.get_path().to_vec()))
+            } else {
+                Ok(None)
+            }
+        }).collect::<Result<Vec<Option<Vec<String>>>>>()?;
+
+        Ok(paths.into_iter().flatten().collect())
+    }
+
     /// This function returns the notes contained within the provided `PackFile`.
-    pub fn get_notes(&self) -> &Option<String> {
-        &self.notes
+    pub fn get_notes(&self) -> Option<&str> {
+        self.notes.as_deref()
     }
 
     /// This function saves your notes within the provided `PackFile`.
-    pub fn set_notes(&mut self, notes: &Option<String>) {
-        self.notes = notes.clone();
+    ///
+    /// They're written as a reserved PackedFile when saving the `PackFile`, and only for mod and movie PackFiles.
+    pub fn set_notes(&mut self, notes: Option<String>) {
+        self.notes = notes;
     }
 
     /// This function returns the settings contained within the provided `PackFile`.
@@ -1683,6 +2172,18 @@ impl PackFile {
         self.timestamp = timestamp;
     }
 
+    /// This function returns if the provided `PackFile` keeps its timestamp when saved.
+    pub fn get_deterministic_timestamp(&self) -> bool {
+        self.deterministic_timestamp
+    }
+
+    /// This function sets if the provided `PackFile` should keep its timestamp when saved, instead of using the current time.
+    ///
+    /// Combined with `set_timestamp`, this makes saving the same `PackFile` twice produce byte-identical files.
+    pub fn set_deterministic_timestamp(&mut self, deterministic: bool) {
+        self.deterministic_timestamp = deterministic;
+    }
+
     /// This function returns the `PFHVersion` of the provided `PackFile`.
     pub fn get_pfh_version(&self) -> PFHVersion {
         self.pfh_version
@@ -1731,6 +2232,54 @@ impl PackFile {
         self.bitmask = bitmask;
     }
 
+    /// This function enables or disables a flag of the `Bitmask` of the provided `PackFile`.
+    ///
+    /// As we only support saving PackFiles with the `HAS_INDEX_WITH_TIMESTAMPS` flag, that's the only one that can be changed.
+    /// PFH0 PackFiles don't have a bitmask, so nothing can be changed on them.
+    pub fn set_flag(&mut self, flag: PFHFlags, value: bool) -> Result<()> {
+        if flag != PFHFlags::HAS_INDEX_WITH_TIMESTAMPS || self.pfh_version == PFHVersion::PFH0 {
+            return Err(ErrorKind::PackFileFlagNotEditable(flag.get_names().join(" | "), self.pfh_version.to_string()).into());
+        }
+
+        self.bitmask.set(flag, value);
+        Ok(())
+    }
+
+    /// This function returns the keys of the games this `PackFile` may belong to, sorted from most to least likely.
+    ///
+    /// Only games using the `PFHVersion` of the `PackFile` are considered. They're scored by how many of the DB tables in the
+    /// `PackFile` decode with their schema. Games whose schema is not on disk are returned last.
+    pub fn guess_game(&self) -> Vec<&'static str> {
+        self.guess_game_with_schemas(|schema_name| Schema::load(schema_name).ok())
+    }
+
+    /// This function returns the keys of the games this `PackFile` may belong to, using the provided function to get the schema of each game.
+    ///
+    /// Check `guess_game` for how the games are scored.
+    fn guess_game_with_schemas<F: Fn(&str) -> Option<Schema>>(&self, load_schema: F) -> Vec<&'static str> {
+        let tables = self.get_ref_packed_files_by_type(PackedFileType::DB, false).iter()
+            .filter_map(|packed_file| Some((packed_file.get_path().get(1)?.to_owned(), packed_file.get_raw_data().ok()?)))
+            .collect::<Vec<(String, Vec<u8>)>>();
+
+        // Many games share schemas, so load each one only once.
+        let mut schemas: HashMap<String, Option<Schema>> = HashMap::new();
+        let mut candidates = SUPPORTED_GAMES.get_games_with_keys().into_iter()
+            .filter(|(_, game)| game.get_pfh_versions().values().any(|version| *version == self.pfh_version))
+            .map(|(key, game)| {
+                let schema = schemas.entry(game.get_schema_name().to_owned()).or_insert_with(|| load_schema(game.get_schema_name()));
+                let score = match schema {
+                    Some(schema) => tables.iter().filter(|(name, data)| DB::read(data, name, schema, false).is_ok()).count() as i64,
+                    None => -1,
+                };
+                (key, score)
+            })
+            .collect::<Vec<(&'static str, i64)>>();
+
+        // The sort is stable, so games with the same score keep their order.
+        candidates.sort_by_key(|(_, score)| Reverse(*score));
+        candidates.into_iter().map(|(key, _)| key).collect()
+    }
+
     /// This function remove all `PackedFiles` from a `PackFile`.
     pub fn remove_all_packedfiles(&mut self) {
         self.packed_files = vec![];
@@ -1750,6 +2299,19 @@ impl PackFile {
         }
     }
 
+    /// This function takes an slice of paths and turns them into their corresponding `PathType`, checking this `PackFile` to know what they are.
+    ///
+    /// Empty paths are considered the `PackFile` itself, and paths not found in the `PackFile` are returned as `PathType::None`.
+    /// If a path is both a file and a folder, it's considered a file.
+    pub fn classify_paths(&self, paths: &[Vec<String>]) -> Vec<PathType> {
+        paths.par_iter().map(|path| {
+            if path.is_empty() { PathType::PackFile }
+            else if self.packedfile_exists(path) { PathType::File(path.to_vec()) }
+            else if self.folder_exists(path) { PathType::Folder(path.to_vec()) }
+            else { PathType::None }
+        }).collect()
+    }
+
     /// This function takes an slice of PathTypes and turns it into a vector of individual PackedFile's paths.
     ///
     /// This is intended to be done after a dedup. Otherwise, you'll probably get duplicated paths at the end.
@@ -1793,6 +2355,46 @@ impl PackFile {
         self.packed_files.retain(|x| x.is_in_memory())
     }
 
+    /// This function splits the `PackedFiles` of this `PackFile` into new `PackFiles` under `max_bytes` of data each.
+    ///
+    /// `PackedFiles` are binned greedily, in the order given by the `SortMode` of this `PackFile`, which is the order they're saved in.
+    /// Those bigger than `max_bytes` on their own go into their own `PackFile`.
+    /// The new `PackFiles` keep the header, dependencies and settings of this one, including its path, so remember to give them a new one before saving them.
+    pub fn split_by_size(&mut self, max_bytes: u64) -> Result<Vec<PackFile>> {
+        if max_bytes == 0 { return Err(ErrorKind::EmptyInput.into()) }
+
+        // Use the same order we use when saving, so the result is predictable.
+        let mut packed_files = self.packed_files.iter().collect::<Vec<&PackedFile>>();
+        self.get_sort_mode().sort(&mut packed_files);
+
+        let mut bins: Vec<Vec<PackedFile>> = vec![];
+        let mut current_size = 0u64;
+        for packed_file in packed_files {
+            let size = u64::from(packed_file.get_ref_raw().get_size());
+            match bins.last_mut() {
+                Some(bin) if current_size + size <= max_bytes => {
+                    bin.push(packed_file.clone());
+                    current_size += size;
+                }
+                _ => {
+                    bins.push(vec![packed_file.clone()]);
+                    current_size = size;
+                }
+            }
+        }
+
+        // Clone the header and the rest of the PackFile-specific data once, without the PackedFiles.
+        let packed_files = std::mem::take(&mut self.packed_files);
+        let base = self.clone();
+        self.packed_files = packed_files;
+
+        Ok(bins.into_iter().map(|packed_files| {
+            let mut pack_file = base.clone();
+            pack_file.packed_files = packed_files;
+            pack_file
+        }).collect())
+    }
+
     /// This function allows you to change the path of a `PackedFile` inside a `PackFile`.
     ///
     /// By default this append a `_number` to the file name in case of collision. If you want it to overwrite instead,
@@ -1849,6 +2451,17 @@ impl PackFile {
         }
     }
 
+    /// This function allows you to change the path of a `PackedFile` inside a `PackFile`, failing if the new path is already in use.
+    ///
+    /// Unlike `move_packedfile`, this never renames nor overwrites on collision.
+    pub fn rename_packed_file(&mut self, from: &[String], to: &[String]) -> Result<()> {
+        if !self.packedfile_exists(from) { return Err(ErrorKind::PackedFileNotFound.into()) }
+        if Self::get_reserved_packed_file_names().iter().any(|x| x == to) { return Err(ErrorKind::ReservedFiles.into()) }
+        if self.packedfile_exists(to) { return Err(ErrorKind::FileAlreadyInPackFile.into()) }
+
+        self.move_packedfile(from, to, false).map(|_| ())
+    }
+
     /// This function allows you to change the name of a folder inside a `PackFile`.
     ///
     /// By default this append a `_number` to the file names in case of collision. If you want it to overwrite instead,
@@ -2003,6 +2616,109 @@ impl PackFile {
         self.add_packed_file(&packed_file, true)
     }
 
+    /// This function renames the key of an entity in the provided DB table, alongside all the loc keys associated with it.
+    ///
+    /// `keys` contains the old and new values of each key column of the table, in the order of the columns, so tables with
+    /// composite keys are supported. Only rows matching all the old values are renamed. If any of the tables has a different amount
+    /// of key columns, or the entity has a composite key and the table has localised fields, nothing is renamed and an error is returned.
+    ///
+    /// The renamed rows keep their position in their tables. Localised fields are taken from all the definitions
+    /// of the table in the provided schema, so locs get renamed even if the DB table is not in this `PackFile`.
+    ///
+    /// It returns the list of edited paths.
+    pub fn rename_entity_key(
+        &mut self,
+        table_name: &str,
+        keys: &[(&str, &str)],
+        schema: &Schema,
+    ) -> Result<Vec<Vec<String>>> {
+        let mut edited_paths = vec![];
+        if keys.iter().all(|(old_key, new_key)| old_key == new_key) { return Ok(edited_paths) }
+
+        let mut localised_fields = schema.get_ref_versioned_file_db(table_name)
+            .map(|versioned_file| versioned_file.get_version_list().iter()
+                .flat_map(|definition| definition.get_localised_fields().iter().map(|field| field.get_name().to_owned()))
+                .collect::<Vec<String>>()
+            ).unwrap_or_default();
+
+        // First, check all the DB tables can be renamed, so we don't leave the PackFile half-renamed if one of them cannot.
+        let mut new_keys_by_path = HashMap::new();
+        for packed_file in self.get_ref_mut_packed_files_by_path_start(&["db".to_owned(), table_name.to_owned()]) {
+            let path = packed_file.get_path().to_vec();
+            if let DecodedPackedFile::DB(db) = packed_file.decode_return_ref_mut_no_locks(schema)? {
+                let definition = db.get_definition();
+                localised_fields.extend(definition.get_localised_fields().iter().map(|field| field.get_name().to_owned()));
+
+                let fields = definition.get_fields_processed();
+                let key_columns = fields.iter().enumerate().filter(|(_, field)| field.get_is_key()).map(|(column, _)| column).collect::<Vec<usize>>();
+                if key_columns.len() != keys.len() {
+                    return Err(ErrorKind::DBTableRenameWrongKeyCount(key_columns.len() as u32, keys.len() as u32).into());
+                }
+
+                // The new keys are converted to the type of their columns, so non-string keys can be renamed too.
+                let new_keys = key_columns.iter().zip(keys)
+                    .map(|(column, (_, new_key))| DecodedData::StringU8(new_key.to_string()).convert_between_types(&fields[*column].get_field_type()))
+                    .collect::<Result<Vec<DecodedData>>>()?;
+
+                new_keys_by_path.insert(path, (key_columns, new_keys));
+            }
+        }
+
+        // We don't know how the game builds the loc keys of composite keys, so don't guess.
+        if keys.len() > 1 && !localised_fields.is_empty() {
+            return Err(ErrorKind::DBTableRenameCompositeKeyLocalised.into());
+        }
+
+        // Then, rename the keys in the DB tables.
+        for packed_file in self.get_ref_mut_packed_files_by_path_start(&["db".to_owned(), table_name.to_owned()]) {
+            let path = packed_file.get_path().to_vec();
+            if let Some((key_columns, new_keys)) = new_keys_by_path.get(&path) {
+                if let DecodedPackedFile::DB(db) = packed_file.decode_return_ref_mut_no_locks(schema)? {
+                    let mut table_data = db.get_table_data();
+                    let mut edited = false;
+                    for row in &mut table_data {
+                        if key_columns.iter().zip(keys).all(|(column, (old_key, _))| row[*column].data_to_string() == *old_key) {
+                            key_columns.iter().zip(new_keys).for_each(|(column, new_key)| row[*column] = new_key.clone());
+                            edited = true;
+                        }
+                    }
+
+                    if edited {
+                        db.set_table_data(&table_data)?;
+                        edited_paths.push(path);
+                    }
+                }
+            }
+        }
+
+        // Then, rename all the loc keys of the localised fields of the table.
+        localised_fields.sort();
+        localised_fields.dedup();
+        if !localised_fields.is_empty() {
+            let short_table_name = table_name.strip_suffix("_tables").unwrap_or(table_name);
+            let (old_key, new_key) = keys[0];
+            let loc_keys = localised_fields.iter()
+                .map(|field| (format!("{}_{}_{}", short_table_name, field, old_key), format!("{}_{}_{}", short_table_name, field, new_key)))
+                .collect::<Vec<(String, String)>>();
+
+            for packed_file in self.get_ref_mut_packed_files_by_type(PackedFileType::Loc, false) {
+                let path = packed_file.get_path().to_vec();
+                if let Ok(DecodedPackedFile::Loc(loc)) = packed_file.decode_return_ref_mut_no_locks(schema) {
+                    let mut edited = false;
+                    for (old_loc_key, new_loc_key) in &loc_keys {
+                        edited |= loc.rename_key(old_loc_key, new_loc_key);
+                    }
+
+                    if edited {
+                        edited_paths.push(path);
+                    }
+                }
+            }
+        }
+
+        Ok(edited_paths)
+    }
+
     /// This function is used to optimize a `PackFile` by removing extra useless data from it.
     ///
     /// Currently, this function does the following:
@@ -2217,6 +2933,35 @@ impl PackFile {
         name: Option<String>,
         overwrite: bool
     ) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>)> {
+        self.mass_import_tsv_cancellable(tsv_paths, name, overwrite, &AtomicBool::new(false))
+    }
+
+    /// This function is used to Mass-Import TSV files into a PackFile, checking the provided flag before each file.
+    ///
+    /// If the flag gets raised, the import stops and the PackFile is left untouched.
+    pub fn mass_import_tsv_cancellable(
+        &mut self,
+        tsv_paths: &[PathBuf],
+        name: Option<String>,
+        overwrite: bool,
+        cancel: &AtomicBool,
+    ) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>)> {
+
+        match *SCHEMA.read().unwrap() {
+            Some(ref schema) => self.mass_import_tsv_with_schema(schema, tsv_paths, name, overwrite, cancel),
+            None => Err(ErrorKind::SchemaNotFound.into()),
+        }
+    }
+
+    /// This function does the actual Mass-Import of TSV files, using the provided `Schema`.
+    fn mass_import_tsv_with_schema(
+        &mut self,
+        schema: &Schema,
+        tsv_paths: &[PathBuf],
+        name: Option<String>,
+        overwrite: bool,
+        cancel: &AtomicBool,
+    ) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>)> {
 
         // Create the following lists:
         // - PackedFiles to add.
@@ -2226,129 +2971,126 @@ impl PackFile {
         let mut packed_files_to_remove = vec![];
         let mut error_files = vec![];
 
-        // If there is not a schema, don't do anything.
-        if let Some(ref schema) = *SCHEMA.read().unwrap() {
-            for path in tsv_paths {
-
-                // The first row has the PackedFile Type (or name, in case of tables) and version.
-                // The second row contains the column names, and it can be ignored.
-                let mut tsv = String::new();
-                BufReader::new(File::open(&path)?).read_to_string(&mut tsv)?;
-
-                // We get his second line, if it has it. Otherwise, we return an error in this file.
-                let mut iter = tsv.lines();
-                iter.next();
-                if let Some(line) = iter.next() {
-
-                    // Split the first line by \t so we can get the info of the table.
-                    // We expect to have 2 or 3 items here. If we have more or less, stop.
-                    let tsv_info = line.split(';').collect::<Vec<&str>>();
-                    if tsv_info.len() == 2 || tsv_info.len() == 3 {
-
-                        // Get the type of the table.
-                        let mut table_type = tsv_info[0].to_owned();
-                        if table_type.starts_with("#") {
-                            table_type.remove(0);
-                        }
+        for path in tsv_paths {
+            if cancel.load(Ordering::SeqCst) {
+                return Err(ErrorKind::OperationCancelled.into());
+            }
 
-                        // Get the definition, depending on the table type and version.
-                        // If the name is not specific for a type of file, we treat it as a DB Table.
-                        match &*table_type {
-                            TSV_NAME_LOC => {
-                                if let Ok((table, file_path)) = Loc::import_tsv(schema, path) {
-
-                                    // Depending on the name received, call it one thing or another.
-                                    let mut path = match name {
-                                        Some(ref name) => vec!["text".to_owned(), "db".to_owned(), if name.ends_with(".loc") { name.to_string() } else { format!("{}.loc", name) }],
-                                        None => match file_path {
-                                            Some(file_path) => file_path,
-                                            None => vec!["text".to_owned(), "db".to_owned(), format!("{}.loc", path.file_stem().unwrap().to_str().unwrap())],
-                                        },
-                                    };
-
-                                    let name = path.last().unwrap().to_owned();
-
-                                    // If that path already exists in the list of new PackedFiles to add, change it using the index.
-                                    if !overwrite {
-                                        let mut index = 1;
-                                        while packed_files.iter().any(|x| x.get_path() == &*path) {
-                                            path[2] = format!("{}_{}.loc", name, index);
-                                            index += 1;
-                                        }
+            // The first row has the PackedFile Type (or name, in case of tables) and version.
+            // The second row contains the column names, and it can be ignored.
+            let mut tsv = String::new();
+            BufReader::new(File::open(path)?).read_to_string(&mut tsv)?;
+
+            // We get his second line, if it has it. Otherwise, we return an error in this file.
+            let mut iter = tsv.lines();
+            iter.next();
+            if let Some(line) = iter.next() {
+
+                // Split the first line by \t so we can get the info of the table.
+                // We expect to have 2 or 3 items here. If we have more or less, stop.
+                let tsv_info = line.split(';').collect::<Vec<&str>>();
+                if tsv_info.len() == 2 || tsv_info.len() == 3 {
+
+                    // Get the type of the table.
+                    let mut table_type = tsv_info[0].to_owned();
+                    if table_type.starts_with("#") {
+                        table_type.remove(0);
+                    }
+
+                    // Get the definition, depending on the table type and version.
+                    // If the name is not specific for a type of file, we treat it as a DB Table.
+                    match &*table_type {
+                        TSV_NAME_LOC => {
+                            if let Ok((table, file_path)) = Loc::import_tsv(schema, path) {
+
+                                // Depending on the name received, call it one thing or another.
+                                let mut path = match name {
+                                    Some(ref name) => vec!["text".to_owned(), "db".to_owned(), if name.ends_with(".loc") { name.to_string() } else { format!("{}.loc", name) }],
+                                    None => match file_path {
+                                        Some(file_path) => file_path,
+                                        None => vec!["text".to_owned(), "db".to_owned(), format!("{}.loc", path.file_stem().unwrap().to_str().unwrap())],
+                                    },
+                                };
+
+                                let name = path.last().unwrap().to_owned();
+
+                                // If that path already exists in the list of new PackedFiles to add, change it using the index.
+                                if !overwrite {
+                                    let mut index = 1;
+                                    while packed_files.iter().any(|x| x.get_path() == &*path) {
+                                        path[2] = format!("{}_{}.loc", name, index);
+                                        index += 1;
                                     }
+                                }
 
-                                    // If that path already exist in the PackFile, add it to the "remove" list.
-                                    if self.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
+                                // If that path already exist in the PackFile, add it to the "remove" list.
+                                if self.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
 
-                                    // Create and add the new PackedFile to the list of PackedFiles to add.
-                                    let mut packed_file = PackedFile::new(path, self.get_file_name());
-                                    packed_file.set_decoded(&DecodedPackedFile::Loc(table));
-                                    packed_files.push(packed_file);
-                                }
-                                else { error_files.push(path.to_string_lossy().to_string()); }
+                                // Create and add the new PackedFile to the list of PackedFiles to add.
+                                let mut packed_file = PackedFile::new(path, self.get_file_name());
+                                packed_file.set_decoded(&DecodedPackedFile::Loc(table));
+                                packed_files.push(packed_file);
                             }
-                            _ => {
-                                if let Ok((table, file_name)) = DB::import_tsv(schema, path) {
-
-                                    // Depending on the name received, call it one thing or another.
-                                    let mut path = match name {
-                                        Some(ref name) => vec!["db".to_owned(), table_type.to_owned(), name.to_owned()],
-                                        None => match file_name {
-                                            Some(name) => name,
-                                            None => vec!["db".to_owned(), table_type.to_owned(), path.file_stem().unwrap().to_str().unwrap().to_string()],
-                                        },
-                                    };
-
-                                    let name = path.last().unwrap().to_owned();
-
-                                    // If that path already exists in the list of new PackedFiles to add, change it using the index.
-                                    if !overwrite {
-                                        let mut index = 1;
-                                        while packed_files.iter().any(|x| x.get_path() == &*path) {
-                                            path[2] = format!("{}_{}", name, index);
-                                            index += 1;
-                                        }
+                            else { error_files.push(path.to_string_lossy().to_string()); }
+                        }
+                        _ => {
+                            if let Ok((table, file_name)) = DB::import_tsv(schema, path) {
+
+                                // Depending on the name received, call it one thing or another.
+                                let mut path = match name {
+                                    Some(ref name) => vec!["db".to_owned(), table_type.to_owned(), name.to_owned()],
+                                    None => match file_name {
+                                        Some(name) => name,
+                                        None => vec!["db".to_owned(), table_type.to_owned(), path.file_stem().unwrap().to_str().unwrap().to_string()],
+                                    },
+                                };
+
+                                let name = path.last().unwrap().to_owned();
+
+                                // If that path already exists in the list of new PackedFiles to add, change it using the index.
+                                if !overwrite {
+                                    let mut index = 1;
+                                    while packed_files.iter().any(|x| x.get_path() == &*path) {
+                                        path[2] = format!("{}_{}", name, index);
+                                        index += 1;
                                     }
+                                }
 
-                                    // If that path already exists in the PackFile, add it to the "remove" list.
-                                    if self.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
+                                // If that path already exists in the PackFile, add it to the "remove" list.
+                                if self.packedfile_exists(&path) { packed_files_to_remove.push(path.to_vec()) }
 
-                                    // Create and add the new PackedFile to the list of PackedFiles to add.
-                                    let mut packed_file = PackedFile::new(path, self.get_file_name());
-                                    packed_file.set_decoded(&DecodedPackedFile::DB(table));
-                                    packed_files.push(packed_file);
-                                }
-                                else { error_files.push(path.to_string_lossy().to_string()); }
+                                // Create and add the new PackedFile to the list of PackedFiles to add.
+                                let mut packed_file = PackedFile::new(path, self.get_file_name());
+                                packed_file.set_decoded(&DecodedPackedFile::DB(table));
+                                packed_files.push(packed_file);
                             }
+                            else { error_files.push(path.to_string_lossy().to_string()); }
                         }
                     }
-                    else { error_files.push(path.to_string_lossy().to_string()); }
                 }
                 else { error_files.push(path.to_string_lossy().to_string()); }
             }
+            else { error_files.push(path.to_string_lossy().to_string()); }
+        }
 
-            // If any of the files returned error, return error.
-            if !error_files.is_empty() {
-                let error_files_string = error_files.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
-                return Err(ErrorKind::MassImport(error_files_string).into())
-            }
+        // If any of the files returned error, return error.
+        if !error_files.is_empty() {
+            let error_files_string = error_files.iter().map(|x| format!("<li>{}</li>", x)).collect::<String>();
+            return Err(ErrorKind::MassImport(error_files_string).into())
+        }
 
-            // Get the "TreePath" of the new PackFiles to return them.
-            let tree_path = packed_files.iter().map(|x| x.get_path().to_vec()).collect::<Vec<Vec<String>>>();
+        // Get the "TreePath" of the new PackFiles to return them.
+        let tree_path = packed_files.iter().map(|x| x.get_path().to_vec()).collect::<Vec<Vec<String>>>();
 
-            // Remove all the "conflicting" PackedFiles from the PackFile, before adding the new ones.
-            for packed_file_to_remove in &packed_files_to_remove {
-                self.remove_packed_file_by_path(packed_file_to_remove);
-            }
-
-            // We add all the files to the PackFile, and return success.
-            let packed_files_to_add = packed_files.iter().collect::<Vec<&PackedFile>>();
-            self.add_packed_files(&packed_files_to_add, true, true)?;
-            Ok((packed_files_to_remove, tree_path))
-        }
-        else {
-            Err(ErrorKind::SchemaNotFound.into())
+        // Remove all the "conflicting" PackedFiles from the PackFile, before adding the new ones.
+        for packed_file_to_remove in &packed_files_to_remove {
+            self.remove_packed_file_by_path(packed_file_to_remove);
         }
+
+        // We add all the files to the PackFile, and return success.
+        let packed_files_to_add = packed_files.iter().collect::<Vec<&PackedFile>>();
+        self.add_packed_files(&packed_files_to_add, true, true)?;
+        Ok((packed_files_to_remove, tree_path))
     }
 
     /// This function is used to Mass-Export TSV files from a PackFile.
@@ -2517,7 +3259,63 @@ impl PackFile {
     /// This function tries to get the list of CA PackFile of the currently selected game from the manifest.txt on /data,
     /// then it tries to open them all as one. Simple and effective.
     pub fn open_all_ca_packfiles() -> Result<Self> {
-        let pack_file_paths = GAME_SELECTED.read().unwrap().get_all_ca_packfiles_paths()?;
+        let game_selected = GAME_SELECTED.read().unwrap().clone();
+        Self::open_all_ca_packfiles_for_game(&game_selected)
+    }
+
+    /// This function allows you to open all CA PackFiles as one for the currently selected Game, checking the provided flag before each PackFile.
+    ///
+    /// If the flag gets raised, this stops reading PackFiles and returns an `OperationCancelled` error.
+    pub fn open_all_ca_packfiles_cancellable(cancel: &AtomicBool) -> Result<Self> {
+        let game_selected = GAME_SELECTED.read().unwrap().clone();
+        if SETTINGS.read().unwrap().paths.get(&game_selected.get_game_key_name()).cloned().flatten().is_none() {
+            return Err(ErrorKind::GameSelectedPathNotCorrectlyConfigured.into());
+        }
+
+        let pack_file_paths = game_selected.get_all_ca_packfiles_paths()?;
+        Self::open_packfiles_with_types_for_game(&game_selected, &pack_file_paths, true, true, true, &None, cancel)
+    }
+
+    /// This function allows you to open all CA PackFiles as one for the provided Game.
+    ///
+    /// Unlike `PackFile::open_all_ca_packfiles()`, this doesn't depend on the Game Selected. The path of the game is taken from the settings,
+    /// and if it's not configured, this returns an error.
+    pub fn open_all_ca_packfiles_for_game(game: &GameInfo) -> Result<Self> {
+        Self::open_all_ca_packfiles_with_types_for_game(game, &None)
+    }
+
+    /// This function allows you to open all CA PackFiles as one for the currently selected Game, keeping only the `PackedFiles` of the provided types.
+    ///
+    /// Useful when you only need a few types (like DB and Loc tables), as the rest of `PackedFiles` are never added to the `PackFile`.
+    pub fn open_all_ca_packfiles_with_types(types_to_load: &Option<Vec<PackedFileType>>) -> Result<Self> {
+        let game_selected = GAME_SELECTED.read().unwrap().clone();
+        Self::open_all_ca_packfiles_with_types_for_game(&game_selected, types_to_load)
+    }
+
+    /// This function allows you to open all CA PackFiles as one for the provided Game, keeping only the `PackedFiles` of the provided types.
+    ///
+    /// It works like `PackFile::open_all_ca_packfiles_with_types()`, but it doesn't depend on the Game Selected.
+    pub fn open_all_ca_packfiles_with_types_for_game(game: &GameInfo, types_to_load: &Option<Vec<PackedFileType>>) -> Result<Self> {
+        if SETTINGS.read().unwrap().paths.get(&game.get_game_key_name()).cloned().flatten().is_none() {
+            return Err(ErrorKind::GameSelectedPathNotCorrectlyConfigured.into());
+        }
+
+        let pack_file_paths = game.get_all_ca_packfiles_paths()?;
+        Self::open_packfiles_with_types_for_game(game, &pack_file_paths, true, true, true, types_to_load, &AtomicBool::new(false))
+    }
+
+    /// This function allows you to open only the CA PackFiles with the provided names (like `data.pack`) for the provided Game, as one.
+    ///
+    /// If any of the requested PackFiles is not one of the game's CA PackFiles, this returns an error.
+    pub fn open_ca_packfiles_named(game: &GameInfo, names: &[&str]) -> Result<Self> {
+        let ca_pack_file_paths = game.get_all_ca_packfiles_paths()?;
+        let pack_file_paths = names.iter().map(|name| {
+            ca_pack_file_paths.iter()
+                .find(|path| path.file_name().map(|file_name| file_name.to_string_lossy().eq_ignore_ascii_case(name)).unwrap_or(false))
+                .cloned()
+                .ok_or_else(|| Error::from(ErrorKind::CAPackFileNotFound(name.to_string())))
+        }).collect::<Result<Vec<PathBuf>>>()?;
+
         Self::open_packfiles(&pack_file_paths, true, true, true)
     }
 
@@ -2535,11 +3333,40 @@ impl PackFile {
         ignore_mods: bool,
         lock_packfile: bool
     ) -> Result<Self> {
+        Self::open_packfiles_with_types(packs_paths, use_lazy_loading, ignore_mods, lock_packfile, &None)
+    }
+
+    /// This function allows you to open one or more `PackFiles`, keeping only the `PackedFiles` of the provided types.
+    ///
+    /// It works like `PackFile::open_packfiles()`. If `types_to_load` is `None`, all the `PackedFiles` are loaded.
+    pub fn open_packfiles_with_types(
+        packs_paths: &[PathBuf],
+        use_lazy_loading: bool,
+        ignore_mods: bool,
+        lock_packfile: bool,
+        types_to_load: &Option<Vec<PackedFileType>>,
+    ) -> Result<Self> {
+        let game_selected = GAME_SELECTED.read().unwrap().clone();
+        Self::open_packfiles_with_types_for_game(&game_selected, packs_paths, use_lazy_loading, ignore_mods, lock_packfile, types_to_load, &AtomicBool::new(false))
+    }
+
+    /// This function allows you to open one or more `PackFiles` of the provided Game, keeping only the `PackedFiles` of the provided types.
+    ///
+    /// The game is used to get the version and name of the merged `PackFile`, when there are multiple ones.
+    fn open_packfiles_with_types_for_game(
+        game: &GameInfo,
+        packs_paths: &[PathBuf],
+        use_lazy_loading: bool,
+        ignore_mods: bool,
+        lock_packfile: bool,
+        types_to_load: &Option<Vec<PackedFileType>>,
+        cancel: &AtomicBool,
+    ) -> Result<Self> {
 
         // If we just have one `PackFile`, just read it. No fancy logic needed. If you're an asshole and tried to break this
         // by passing it no paths, enjoy the error.
         if packs_paths.is_empty() { return Err(ErrorKind::PackFileNoPathProvided.into()) }
-        if packs_paths.len() == 1 { Self::read(&packs_paths[0], use_lazy_loading) }
+        if packs_paths.len() == 1 { Self::read_with_types(&packs_paths[0], use_lazy_loading, types_to_load) }
 
         // Otherwise, read all of them into a *fake* `PackFile` and take care of the duplicated files like the game will do.
         else {
@@ -2548,8 +3375,8 @@ impl PackFile {
             let mut packs_paths = packs_paths.iter().filter(|x| x.is_file()).collect::<Vec<&PathBuf>>();
             packs_paths.sort_by_key(|x| x.file_name().unwrap().to_string_lossy().to_string());
 
-            let pfh_version = GAME_SELECTED.read().unwrap().get_pfh_version_by_file_type(PFHFileType::Mod);
-            let pfh_name = if ignore_mods { GAME_SELECTED.read().unwrap().get_game_key_name() } else { String::from("merged_mod.pack")};
+            let pfh_version = game.get_pfh_version_by_file_type(PFHFileType::Mod);
+            let pfh_name = if ignore_mods { game.get_game_key_name() } else { String::from("merged_mod.pack")};
             let mut pack_file = Self::new_with_name(&pfh_name, pfh_version);
 
             // Read all the `PackFiles`, one by one, and separate their files by `PFHFileType`.
@@ -2559,7 +3386,11 @@ impl PackFile {
             let mut mod_files = vec![];
             let mut movie_files = vec![];
             for path in packs_paths {
-                match Self::read(path, use_lazy_loading) {
+                if cancel.load(Ordering::SeqCst) {
+                    return Err(ErrorKind::OperationCancelled.into());
+                }
+
+                match Self::read_with_types(path, use_lazy_loading, types_to_load) {
                     Ok(mut pack) => match pack.get_pfh_file_type() {
                         PFHFileType::Boot => boot_files.append(&mut pack.packed_files),
                         PFHFileType::Release => release_files.append(&mut pack.packed_files),
@@ -2619,18 +3450,136 @@ impl PackFile {
         file_path: &Path,
         use_lazy_loading: bool
     ) -> Result<Self> {
+        Self::read_with_types(file_path, use_lazy_loading, &None)
+    }
+
+    /// This function reads the content of a PackFile into a `PackFile` struct, keeping only the `PackedFiles` of the provided types.
+    ///
+    /// The types are checked non-strictly, using only the path of each `PackedFile`, so the discarded ones never have their data read.
+    /// If `types_to_load` is `None`, all the `PackedFiles` are loaded.
+    pub fn read_with_types(
+        file_path: &Path,
+        use_lazy_loading: bool,
+        types_to_load: &Option<Vec<PackedFileType>>,
+    ) -> Result<Self> {
 
         // Check if what we received is even a `PackFile`.
         if !file_path.file_name().unwrap().to_string_lossy().to_string().ends_with(".pack") { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
 
-        // Prepare the PackFile to be read and the virtual PackFile to be written.
-        let mut pack_file = BufReader::new(File::open(&file_path)?);
+        Self::read_from_path(file_path, use_lazy_loading, types_to_load, None)
+    }
+
+    /// This function reads the content of a PackFile into a `PackFile` struct, reporting its progress to the provided callback.
+    ///
+    /// The callback receives the bytes of the PackFile processed so far and its total size. With lazy-loading, progress is
+    /// reported as the PackedFiles are indexed. Otherwise, as their data is loaded to memory.
+    pub fn read_with_progress(
+        file_path: &Path,
+        use_lazy_loading: bool,
+        progress: Option<&dyn Fn(u64, u64)>,
+    ) -> Result<Self> {
+
+        // Check if what we received is even a `PackFile`.
+        if !file_path.file_name().unwrap().to_string_lossy().to_string().ends_with(".pack") { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
+
+        Self::read_from_path(file_path, use_lazy_loading, &None, progress)
+    }
+
+    /// This function reads the content of a PackFile into a `PackFile` struct, without checking the extension of the file.
+    ///
+    /// Meant for PackFiles renamed for distribution. The file still needs to start with a valid PFH signature.
+    pub fn read_ignoring_extension(
+        file_path: &Path,
+        use_lazy_loading: bool
+    ) -> Result<Self> {
+        Self::read_from_path(file_path, use_lazy_loading, &None, None)
+    }
+
+    /// This function reads the content of a PackFile from disk, once its path has been checked.
+    fn read_from_path(
+        file_path: &Path,
+        use_lazy_loading: bool,
+        types_to_load: &Option<Vec<PackedFileType>>,
+        progress: Option<&dyn Fn(u64, u64)>,
+    ) -> Result<Self> {
+        let pack_file = BufReader::new(File::open(file_path)?);
+        let pack_file_len = pack_file.get_ref().metadata()?.len();
+
+        // The PackedFiles keep a reader over the PackFile, so they can load their data when needed.
+        Self::read_from_reader(pack_file, pack_file_len, file_path, use_lazy_loading, types_to_load, progress, |pack_file| {
+            let pack_file = Arc::new(Mutex::new(pack_file));
+            Box::new(move |start, size, is_compressed, is_encrypted| Ok(PackedFileData::OnDisk(RawOnDisk::new(pack_file.clone(), start, size, is_compressed, is_encrypted))))
+        })
+    }
+
+    /// This function reads the content of a PackFile into a `PackFile` struct, reading the data of its PackedFiles from a memory-mapping of the file.
+    ///
+    /// Meant for read-only workflows over huge PackFiles, like inspecting or exporting them: the PackedFiles are lazy-loaded from the mapping,
+    /// so the OS can keep in memory only the parts of the PackFile in use.
+    ///
+    /// Saving a `PackFile` read this way copies the data of all its PackedFiles to memory first, like with any lazy-loaded PackFile,
+    /// so the memory savings are lost. If you need to save it, do it to a new path, as a copy.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other process, while the returned `PackFile` or any `PackedFile`
+    /// taken from it is alive. Accessing a mapped region that no longer exists on disk is undefined behavior (usually a SIGBUS),
+    /// and the last modified date check done before each read can't catch changes done after it, while the data is being read.
+    pub unsafe fn read_mmap(file_path: &PathBuf) -> Result<Self> {
+
+        // Check if what we received is even a `PackFile`.
+        if !file_path.file_name().unwrap().to_string_lossy().to_string().ends_with(".pack") { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
+
+        let pack_file = BufReader::new(File::open(file_path)?);
+        let pack_file_len = pack_file.get_ref().metadata()?.len();
+
+        // SAFETY: The caller guarantees the file is not truncated or modified while the mapping is alive. See this function's docs.
+        let mmap = Arc::new(Mmap::map(pack_file.get_ref())?);
+
+        Self::read_from_reader(pack_file, pack_file_len, file_path, true, &None, None, |pack_file| {
+            let pack_file = Arc::new(Mutex::new(pack_file));
+            Box::new(move |start, size, is_compressed, is_encrypted| Ok(PackedFileData::OnDisk(RawOnDisk::new_mmap(pack_file.clone(), mmap.clone(), start, size, is_compressed, is_encrypted))))
+        })
+    }
+
+    /// This function reads the content of a PackFile from memory into a `PackFile` struct.
+    ///
+    /// `file_name` is used as the name of the PackFile, and must end in `.pack`. As there is no file to read the PackedFiles
+    /// from later, lazy-loading is not supported here: the data of every PackedFile is loaded to memory.
+    pub fn read_from_vec(data: Vec<u8>, file_name: &str) -> Result<Self> {
+        if !file_name.ends_with(".pack") { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) }
+
+        let pack_file_len = data.len() as u64;
+        Self::read_from_reader(Cursor::new(data), pack_file_len, Path::new(file_name), false, &None, None, |pack_file| {
+            let data = pack_file.into_inner();
+            Box::new(move |start, size, is_compressed, is_encrypted| {
+                let end = start + u64::from(size);
+                if end > data.len() as u64 { return Err(ErrorKind::PackFileSizeIsNotWhatWeExpect(data.len() as u64, end).into()) }
+                Ok(PackedFileData::OnMemory(data[start as usize..end as usize].to_vec(), is_compressed, is_encrypted))
+            })
+        })
+    }
+
+    /// This function reads the content of a PackFile from the provided reader into a `PackFile` struct.
+    ///
+    /// Once the indexes have been read, the reader is passed to `data_source`, which returns the function used to get the data
+    /// of each PackedFile from its start position, size, compression and encryption.
+    fn read_from_reader<R: Read + Seek>(
+        mut pack_file: R,
+        pack_file_len: u64,
+        file_path: &Path,
+        use_lazy_loading: bool,
+        types_to_load: &Option<Vec<PackedFileType>>,
+        progress: Option<&dyn Fn(u64, u64)>,
+        data_source: impl FnOnce(R) -> Box<dyn Fn(u64, u32, bool, Option<PFHVersion>) -> Result<PackedFileData>>,
+    ) -> Result<Self> {
+
+        // Prepare the virtual PackFile to be written.
         let pack_file_name = file_path.file_name().unwrap().to_string_lossy().to_string();
         let mut pack_file_decoded = Self::new();
 
         // First, we do some quick checkings to ensure it's a valid PackFile.
         // 24 is the bare minimum that we need to check how a PackFile should be internally, so any file with less than that is not a valid PackFile.
-        let pack_file_len = pack_file.get_ref().metadata()?.len();
         if pack_file_len < 24 { return Err(ErrorKind::PackFileHeaderNotComplete.into()) }
 
         // Check if it has the weird steam-only header, and skip it if found.
@@ -2746,8 +3695,9 @@ impl PackFile {
         };
 
         // Prepare the needed stuff to read the PackedFiles.
+        let data_start = data_position;
         let mut index_position: usize = 0;
-        let pack_file = Arc::new(Mutex::new(pack_file));
+        let get_packed_file_data = data_source(pack_file);
         for packed_files_to_decode in (0..packed_file_count).rev() {
 
             // Get his size. If it's encrypted, decrypt it first.
@@ -2795,13 +3745,12 @@ impl PackFile {
                 timestamp,
                 is_compressed,
                 if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_DATA) { Some(pack_file_decoded.pfh_version) } else { None },
-                PackedFileData::OnDisk(RawOnDisk::new(
-                    pack_file.clone(),
+                get_packed_file_data(
                     data_position,
                     size,
                     is_compressed,
                     if pack_file_decoded.bitmask.contains(PFHFlags::HAS_ENCRYPTED_DATA) { Some(pack_file_decoded.pfh_version) } else { None },
-                ))
+                )?
             );
 
             let mut packed_file = PackedFile::new_from_raw(&raw_data);
@@ -2824,6 +3773,13 @@ impl PackFile {
                     };
                 }
             }
+            // If we only want some types, skip the rest. Their data is never read, so this is cheap even without lazy-loading.
+            else if let Some(types_to_load) = types_to_load {
+                if packed_file.get_packed_file_type(false).eq_non_strict_slice(types_to_load) {
+                    pack_file_decoded.packed_files.push(packed_file);
+                }
+            }
+
             else {
                 pack_file_decoded.packed_files.push(packed_file);
             }
@@ -2837,6 +3793,10 @@ impl PackFile {
                 data_position += u64::from(padded_size);
             }
             else { data_position += u64::from(size); }
+
+            if use_lazy_loading {
+                if let Some(progress) = progress { progress(data_position, pack_file_len); }
+            }
         }
 
         // If at this point we have not reached the end of the PackFile, there is something wrong with it.
@@ -2847,27 +3807,213 @@ impl PackFile {
         else if data_position != pack_file_len { return Err(ErrorKind::PackFileSizeIsNotWhatWeExpect(pack_file_len, data_position).into()) }
 
         // If we disabled lazy-loading, load every PackedFile to memory.
-        if !use_lazy_loading { for packed_file in &mut pack_file_decoded.packed_files { packed_file.get_ref_mut_raw().load_data()?; }}
+        if !use_lazy_loading {
+            let mut loaded = data_start;
+            for packed_file in &mut pack_file_decoded.packed_files {
+                packed_file.get_ref_mut_raw().load_data()?;
+
+                loaded += u64::from(packed_file.get_ref_raw().get_size());
+                if let Some(progress) = progress { progress(loaded, pack_file_len); }
+            }
+        }
+
+        // Skipped PackedFiles are never loaded, so make sure we always end at 100%.
+        if let Some(progress) = progress { progress(pack_file_len, pack_file_len); }
 
         // Return our PackFile.
         Ok(pack_file_decoded)
     }
 
+    /// This function reads a `PackFile` split across multiple files, as if it was a single `PackFile`.
+    ///
+    /// The parts must be provided in order, and only the first one can contain the header of the `PackFile`. The parts are
+    /// read one after another without joining them on disk, but lazy-loading is not supported here. The path of the resulting
+    /// `PackFile` is the one of the first part, without its part extension (for example, `mod.pack.001` becomes `mod.pack`).
+    pub fn read_split(parts: &[PathBuf]) -> Result<Self> {
+        let first_part = parts.first().ok_or_else(|| Error::from(ErrorKind::PackFileSplitNoParts))?;
+
+        // The name of the PackFile is the name of the first part, minus the part extension if it has one.
+        let file_name = first_part.file_name().ok_or_else(|| Error::from(ErrorKind::PackFileSplitPartNotFound(first_part.to_path_buf())))?.to_string_lossy().to_string();
+        let file_name = if file_name.ends_with(".pack") { file_name }
+            else if let Some(file_stem) = first_part.file_stem().map(|x| x.to_string_lossy().to_string()).filter(|x| x.ends_with(".pack")) { file_stem }
+            else { return Err(ErrorKind::OpenPackFileInvalidExtension.into()) };
+
+        // Before joining them, make sure all the parts are there and only the first one has a header.
+        for (index, part) in parts.iter().enumerate() {
+            if !part.is_file() { return Err(ErrorKind::PackFileSplitPartNotFound(part.to_path_buf()).into()) }
+
+            let mut preamble = vec![0; 4];
+            let has_header = File::open(part)?.read_exact(&mut preamble).is_ok() && {
+                let preamble = String::from_utf8_lossy(&preamble);
+                PFHVersion::get_version(&preamble).is_ok() || preamble.starts_with(MFH_PREAMBLE)
+            };
+
+            if (index == 0) != has_header { return Err(ErrorKind::PackFileSplitPartOutOfOrder(part.to_path_buf()).into()) }
+        }
+
+        let reader = SplitPackFileReader::new(parts)?;
+        let pack_file_len = reader.len;
+        let result = Self::read_from_reader(BufReader::new(reader), pack_file_len, Path::new(&file_name), false, &None, None, |pack_file| {
+            let pack_file = Mutex::new(pack_file);
+            Box::new(move |start, size, is_compressed, is_encrypted| {
+                let mut data = vec![0; size as usize];
+                let mut pack_file = pack_file.lock().unwrap();
+                pack_file.seek(SeekFrom::Start(start))?;
+                pack_file.read_exact(&mut data)?;
+                Ok(PackedFileData::OnMemory(data, is_compressed, is_encrypted))
+            })
+        });
+
+        // If the size is wrong, it's either a missing part or parts in the wrong order, so report it as such.
+        let mut pack_file = result.map_err(|error| match error.kind() {
+            ErrorKind::PackFileSizeIsNotWhatWeExpect(combined_size, expected_size) => ErrorKind::PackFileSplitSizeMismatch(*combined_size, *expected_size).into(),
+            _ => error,
+        })?;
+
+        pack_file.set_file_path(&first_part.with_file_name(file_name))?;
+        Ok(pack_file)
+    }
+
+    /// This function returns the decoded AnimTable of the `PackFile`, using the currently loaded schema.
+    ///
+    /// If the `PackFile` has more than one AnimTable, the first one is returned.
+    pub fn get_animtable(&mut self) -> Result<AnimTable> {
+        let packed_file = self.get_ref_mut_packed_files_by_type(PackedFileType::AnimTable, false).into_iter().next().ok_or_else(|| Error::from(ErrorKind::NoAnimTableInPackFile))?;
+        match packed_file.decode_return_ref()? {
+            DecodedPackedFile::AnimTable(table) => Ok(table.clone()),
+            _ => Err(ErrorKind::AnimTableDecode("This PackedFile is not an AnimTable.".to_owned()).into()),
+        }
+    }
+
+    /// This function calculates a hash of the contents of the `PackFile`, from the path and data of each of its `PackedFiles`.
+    ///
+    /// Neither the header (with its timestamp) nor the order of the `PackedFiles` are hashed, so two `PackFiles` with the same
+    /// `PackedFiles` get the same hash. The hash is a 64-bit FNV-1a over the bytes of the paths and data, so it's stable between builds.
+    pub fn content_hash(&mut self) -> Result<String> {
+        let mut hashes = self.packed_files.iter_mut()
+            .map(|packed_file| {
+                packed_file.encode_and_clean_cache()?;
+                let mut hasher = FnvHasher::default();
+                hasher.write(&packed_file.get_raw_data()?);
+                Ok((packed_file.get_path().to_vec(), hasher.finish()))
+            })
+            .collect::<Result<Vec<(Vec<String>, u64)>>>()?;
+        hashes.sort();
+
+        // Prefix each path segment with its length, so different paths can't end up writing the same bytes.
+        let mut hasher = FnvHasher::default();
+        for (path, hash) in &hashes {
+            hasher.write(&(path.len() as u64).to_le_bytes());
+            for segment in path {
+                hasher.write(&(segment.len() as u64).to_le_bytes());
+                hasher.write(segment.as_bytes());
+            }
+            hasher.write(&hash.to_le_bytes());
+        }
+
+        Ok(format!("{:016x}", hasher.finish()))
+    }
+
+    /// This function checks the integrity of the data of all the `PackedFiles` in the `PackFile`.
+    ///
+    /// It returns the paths of the `PackedFiles` that failed the check. Check `PackedFile::verify_checksum` for what's checked.
+    pub fn verify_all_checksums(&self) -> Vec<Vec<String>> {
+        let mut failed = self.packed_files.par_iter()
+            .filter(|packed_file| !packed_file.verify_checksum().unwrap_or(false))
+            .map(|packed_file| packed_file.get_path().to_vec())
+            .collect::<Vec<Vec<String>>>();
+
+        failed.sort();
+        failed
+    }
+
+    /// This function checks the `PackFile` for problems that would make the game reject it once saved.
+    ///
+    /// It doesn't stop on the first problem found. Instead, it returns all the problems it finds. If the `validate_packfile_before_save`
+    /// setting is enabled, `save` calls this and refuses to save if any problem is found.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let reserved_names = Self::get_reserved_packed_file_names();
+        let mut issues = vec![];
+        let mut paths_lower = HashSet::new();
+
+        for packed_file in &self.packed_files {
+            let path = packed_file.get_path();
+            if reserved_names.iter().any(|x| x == path) {
+                issues.push(ValidationIssue::ReservedName(path.to_vec()));
+            }
+
+            if path.is_empty() || path.iter().any(|x| x.is_empty()) {
+                issues.push(ValidationIssue::EmptyPathSegment(path.to_vec()));
+            }
+
+            // The game is case-insensitive with paths, so these are duplicates too.
+            if !paths_lower.insert(path.join("\\").to_lowercase()) {
+                issues.push(ValidationIssue::DuplicatedPath(path.to_vec()));
+            }
+        }
+
+        // Type checks may need to read the data from disk, so we do them in parallel.
+        issues.par_extend(self.packed_files.par_iter().filter_map(|packed_file| {
+            let packed_file_type = packed_file.get_packed_file_type(false);
+            let is_valid = match packed_file.get_ref_decoded() {
+                DecodedPackedFile::Unknown => match packed_file_type {
+                    PackedFileType::DB => packed_file.get_ref_raw().get_data().map(|data| DB::read_header(&data).is_ok()).unwrap_or(false),
+                    PackedFileType::Loc => packed_file.get_ref_raw().get_data().map(|data| Loc::is_loc(&data)).unwrap_or(false),
+                    _ => true,
+                },
+                decoded => packed_file_type == PackedFileType::Unknown || PackedFileType::from(decoded).eq_non_strict(packed_file_type),
+            };
+
+            if is_valid { None } else { Some(ValidationIssue::TypeMismatch(packed_file.get_path().to_vec(), packed_file_type)) }
+        }));
+
+        // Variant Mesh Definitions are edited as text, so they can end up with broken XML or unknown elements.
+        issues.par_extend(self.packed_files.par_iter().filter_map(|packed_file| {
+            if packed_file.get_packed_file_type(false) != PackedFileType::VariantMeshDefinition { return None }
+
+            let contents = match packed_file.get_ref_decoded() {
+                DecodedPackedFile::Text(text) => Ok(text.get_ref_contents().to_owned()),
+                _ => packed_file.get_ref_raw().get_data().and_then(|data| Text::read(&data)).map(|text| text.get_ref_contents().to_owned()),
+            };
+
+            contents.and_then(|contents| variant_mesh_definition::validate(&contents)).err()
+                .map(|error| ValidationIssue::InvalidVariantMeshDefinition(packed_file.get_path().to_vec(), error.kind().clone()))
+        }));
+
+        issues
+    }
+
     /// This function tries to save a `PackFile` to a file in the filesystem.
     ///
     /// If no path is passed, the `PackFile` will be saved in his current path.
     /// If a path is passed as `new_path` the `PackFile` will be saved in that path.
     pub fn save(&mut self, new_path: Option<PathBuf>) -> Result<()> {
+        self.save_with_progress(new_path, None)
+    }
+
+    /// This function tries to save a `PackFile` to a file in the filesystem, reporting its progress to the provided callback.
+    ///
+    /// The callback receives the bytes of PackedFile data written so far and the total to write. See `save` for the rest.
+    pub fn save_with_progress(&mut self, new_path: Option<PathBuf>, progress: Option<&dyn Fn(u64, u64)>) -> Result<()> {
 
         // If any of the problematic masks in the header is set or is one of CA's, return an error.
         if !self.is_editable(*SETTINGS.read().unwrap().settings_bool.get("allow_editing_of_ca_packfiles").unwrap()) { return Err(ErrorKind::PackFileIsNonEditable.into()) }
 
+        // If we have to validate the PackFile before saving it, report all the problems found at once.
+        if SETTINGS.read().unwrap().settings_bool.get("validate_packfile_before_save").copied().unwrap_or(false) {
+            let issues = self.validate();
+            if !issues.is_empty() {
+                return Err(ErrorKind::PackFileValidationFailed(issues.iter().map(|issue| issue.to_string()).collect()).into());
+            }
+        }
+
         // If we receive a new path, update it. Otherwise, ensure the file actually exists on disk.
         if let Some(path) = new_path { self.set_file_path(&path)?; }
         else if !self.get_file_path().is_file() { return Err(ErrorKind::PackFileIsNotAFile.into()) }
 
         // We ensure that all the data is loaded and in his right form (compressed/encrypted) before attempting to save.
         // We need to do this here because we need later on their compressed size.
+        let mut data_size = 0u64;
         for packed_file in &mut self.packed_files {
 
             // If we decoded it, re-encode it. Otherwise, just load it.
@@ -2897,6 +4043,13 @@ impl PackFile {
                 *is_encrypted = None;
                 *should_be_encrypted = None;
             }
+
+            data_size += data.len() as u64;
+        }
+
+        // Older PackFiles use 32-bit offsets, so anything bigger than 4GB ends up corrupted. Stop before writing anything.
+        if self.pfh_version.has_32_bit_offsets() && data_size > u64::from(u32::MAX) {
+            return Err(ErrorKind::PackFileTooLargeForFormat(self.pfh_version.to_string(), data_size).into());
         }
 
         // Only do this in non-vanilla files.
@@ -2920,9 +4073,9 @@ impl PackFile {
         }
 
         // For some bizarre reason, if the PackedFiles are not alphabetically sorted they may or may not crash the game for particular people.
-        // So, to fix it, we have to sort all the PackedFiles here by path.
+        // So, to fix it, by default we have to sort all the PackedFiles here by path.
         // NOTE: This sorting has to be CASE INSENSITIVE. This means for "ac", "Ab" and "aa" it'll be "aa", "Ab", "ac".
-        self.packed_files.sort_unstable_by_key(|a| a.get_path().join("\\").to_lowercase());
+        self.get_sort_mode().sort(&mut self.packed_files);
 
         // First we encode the indexes and the data (just in case we compressed it).
         let mut pack_file_index = vec![];
@@ -2971,7 +4124,10 @@ impl PackFile {
         header.encode_integer_u32(packed_file_index.len() as u32);
 
         // Update the creation time, then save it. PFH0 files don't have timestamp in the headers.
-        self.timestamp = get_current_time();
+        // If we want a reproducible PackFile, we keep whatever timestamp we have.
+        if !self.deterministic_timestamp {
+            self.timestamp = get_current_time();
+        }
         match self.pfh_version {
             PFHVersion::PFH6 | PFHVersion::PFH5 | PFHVersion::PFH4 => header.encode_integer_u32(self.timestamp as u32),
             PFHVersion::PFH3 | PFHVersion::PFH2 => header.encode_integer_i64((self.timestamp + SEC_TO_UNIX_EPOCH) * WINDOWS_TICK),
@@ -3003,9 +4159,15 @@ impl PackFile {
         file.write_all(&header)?;
         file.write_all(&pack_file_index)?;
         file.write_all(&packed_file_index)?;
+
+        // Progress is counted in the same unit as the total: the size of each PackedFile, as written in the index.
+        let total_size = self.packed_files.iter().map(|packed_file| u64::from(packed_file.get_ref_raw().get_size())).sum::<u64>();
+        let mut written = 0;
         for packed_file in &self.packed_files {
-            let data = packed_file.get_ref_raw().get_raw_data()?;
-            file.write_all(&data)?;
+            file.write_all(&packed_file.get_ref_raw().get_raw_data()?)?;
+
+            written += u64::from(packed_file.get_ref_raw().get_size());
+            if let Some(progress) = progress { progress(written, total_size); }
         }
 
         // Remove again the reserved PackedFiles.
@@ -3017,6 +4179,59 @@ impl PackFile {
     }
 }
 
+/// Implementation of `SplitPackFileReader`.
+impl SplitPackFileReader {
+
+    /// This function opens all the provided parts, in order, to read them as a single file.
+    fn new(parts: &[PathBuf]) -> Result<Self> {
+        let mut len = 0;
+        let parts = parts.iter().map(|part| {
+            let file = File::open(part)?;
+            let start = len;
+            len += file.metadata()?.len();
+            Ok((start, file))
+        }).collect::<Result<Vec<(u64, File)>>>()?;
+
+        Ok(Self {
+            parts,
+            len,
+            position: 0,
+        })
+    }
+}
+
+/// Implementation of trait `Read` for `SplitPackFileReader`.
+impl Read for SplitPackFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position >= self.len || buf.is_empty() { return Ok(0) }
+
+        // Reads never cross the end of a part, so we only need to care about the part the position is in.
+        let index = self.parts.iter().rposition(|(start, _)| *start <= self.position).unwrap_or(0);
+        let part_end = self.parts.get(index + 1).map(|(start, _)| *start).unwrap_or(self.len);
+        let (part_start, part) = &mut self.parts[index];
+
+        let max_len = buf.len().min((part_end - self.position) as usize);
+        part.seek(SeekFrom::Start(self.position - *part_start))?;
+        let read = part.read(&mut buf[..max_len])?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+/// Implementation of trait `Seek` for `SplitPackFileReader`.
+impl Seek for SplitPackFileReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => if offset < 0 { self.len.checked_sub(offset.unsigned_abs()) } else { self.len.checked_add(offset as u64) },
+            SeekFrom::Current(offset) => if offset < 0 { self.position.checked_sub(offset.unsigned_abs()) } else { self.position.checked_add(offset as u64) },
+        };
+
+        self.position = position.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position"))?;
+        Ok(self.position)
+    }
+}
+
 /// Implementation of trait `Default` for `PackFile`.
 impl Default for PackFile {
 
@@ -3048,7 +4263,13 @@ impl Manifest {
 
     /// This function returns a parsed version of the `manifest.txt` of the Game Selected, if exists and is parsable.
     pub fn read_from_game_selected() -> Result<Self> {
-        let mut manifest_path = GAME_SELECTED.read().unwrap().get_data_path().map_err(|_| Error::from(ErrorKind::GameManifestNotFound))?;
+        let game_selected = GAME_SELECTED.read().unwrap().clone();
+        Self::read_from_game(&game_selected)
+    }
+
+    /// This function returns a parsed version of the `manifest.txt` of the provided Game, if exists and is parsable.
+    pub fn read_from_game(game: &GameInfo) -> Result<Self> {
+        let mut manifest_path = game.get_data_path().map_err(|_| Error::from(ErrorKind::GameManifestNotFound))?;
         manifest_path.push("manifest.txt");
 
         let mut reader = ReaderBuilder::new()
@@ -3071,7 +4292,7 @@ impl Manifest {
             } else {
                 let mut manifest_entry = ManifestEntry {
                     relative_path: record.get(0).ok_or_else(|| Error::from(ErrorKind::ManifestError))?.to_owned(),
-                    size: record.get(1).ok_or_else(|| Error::from(ErrorKind::ManifestError))?.parse()?,
+                    size: record.get(1).ok_or_else(|| Error::from(ErrorKind::ManifestError))?.parse().map_err(|_| Error::from(ErrorKind::ManifestError))?,
                     ..Default::default()
                 };
 
@@ -3114,7 +4335,7 @@ impl Manifest {
             } else {
                 let mut manifest_entry = ManifestEntry {
                     relative_path: record.get(0).ok_or_else(|| Error::from(ErrorKind::ManifestError))?.to_owned(),
-                    size: record.get(1).ok_or_else(|| Error::from(ErrorKind::ManifestError))?.parse()?,
+                    size: record.get(1).ok_or_else(|| Error::from(ErrorKind::ManifestError))?.parse().map_err(|_| Error::from(ErrorKind::ManifestError))?,
                     ..Default::default()
                 };
 
@@ -3145,13 +4366,15 @@ impl Default for PackFileSettings {
 
     fn default() -> Self {
         let mut settings_text = BTreeMap::new();
-        let settings_string = BTreeMap::new();
+        let mut settings_string = BTreeMap::new();
         let mut settings_bool = BTreeMap::new();
         let settings_number = BTreeMap::new();
 
         settings_text.insert("diagnostics_files_to_ignore".to_owned(), "".to_owned());
         settings_text.insert("import_files_to_ignore".to_owned(), "".to_owned());
         settings_bool.insert("disable_autosaves".to_owned(), false);
+        settings_bool.insert("compress_new_files".to_owned(), false);
+        settings_string.insert("sort_mode".to_owned(), SortMode::default().get_value().to_owned());
 
         Self {
             settings_text,
@@ -3224,6 +4447,20 @@ impl PackFileSettings {
     }
 }
 
+/// Implementation of `PFHFlags`.
+impl PFHFlags {
+
+    /// This function returns the names of the flags enabled, in the same order they're in the bitmask.
+    pub fn get_names(&self) -> Vec<&'static str> {
+        let mut names = vec![];
+        if self.contains(Self::HAS_EXTENDED_HEADER) { names.push("HAS_EXTENDED_HEADER"); }
+        if self.contains(Self::HAS_ENCRYPTED_INDEX) { names.push("HAS_ENCRYPTED_INDEX"); }
+        if self.contains(Self::HAS_INDEX_WITH_TIMESTAMPS) { names.push("HAS_INDEX_WITH_TIMESTAMPS"); }
+        if self.contains(Self::HAS_ENCRYPTED_DATA) { names.push("HAS_ENCRYPTED_DATA"); }
+        names
+    }
+}
+
 /// Implementation of trait `Default` for `PFHFlags`.
 impl Default for PFHFlags {
     fn default() -> Self {
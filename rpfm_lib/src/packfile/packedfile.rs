@@ -18,7 +18,9 @@ You'll rarely have to touch anything here.
 !*/
 
 use filepath::FilePath;
+use memmap2::Mmap;
 
+use std::borrow::Cow;
 use std::collections::hash_map::DefaultHasher;
 use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
@@ -30,9 +32,10 @@ use std::sync::{Arc, Mutex};
 use rpfm_macros::*;
 use rpfm_error::Error;
 
+use crate::common::decoder::Decoder;
 use crate::packedfile::animpack::AnimPacked;
 use crate::packfile::*;
-use crate::packfile::compression::decompress_data;
+use crate::packfile::compression::{decompress_data, decompress_data_to_writer};
 use crate::packedfile::{DecodedPackedFile, PackedFileType};
 use crate::packedfile::table::{db::DB, loc::Loc};
 use crate::schema::Schema;
@@ -89,6 +92,10 @@ pub struct RawOnDisk {
 
     /// Reader over the PackFile containing the PackedFile.
     reader: Arc<Mutex<BufReader<File>>>,
+
+    /// Memory-mapping of the PackFile containing the PackedFile. If present, the data is read from it instead of from the reader.
+    mmap: Option<Arc<Mmap>>,
+
     start: u64,
     size: u32,
     is_compressed: bool,
@@ -241,6 +248,33 @@ impl PackedFile {
         &self.raw
     }
 
+    /// This function sets if this `PackedFile` should be compressed or not on the next save.
+    ///
+    /// Only `PFHVersion::PFH5` PackFiles support compression. Use `PackFile::set_compression_for_all` if you need that check.
+    pub fn set_should_compress(&mut self, compress: bool) {
+        self.raw.set_should_be_compressed(compress);
+    }
+
+    /// This function checks the integrity of the data of this `PackedFile`.
+    ///
+    /// PFH formats store no per-file checksums, so this checks that the data can be read, decrypted and decompressed and, for compressed data,
+    /// that the decompressed size matches the one in its compression header, which catches truncated data. It only returns an error
+    /// if the PackFile changed on disk since it was opened.
+    pub fn verify_checksum(&self) -> Result<bool> {
+        let result = self.raw.get_data().and_then(|data| {
+            if data.len() as u64 == self.raw.get_decompressed_size()? { Ok(()) }
+            else { Err(ErrorKind::PackedFileChecksumFailed.into()) }
+        });
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(error) => match error.kind() {
+                ErrorKind::PackedFileSourceChanged => Err(error),
+                _ => Ok(false),
+            }
+        }
+    }
+
     /// This function returns a reference to the `DecodedPackedFile` part of a `PackedFile`.
     pub fn get_ref_decoded(&self) -> &DecodedPackedFile {
         &self.decoded
@@ -337,7 +371,10 @@ impl PackedFile {
     /// This function tries to decode a `RawPackedFile` into a `DecodedPackedFile`, storing the results in the `Packedfile`,
     /// and returning a reference to it.
     ///
-    /// This variant doesn't lock the Schema. This means is faster if you're decoding `PackedFiles` in batches.
+    /// This variant doesn't lock the Schema. Instead, it decodes using only the schema you pass it, so it's faster if you're decoding
+    /// `PackedFiles` in batches, and it works even if the global Schema is not loaded or belongs to another game.
+    ///
+    /// This takes into account cached decoding so, if it has already been decoded, it doesn't decode it again, even if the schema is different.
     pub fn decode_return_ref_no_locks(&mut self, schema: &Schema) -> Result<&DecodedPackedFile> {
         if self.decoded == DecodedPackedFile::Unknown {
             self.decoded = DecodedPackedFile::decode_no_locks(&mut self.raw, schema)?;
@@ -345,6 +382,18 @@ impl PackedFile {
         Ok(&self.decoded)
     }
 
+    /// This function tries to decode a `RawPackedFile` into a `DecodedPackedFile` using the provided schema, without touching the `PackedFile`.
+    ///
+    /// If the `PackedFile` is already decoded, it returns a reference to the cached decoded data. Otherwise, it returns a new decoded copy.
+    /// Like `decode_return_ref_no_locks`, this doesn't use the global Schema, so you can use it to decode files of different games in the same process.
+    pub fn decode_with_schema(&self, schema: &Schema) -> Result<Cow<'_, DecodedPackedFile>> {
+        if self.decoded == DecodedPackedFile::Unknown {
+            Ok(Cow::Owned(DecodedPackedFile::decode_no_locks(&mut self.raw.clone(), schema)?))
+        } else {
+            Ok(Cow::Borrowed(&self.decoded))
+        }
+    }
+
     /// This function tries to decode a `RawPackedFile` into a `DecodedPackedFile`, storing the results in the `Packedfile`,
     /// and returning a mutable reference to it.
     ///
@@ -447,6 +496,14 @@ impl PackedFile {
         self.raw.get_data()
     }
 
+    /// This function returns the size of the data of a PackedFile once decompressed, encoding it first in case it's cached.
+    ///
+    /// For compressed PackedFiles, only the header of the data is read, so we don't need to decompress it.
+    pub fn get_decompressed_size(&mut self) -> Result<u64> {
+        self.encode_no_load()?;
+        self.raw.get_decompressed_size()
+    }
+
     /// This function returns the data of a PackedFile.
     pub fn get_raw_data_and_keep_it(&mut self) -> Result<Vec<u8>> {
         self.raw.get_data_and_keep_it()
@@ -510,14 +567,19 @@ impl PackedFile {
     }
 
     /// This function saves the latest data of a PackedFile to disk.
-    fn encode_and_save_to_disk(&mut self, path: &Path) -> Result<()> {
+    pub(crate) fn encode_and_save_to_disk(&mut self, path: &Path) -> Result<()> {
 
         // Save it, in case it's cached.
         self.encode_no_load()?;
 
-        // Try to save the file to disk.
+        // Try to save the file to disk. If its data is still on disk, stream it so we don't need to load big files to memory.
         let mut file = BufWriter::new(File::create(&path)?);
-        if file.write_all(&self.get_raw_data()?).is_err() {
+        if let PackedFileData::OnDisk(ref raw_on_disk) = self.get_ref_raw_inner_data() {
+            raw_on_disk.copy_to_writer(&mut file)?;
+            file.flush()?;
+        }
+
+        else if file.write_all(&self.get_raw_data()?).is_err() {
             return Err(ErrorKind::ExtractError(self.get_path().to_vec()).into());
         }
         Ok(())
@@ -635,13 +697,28 @@ impl RawPackedFile {
                 if is_compressed { data = decompress_data(&data)?; }
                 Ok(data)
             },
-            PackedFileData::OnDisk(ref raw_on_disk) => {
-                let mut data = raw_on_disk.read()?;
-                if raw_on_disk.get_encryption_state() { data = decrypt_packed_file(&data); }
-                if raw_on_disk.get_compression_state() { decompress_data(&data) }
-                else { Ok(data) }
-            }
+            PackedFileData::OnDisk(ref raw_on_disk) => raw_on_disk.read_data(),
+        }
+    }
+
+    /// This function returns the first `size` bytes of the data of the provided `RawPackedFile`, without reading the rest of it.
+    ///
+    /// Compressed data can't be decompressed in chunks, so compressed PackedFiles are fully read to get them.
+    pub fn get_data_start(&self, size: u32) -> Result<Vec<u8>> {
+        if self.get_compression_state() {
+            let mut data = self.get_data()?;
+            data.truncate(size as usize);
+            return Ok(data);
         }
+
+        // Decryption goes in blocks, each one independent from the rest, so we can decrypt only the start of the data.
+        let data = match self.data {
+            PackedFileData::OnMemory(ref data, _, _) => data[..data.len().min(size as usize)].to_vec(),
+            PackedFileData::OnDisk(ref raw_on_disk) => raw_on_disk.read_start(size)?,
+        };
+
+        if self.get_encryption_state() { Ok(decrypt_packed_file(&data)) }
+        else { Ok(data) }
     }
 
     /// This function returns the data of the provided `RawPackedFile` loading it to memory in the process if it isn't already loaded.
@@ -656,12 +733,7 @@ impl RawPackedFile {
                 *is_encrypted = None;
                 return Ok(data.to_vec())
             },
-            PackedFileData::OnDisk(ref raw_on_disk) => {
-                let mut data = raw_on_disk.read()?;
-                if raw_on_disk.get_encryption_state() { data = decrypt_packed_file(&data); }
-                if raw_on_disk.get_compression_state() { decompress_data(&data)? }
-                else { data }
-            }
+            PackedFileData::OnDisk(ref raw_on_disk) => raw_on_disk.read_data()?,
         };
 
         self.data = PackedFileData::OnMemory(data.to_vec(), false, None);
@@ -681,12 +753,7 @@ impl RawPackedFile {
                 *is_encrypted = None;
                 return Ok(data)
             },
-            PackedFileData::OnDisk(ref raw_on_disk) => {
-                let mut data = raw_on_disk.read()?;
-                if raw_on_disk.get_encryption_state() { data = decrypt_packed_file(&data); }
-                if raw_on_disk.get_compression_state() { decompress_data(&data)? }
-                else { data }
-            }
+            PackedFileData::OnDisk(ref raw_on_disk) => raw_on_disk.read_data()?,
         };
 
         self.data = PackedFileData::OnMemory(data, false, None);
@@ -720,6 +787,28 @@ impl RawPackedFile {
         }
     }
 
+    /// This function returns the size of the data of the provided `RawPackedFile` once decompressed.
+    ///
+    /// For compressed data, the size is read from the header of the compressed data, so we don't need to decompress it.
+    pub fn get_decompressed_size(&self) -> Result<u64> {
+        if !self.get_compression_state() {
+            return Ok(u64::from(self.get_size()));
+        }
+
+        // The decryption goes in blocks of 8 bytes, so we need the first 16 bytes to get the 9 bytes of the header decrypted.
+        let mut header = match self.data {
+            PackedFileData::OnMemory(ref data, _, _) => data[..data.len().min(16)].to_vec(),
+            PackedFileData::OnDisk(ref raw_on_disk) => raw_on_disk.read_start(16)?,
+        };
+
+        if self.get_encryption_state() { header = decrypt_packed_file(&header); }
+
+        // Same as when decompressing: empty data is valid, incomplete headers are not.
+        if header.is_empty() { Ok(0) }
+        else if header.len() < 9 { Err(ErrorKind::PackedFileDataCouldNotBeDecompressed.into()) }
+        else { Ok(u64::from(header.decode_integer_u32(0)?)) }
+    }
+
     /// This function returns the current compression state of the provided `RawPackedFile`.
     pub fn get_compression_state(&self) -> bool {
         match self.data {
@@ -809,6 +898,7 @@ impl RawOnDisk {
         let last_modified_date_pack = if let Ok(date) = get_last_modified_time_from_buffered_file(&*reader.lock().unwrap()) { date } else { 0};
         Self {
             reader,
+            mmap: None,
             start,
             size,
             is_compressed,
@@ -818,20 +908,69 @@ impl RawOnDisk {
         }
     }
 
+    /// This function creates a new RawOnDisk that reads its data from a memory-mapping of the PackFile.
+    ///
+    /// The reader is still needed to check the PackFile hasn't been modified since we mapped it.
+    pub fn new_mmap(
+        reader: Arc<Mutex<BufReader<File>>>,
+        mmap: Arc<Mmap>,
+        start: u64,
+        size: u32,
+        is_compressed: bool,
+        is_encrypted: Option<PFHVersion>,
+    ) -> Self {
+        let mut raw_on_disk = Self::new(reader, start, size, is_compressed, is_encrypted);
+        raw_on_disk.mmap = Some(mmap);
+        raw_on_disk
+    }
+
     /// This function tries to read and return the raw data of the PackedFile.
     pub fn read(&self) -> Result<Vec<u8>> {
+        self.read_with(|data| Ok(data.to_vec()))
+    }
+
+    /// This function tries to read the raw data of the PackedFile, and returns it decrypted and decompressed.
+    ///
+    /// When reading from a memory-mapping, the data is decrypted/decompressed straight from it, without copying the raw data first.
+    pub fn read_data(&self) -> Result<Vec<u8>> {
+        self.read_with(|data| {
+            match (self.is_encrypted.is_some(), self.is_compressed) {
+                (true, true) => decompress_data(&decrypt_packed_file(data)),
+                (true, false) => Ok(decrypt_packed_file(data)),
+                (false, true) => decompress_data(data),
+                (false, false) => Ok(data.to_vec()),
+            }
+        })
+    }
+
+    /// This function reads the raw data of the PackedFile and passes it to the provided closure, returning whatever it returns.
+    ///
+    /// When reading from a memory-mapping, the closure gets the mapped data directly, so it's not copied to memory.
+    pub fn read_with<T, F: FnOnce(&[u8]) -> Result<T>>(&self, f: F) -> Result<T> {
 
         // Date check, to ensure the PackFile hasn't been modified since we got the indexes to read it.
         let mut file = self.reader.lock().unwrap();
-        let current_date = get_last_modified_time_from_buffered_file(&*file)?;
+        let current_date = get_last_modified_time_from_buffered_file(&file)?;
         if current_date != self.last_modified_date_pack {
             return Err(ErrorKind::PackedFileSourceChanged.into());
         }
 
-        // Read the data from disk.
-        let mut data = vec![0; self.size as usize];
-        file.seek(SeekFrom::Start(self.start))?;
-        file.read_exact(&mut data)?;
+        // Read the data from the mapping if we have one, or from disk if we don't.
+        let data_from_disk;
+        let data = match self.mmap {
+            Some(ref mmap) => {
+                let end = self.start + u64::from(self.size);
+                mmap.get(self.start as usize..end as usize)
+                    .ok_or_else(|| Error::from(ErrorKind::PackFileSizeIsNotWhatWeExpect(mmap.len() as u64, end)))?
+            }
+            None => {
+                let mut data = vec![0; self.size as usize];
+                file.seek(SeekFrom::Start(self.start))?;
+                file.read_exact(&mut data)?;
+                data_from_disk = data;
+                &data_from_disk
+            }
+        };
 
         // Hash check, to ensure the integrity of the data if we loaded it before.
         let mut current_hash = self.hash.lock().unwrap();
@@ -849,7 +988,75 @@ impl RawOnDisk {
             return Err(ErrorKind::PackedFileChecksumFailed.into());
         }
 
-        Ok(data)
+        f(data)
+    }
+
+    /// This function reads the first `size` bytes of the data of the PackedFile, or all of them if the PackedFile is smaller than that.
+    pub(crate) fn read_start(&self, size: u32) -> Result<Vec<u8>> {
+
+        // Date check, to ensure the PackFile hasn't been modified since we got the indexes to read it.
+        let mut file = self.reader.lock().unwrap();
+        let current_date = get_last_modified_time_from_buffered_file(&file)?;
+        if current_date != self.last_modified_date_pack {
+            return Err(ErrorKind::PackedFileSourceChanged.into());
+        }
+
+        let size = size.min(self.size);
+        match self.mmap {
+            Some(ref mmap) => {
+                let end = self.start + u64::from(size);
+                mmap.get(self.start as usize..end as usize)
+                    .map(|data| data.to_vec())
+                    .ok_or_else(|| Error::from(ErrorKind::PackFileSizeIsNotWhatWeExpect(mmap.len() as u64, end)))
+            }
+            None => {
+                let mut data = vec![0; size as usize];
+                file.seek(SeekFrom::Start(self.start))?;
+                file.read_exact(&mut data)?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// This function writes the data of the PackedFile to the provided writer, decompressing it if needed, without loading it all to memory.
+    ///
+    /// Encrypted data can't be decrypted in chunks, so encrypted PackedFiles are fully loaded before writing them. And as the data
+    /// is never fully loaded otherwise, the hash check of `read` is not done here.
+    pub fn copy_to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if self.is_encrypted.is_some() {
+            let mut data = decrypt_packed_file(&self.read()?);
+            if self.is_compressed { data = decompress_data(&data)?; }
+            writer.write_all(&data)?;
+            return Ok(());
+        }
+
+        // Date check, to ensure the PackFile hasn't been modified since we got the indexes to read it.
+        let mut file = self.reader.lock().unwrap();
+        let current_date = get_last_modified_time_from_buffered_file(&file)?;
+        if current_date != self.last_modified_date_pack {
+            return Err(ErrorKind::PackedFileSourceChanged.into());
+        }
+
+        // Stream the data from the mapping if we have one, or from disk if we don't.
+        match self.mmap {
+            Some(ref mmap) => {
+                let end = self.start + u64::from(self.size);
+                let data = mmap.get(self.start as usize..end as usize)
+                    .ok_or_else(|| Error::from(ErrorKind::PackFileSizeIsNotWhatWeExpect(mmap.len() as u64, end)))?;
+
+                if self.is_compressed && self.size > 0 { decompress_data_to_writer(data, writer)?; }
+                else { writer.write_all(data)?; }
+            }
+            None => {
+                file.seek(SeekFrom::Start(self.start))?;
+                let mut data = (&mut *file).take(u64::from(self.size));
+
+                if self.is_compressed && self.size > 0 { decompress_data_to_writer(data, writer)?; }
+                else { std::io::copy(&mut data, writer)?; }
+            }
+        }
+
+        Ok(())
     }
 
     /// This function returns the size of the PackedFile.
@@ -968,6 +1175,7 @@ impl TryFrom<&CachedPackedFile> for PackedFile {
                 data: PackedFileData::OnDisk(
                     RawOnDisk {
                         reader: Arc::new(Mutex::new(BufReader::new(File::open(PathBuf::from(&cached_packed_file.pack_file_path))?))),
+                        mmap: None,
                         start: cached_packed_file.data_start,
                         size: cached_packed_file.data_size,
                         is_compressed: cached_packed_file.is_compressed,
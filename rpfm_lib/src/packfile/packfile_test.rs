@@ -12,15 +12,54 @@
 Module containing test for the `PackFile` module, just to make sure we don't break it... again...
 !*/
 
+
+use std::env::temp_dir;
+use std::fs::{create_dir_all, read, remove_dir_all, remove_file, write};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use rpfm_error::ErrorKind;
 
-use super::PackFile;
+use crate::games::supported_games::{KEY_ARENA, KEY_EMPIRE, KEY_THREE_KINGDOMS, KEY_TROY, KEY_WARHAMMER_2, KEY_WARHAMMER_3};
+use crate::packedfile::{DecodedPackedFile, PackedFileType};
+use crate::packedfile::table::{DecodedData, Delimiter};
+use crate::packedfile::table::animtable::AnimTable;
+use crate::packedfile::table::loc::Loc;
+use crate::schema::FieldType;
+use crate::test_helpers::*;
+use crate::{SETTINGS, SUPPORTED_GAMES};
+
+use super::{PackFile, PathType, PFHFileType, PFHFlags, PFHVersion, SortMode, ValidationIssue, RESERVED_NAME_NOTES};
+use super::packedfile::{PackedFile, PackedFileData, RawPackedFile};
 
 #[test]
 fn test_decode_pfh6() {
     assert_eq!(PackFile::read(&PathBuf::from("../test_files/PFH6_test.pack"), false).is_ok(), true);
 }
 
+#[test]
+fn test_read_mmap() {
+    let path = PathBuf::from("../test_files/PFH5_test.pack");
+    let mut pack_file = PackFile::read(&path, false).unwrap();
+    // SAFETY: Nothing modifies the test PackFiles while the tests run.
+    let mut pack_file_mmap = unsafe { PackFile::read_mmap(&path).unwrap() };
+    assert_eq!(pack_file.content_hash().unwrap(), pack_file_mmap.content_hash().unwrap());
+}
+
+#[test]
+fn test_copy_to_writer() {
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), true).unwrap();
+    for packed_file in pack_file.get_ref_packed_files_all() {
+        if let PackedFileData::OnDisk(raw_on_disk) = packed_file.get_ref_raw_inner_data() {
+            let mut data = vec![];
+            raw_on_disk.copy_to_writer(&mut data).unwrap();
+            assert_eq!(data, packed_file.get_raw_data().unwrap());
+        } else { panic!("The PackedFile is not lazy-loaded.") }
+    }
+}
+
 #[test]
 fn test_decode_pfh5() {
     assert_eq!(PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).is_ok(), true);
@@ -62,6 +101,19 @@ fn test_encode_pfh6() {
     assert_eq!(pack_file_base, pack_file_new);
 }
 
+#[test]
+fn test_encode_pfh6_subheader() {
+
+    // The subheader (from the subheader mark to the end of the extra data) must be written back byte-identically.
+    let mut pack_file = PackFile::read(&PathBuf::from("../test_files/PFH6_test.pack"), false).unwrap();
+    pack_file.save(Some(PathBuf::from("../test_files/PFH6_test_subheader.pack"))).unwrap();
+
+    let data_base = read("../test_files/PFH6_test.pack").unwrap();
+    let data_new = read("../test_files/PFH6_test_subheader.pack").unwrap();
+    assert_eq!(data_base[..4], data_new[..4]);
+    assert_eq!(data_base[28..308], data_new[28..308]);
+}
+
 #[test]
 fn test_encode_pfh5() {
 
@@ -142,3 +194,729 @@ fn test_encode_pfh0() {
 
 	assert_eq!(pack_file_base, pack_file_new);
 }
+
+#[test]
+fn test_classify_paths() {
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    let paths = vec![
+        vec![],
+        vec!["test.lua".to_owned()],
+        vec!["text".to_owned(), "db".to_owned()],
+        vec!["not_here".to_owned()],
+    ];
+
+    assert_eq!(pack_file.classify_paths(&paths), vec![
+        PathType::PackFile,
+        PathType::File(vec!["test.lua".to_owned()]),
+        PathType::Folder(vec!["text".to_owned(), "db".to_owned()]),
+        PathType::None,
+    ]);
+}
+
+#[test]
+fn test_validate() {
+    let mut pack_file = PackFile::new();
+    let paths = vec![
+        vec!["script".to_owned(), "test.lua".to_owned()],
+        vec!["Script".to_owned(), "TEST.lua".to_owned()],
+        vec!["script".to_owned(), "".to_owned(), "empty.lua".to_owned()],
+        vec![RESERVED_NAME_NOTES.to_owned()],
+        vec!["text".to_owned(), "db".to_owned(), "broken.loc".to_owned()],
+        vec!["variantmeshes".to_owned(), "broken.variantmeshdefinition".to_owned()],
+        vec!["variantmeshes".to_owned(), "valid.variantmeshdefinition".to_owned()],
+    ];
+
+    for path in &paths {
+        let data = if path.last().unwrap() == "valid.variantmeshdefinition" { b"<VARIANT_MESH><SLOT/></VARIANT_MESH>".to_vec() } else { vec![] };
+        let raw_data = RawPackedFile::read_from_vec(path.to_vec(), pack_file.get_file_name(), 0, false, data);
+        pack_file.packed_files.push(PackedFile::new_from_raw(&raw_data));
+    }
+
+    assert_eq!(pack_file.validate(), vec![
+        ValidationIssue::DuplicatedPath(paths[1].to_vec()),
+        ValidationIssue::EmptyPathSegment(paths[2].to_vec()),
+        ValidationIssue::ReservedName(paths[3].to_vec()),
+        ValidationIssue::TypeMismatch(paths[4].to_vec(), PackedFileType::Loc),
+        ValidationIssue::InvalidVariantMeshDefinition(paths[5].to_vec(), ErrorKind::VariantMeshDefinitionInvalidXML(1, 1, "Unexpected end of file.".to_owned())),
+    ]);
+
+    // With the setting enabled, saving must fail reporting all the issues, without writing anything.
+    let path = temp_dir().join("rpfm_test_validate.pack");
+    SETTINGS.write().unwrap().settings_bool.insert("validate_packfile_before_save".to_owned(), true);
+    let result = pack_file.save(Some(path.to_path_buf()));
+    SETTINGS.write().unwrap().settings_bool.insert("validate_packfile_before_save".to_owned(), false);
+
+    match result.unwrap_err().kind() {
+        ErrorKind::PackFileValidationFailed(issues) => assert_eq!(issues.len(), 5),
+        _ => panic!("The PackFile has not been validated before saving."),
+    }
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_read_split() {
+    let data = read("../test_files/PFH5_test.pack").unwrap();
+    let parts = vec![
+        PathBuf::from("../test_files/PFH5_test_split.pack.001"),
+        PathBuf::from("../test_files/PFH5_test_split.pack.002"),
+        PathBuf::from("../test_files/PFH5_test_split.pack.003"),
+    ];
+
+    let part_size = data.len() / 3 + 1;
+    for (part, chunk) in parts.iter().zip(data.chunks(part_size)) {
+        write(part, chunk).unwrap();
+    }
+
+    let pack_file_base = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    let mut pack_file_split = PackFile::read_split(&parts).unwrap();
+    assert_eq!(pack_file_split.get_file_path(), &PathBuf::from("../test_files/PFH5_test_split.pack"));
+
+    pack_file_split.set_file_path(&PathBuf::from("../test_files/PFH5_test.pack")).unwrap();
+    assert_eq!(pack_file_base, pack_file_split);
+
+    // Missing and misplaced parts must fail.
+    assert!(PackFile::read_split(&parts[..2]).is_err());
+    assert!(PackFile::read_split(&[parts[1].to_path_buf(), parts[0].to_path_buf(), parts[2].to_path_buf()]).is_err());
+}
+
+#[test]
+fn test_packfiles_list_round_trip() {
+
+    // The dependency list must survive a save-read cycle untouched, order included, as the game uses it for load order.
+    let dependencies = vec![
+        "z_last_alphabetically.pack".to_owned(),
+        "a_first_alphabetically.pack".to_owned(),
+        "m_middle.pack".to_owned(),
+    ];
+
+    let mut pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    pack_file.set_packfiles_list(&dependencies);
+    pack_file.save(Some(PathBuf::from("../test_files/PFH5_test_dependencies.pack"))).unwrap();
+
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test_dependencies.pack"), false).unwrap();
+    assert_eq!(pack_file.get_packfiles_list(), &dependencies[..]);
+}
+
+#[test]
+fn test_notes_round_trip() {
+    let notes = "v1.2: fixed the missing references.".to_owned();
+
+    let mut pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    pack_file.set_notes(Some(notes.clone()));
+    pack_file.save(Some(PathBuf::from("../test_files/PFH5_test_notes.pack"))).unwrap();
+
+    // The reserved PackedFile must only exist in the file on disk, not in the PackFile after saving.
+    assert!(pack_file.get_ref_packed_file_by_path(&[RESERVED_NAME_NOTES.to_owned()]).is_none());
+
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test_notes.pack"), false).unwrap();
+    assert_eq!(pack_file.get_notes(), Some(&*notes));
+    assert!(pack_file.get_ref_packed_file_by_path(&[RESERVED_NAME_NOTES.to_owned()]).is_none());
+}
+
+#[test]
+fn test_set_flag() {
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    pack_file.set_flag(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS, true).unwrap();
+    assert_eq!(pack_file.get_bitmask().get_names(), vec!["HAS_INDEX_WITH_TIMESTAMPS"]);
+
+    pack_file.set_flag(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS, false).unwrap();
+    assert!(pack_file.get_bitmask().is_empty());
+
+    // We cannot save encrypted PackFiles, and PFH0 PackFiles have no bitmask.
+    assert!(pack_file.set_flag(PFHFlags::HAS_ENCRYPTED_DATA, true).is_err());
+    assert!(PackFile::new_with_name("test.pack", PFHVersion::PFH0).set_flag(PFHFlags::HAS_INDEX_WITH_TIMESTAMPS, true).is_err());
+}
+
+#[test]
+fn test_read_and_save_with_progress() {
+    let reported = std::cell::RefCell::new(vec![]);
+    let progress = |done, total| reported.borrow_mut().push((done, total));
+
+    let mut pack_file = PackFile::read_with_progress(&PathBuf::from("../test_files/PFH5_test.pack"), false, Some(&progress)).unwrap();
+    let len = read("../test_files/PFH5_test.pack").unwrap().len() as u64;
+    assert_eq!(reported.borrow().last(), Some(&(len, len)));
+
+    // Progress must never go backwards, and saving must end with everything written.
+    reported.borrow_mut().clear();
+    pack_file.save_with_progress(Some(PathBuf::from("../test_files/PFH5_test_progress.pack")), Some(&progress)).unwrap();
+    assert!(reported.borrow().windows(2).all(|x| x[0].0 <= x[1].0));
+
+    // The reserved PackedFiles are also written, so the total must cover at least the size of every PackedFile in the PackFile.
+    let (done, total) = *reported.borrow().last().unwrap();
+    assert_eq!(done, total);
+    assert!(total >= pack_file.get_ref_packed_files_all().iter().map(|x| u64::from(x.get_ref_raw().get_size())).sum::<u64>());
+}
+
+#[test]
+fn test_read_ignoring_extension() {
+    let path = temp_dir().join("rpfm_test_read_ignoring_extension.bin");
+    write(&path, read("../test_files/PFH5_test.pack").unwrap()).unwrap();
+    let result = PackFile::read(&path, false);
+    let result_ignoring_extension = PackFile::read_ignoring_extension(&path, false);
+
+    // Even ignoring the extension, files that are not PackFiles must fail.
+    write(&path, vec![0; 64]).unwrap();
+    let result_not_a_packfile = PackFile::read_ignoring_extension(&path, false);
+
+    // Clean up before checking anything, so a failed check doesn't leave the file behind.
+    remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+    assert!(result_ignoring_extension.is_ok());
+    assert!(result_not_a_packfile.is_err());
+}
+
+#[test]
+fn test_read_from_vec() {
+    let data = read("../test_files/PFH5_test.pack").unwrap();
+    let pack_file_base = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    let mut pack_file_memory = PackFile::read_from_vec(data, "PFH5_test.pack").unwrap();
+    assert_eq!(pack_file_memory.get_file_path(), &PathBuf::from("PFH5_test.pack"));
+
+    pack_file_memory.set_file_path(&PathBuf::from("../test_files/PFH5_test.pack")).unwrap();
+    assert_eq!(pack_file_base, pack_file_memory);
+
+    assert!(PackFile::read_from_vec(vec![0; 64], "PFH5_test.bin").is_err());
+}
+
+#[test]
+fn test_mass_import_tsv_cancelled() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true)]);
+    let schema = schema_with_db(&definition);
+
+    let folder = temp_dir().join("rpfm_test_mass_import_tsv_cancelled");
+    create_dir_all(&folder).unwrap();
+
+    let db = db(&definition, &[vec![DecodedData::StringU8("key_1".to_owned())]]);
+    let tsv_paths = (0..3).map(|index| {
+        let path = folder.join(format!("table_{}.tsv", index));
+        let mut data = vec![];
+        db.export_tsv_to_writer(&mut data, TEST_TABLE_NAME, &["db".to_owned(), TEST_TABLE_NAME.to_owned(), format!("table_{}", index)], Delimiter::Tab, false).unwrap();
+        write(&path, &data).unwrap();
+        path
+    }).collect::<Vec<PathBuf>>();
+
+    // Cancelling from another thread, like the UI does, must stop the import without touching the PackFile.
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let cancel_ui = cancel.clone();
+    thread::spawn(move || cancel_ui.store(true, Ordering::SeqCst)).join().unwrap();
+    let cancelled = pack_file.mass_import_tsv_with_schema(&schema, &tsv_paths, None, true, &cancel);
+    let untouched = pack_file.get_ref_packed_files_all().is_empty();
+
+    cancel.store(false, Ordering::SeqCst);
+    let imported = pack_file.mass_import_tsv_with_schema(&schema, &tsv_paths, None, true, &cancel);
+    remove_dir_all(&folder).unwrap();
+
+    assert!(matches!(cancelled.unwrap_err().kind(), ErrorKind::OperationCancelled));
+    assert!(untouched);
+
+    let (_, added) = imported.unwrap();
+    assert_eq!(added.len(), 3);
+    assert_eq!(pack_file.get_ref_packed_files_all().len(), 3);
+}
+
+#[test]
+fn test_read_with_types() {
+    let types_to_load = Some(vec![PackedFileType::DB, PackedFileType::Loc]);
+    let pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    let pack_file_filtered = PackFile::read_with_types(&PathBuf::from("../test_files/PFH5_test.pack"), false, &types_to_load).unwrap();
+
+    // Only the PackedFiles of the requested types must be loaded, and all of them must be there.
+    let expected = pack_file.get_ref_packed_files_by_types(&[PackedFileType::DB, PackedFileType::Loc], false).len();
+    assert_eq!(pack_file_filtered.get_packedfiles_list().len(), expected);
+    assert!(pack_file_filtered.get_ref_packed_files_all().iter().all(|x| x.get_packed_file_type(false).eq_non_strict_slice(&[PackedFileType::DB, PackedFileType::Loc])));
+}
+
+#[test]
+fn test_extract_by_path_start() {
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for path in [&["text", "a.txt"][..], &["text", "sub", "b.txt"], &["text_other", "c.txt"], &["d.txt"]] {
+        let path = path.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        pack_file.add_packed_file(&PackedFile::new_from_raw(&RawPackedFile::read_from_vec(path, "test.pack".to_owned(), 0, false, vec![1, 2, 3])), true).unwrap();
+    }
+    let prefix = vec!["text".to_owned()];
+
+    let destination = temp_dir().join("rpfm_test_extract_by_path_start");
+    let _ = remove_dir_all(&destination);
+    let mut extracted = pack_file.extract_by_path_start(&prefix, &destination, true).unwrap();
+    let extracted_files_exist = extracted.iter().all(|path| read(destination.join(path)).ok() == Some(vec![1, 2, 3]));
+
+    // Without the structure, everything ends up directly in the destination folder.
+    let destination_flat = temp_dir().join("rpfm_test_extract_by_path_start_flat");
+    let _ = remove_dir_all(&destination_flat);
+    let mut extracted_flat = pack_file.extract_by_path_start(&prefix, &destination_flat, false).unwrap();
+    let extracted_flat_files_exist = extracted_flat.iter().all(|path| destination_flat.join(path).is_file());
+
+    // Clean up before checking anything, so a failed check doesn't leave the folders behind.
+    let _ = remove_dir_all(&destination);
+    let _ = remove_dir_all(&destination_flat);
+
+    extracted.sort();
+    extracted_flat.sort();
+    assert_eq!(extracted, vec!["text/a.txt".to_owned(), "text/sub/b.txt".to_owned()]);
+    assert_eq!(extracted_flat, vec!["a.txt".to_owned(), "b.txt".to_owned()]);
+    assert!(extracted_files_exist);
+    assert!(extracted_flat_files_exist);
+}
+
+#[test]
+fn test_content_hash() {
+    let mut pack_file_a = PackFile::new_with_name("test_a.pack", PFHVersion::PFH5);
+    let mut pack_file_b = PackFile::new_with_name("test_b.pack", PFHVersion::PFH5);
+    let file_1 = PackedFile::new_from_raw(&RawPackedFile::read_from_vec(vec!["text".to_owned(), "a.txt".to_owned()], "test_a.pack".to_owned(), 1, false, vec![1, 2, 3]));
+    let file_2 = PackedFile::new_from_raw(&RawPackedFile::read_from_vec(vec!["text".to_owned(), "b.txt".to_owned()], "test_a.pack".to_owned(), 2, false, vec![4, 5, 6]));
+
+    // Neither the order of the files nor the timestamps should change the hash.
+    pack_file_a.add_packed_file(&file_1, true).unwrap();
+    pack_file_a.add_packed_file(&file_2, true).unwrap();
+    pack_file_b.add_packed_file(&file_2, true).unwrap();
+    pack_file_b.add_packed_file(&file_1, true).unwrap();
+    pack_file_b.set_timestamp(1234);
+    assert_eq!(pack_file_a.content_hash().unwrap(), pack_file_b.content_hash().unwrap());
+
+    // The algorithm is fixed, so the hash must not change between builds.
+    assert_eq!(pack_file_a.content_hash().unwrap(), "4e2669fb181227b0");
+
+    let file_3 = PackedFile::new_from_raw(&RawPackedFile::read_from_vec(vec!["text".to_owned(), "b.txt".to_owned()], "test_a.pack".to_owned(), 2, false, vec![4, 5, 7]));
+    pack_file_b.add_packed_file(&file_3, true).unwrap();
+    assert_ne!(pack_file_a.content_hash().unwrap(), pack_file_b.content_hash().unwrap());
+}
+
+#[test]
+fn test_search_and_replace() {
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false), field("enabled", FieldType::Boolean, false)]);
+    let schema = schema_with_db(&definition);
+    let db = db(&definition, &[
+        vec![DecodedData::StringU8("old_unit".to_owned()), DecodedData::I32(10), DecodedData::Boolean(true)],
+        vec![DecodedData::StringU8("old_lord".to_owned()), DecodedData::I32(1), DecodedData::Boolean(false)],
+    ]);
+
+    let path = vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), "test".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(db), &path), true).unwrap();
+
+    // Number and boolean cells must be left alone, even if their text contains the pattern.
+    assert_eq!(pack_file.search_and_replace(&schema, "1", "2", None, false).unwrap(), 0);
+    assert_eq!(pack_file.search_and_replace(&schema, "e", "E", Some(&["enabled".to_owned()]), false).unwrap(), 0);
+
+    assert_eq!(pack_file.search_and_replace(&schema, "^old_(.*)$", "new_$1", None, true).unwrap(), 2);
+    assert_eq!(pack_file.search_and_replace(&schema, "e", "E", None, false).unwrap(), 2);
+    if let Ok(DecodedPackedFile::DB(table)) = pack_file.get_ref_mut_packed_file_by_path(&path).unwrap().decode_return_ref_no_locks(&schema) {
+        assert_eq!(table.get_ref_table_data(), &[
+            vec![DecodedData::StringU8("nEw_unit".to_owned()), DecodedData::I32(10), DecodedData::Boolean(true)],
+            vec![DecodedData::StringU8("nEw_lord".to_owned()), DecodedData::I32(1), DecodedData::Boolean(false)],
+        ]);
+    } else { panic!("The table is not decoded.") }
+}
+
+#[test]
+fn test_save_deterministic_timestamp() {
+    let path_a = temp_dir().join("rpfm_test_save_deterministic_timestamp_a.pack");
+    let path_b = temp_dir().join("rpfm_test_save_deterministic_timestamp_b.pack");
+    let mut pack_file = PackFile::read(&PathBuf::from("../test_files/PFH5_test.pack"), false).unwrap();
+    pack_file.set_timestamp(1234);
+    pack_file.set_deterministic_timestamp(true);
+
+    // Saving twice must keep the timestamp and produce the exact same bytes.
+    pack_file.save(Some(path_a.clone())).unwrap();
+    pack_file.save(Some(path_b.clone())).unwrap();
+    let data_a = read(&path_a).unwrap();
+    let data_b = read(&path_b).unwrap();
+    let timestamp_a = PackFile::read(&path_a, false).unwrap().get_timestamp();
+
+    // Clean up before checking anything, so a failed check doesn't leave the files behind.
+    remove_file(&path_a).unwrap();
+    remove_file(&path_b).unwrap();
+
+    assert_eq!(pack_file.get_timestamp(), 1234);
+    assert_eq!(data_a, data_b);
+    assert_eq!(timestamp_a, 1234);
+}
+
+#[test]
+fn test_table_summary() {
+    let definition_v1 = definition(1, vec![field("key", FieldType::StringU8, true)]);
+    let definition_v5 = definition(5, vec![field("key", FieldType::StringU8, true)]);
+    let schema = schema_with_db(&definition_v1);
+    let row = |key: &str| vec![DecodedData::StringU8(key.to_owned())];
+
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for (name, table) in [("a", db(&definition_v1, &[row("a"), row("b")])), ("b", db(&definition_v1, &[row("c")])), ("c", db(&definition_v5, &[row("d")]))] {
+        let path = vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), name.to_owned()];
+        pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &path), true).unwrap();
+    }
+
+    // Read it back lazily, so only the headers of the tables are read from disk.
+    let path = temp_dir().join("rpfm_test_table_summary.pack");
+    pack_file.save(Some(path.to_path_buf())).unwrap();
+    let pack_file = PackFile::read(&path, true).unwrap();
+
+    let raw = pack_file.get_ref_packed_file_by_path(&["db".to_owned(), TEST_TABLE_NAME.to_owned(), "a".to_owned()]).unwrap().get_ref_raw();
+    assert_eq!(raw.get_data_start(8).unwrap(), raw.get_data().unwrap()[..8].to_vec());
+
+    let summary = pack_file.table_summary(&schema);
+    remove_file(&path).unwrap();
+    assert_eq!(summary, vec![
+        (TEST_TABLE_NAME.to_owned(), "1".to_owned(), 3),
+        (TEST_TABLE_NAME.to_owned(), "5 (no definition)".to_owned(), 1),
+    ]);
+}
+
+#[test]
+fn test_get_decompressed_size() {
+    let path = vec!["text".to_owned(), "a.txt".to_owned()];
+    let raw = RawPackedFile::read_from_vec(path.to_vec(), "test.pack".to_owned(), 0, false, vec![1, 2, 3]);
+    assert_eq!(raw.get_decompressed_size().unwrap(), 3);
+
+    // For compressed data, the size is the one in the header, and incomplete headers are an error.
+    let raw = RawPackedFile::read_from_vec(path.to_vec(), "test.pack".to_owned(), 0, true, vec![1, 2, 3]);
+    assert!(raw.get_decompressed_size().is_err());
+
+    let raw = RawPackedFile::read_from_vec(path, "test.pack".to_owned(), 0, true, vec![0, 1, 0, 0, 93, 0, 0, 1, 0, 1, 2]);
+    assert_eq!(raw.get_decompressed_size().unwrap(), 256);
+}
+
+#[test]
+fn test_split_by_size() {
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for (name, size) in [("c.txt", 6), ("B.txt", 4), ("a.txt", 3), ("D.txt", 2), ("E.txt", 12)] {
+        let raw_data = RawPackedFile::read_from_vec(vec!["text".to_owned(), name.to_owned()], pack_file.get_file_name(), 0, false, vec![0; size]);
+        pack_file.packed_files.push(PackedFile::new_from_raw(&raw_data));
+    }
+
+    // Each part must be under the limit unless it's a single file, and follow the order of the sort mode of the PackFile.
+    let parts = |pack_file: &mut PackFile| pack_file.split_by_size(8).unwrap().iter().map(|part| {
+        let size = part.get_ref_packed_files_all().iter().map(|x| x.get_ref_raw().get_size()).sum::<u32>();
+        assert!(size <= 8 || part.get_ref_packed_files_all().len() == 1);
+        part.get_ref_packed_files_all().iter().map(|x| x.get_path()[1].to_owned()).collect::<Vec<String>>()
+    }).collect::<Vec<Vec<String>>>();
+
+    assert_eq!(parts(&mut pack_file), vec![vec!["a.txt", "B.txt"], vec!["c.txt", "D.txt"], vec!["E.txt"]]);
+
+    pack_file.set_sort_mode(SortMode::CaseSensitivePath);
+    assert_eq!(parts(&mut pack_file), vec![vec!["B.txt", "D.txt"], vec!["E.txt"], vec!["a.txt"], vec!["c.txt"]]);
+
+    pack_file.set_sort_mode(SortMode::Insertion);
+    assert_eq!(parts(&mut pack_file), vec![vec!["c.txt"], vec!["B.txt", "a.txt"], vec!["D.txt"], vec!["E.txt"]]);
+
+    assert!(pack_file.split_by_size(0).is_err());
+}
+
+#[test]
+fn test_rename_entity_key() {
+    let definition_composite = definition(1, vec![
+        field("key_a", FieldType::StringU8, true),
+        field("key_b", FieldType::I32, true),
+        field("value", FieldType::I32, false),
+    ]);
+    let schema = schema_with_db(&definition_composite);
+
+    let row = |key_a: &str, key_b, value| vec![DecodedData::StringU8(key_a.to_owned()), DecodedData::I32(key_b), DecodedData::I32(value)];
+    let table = db(&definition_composite, &[row("a", 1, 10), row("a", 2, 20), row("b", 1, 30)]);
+
+    let definition_loc = definition(1, vec![
+        field("key", FieldType::StringU16, true),
+        field("text", FieldType::StringU16, false),
+        field("tooltip", FieldType::Boolean, false),
+    ]);
+    let loc_row = |key: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16(String::new()), DecodedData::Boolean(false)];
+    let mut loc = Loc::new(&definition_loc);
+    loc.set_table_data(&[loc_row("test_name_a"), loc_row("test_name_b")]).unwrap();
+
+    let db_path = vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), "table".to_owned()];
+    let loc_path = vec!["text".to_owned(), "db".to_owned(), "test.loc".to_owned()];
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &db_path), true).unwrap();
+    pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::Loc(loc), &loc_path), true).unwrap();
+
+    // Only the row matching all the keys must be renamed, in place.
+    let edited_paths = pack_file.rename_entity_key(TEST_TABLE_NAME, &[("a", "c"), ("1", "3")], &schema).unwrap();
+    assert_eq!(edited_paths, vec![db_path.to_vec()]);
+
+    match pack_file.get_ref_mut_packed_file_by_path(&db_path).unwrap().decode_return_ref().unwrap() {
+        DecodedPackedFile::DB(table) => assert_eq!(table.get_ref_table_data(), &[row("c", 3, 10), row("a", 2, 20), row("b", 1, 30)]),
+        _ => panic!("The table is not a DB."),
+    }
+
+    // Keys that don't cover all the key columns of the table, keys of the wrong type, and composite keys
+    // in tables with localised fields must fail without renaming anything.
+    let mut definition_localised = definition_composite.clone();
+    definition_localised.get_ref_mut_localised_fields().push(field("name", FieldType::StringU16, false));
+    let schema_localised = schema_with_db(&definition_localised);
+
+    assert!(matches!(pack_file.rename_entity_key(TEST_TABLE_NAME, &[("a", "d")], &schema_localised).unwrap_err().kind(), ErrorKind::DBTableRenameWrongKeyCount(2, 1)));
+    assert!(pack_file.rename_entity_key(TEST_TABLE_NAME, &[("a", "d"), ("2", "two")], &schema).is_err());
+    assert!(matches!(pack_file.rename_entity_key(TEST_TABLE_NAME, &[("a", "d"), ("2", "4")], &schema_localised).unwrap_err().kind(), ErrorKind::DBTableRenameCompositeKeyLocalised));
+
+    match pack_file.get_ref_mut_packed_file_by_path(&db_path).unwrap().decode_return_ref().unwrap() {
+        DecodedPackedFile::DB(table) => assert_eq!(table.get_ref_table_data(), &[row("c", 3, 10), row("a", 2, 20), row("b", 1, 30)]),
+        _ => panic!("The table is not a DB."),
+    }
+
+    match pack_file.get_ref_mut_packed_file_by_path(&loc_path).unwrap().decode_return_ref().unwrap() {
+        DecodedPackedFile::Loc(loc) => assert_eq!(loc.get_ref_table_data(), &[loc_row("test_name_a"), loc_row("test_name_b")]),
+        _ => panic!("The table is not a Loc."),
+    }
+
+    // Single keys in tables with localised fields must rename their loc keys too.
+    let definition_single = definition(1, vec![field("key", FieldType::StringU8, true)]);
+    let mut definition_single_localised = definition_single.clone();
+    definition_single_localised.get_ref_mut_localised_fields().push(field("name", FieldType::StringU16, false));
+    let schema_single = schema_with_db(&definition_single_localised);
+
+    pack_file.remove_packed_file_by_path(&db_path);
+    pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::DB(db(&definition_single, &[vec![DecodedData::StringU8("a".to_owned())]])), &db_path), true).unwrap();
+
+    let edited_paths = pack_file.rename_entity_key(TEST_TABLE_NAME, &[("a", "c")], &schema_single).unwrap();
+    assert_eq!(edited_paths, vec![db_path.to_vec(), loc_path.to_vec()]);
+
+    match pack_file.get_ref_mut_packed_file_by_path(&loc_path).unwrap().decode_return_ref().unwrap() {
+        DecodedPackedFile::Loc(loc) => assert_eq!(loc.get_ref_table_data(), &[loc_row("test_name_c"), loc_row("test_name_b")]),
+        _ => panic!("The table is not a Loc."),
+    }
+}
+
+#[test]
+fn test_guess_game() {
+    let wrong_definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false), field("extra", FieldType::I64, false)]);
+    let definition = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let db = db(&definition, &[vec![DecodedData::StringU8("unit".to_owned()), DecodedData::I32(10)]]);
+
+    let path = vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), "test".to_owned()];
+    let mut packed_file = PackedFile::new_from_decoded(&DecodedPackedFile::DB(db), &path);
+    packed_file.encode().unwrap();
+
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    pack_file.add_packed_file(&packed_file, true).unwrap();
+
+    // The game whose schema decodes the table goes first, then the ones whose schema doesn't, then the ones without schema.
+    // Games not using PFH5 for any of their PackFile types must not be returned at all.
+    let games = pack_file.guess_game_with_schemas(|schema_name| match schema_name {
+        "schema_wh2.ron" => Some(schema_with_db(&definition)),
+        "schema_3k.ron" => Some(schema_with_db(&wrong_definition)),
+        _ => None,
+    });
+
+    assert_eq!(games[0], KEY_WARHAMMER_2);
+    assert_eq!(games[1], KEY_THREE_KINGDOMS);
+    assert!(games[2..].contains(&KEY_WARHAMMER_3));
+    assert!(games[2..].contains(&KEY_ARENA));
+    assert!(!games.contains(&KEY_EMPIRE));
+}
+
+#[test]
+fn test_table_coverage() {
+    let definition_v1 = definition(1, vec![field("key", FieldType::StringU8, true)]);
+    let definition_v5 = definition(5, vec![field("key", FieldType::StringU8, true)]);
+    let schema = schema_with_db(&definition_v1);
+    let row = |key: &str| vec![DecodedData::StringU8(key.to_owned())];
+
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for (name, table) in [("b", db(&definition_v5, &[row("b")])), ("a", db(&definition_v1, &[row("a")]))] {
+        let mut packed_file = PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &["db".to_owned(), TEST_TABLE_NAME.to_owned(), name.to_owned()]);
+        packed_file.encode().unwrap();
+        pack_file.add_packed_file(&packed_file, true).unwrap();
+    }
+
+    // Tables are sorted by path, and only the ones that fail to decode have an error.
+    let coverage = pack_file.table_coverage(&schema);
+    assert_eq!(coverage.iter().map(|(path, _)| path.as_str()).collect::<Vec<&str>>(), vec![
+        format!("db/{}/a", TEST_TABLE_NAME),
+        format!("db/{}/b", TEST_TABLE_NAME),
+    ]);
+    assert!(coverage[0].1.is_none());
+    assert_eq!(coverage[1].1, Some(ErrorKind::SchemaDefinitionNotFound));
+}
+
+#[test]
+fn test_verify_all_checksums() {
+
+    // "rpfm" 16 times, compressed the same way CA compresses their files: a CA LZMA header (decompressed size, props and dictionary size)
+    // followed by a LZMA1 stream without end marker.
+    let data = b"rpfm".repeat(16);
+    let compressed = vec![0x40, 0x00, 0x00, 0x00, 0x5d, 0x00, 0x00, 0x40, 0x00, 0x00, 0x39, 0x1c, 0x08, 0xf0, 0x85, 0x63, 0x34, 0xa3, 0x20, 0x00];
+    let mut wrong_size = compressed.to_vec();
+    wrong_size[0] = 0x20;
+
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for (name, data, is_compressed) in [
+        ("plain", data.to_vec(), false),
+        ("compressed", compressed.to_vec(), true),
+        ("wrong_size", wrong_size, true),
+        ("truncated", compressed[..15].to_vec(), true),
+        ("garbage", vec![1; 32], true),
+    ] {
+        let raw = RawPackedFile::read_from_vec(vec![name.to_owned()], "test.pack".to_owned(), 0, is_compressed, data);
+        pack_file.add_packed_file(&PackedFile::new_from_raw(&raw), true).unwrap();
+    }
+
+    assert_eq!(pack_file.get_ref_packed_file_by_path(&["compressed".to_owned()]).unwrap().get_ref_raw().get_data().unwrap(), data);
+    assert_eq!(pack_file.verify_all_checksums(), vec![
+        vec!["garbage".to_owned()],
+        vec!["truncated".to_owned()],
+        vec!["wrong_size".to_owned()],
+    ]);
+}
+
+#[test]
+fn test_sort_mode() {
+    let path = temp_dir().join("rpfm_test_sort_mode.pack");
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for name in ["c.txt", "B.txt", "a.txt"] {
+        let raw_data = RawPackedFile::read_from_vec(vec!["text".to_owned(), name.to_owned()], pack_file.get_file_name(), 0, false, vec![0; 4]);
+        pack_file.packed_files.push(PackedFile::new_from_raw(&raw_data));
+    }
+
+    // The order on disk must follow the sort mode, and the sort mode must be saved within the PackFile.
+    let mut saved = vec![];
+    for sort_mode in [SortMode::Insertion, SortMode::CaseSensitivePath, SortMode::CaseInsensitivePath] {
+        pack_file.set_sort_mode(sort_mode);
+        pack_file.save(Some(path.to_path_buf())).unwrap();
+
+        let pack_file = PackFile::read(&path, false).unwrap();
+        let names = pack_file.get_ref_packed_files_all().iter()
+            .filter(|packed_file| packed_file.get_path()[0] == "text")
+            .map(|packed_file| packed_file.get_path()[1].to_owned())
+            .collect::<Vec<String>>();
+        saved.push((pack_file.get_sort_mode(), names));
+    }
+    remove_file(&path).unwrap();
+
+    assert_eq!(saved, vec![
+        (SortMode::Insertion, vec!["c.txt".to_owned(), "B.txt".to_owned(), "a.txt".to_owned()]),
+        (SortMode::CaseSensitivePath, vec!["B.txt".to_owned(), "a.txt".to_owned(), "c.txt".to_owned()]),
+        (SortMode::CaseInsensitivePath, vec!["a.txt".to_owned(), "B.txt".to_owned(), "c.txt".to_owned()]),
+    ]);
+    assert_eq!(SortMode::get_sort_mode("not_a_sort_mode"), SortMode::CaseInsensitivePath);
+}
+
+#[test]
+fn test_open_all_ca_packfiles_for_game() {
+    let game = SUPPORTED_GAMES.get_supported_game_from_key(KEY_TROY).unwrap();
+    let game_path = temp_dir().join("rpfm_test_open_all_ca_packfiles_for_game");
+    let data_path = game_path.join("data");
+    let _ = remove_dir_all(&game_path);
+    create_dir_all(&data_path).unwrap();
+
+    // Without a path for the game, there is nothing to open.
+    SETTINGS.write().unwrap().paths.insert(game.get_game_key_name(), None);
+    let result_not_configured = PackFile::open_all_ca_packfiles_for_game(game);
+
+    // A fake install with a Release and a Patch PackFile, both with a shared file. CA PackFiles can't be saved, so we save them as mods and patch the type in the header.
+    for (name, pfh_file_type) in [("data.pack", PFHFileType::Release), ("patch.pack", PFHFileType::Patch)] {
+        let path = data_path.join(name);
+        let mut pack_file = PackFile::new_with_name(name, PFHVersion::PFH5);
+        for file_name in ["shared.txt".to_owned(), format!("{}.txt", name)] {
+            let raw_data = RawPackedFile::read_from_vec(vec!["text".to_owned(), file_name], name.to_owned(), 0, false, name.as_bytes().to_vec());
+            pack_file.add_packed_file(&PackedFile::new_from_raw(&raw_data), true).unwrap();
+        }
+        pack_file.save(Some(path.to_path_buf())).unwrap();
+
+        let mut data = read(&path).unwrap();
+        data[4..8].copy_from_slice(&pfh_file_type.get_value().to_le_bytes());
+        write(&path, data).unwrap();
+    }
+    write(data_path.join("manifest.txt"), "data.pack\t1024\t1\npatch.pack\t1024\t1\n").unwrap();
+    write(data_path.join("language.txt"), "EN").unwrap();
+
+    SETTINGS.write().unwrap().paths.insert(game.get_game_key_name(), Some(game_path.to_path_buf()));
+    let result = PackFile::open_all_ca_packfiles_for_game(game);
+    SETTINGS.write().unwrap().paths.insert(game.get_game_key_name(), None);
+
+    // Clean up before checking anything, so a failed check doesn't leave the folder behind. PackFiles are lazy-loaded, so we need to read them first.
+    let result = result.map(|pack_file| (pack_file.get_file_name(), pack_file.get_ref_packed_files_all().iter()
+        .map(|packed_file| (packed_file.get_path().join("/"), packed_file.get_raw_data().unwrap()))
+        .collect::<Vec<(String, Vec<u8>)>>()
+    ));
+    remove_dir_all(&game_path).unwrap();
+
+    assert_eq!(result_not_configured.unwrap_err().kind(), &ErrorKind::GameSelectedPathNotCorrectlyConfigured);
+
+    // Both PackFiles are merged into one named after the game, with the Patch one taking priority over the Release one.
+    let (name, mut packed_files) = result.unwrap();
+    packed_files.sort();
+    assert_eq!(name, KEY_TROY);
+    assert_eq!(packed_files, vec![
+        ("text/data.pack.txt".to_owned(), b"data.pack".to_vec()),
+        ("text/patch.pack.txt".to_owned(), b"patch.pack".to_vec()),
+        ("text/shared.txt".to_owned(), b"patch.pack".to_vec()),
+    ]);
+}
+
+#[test]
+fn test_get_animtable() {
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    assert_eq!(pack_file.get_animtable().unwrap_err().kind(), &ErrorKind::NoAnimTableInPackFile);
+
+    let definition = definition(2, vec![field("skeleton_type", FieldType::StringU8, false)]);
+    let mut table = AnimTable::new(&definition);
+    table.set_table_data(&[vec![DecodedData::StringU8("humanoid01".to_owned())]]).unwrap();
+
+    // AnimTables are identified by their path: they must be in the animations folder, and end in "_tables.bin".
+    let path = vec!["animations".to_owned(), "animation_tables".to_owned(), "test_tables.bin".to_owned()];
+    pack_file.add_packed_file(&PackedFile::new_from_decoded(&DecodedPackedFile::AnimTable(table.clone()), &path), true).unwrap();
+    assert_eq!(pack_file.get_animtable().unwrap(), table);
+}
+
+#[test]
+fn test_extract_modified_only() {
+    let definition_db = definition(1, vec![field("key", FieldType::StringU8, true), field("value", FieldType::I32, false)]);
+    let schema = schema_with_db(&definition_db);
+    let row = |key: &str, value| vec![DecodedData::StringU8(key.to_owned()), DecodedData::I32(value)];
+    let vanilla_table = db(&definition_db, &[row("a", 1), row("b", 2)]);
+
+    let definition_loc = definition(1, vec![
+        field("key", FieldType::StringU16, true),
+        field("text", FieldType::StringU16, false),
+        field("tooltip", FieldType::Boolean, false),
+    ]);
+    let loc_row = |key: &str| vec![DecodedData::StringU16(key.to_owned()), DecodedData::StringU16("text".to_owned()), DecodedData::Boolean(false)];
+    let loc = |rows: &[Vec<DecodedData>]| {
+        let mut loc = Loc::new(&definition_loc);
+        loc.set_table_data(rows).unwrap();
+        loc
+    };
+
+    let text_path = |name: &str| vec!["text".to_owned(), name.to_owned()];
+    let text = |name: &str, data: &[u8]| PackedFile::new_from_raw(&RawPackedFile::read_from_vec(text_path(name), "test.pack".to_owned(), 0, false, data.to_vec()));
+
+    // Tables with vanilla rows removed but nothing added or changed are not modified.
+    let mut pack_file = PackFile::new_with_name("test.pack", PFHVersion::PFH5);
+    for (name, decoded) in [
+        ("db_same", DecodedPackedFile::DB(db(&definition_db, &[row("a", 1)]))),
+        ("db_changed", DecodedPackedFile::DB(db(&definition_db, &[row("a", 10)]))),
+        ("loc_same.loc", DecodedPackedFile::Loc(loc(&[loc_row("vanilla")]))),
+        ("loc_added.loc", DecodedPackedFile::Loc(loc(&[loc_row("vanilla"), loc_row("new")]))),
+    ] {
+        let path = if name.ends_with(".loc") { text_path(name) } else { vec!["db".to_owned(), TEST_TABLE_NAME.to_owned(), name.to_owned()] };
+        let mut packed_file = PackedFile::new_from_decoded(&decoded, &path);
+        packed_file.encode().unwrap();
+        pack_file.add_packed_file(&packed_file, true).unwrap();
+    }
+    for packed_file in [text("same.txt", b"vanilla"), text("changed.txt", b"mod"), text("new.txt", b"mod")] {
+        pack_file.add_packed_file(&packed_file, true).unwrap();
+    }
+
+    let destination = temp_dir().join("rpfm_test_extract_modified_only");
+    let _ = remove_dir_all(&destination);
+    let extracted = pack_file.extract_modified_only_with(
+        &schema,
+        &destination,
+        Some(loc(&[loc_row("vanilla")])),
+        |table| vanilla_table.diff(table),
+        |path| if path == text_path("same.txt") || path == text_path("changed.txt") { Ok(text("vanilla.txt", b"vanilla")) } else { Err(ErrorKind::PackedFileNotFound.into()) }
+    );
+    let extracted_files_exist = extracted.as_ref().map(|paths| paths.iter().all(|path| destination.join(path).is_file())).unwrap_or(false);
+    let _ = remove_dir_all(&destination);
+
+    let mut extracted = extracted.unwrap();
+    extracted.sort();
+    assert_eq!(extracted, vec![
+        format!("db/{}/db_changed", TEST_TABLE_NAME),
+        "text/changed.txt".to_owned(),
+        "text/loc_added.loc".to_owned(),
+        "text/new.txt".to_owned(),
+    ]);
+    assert!(extracted_files_exist);
+}
@@ -65,24 +65,29 @@ Inside the schema there are `VersionedFile` variants of different types, with a
 
 use git2::{Reference, ReferenceFormat, Repository, Signature, StashFlags, build::CheckoutBuilder};
 use itertools::Itertools;
+use log::warn;
 use rayon::prelude::*;
 use ron::de::from_bytes;
 use ron::ser::{to_string_pretty, PrettyConfig};
 use serde_derive::{Serialize, Deserialize};
 
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::fs::{DirBuilder, File};
 use std::{fmt, fmt::Display};
 use std::io::{BufReader, Read, Write};
+use std::path::Path;
 use std::process::Command as SystemCommand;
 
 use rpfm_error::{Error, ErrorKind, Result};
 
-use crate::assembly_kit::localisable_fields::RawLocalisableField;
+use crate::assembly_kit::{BLACKLISTED_TABLES, get_raw_definition_paths};
+use crate::assembly_kit::localisable_fields::{RawLocalisableField, RawLocalisableFields};
 use crate::assembly_kit::table_definition::{RawDefinition, RawField};
 use crate::common::get_schemas_path;
 use crate::dependencies::Dependencies;
+use crate::games::GameInfo;
+use crate::packedfile::table::db::DB;
 use crate::settings::get_config_path;
 use crate::{SETTINGS, SCHEMA_PATCHES, GAME_SELECTED};
 use crate::SUPPORTED_GAMES;
@@ -94,6 +99,9 @@ pub(crate) mod v1;
 pub(crate) mod v0;
 pub mod patch;
 
+#[cfg(test)]
+mod schema_test;
+
 /// Name of the folder containing all the schemas.
 pub const SCHEMA_FOLDER: &str = "schemas";
 
@@ -225,7 +233,11 @@ pub enum FieldType {
     OptionalStringU8,
     OptionalStringU16,
     SequenceU16(Definition),
-    SequenceU32(Definition)
+    SequenceU32(Definition),
+
+    // New variants go at the end, so bincode-serialized data from older versions keeps its discriminants.
+    ColourRGBA,
+    OptionalI32
 }
 
 /// This enum controls the possible responses from the server when asking if there is a new Schema update.
@@ -428,15 +440,99 @@ impl Schema {
 
         // If there was no coincidence in the dependency database... we risk ourselves getting the last definition we have for
         // that db from the schema.
-        else{
-            let versioned_file = self.get_ref_versioned_file_db(table_name)?;
-            if let VersionedFile::DB(_,definitions) = versioned_file {
-                if let Some(definition) = definitions.get(0) {
-                    Ok(definition)
+        else {
+            self.get_ref_last_definition_db_from_schema(table_name)
+        }
+    }
+
+    /// This function returns the definition with the highest version of a DB Table, using only the schema.
+    ///
+    /// Unlike `get_ref_last_definition_db`, this doesn't check what version the game actually uses, so use that one when you have the dependencies loaded.
+    pub fn get_ref_last_definition_db_from_schema(&self, table_name: &str) -> Result<&Definition> {
+        let versioned_file = self.get_ref_versioned_file_db(table_name)?;
+        if let VersionedFile::DB(_,definitions) = versioned_file {
+            definitions.iter()
+                .max_by_key(|x| x.get_version())
+                .ok_or_else(|| ErrorKind::SchemaDefinitionNotFound.into())
+        } else { Err(ErrorKind::SchemaVersionedFileNotFound.into()) }
+    }
+
+    /// This function tries to guess which pairs of columns of the provided table have been swapped in its definition.
+    ///
+    /// Vanilla files decoded with the same definition would have the same columns swapped, so the table is compared against the vanilla files
+    /// using a different version of its definition. See `detect_swapped_columns_with_references` for how the columns are compared.
+    /// If all the vanilla files use the same version as the table, nothing can be detected.
+    pub fn detect_swapped_columns(&self, table: &DB, dependencies: &Dependencies) -> Vec<(usize, usize)> {
+        let version = table.get_ref_definition().get_version();
+        match dependencies.get_vanilla_tables(table.get_ref_table_name(), self) {
+            Ok(vanilla_tables) => {
+                let references = vanilla_tables.into_iter().filter(|x| x.get_ref_definition().get_version() != version).collect::<Vec<DB>>();
+                Self::detect_swapped_columns_with_references(table, &references)
+            }
+            Err(_) => vec![],
+        }
+    }
+
+    /// This function tries to guess which pairs of columns of the provided table have been swapped in its definition, using the provided tables as reference.
+    ///
+    /// Columns are matched with the ones of the references by name and type. If two columns of the same type have more values in common
+    /// with each other's reference column than with their own, they're probably swapped. Pairs are picked from the most to the least likely,
+    /// and no column is part of more than one pair, so all of them can be fixed at once. The indexes returned are the ones of the fields
+    /// in the definition, sorted. Tables with bitwise or colour fields are not checked.
+    pub fn detect_swapped_columns_with_references(table: &DB, references: &[DB]) -> Vec<(usize, usize)> {
+        let fields = table.get_ref_definition().get_ref_fields();
+        let fields_processed = table.get_ref_definition().get_fields_processed();
+        if fields.len() != fields_processed.len() || fields.iter().zip(fields_processed.iter()).any(|(x, y)| x.get_name() != y.get_name()) {
+            return vec![];
+        }
+
+        // Values of each column in the references, with the columns matched to the ones of the table by name and type.
+        let mut reference_values = vec![None; fields.len()];
+        for reference in references {
+            let reference_fields = reference.get_ref_definition().get_fields_processed();
+            for (column, field) in fields.iter().enumerate() {
+                if let Some(reference_column) = reference_fields.iter().position(|x| x.get_name() == field.get_name() && x.get_ref_field_type() == field.get_ref_field_type()) {
+                    reference_values[column].get_or_insert_with(HashSet::new)
+                        .extend(reference.get_ref_table_data().iter().map(|row| row[reference_column].data_to_string()));
                 }
-                else { Err(ErrorKind::SchemaDefinitionNotFound.into()) }
-            } else { Err(ErrorKind::SchemaVersionedFileNotFound.into()) }
+            }
         }
+
+        // Amount of values of the column `column` of the table that can be found in the column `reference_column` of the references.
+        let hits = |column: usize, reference_column: usize| match &reference_values[reference_column] {
+            Some(values) => table.get_ref_table_data().iter().filter(|row| values.contains(&row[column].data_to_string())).count(),
+            None => 0,
+        };
+
+        let mut candidates = vec![];
+        for column_a in 0..fields.len() {
+            for column_b in column_a + 1..fields.len() {
+                if fields[column_a].get_field_type() != fields[column_b].get_field_type() || reference_values[column_a].is_none() || reference_values[column_b].is_none() {
+                    continue;
+                }
+
+                let own = hits(column_a, column_a) + hits(column_b, column_b);
+                let crossed = hits(column_a, column_b) + hits(column_b, column_a);
+                if crossed > own {
+                    candidates.push((crossed - own, column_a, column_b));
+                }
+            }
+        }
+
+        // Pick the most likely pairs first. A column can only be swapped once.
+        candidates.sort_by(|x, y| y.0.cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+        let mut used = HashSet::new();
+        let mut swaps = vec![];
+        for (_, column_a, column_b) in candidates {
+            if !used.contains(&column_a) && !used.contains(&column_b) {
+                used.insert(column_a);
+                used.insert(column_b);
+                swaps.push((column_a, column_b));
+            }
+        }
+
+        swaps.sort_unstable();
+        swaps
     }
 
     /// This function returns the last compatible definition of a Loc Table.
@@ -458,7 +554,69 @@ impl Schema {
         let mut file = BufReader::new(File::open(&file_path)?);
         let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
         file.read_to_end(&mut data)?;
-        from_bytes(&data).map_err(From::from)
+        let schema: Self = from_bytes(&data)?;
+        schema.log_invalid_defaults();
+        Ok(schema)
+    }
+
+    /// This function generates a `Schema` from the raw DB files of the provided Assembly Kit, merging it with the current schema of the game, if any.
+    ///
+    /// The resulting schema is not saved. Check `update_from_assembly_kit` for how the Assembly Kit data is merged.
+    /// If the game has no schema yet, a new one is generated. Any other error loading the current schema is returned.
+    pub fn generate_from_assembly_kit(ak_path: &Path, game: &GameInfo) -> Result<Schema> {
+        let schema_path = get_config_path()?.join(SCHEMA_FOLDER).join(game.get_schema_name());
+        let mut schema = if schema_path.is_file() { Schema::load(game.get_schema_name())? } else { Schema::default() };
+        schema.update_from_assembly_kit(ak_path, game.get_raw_db_version())?;
+        Ok(schema)
+    }
+
+    /// This function updates this `Schema` with the raw DB files of the provided Assembly Kit.
+    ///
+    /// Tables already in the schema get their newest definition updated with the Assembly Kit data. Tables not in the schema get a new
+    /// definition with version 0, containing the non-localisable fields of the raw definition. Raw definitions that are not `.xml` files are skipped.
+    pub fn update_from_assembly_kit(&mut self, ak_path: &Path, raw_db_version: i16) -> Result<()> {
+        if raw_db_version != 2 && raw_db_version != 1 {
+            return Err(ErrorKind::AssemblyKitUnsupportedVersion(raw_db_version).into());
+        }
+
+        let raw_db_path = ak_path.join("raw_data").join("db");
+        let raw_localisable_fields = RawLocalisableFields::read(&raw_db_path, raw_db_version).map(|x| x.fields).unwrap_or_default();
+        let raw_definitions = get_raw_definition_paths(&raw_db_path, raw_db_version)?.par_iter()
+            .map(|x| RawDefinition::read(x, raw_db_version))
+            .collect::<Result<Vec<RawDefinition>>>()?;
+
+        for raw_definition in &raw_definitions {
+            let name = match raw_definition.name {
+                Some(ref name) if !BLACKLISTED_TABLES.contains(&name.as_str()) => name,
+                _ => continue,
+            };
+
+            let table_name = match name.strip_suffix(".xml") {
+                Some(name) => format!("{}_tables", name),
+                None => continue,
+            };
+
+            if let Ok(VersionedFile::DB(_, definitions)) = self.get_ref_mut_versioned_file_db(&table_name) {
+                if let Some(definition) = definitions.iter_mut().max_by_key(|x| x.get_version()) {
+                    definition.update_from_raw_definition(raw_definition);
+                    definition.update_from_raw_localisable_fields(raw_definition, &raw_localisable_fields);
+                }
+                continue;
+            }
+
+            let mut definition = Definition::new(0);
+            definition.update_from_raw_localisable_fields(raw_definition, &raw_localisable_fields);
+            let fields = raw_definition.fields.iter()
+                .filter(|x| !definition.localised_fields.iter().any(|y| y.name == x.name))
+                .map(From::from)
+                .collect();
+            definition.fields = fields;
+            definition.update_from_raw_definition(raw_definition);
+
+            self.add_versioned_file(&VersionedFile::DB(table_name, vec![definition]));
+        }
+
+        Ok(())
     }
 
     /// This function saves a `Schema` from memory to a file in the `schemas/` folder.
@@ -501,7 +659,29 @@ impl Schema {
         let mut file = BufReader::new(File::open(&file_path)?);
         let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
         file.read_to_end(&mut data)?;
-        bincode::deserialize(&data).map_err(From::from)
+        let schema: Self = bincode::deserialize(&data)?;
+        schema.log_invalid_defaults();
+        Ok(schema)
+    }
+
+    /// This function logs a warning for every default value in the `Schema` that doesn't parse to the type of its field.
+    fn log_invalid_defaults(&self) {
+        for versioned_file in &self.versioned_files {
+            let name = match versioned_file {
+                VersionedFile::AnimFragment(_) => "AnimFragment",
+                VersionedFile::AnimTable(_) => "AnimTable",
+                VersionedFile::DB(name, _) => name,
+                VersionedFile::DepManager(_) => "DepManager",
+                VersionedFile::Loc(_) => "Loc",
+                VersionedFile::MatchedCombat(_) => "MatchedCombat",
+            };
+
+            for definition in versioned_file.get_version_list() {
+                for issue in definition.validate_defaults() {
+                    warn!("Invalid default value in {}, version {}: {}", name, definition.get_version(), issue);
+                }
+            }
+        }
     }
 
     /// This function saves a `Schema` from memory to a file in the `schemas/` folder.
@@ -919,6 +1099,13 @@ impl Definition {
         &mut self.fields
     }
 
+    /// This function swaps the position of two fields in the definition. Used to fix definitions with swapped columns.
+    ///
+    /// Panics if any of the indexes is out of bounds.
+    pub fn swap_columns(&mut self, a: usize, b: usize) {
+        self.fields.swap(a, b);
+    }
+
     /// This function returns the reference and lookup data of a definition.
     pub fn get_reference_data(&self) -> BTreeMap<i32, (String, String, Option<Vec<String>>)> {
         self.fields.iter()
@@ -984,6 +1171,39 @@ impl Definition {
         fields
     }
 
+    /// This function checks that the default values of the fields of this definition parse to the type of their field.
+    ///
+    /// It returns a list of `field_name: reason` for each default value that doesn't, including the ones in sequence fields.
+    pub fn validate_defaults(&self) -> Vec<String> {
+        let mut issues = vec![];
+        for field in self.get_fields_processed() {
+            match field.get_ref_field_type() {
+                FieldType::SequenceU16(definition) |
+                FieldType::SequenceU32(definition) => issues.extend(definition.validate_defaults().iter().map(|issue| format!("{}/{}", field.get_name(), issue))),
+                field_type => if let Some(default_value) = field.default_value.as_ref().filter(|x| !x.is_empty()) {
+                    let is_valid = match field_type {
+                        FieldType::Boolean => default_value.to_lowercase() == "true" || default_value.to_lowercase() == "false",
+                        FieldType::F32 => default_value.parse::<f32>().is_ok(),
+                        FieldType::F64 => default_value.parse::<f64>().is_ok(),
+                        FieldType::I16 => default_value.parse::<i16>().is_ok(),
+                        FieldType::I32 |
+                        FieldType::OptionalI32 => default_value.parse::<i32>().is_ok(),
+                        FieldType::I64 => default_value.parse::<i64>().is_ok(),
+                        FieldType::ColourRGB |
+                        FieldType::ColourRGBA => u32::from_str_radix(default_value, 16).is_ok(),
+                        _ => true,
+                    };
+
+                    if !is_valid {
+                        issues.push(format!("{}: \"{}\" is not a valid {} value.", field.get_name(), default_value, field_type));
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+
     /// Note, this doesn't work with combined fields.
     pub fn get_original_field_from_processed(&self, index: usize) -> Field {
         let fields = self.get_ref_fields();
@@ -1308,6 +1528,25 @@ impl Field {
         self.default_value.clone()
     }
 
+    /// This function returns the default value of the field or, if it doesn't have one and it's a reference, the first valid value of the referenced column.
+    ///
+    /// Referenced values are taken from vanilla tables first, then from parent mod tables, and the first non-empty one is returned.
+    pub fn resolve_default(&self, dependencies: &Dependencies) -> Option<String> {
+        if let Some(default_value) = self.get_default_value(None) {
+            return Some(default_value);
+        }
+
+        let (ref_table, ref_column) = self.get_is_reference().as_ref()?;
+        dependencies.get_db_tables_from_cache(&format!("{}_tables", ref_table), true, true).ok()?
+            .iter()
+            .filter_map(|db| {
+                let column = db.get_ref_definition().get_fields_processed().iter().position(|x| x.get_name() == ref_column)?;
+                Some(db.get_ref_table_data().iter().map(|row| row[column].data_to_string()).collect::<Vec<String>>())
+            })
+            .flatten()
+            .find(|x| !x.is_empty())
+    }
+
     /// Getter for the `is_filename` field.
     pub fn get_is_filename(&self) -> bool {
         self.is_filename
@@ -1430,10 +1669,12 @@ impl Display for FieldType {
             FieldType::I32 => write!(f, "I32"),
             FieldType::I64 => write!(f, "I64"),
             FieldType::ColourRGB => write!(f, "ColourRGB"),
+            FieldType::ColourRGBA => write!(f, "ColourRGBA"),
             FieldType::StringU8 => write!(f, "StringU8"),
             FieldType::StringU16 => write!(f, "StringU16"),
             FieldType::OptionalStringU8 => write!(f, "OptionalStringU8"),
             FieldType::OptionalStringU16 => write!(f, "OptionalStringU16"),
+            FieldType::OptionalI32 => write!(f, "OptionalI32"),
             FieldType::SequenceU16(sequence) => write!(f, "SequenceU16 of: {:#?}", sequence),
             FieldType::SequenceU32(sequence) => write!(f, "SequenceU32 of: {:#?}", sequence),
         }
@@ -0,0 +1,130 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing test for the `Schema` module.
+!*/
+
+use std::collections::BTreeMap;
+use std::env::temp_dir;
+use std::fs::{create_dir_all, remove_dir_all, write};
+
+use crate::dependencies::Dependencies;
+use crate::packedfile::table::DecodedData;
+use crate::test_helpers::*;
+
+use super::{Definition, Field, FieldType, Schema, VersionedFile};
+
+#[test]
+fn test_definition_validate_defaults() {
+    let definition = definition(1, vec![
+        field_with_default("valid", FieldType::I32, false, "5"),
+        field_with_default("invalid", FieldType::I32, false, "five"),
+    ]);
+
+    let issues = definition.validate_defaults();
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].starts_with("invalid: "));
+}
+
+#[test]
+fn test_detect_swapped_columns() {
+    let fields = |names: [&str; 4]| names.iter().map(|name| field(name, if *name == "key" { FieldType::StringU8 } else { FieldType::I32 }, *name == "key")).collect();
+    let row = |key: &str, values: [i32; 3]| {
+        let mut row = vec![DecodedData::StringU8(key.to_owned())];
+        row.extend(values.iter().map(|x| DecodedData::I32(*x)));
+        row
+    };
+
+    // The vanilla table, with the right definition.
+    let definition_vanilla = definition(1, fields(["key", "a", "b", "c"]));
+    let vanilla = db(&definition_vanilla, &[row("key_1", [1, 10, 100]), row("key_2", [2, 20, 200]), row("key_3", [3, 30, 300])]);
+
+    // A newer definition with `a` and `b` swapped, so the values of `a` end up in the `b` column and vice versa.
+    // The `c` column also has values of the vanilla `b` column, so (b, c) looks swapped too, but less than (a, b).
+    let mut definition_swapped = definition(2, fields(["key", "b", "a", "c"]));
+    let table = db(&definition_swapped, &[row("key_1", [1, 10, 10]), row("key_2", [2, 20, 20]), row("key_4", [3, 30, 400])]);
+
+    // Comparing against a table with the same definition can't detect anything, as it has the same columns swapped.
+    let vanilla_same_definition = db(&definition_swapped, vanilla.get_ref_table_data());
+    assert!(Schema::detect_swapped_columns_with_references(&table, &[vanilla_same_definition]).is_empty());
+
+    // Against the other version, only the most likely of the overlapping pairs must be returned.
+    let swaps = Schema::detect_swapped_columns_with_references(&table, &[vanilla]);
+    assert_eq!(swaps, vec![(1, 2)]);
+
+    swaps.iter().for_each(|(a, b)| definition_swapped.swap_columns(*a, *b));
+    assert_eq!(definition_swapped.get_ref_fields().iter().map(|x| x.get_name()).collect::<Vec<&str>>(), vec!["key", "a", "b", "c"]);
+}
+
+#[test]
+fn test_last_definition_db_from_schema() {
+    let mut schema = Schema::default();
+    schema.add_versioned_file(&VersionedFile::DB(TEST_TABLE_NAME.to_owned(), vec![Definition::new(2), Definition::new(5), Definition::new(3)]));
+
+    assert_eq!(schema.get_ref_last_definition_db_from_schema(TEST_TABLE_NAME).unwrap().get_version(), 5);
+    assert!(schema.get_ref_last_definition_db_from_schema("missing_tables").is_err());
+}
+
+#[test]
+fn test_update_from_assembly_kit() {
+    let ak_path = temp_dir().join("rpfm_test_update_from_assembly_kit");
+    let raw_db_path = ak_path.join("raw_data").join("db");
+    let _ = remove_dir_all(&ak_path);
+    create_dir_all(&raw_db_path).unwrap();
+
+    let raw_definition = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<root>
+    <field><primary_key>1</primary_key><name>key</name><field_type>text</field_type><required>1</required></field>
+    <field><primary_key>0</primary_key><name>value</name><field_type>integer</field_type><required>1</required><default_value>5</default_value></field>
+</root>";
+
+    // Raw definitions without the `.xml` extension must be skipped, no matter how short their names are.
+    write(raw_db_path.join("TWaD_new.xml"), raw_definition).unwrap();
+    write(raw_db_path.join("TWaD_existing.xml"), raw_definition).unwrap();
+    write(raw_db_path.join("TWaD_ab"), raw_definition).unwrap();
+
+    // Tables already in the schema only get their newest definition updated.
+    let mut schema = Schema::default();
+    schema.add_versioned_file(&VersionedFile::DB("existing_tables".to_owned(), vec![
+        definition(2, vec![field("key", FieldType::StringU8, false)]),
+        definition(1, vec![field("key", FieldType::StringU8, false)]),
+    ]));
+
+    schema.update_from_assembly_kit(&ak_path, 2).unwrap();
+    remove_dir_all(&ak_path).unwrap();
+
+    let new = schema.get_ref_last_definition_db_from_schema("new_tables").unwrap();
+    assert_eq!(new.get_version(), 0);
+    assert_eq!(new.get_ref_fields().iter().map(|x| (x.get_name(), x.get_is_key(), x.get_default_value(None))).collect::<Vec<_>>(), vec![("key", true, None), ("value", false, Some("5".to_owned()))]);
+
+    if let Ok(VersionedFile::DB(_, definitions)) = schema.get_ref_versioned_file_db("existing_tables") {
+        assert_eq!(definitions.iter().map(|x| (x.get_version(), x.get_ref_fields()[0].get_is_key())).collect::<Vec<_>>(), vec![(2, true), (1, false)]);
+    } else { panic!("The table is not in the schema.") }
+
+    assert!(schema.get_ref_last_definition_db_from_schema("ab_tables").is_err());
+    assert!(schema.update_from_assembly_kit(&ak_path, 0).is_err());
+}
+
+#[test]
+fn test_field_resolve_default() {
+    let dependencies = Dependencies::default();
+    let reference = Some(("units".to_owned(), "key".to_owned()));
+    let reference_with_default = Field::new("unit".to_owned(), FieldType::StringU8, false, Some("unit_1".to_owned()), false, None, reference.clone(), None, String::new(), -1, 0, BTreeMap::new(), None);
+    let reference_without_default = Field::new("unit".to_owned(), FieldType::StringU8, false, None, false, None, reference, None, String::new(), -1, 0, BTreeMap::new(), None);
+
+    // The field's own default always wins, so it doesn't need the dependencies.
+    assert_eq!(field_with_default("value", FieldType::I32, false, "5").resolve_default(&dependencies), Some("5".to_owned()));
+    assert_eq!(reference_with_default.resolve_default(&dependencies), Some("unit_1".to_owned()));
+
+    // Without a default, only references can be resolved, and only with a dependencies cache to take the values from.
+    assert_eq!(field("value", FieldType::I32, false).resolve_default(&dependencies), None);
+    assert_eq!(reference_without_default.resolve_default(&dependencies), None);
+}
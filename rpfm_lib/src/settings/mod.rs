@@ -115,6 +115,7 @@ impl Settings {
         settings_bool.insert("expand_treeview_when_adding_items".to_owned(), true);
         settings_bool.insert("use_right_size_markers".to_owned(), false);
         settings_bool.insert("disable_file_previews".to_owned(), false);
+        settings_bool.insert("validate_packfile_before_save".to_owned(), false);
 
         // Table Settings.
         settings_bool.insert("adjust_columns_to_content".to_owned(), true);
@@ -0,0 +1,53 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2022 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted PackFile Manager (RPFM) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+/*!
+Module containing the fixtures shared by the tests of the lib.
+!*/
+
+use std::collections::BTreeMap;
+
+use crate::packedfile::table::DecodedData;
+use crate::packedfile::table::db::DB;
+use crate::schema::{Definition, Field, FieldType, Schema, VersionedFile};
+
+/// Name of the table used by the test fixtures.
+pub const TEST_TABLE_NAME: &str = "test_tables";
+
+/// This function creates a field with only a name, a type and its key status, leaving the rest to its defaults.
+pub fn field(name: &str, field_type: FieldType, is_key: bool) -> Field {
+    Field::new(name.to_owned(), field_type, is_key, None, false, None, None, None, String::new(), -1, 0, BTreeMap::new(), None)
+}
+
+/// This function creates a field like `field`, but with a default value.
+pub fn field_with_default(name: &str, field_type: FieldType, is_key: bool, default_value: &str) -> Field {
+    Field::new(name.to_owned(), field_type, is_key, Some(default_value.to_owned()), false, None, None, None, String::new(), -1, 0, BTreeMap::new(), None)
+}
+
+/// This function creates a definition with the provided fields.
+pub fn definition(version: i32, fields: Vec<Field>) -> Definition {
+    let mut definition = Definition::new(version);
+    *definition.get_ref_mut_fields() = fields;
+    definition
+}
+
+/// This function creates a `test_tables` DB with the provided definition and rows.
+pub fn db(definition: &Definition, rows: &[Vec<DecodedData>]) -> DB {
+    let mut db = DB::new(TEST_TABLE_NAME, None, definition);
+    db.set_table_data(rows).unwrap();
+    db
+}
+
+/// This function creates a schema containing only the provided definition for the `test_tables` DB.
+pub fn schema_with_db(definition: &Definition) -> Schema {
+    let mut schema = Schema::default();
+    schema.add_versioned_file(&VersionedFile::DB(TEST_TABLE_NAME.to_owned(), vec![definition.clone()]));
+    schema
+}
@@ -178,3 +178,61 @@ pub fn setter(input: TokenStream) -> TokenStream {
         _ => "".parse().unwrap()
     }
 }
+
+/// Macro to generate a function returning a stable id for each variant of an Enum.
+///
+/// The id is the name of the variant in snake_case, with acronyms kept together (`IOGenericError` => `io_generic_error`).
+/// It doesn't depend on the data the variant contains.
+#[proc_macro_derive(GetVariantId)]
+pub fn variant_id(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let enum_name = &ast.ident;
+    match ast.data {
+        Data::Enum(e) => {
+
+            let variant_names : Vec<_> = e.variants
+                .iter()
+                .map(|x| x.ident.clone()).collect();
+
+            let variant_ids = variant_names
+                .iter()
+                .map(|x| to_snake_case(&x.to_string()));
+
+            let quoted_code = quote!{
+
+                #[allow(dead_code)]
+                impl #enum_name {
+                    pub fn get_variant_id(&self) -> &'static str {
+                        match self {
+                            #(
+                                #enum_name::#variant_names { .. } => #variant_ids,
+                            )*
+                        }
+                    }
+                }
+            };
+            TokenStream::from(quoted_code)
+        }
+
+        // not an enum
+        _ => "".parse().unwrap()
+    }
+}
+
+/// This function turns a name in CamelCase into snake_case, keeping acronyms together.
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+    let characters = name.chars().collect::<Vec<char>>();
+    for (index, character) in characters.iter().enumerate() {
+        if index > 0 && character.is_uppercase() {
+            let previous = characters[index - 1];
+            let next_is_lowercase = characters.get(index + 1).map_or(false, |next| next.is_lowercase());
+            if !previous.is_uppercase() || next_is_lowercase {
+                snake_case.push('_');
+            }
+        }
+        snake_case.push(character.to_ascii_lowercase());
+    }
+    snake_case
+}
@@ -1651,7 +1651,7 @@ impl AppUI {
                         }
 
                         // If the file is a Text PackedFile...
-                        PackedFileType::Text(_) => {
+                        PackedFileType::Text(_) | PackedFileType::VariantMeshDefinition => {
                             match PackedFileTextView::new_view(&mut tab, app_ui, pack_file_contents_ui) {
                                 Ok(packed_file_info) => {
 
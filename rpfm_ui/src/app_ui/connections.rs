@@ -77,6 +77,7 @@ pub unsafe fn set_connections(app_ui: &Rc<AppUI>, slots: &AppUISlots) {
     app_ui.game_selected_open_game_data_folder.triggered().connect(&slots.game_selected_open_game_data_folder);
     app_ui.game_selected_open_game_assembly_kit_folder.triggered().connect(&slots.game_selected_open_game_assembly_kit_folder);
     app_ui.game_selected_open_config_folder.triggered().connect(&slots.game_selected_open_config_folder);
+    app_ui.game_selected_suggest_game.triggered().connect(&slots.game_selected_suggest_game);
 
     app_ui.game_selected_warhammer_3.triggered().connect(&slots.change_game_selected);
     app_ui.game_selected_troy.triggered().connect(&slots.change_game_selected);
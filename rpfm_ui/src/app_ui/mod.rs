@@ -156,6 +156,7 @@ pub struct AppUI {
     pub game_selected_open_game_data_folder: QPtr<QAction>,
     pub game_selected_open_game_assembly_kit_folder: QPtr<QAction>,
     pub game_selected_open_config_folder: QPtr<QAction>,
+    pub game_selected_suggest_game: QPtr<QAction>,
 
     pub game_selected_warhammer_3: QPtr<QAction>,
     pub game_selected_troy: QPtr<QAction>,
@@ -498,6 +499,7 @@ impl AppUI {
         let game_selected_open_game_data_folder = menu_bar_game_selected.add_action_q_string(&qtr("game_selected_open_game_data_folder"));
         let game_selected_open_game_assembly_kit_folder = menu_bar_game_selected.add_action_q_string(&qtr("game_selected_open_game_assembly_kit_folder"));
         let game_selected_open_config_folder = menu_bar_game_selected.add_action_q_string(&qtr("game_selected_open_config_folder"));
+        let game_selected_suggest_game = menu_bar_game_selected.add_action_q_string(&qtr("game_selected_suggest_game"));
 
         let game_selected_warhammer_3 = menu_bar_game_selected.add_action_q_string(&QString::from_std_str(DISPLAY_NAME_WARHAMMER_3));
         let game_selected_troy = menu_bar_game_selected.add_action_q_string(&QString::from_std_str(DISPLAY_NAME_TROY));
@@ -738,6 +740,7 @@ impl AppUI {
             game_selected_open_game_data_folder,
             game_selected_open_game_assembly_kit_folder,
             game_selected_open_config_folder,
+            game_selected_suggest_game,
 
             game_selected_warhammer_3,
             game_selected_troy,
@@ -123,6 +123,7 @@ pub struct AppUISlots {
     pub game_selected_open_game_data_folder: QBox<SlotOfBool>,
     pub game_selected_open_game_assembly_kit_folder: QBox<SlotOfBool>,
     pub game_selected_open_config_folder: QBox<SlotOfBool>,
+    pub game_selected_suggest_game: QBox<SlotOfBool>,
     pub change_game_selected: QBox<SlotOfBool>,
 
     //-----------------------------------------------//
@@ -433,8 +434,12 @@ impl AppUISlots {
                 GlobalSearchUI::clear(&global_search_ui);
                 let _ = AppUI::purge_them_all(&app_ui, &pack_file_contents_ui, false);
 
-                let receiver = CENTRAL_COMMAND.send_background(Command::LoadAllCAPackFiles);
+                let operation_id = CENTRAL_COMMAND.new_operation();
+                let wait_dialog = show_cancellable_wait_dialog(&app_ui.main_window, "load_all_ca_packfiles_in_progress_message", operation_id);
+
+                let receiver = CENTRAL_COMMAND.send_background(Command::LoadAllCAPackFiles(operation_id));
                 let response = CentralCommand::recv_try(&receiver);
+                wait_dialog.done(1);
                 match response {
 
                     // If it's success....
@@ -909,6 +914,43 @@ impl AppUISlots {
             }
         }));
 
+        // What happens when we trigger the "Suggest Game" action.
+        let game_selected_suggest_game = SlotOfBool::new(&app_ui.main_window, clone!(
+            app_ui => move |_| {
+            let receiver = CENTRAL_COMMAND.send_background(Command::GuessGame);
+            let response = CentralCommand::recv(&receiver);
+            match response {
+                Response::VecString(games) => {
+
+                    // The most likely game goes first. If it's already the one selected, there is nothing to change.
+                    match games.first() {
+                        Some(game) if *game == GAME_SELECTED.read().unwrap().get_game_key_name() => show_dialog(&app_ui.main_window, tr("game_selected_suggest_game_already_selected"), true),
+                        Some(game) => {
+                            match &**game {
+                                KEY_WARHAMMER_3 => app_ui.game_selected_warhammer_3.trigger(),
+                                KEY_TROY => app_ui.game_selected_troy.trigger(),
+                                KEY_THREE_KINGDOMS => app_ui.game_selected_three_kingdoms.trigger(),
+                                KEY_WARHAMMER_2 => app_ui.game_selected_warhammer_2.trigger(),
+                                KEY_WARHAMMER => app_ui.game_selected_warhammer.trigger(),
+                                KEY_THRONES_OF_BRITANNIA => app_ui.game_selected_thrones_of_britannia.trigger(),
+                                KEY_ATTILA => app_ui.game_selected_attila.trigger(),
+                                KEY_ROME_2 => app_ui.game_selected_rome_2.trigger(),
+                                KEY_SHOGUN_2 => app_ui.game_selected_shogun_2.trigger(),
+                                KEY_NAPOLEON => app_ui.game_selected_napoleon.trigger(),
+                                KEY_EMPIRE => app_ui.game_selected_empire.trigger(),
+                                KEY_ARENA => app_ui.game_selected_arena.trigger(),
+                                _ => unreachable!(),
+                            }
+
+                            show_dialog(&app_ui.main_window, tre("game_selected_suggest_game_changed", &[GAME_SELECTED.read().unwrap().get_display_name()]), true);
+                        }
+                        None => show_dialog(&app_ui.main_window, tr("game_selected_suggest_game_none"), false),
+                    }
+                }
+                _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+            }
+        }));
+
         // What happens when we trigger the "Change Game Selected" action.
         //
         // NOTE: NEVER EVER AGAIN SHALL YOU TRIGGER HERE A REBUILD OF THE GAME-SPECIFIC SLOTS!!!!!!!!!!
@@ -1645,6 +1687,7 @@ impl AppUISlots {
             game_selected_open_game_data_folder,
             game_selected_open_game_assembly_kit_folder,
             game_selected_open_config_folder,
+            game_selected_suggest_game,
             change_game_selected,
 
             //-----------------------------------------------//
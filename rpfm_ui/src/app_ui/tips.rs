@@ -61,6 +61,7 @@ pub unsafe fn set_tips(app_ui: &Rc<AppUI>) {
     app_ui.game_selected_open_game_data_folder.set_status_tip(&qtr("tt_game_selected_open_game_data_folder"));
     app_ui.game_selected_open_game_assembly_kit_folder.set_status_tip(&qtr("tt_game_selected_open_game_assembly_kit_folder"));
     app_ui.game_selected_open_config_folder.set_status_tip(&qtr("tt_game_selected_open_config_folder"));
+    app_ui.game_selected_suggest_game.set_status_tip(&qtr("tt_game_selected_suggest_game"));
 
     app_ui.game_selected_warhammer_3.set_status_tip(&qtr("tt_game_selected_warhammer_3"));
     app_ui.game_selected_troy.set_status_tip(&qtr("tt_game_selected_troy"));
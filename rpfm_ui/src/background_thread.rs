@@ -147,14 +147,16 @@ pub fn background_loop() {
             }
 
             // In case we want to "Load All CA PackFiles"...
-            Command::LoadAllCAPackFiles => {
-                match PackFile::open_all_ca_packfiles() {
+            Command::LoadAllCAPackFiles(operation_id) => {
+                let cancel = CENTRAL_COMMAND.get_cancellation_flag(operation_id);
+                match PackFile::open_all_ca_packfiles_cancellable(&cancel) {
                     Ok(pack_file) => {
                         pack_file_decoded = pack_file;
                         CentralCommand::send_back(&sender, Response::PackFileInfo(PackFileInfo::from(&pack_file_decoded)));
                     }
                     Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
                 }
+                CENTRAL_COMMAND.finish_operation(operation_id);
             }
 
             // In case we want to "Save a PackFile"...
@@ -672,7 +674,7 @@ pub fn background_loop() {
                 if path == [RESERVED_NAME_NOTES.to_owned()] {
                     if let DecodedPackedFile::Text(data) = decoded_packed_file {
                         let note = if data.get_ref_contents().is_empty() { None } else { Some(data.get_ref_contents().to_owned()) };
-                        pack_file_decoded.set_notes(&note);
+                        pack_file_decoded.set_notes(note);
                     }
                 }
                 else if let Some(packed_file) = pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
@@ -700,11 +702,13 @@ pub fn background_loop() {
             }
 
             // In case we want to Mass-Import TSV Files...
-            Command::MassImportTSV(paths, name) => {
-                match pack_file_decoded.mass_import_tsv(&paths, name, true) {
+            Command::MassImportTSV(paths, name, operation_id) => {
+                let cancel = CENTRAL_COMMAND.get_cancellation_flag(operation_id);
+                match pack_file_decoded.mass_import_tsv_cancellable(&paths, name, true, &cancel) {
                     Ok(result) => CentralCommand::send_back(&sender, Response::VecVecStringVecVecString(result)),
                     Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
                 }
+                CENTRAL_COMMAND.finish_operation(operation_id);
             }
 
             // In case we want to Mass-Export TSV Files...
@@ -832,6 +836,15 @@ pub fn background_loop() {
                 CentralCommand::send_back(&sender, Response::BTreeMapI32DependencyData(dependency_data));
             }
 
+            // In case we want to get the default values for a definition...
+            Command::GetDefaultValuesFromDefinition(definition) => {
+                let default_values = definition.get_fields_processed().iter()
+                    .filter_map(|field| Some((field.get_name().to_owned(), field.resolve_default(&dependencies)?)))
+                    .collect::<HashMap<String, String>>();
+
+                CentralCommand::send_back(&sender, Response::HashMapStringString(default_values));
+            }
+
             // In case we want to return an entire PackedFile to the UI.
             Command::GetPackedFile(path) => CentralCommand::send_back(&sender, Response::OptionPackedFile(pack_file_decoded.get_packed_file_by_path(&path))),
 
@@ -1333,6 +1346,7 @@ pub fn background_loop() {
                 CentralCommand::send_back(&sender, Response::PackedFileType(PackedFileType::get_packed_file_type(&packed_file, false)));
             }
             Command::GetPackFileName => CentralCommand::send_back(&sender, Response::String(pack_file_decoded.get_file_name())),
+            Command::GuessGame => CentralCommand::send_back(&sender, Response::VecString(pack_file_decoded.guess_game().iter().map(|game| game.to_string()).collect())),
             Command::GetPackedFileRawData(path) => {
                 match pack_file_decoded.get_ref_mut_packed_file_by_path(&path) {
                     Some(ref mut packed_file) => {
@@ -1540,7 +1554,7 @@ pub fn background_loop() {
 
 
             // These two belong to the network thread, not to this one!!!!
-            Command::CheckUpdates | Command::CheckSchemaUpdates | Command::CheckMessageUpdates => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+            Command::Cancel(_) | Command::CheckUpdates | Command::CheckSchemaUpdates | Command::CheckMessageUpdates => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
         }
     }
 }
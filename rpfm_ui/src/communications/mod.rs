@@ -19,6 +19,8 @@ use crossbeam::channel::{Receiver, Sender, unbounded};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 use rpfm_error::Error;
 
@@ -50,6 +52,9 @@ use crate::ui_state::shortcuts::Shortcuts;
 pub const THREADS_COMMUNICATION_ERROR: &str = "Error in thread communication system. Response received: ";
 pub const THREADS_SENDER_ERROR: &str = "Error in thread communication system. Sender failed to send message.";
 
+/// Type used to identify a cancellable operation running on the background thread.
+pub type OperationId = u64;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -63,6 +68,10 @@ pub struct CentralCommand<T: Send + Sync + Debug> {
 
     receiver_background: Receiver<(Sender<T>, Command)>,
     receiver_network:  Receiver<(Sender<T>, Command)>,
+
+    /// Cancellation flags of the cancellable operations currently registered, by id.
+    operations: RwLock<HashMap<OperationId, Arc<AtomicBool>>>,
+    next_operation_id: AtomicU64,
 }
 
 /// This enum defines the commands (messages) you can send to the background thread in order to execute actions.
@@ -75,6 +84,11 @@ pub enum Command {
     /// This command is used to close a thread.
     Exit,
 
+    /// This command is used to cancel a running operation. It contains the id of the operation to cancel.
+    ///
+    /// Unlike the rest of commands, this one is not queued: it raises the cancellation flag of the operation as soon as it's sent.
+    Cancel(OperationId),
+
     /// This command is used when we want to reset the open `PackFile` to his original state.
     ResetPackFile,
 
@@ -111,8 +125,8 @@ pub enum Command {
     /// This command is used to open an extra `PackFile`. It requires the path of the `PackFile`.
     OpenPackFileExtra(PathBuf),
 
-    /// This command is used to open all the CA PackFiles for the game selected as one.
-    LoadAllCAPackFiles,
+    /// This command is used to open all the CA PackFiles for the game selected as one. It contains the id of the operation, so it can be cancelled.
+    LoadAllCAPackFiles(OperationId),
 
     /// This command is used when we want to get the `PackedFileInfo` of one or more `PackedFiles`.
     GetPackedFilesInfo(Vec<Vec<String>>),
@@ -203,8 +217,8 @@ pub enum Command {
     /// This command is used when we want to rename one or more PackedFiles in a PackFile. It contains a Vec with their original PathType and their new name.
     RenamePackedFiles(Vec<(PathType, String)>),
 
-    /// This command is used when we want to import a large amount of table-like files from TSV files.
-    MassImportTSV(Vec<PathBuf>, Option<String>),
+    /// This command is used when we want to import a large amount of table-like files from TSV files. It contains the id of the operation, so it can be cancelled.
+    MassImportTSV(Vec<PathBuf>, Option<String>, OperationId),
 
     /// This command is used when we want to export a large amount of table-like files as TSV files.
     MassExportTSV(Vec<PathType>, PathBuf),
@@ -247,6 +261,9 @@ pub enum Command {
     /// It requires the table name, the definition of the table to get the reference data from and the list of PackedFiles to ignore.
     GetReferenceDataFromDefinition(String, Definition, Vec<Vec<String>>),
 
+    /// This command is used to get the default values of the fields of the provided definition, falling back to the first referenced value for references.
+    GetDefaultValuesFromDefinition(Definition),
+
     /// This command is used to get the list of PackFiles that are marked as dependency of our PackFile.
     GetDependencyPackFilesList,
 
@@ -325,6 +342,9 @@ pub enum Command {
     /// This command is used to get the name of the currently open PackFile.
     GetPackFileName,
 
+    /// This command is used to get the keys of the games the currently open PackFile may belong to, from most to least likely.
+    GuessGame,
+
     /// This command is used to get the raw data of a PackedFile.
     GetPackedFileRawData(Vec<String>),
 
@@ -481,6 +501,9 @@ pub enum Response {
     /// Response to return `BTreeMap<i32, DependencyData>`.
     BTreeMapI32DependencyData(BTreeMap<i32, DependencyData>),
 
+    /// Response to return `HashMap<String, String>`.
+    HashMapStringString(HashMap<String, String>),
+
     /// Response to return `Option<PackedFile>`.
     OptionPackedFile(Option<PackedFile>),
 
@@ -531,6 +554,8 @@ impl<T: Send + Sync + Debug> Default for CentralCommand<T> {
             sender_network,
             receiver_background,
             receiver_network,
+            operations: RwLock::new(HashMap::new()),
+            next_operation_id: AtomicU64::new(0),
         }
     }
 }
@@ -553,10 +578,39 @@ impl<T: Send + Sync + Debug> CentralCommand<T> {
     /// This function serves to send a message from the main thread to the background thread.
     ///
     /// It returns the receiver which will receive the answers for the command, if any.
+    ///
+    /// `Command::Cancel` is handled here instead of being queued, as the background thread is busy with the operation to cancel.
     pub fn send_background(&self, data: Command) -> Receiver<T> {
+        if let Command::Cancel(operation_id) = data {
+            if let Some(cancel) = self.operations.read().unwrap().get(&operation_id) {
+                cancel.store(true, Ordering::SeqCst);
+            }
+
+            return unbounded().1;
+        }
+
         Self::send(&self.sender_background, data)
     }
 
+    /// This function registers a new cancellable operation, returning its id.
+    pub fn new_operation(&self) -> OperationId {
+        let operation_id = self.next_operation_id.fetch_add(1, Ordering::SeqCst);
+        self.operations.write().unwrap().insert(operation_id, Arc::new(AtomicBool::new(false)));
+        operation_id
+    }
+
+    /// This function returns the cancellation flag of the provided operation.
+    ///
+    /// If the operation is not registered (or has already finished) it returns a flag that's never raised.
+    pub fn get_cancellation_flag(&self, operation_id: OperationId) -> Arc<AtomicBool> {
+        self.operations.read().unwrap().get(&operation_id).cloned().unwrap_or_else(|| Arc::new(AtomicBool::new(false)))
+    }
+
+    /// This function unregisters a finished operation, so it cannot be cancelled anymore.
+    pub fn finish_operation(&self, operation_id: OperationId) {
+        self.operations.write().unwrap().remove(&operation_id);
+    }
+
     /// This function serves to send a message from the main thread to the network thread.
     ///
     /// It returns the receiver which will receive the answers for the command, if any.
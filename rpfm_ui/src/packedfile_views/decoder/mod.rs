@@ -1027,10 +1027,12 @@ impl PackedFileDecoderView {
             FieldType::I32 => "I32",
             FieldType::I64 => "I64",
             FieldType::ColourRGB => "ColourRGB",
+            FieldType::ColourRGBA => "ColourRGBA",
             FieldType::StringU8 => "StringU8",
             FieldType::StringU16 => "StringU16",
             FieldType::OptionalStringU8 => "OptionalStringU8",
             FieldType::OptionalStringU16 => "OptionalStringU16",
+            FieldType::OptionalI32 => "OptionalI32",
             FieldType::SequenceU16(_) => "SequenceU16",
             FieldType::SequenceU32(_) => "SequenceU32",
         };
@@ -1209,6 +1211,12 @@ impl PackedFileDecoderView {
                     Err(_) => "Error".to_owned(),
                 }
             },
+            FieldType::ColourRGBA => {
+                match packed_file_data.decode_packedfile_string_colour_rgba(*index, &mut index) {
+                    Ok(result) => result.to_string(),
+                    Err(_) => "Error".to_owned(),
+                }
+            },
             FieldType::StringU8 => {
                 match packed_file_data.decode_packedfile_string_u8(*index, &mut index) {
                     Ok(result) => result,
@@ -1233,6 +1241,12 @@ impl PackedFileDecoderView {
                     Err(_) => "Error".to_owned(),
                 }
             },
+            FieldType::OptionalI32 => {
+                match packed_file_data.decode_packedfile_optional_integer_i32(*index, &mut index) {
+                    Ok(result) => result.map(|result| result.to_string()).unwrap_or_default(),
+                    Err(_) => "Error".to_owned(),
+                }
+            },
             FieldType::SequenceU16(_) => {
                 match packed_file_data.decode_packedfile_integer_i16(*index, &mut index) {
                     Ok(result) => result.to_string(),
@@ -1293,10 +1307,12 @@ impl PackedFileDecoderView {
                         "I32" => FieldType::I32,
                         "I64" => FieldType::I64,
                         "ColourRGB" => FieldType::ColourRGB,
+                        "ColourRGBA" => FieldType::ColourRGBA,
                         "StringU8" => FieldType::StringU8,
                         "StringU16" => FieldType::StringU16,
                         "OptionalStringU8" => FieldType::OptionalStringU8,
                         "OptionalStringU16" => FieldType::OptionalStringU16,
+                        "OptionalI32" => FieldType::OptionalI32,
                         "SequenceU16" => FieldType::SequenceU16(Definition::new(-100)),
                         "SequenceU32" => FieldType::SequenceU32(Definition::new(-100)),
                         _ => unimplemented!("{}", &*row_type.data_1a(0).to_string().to_std_string())
@@ -1436,10 +1452,12 @@ impl PackedFileDecoderView {
                     "I32" => FieldType::I32,
                     "I64" => FieldType::I64,
                     "ColourRGB" => FieldType::ColourRGB,
+                    "ColourRGBA" => FieldType::ColourRGBA,
                     "StringU8" => FieldType::StringU8,
                     "StringU16" => FieldType::StringU16,
                     "OptionalStringU8" => FieldType::OptionalStringU8,
                     "OptionalStringU16" => FieldType::OptionalStringU16,
+                    "OptionalI32" => FieldType::OptionalI32,
                     "SequenceU16" => FieldType::SequenceU16(Definition::new(-100)),
                     "SequenceU32" => FieldType::SequenceU32({
                         let mut definition = Definition::new(-100);
@@ -1652,6 +1670,10 @@ impl PackedFileDecoderView {
                                 let value = data.decode_packedfile_integer_u32(index, &mut index).unwrap();
                                 values_position.push(DecodedData::ColourRGB(value));
                             },
+                            FieldType::ColourRGBA => {
+                                let value = data.decode_packedfile_integer_u32(index, &mut index).unwrap();
+                                values_position.push(DecodedData::ColourRGBA(value));
+                            },
                             FieldType::StringU8 => {
                                 let value = data.decode_packedfile_string_u8(index, &mut index).unwrap();
                                 values_position.push(DecodedData::StringU8(value));
@@ -1900,10 +1922,12 @@ unsafe fn configure_table_view(table_view: &QBox<QTreeView>) {
     list.append_q_string(&QString::from_std_str("I32"));
     list.append_q_string(&QString::from_std_str("I64"));
     list.append_q_string(&QString::from_std_str("ColourRGB"));
+    list.append_q_string(&QString::from_std_str("ColourRGBA"));
     list.append_q_string(&QString::from_std_str("StringU8"));
     list.append_q_string(&QString::from_std_str("StringU16"));
     list.append_q_string(&QString::from_std_str("OptionalStringU8"));
     list.append_q_string(&QString::from_std_str("OptionalStringU16"));
+    list.append_q_string(&QString::from_std_str("OptionalI32"));
     list.append_q_string(&QString::from_std_str("SequenceU16"));
     list.append_q_string(&QString::from_std_str("SequenceU32"));
     new_combobox_item_delegate_safe(&table_view.static_upcast::<QObject>().as_ptr(), 2, list.as_ptr(), false, &QTimer::new_0a().into_ptr(), false);
@@ -47,6 +47,7 @@ use crate::AppUI;
 use crate::dependencies_ui::DependenciesUI;
 use crate::diagnostics_ui::DiagnosticsUI;
 use crate::global_search_ui::GlobalSearchUI;
+use crate::locale::qtr;
 use crate::packedfile_views::DataSource;
 use crate::packedfile_views::esf::esftree::*;
 use crate::packedfile_views::PackFileContentsUI;
@@ -140,13 +141,7 @@ impl ESFDetailedView {
 
         // Save the current data to its node before loading new data.
         self.save_to_tree_node(tree_view);
-        while !layout.item_at(0).is_null() {
-            let widget = layout.take_at(0).widget();
-            widget.delete_later();
-        }
-
-        // Reset the detailed view's data.
-        self.data_types.clear();
+        self.clear(parent_widget);
 
         let filter: QPtr<QSortFilterProxyModel> = tree_view.model().static_downcast();
         let model: QPtr<QStandardItemModel> = filter.source_model().static_downcast();
@@ -158,7 +153,7 @@ impl ESFDetailedView {
             match node {
                 NodeType::Invalid => unimplemented!(),
                 NodeType::Bool(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_bool"), parent_widget);
                     let widget = QCheckBox::from_q_widget(parent_widget);
                     widget.set_checked(*value.get_ref_value());
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -167,7 +162,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Boolean(widget));
                 },
                 NodeType::I8(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i8"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(i8::MAX.into());
                     widget.set_minimum(i8::MIN.into());
@@ -178,7 +173,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I8(widget));
                 },
                 NodeType::I16(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i16"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(i16::MAX.into());
                     widget.set_minimum(i16::MIN.into());
@@ -189,7 +184,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I16(widget));
                 },
                 NodeType::I32(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i32"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
                     widget.set_maximum(i32::MAX);
@@ -200,7 +195,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I32(widget));
                 },
                 NodeType::I64(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i64"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(i32::MAX);
                     widget.set_minimum(i32::MIN);
@@ -211,7 +206,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I64(widget));
                 },
                 NodeType::U8(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u8"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(u8::MAX.into());
                     widget.set_value(*value as i32);
@@ -221,7 +216,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U8(widget));
                 },
                 NodeType::U16(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u16"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(u16::MAX.into());
                     widget.set_value(*value as i32);
@@ -231,7 +226,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U16(widget));
                 },
                 NodeType::U32(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u32"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(u32::MAX as i32);
                     widget.set_value(*value.get_ref_value() as i32);
@@ -241,7 +236,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U32(widget));
                 },
                 NodeType::U64(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u64"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_maximum(u32::MAX as i32);
                     widget.set_value(*value as i32);
@@ -251,7 +246,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U64(widget));
                 },
                 NodeType::F32(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_f32"), parent_widget);
                     let widget = QDoubleSpinBox::new_1a(parent_widget);
                     widget.set_maximum(f32::MAX.into());
                     widget.set_minimum(f32::MIN.into());
@@ -262,7 +257,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::F32(widget));
                 },
                 NodeType::F64(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_f64"), parent_widget);
                     let widget = QDoubleSpinBox::new_1a(parent_widget);
                     widget.set_maximum(f64::MAX);
                     widget.set_minimum(f64::MIN);
@@ -273,12 +268,12 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::F64(widget));
                 },
                 NodeType::Coord2d(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_coord_2d"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let widget_layout = create_grid_layout(widget.static_upcast());
 
-                    let x_label = QLabel::from_q_string_q_widget(&QString::from_std_str("X"), &widget);
-                    let y_label = QLabel::from_q_string_q_widget(&QString::from_std_str("Y"), &widget);
+                    let x_label = QLabel::from_q_string_q_widget(&qtr("esf_coord_x"), &widget);
+                    let y_label = QLabel::from_q_string_q_widget(&qtr("esf_coord_y"), &widget);
                     let x_spinbox = QDoubleSpinBox::new_1a(&widget);
                     let y_spinbox = QDoubleSpinBox::new_1a(&widget);
 
@@ -296,13 +291,13 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Coord2d((x_spinbox, y_spinbox)));
                 },
                 NodeType::Coord3d(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_coord_3d"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let widget_layout = create_grid_layout(widget.static_upcast());
 
-                    let x_label = QLabel::from_q_string_q_widget(&QString::from_std_str("X"), &widget);
-                    let y_label = QLabel::from_q_string_q_widget(&QString::from_std_str("Y"), &widget);
-                    let z_label = QLabel::from_q_string_q_widget(&QString::from_std_str("Z"), &widget);
+                    let x_label = QLabel::from_q_string_q_widget(&qtr("esf_coord_x"), &widget);
+                    let y_label = QLabel::from_q_string_q_widget(&qtr("esf_coord_y"), &widget);
+                    let z_label = QLabel::from_q_string_q_widget(&qtr("esf_coord_z"), &widget);
                     let x_spinbox = QDoubleSpinBox::new_1a(&widget);
                     let y_spinbox = QDoubleSpinBox::new_1a(&widget);
                     let z_spinbox = QDoubleSpinBox::new_1a(&widget);
@@ -324,7 +319,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Coord3d((x_spinbox, y_spinbox, z_spinbox)));
                 },
                 NodeType::Utf16(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_utf16"), parent_widget);
                     let widget = QLineEdit::from_q_widget(parent_widget);
                     widget.set_text(&QString::from_std_str(&value));
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -333,7 +328,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::UTF16(widget));
                 },
                 NodeType::Ascii(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_ascii"), parent_widget);
                     let widget = QLineEdit::from_q_widget(parent_widget);
                     widget.set_text(&QString::from_std_str(&value));
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -342,7 +337,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::UTF8(widget));
                 },
                 NodeType::Angle(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_angle"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_value(*value as i32);
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -351,7 +346,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Angle(widget));
                 },
                 NodeType::Unknown21(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_unknown_21"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_value(*value as i32);
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -360,7 +355,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Unknown21(widget));
                 },
                 NodeType::Unknown23(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_unknown_23"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_value(*value as i32);
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -369,7 +364,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Unknown23(widget));
                 },
                 NodeType::Unknown25(value) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_unknown_25"), parent_widget);
                     let widget = QSpinBox::new_1a(parent_widget);
                     widget.set_value(*value as i32);
                     layout.add_widget_5a(&label, row as i32, 0, 1, 1);
@@ -378,7 +373,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Unknown25(widget));
                 },
                 NodeType::Unknown26(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_unknown_26"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -399,7 +394,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Unknown26(table_view));
                 },
                 NodeType::BoolArray(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_bool_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -420,7 +415,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::BoolArray(table_view));
                 },
                 NodeType::I8Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i8_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -441,7 +436,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I8Array(table_view));
                 },
                 NodeType::I16Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i16_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -462,7 +457,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I16Array(table_view));
                 },
                 NodeType::I32Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i32_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -483,7 +478,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I32Array((table_view, *values.get_ref_optimized())));
                 },
                 NodeType::I64Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_i64_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -504,7 +499,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::I64Array(table_view));
                 },
                 NodeType::U8Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u8_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -525,7 +520,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U8Array(table_view));
                 },
                 NodeType::U16Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u16_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -546,7 +541,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U16Array(table_view));
                 },
                 NodeType::U32Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u32_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -567,7 +562,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U32Array((table_view, *values.get_ref_optimized())));
                 },
                 NodeType::U64Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_u64_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -588,7 +583,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::U64Array(table_view));
                 },
                 NodeType::F32Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_f32_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -609,7 +604,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::F32Array(table_view));
                 },
                 NodeType::F64Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_f64_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -630,7 +625,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::F64Array(table_view));
                 },
                 NodeType::Coord2dArray(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_coord_2d_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -653,7 +648,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Coord2dArray(table_view));
                 },
                 NodeType::Coord3dArray(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_coord_3d_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -678,7 +673,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Coord3dArray(table_view));
                 },
                 NodeType::Utf16Array(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_utf16_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -699,7 +694,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::Utf16Array(table_view));
                 },
                 NodeType::AsciiArray(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_ascii_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -720,7 +715,7 @@ impl ESFDetailedView {
                     self.data_types.push(DataType::AsciiArray(table_view));
                 },
                 NodeType::AngleArray(values) => {
-                    let label = QLabel::from_q_string_q_widget(&QString::from_std_str("label"), parent_widget);
+                    let label = QLabel::from_q_string_q_widget(&qtr("esf_type_angle_array"), parent_widget);
                     let widget = QWidget::new_1a(parent_widget);
                     let _ = create_grid_layout(widget.static_upcast());
 
@@ -747,6 +742,19 @@ impl ESFDetailedView {
         }
     }
 
+    /// This function removes all the data from the detailed view, without saving it to its item in the TreeView.
+    pub unsafe fn clear(&mut self, parent_widget: &QBox<QWidget>) {
+        let layout: QPtr<QGridLayout> = parent_widget.layout().static_downcast();
+        while !layout.item_at(0).is_null() {
+            let widget = layout.take_at(0).widget();
+            widget.delete_later();
+        }
+
+        // Reset the detailed view's data.
+        self.data_types.clear();
+        self.path.write().unwrap().clear();
+    }
+
     /// This function saves the subnodes of a detailed view into their item in the TreeView.
     pub unsafe fn save_to_tree_node(&self, tree_view: &QBox<QTreeView>) {
         if !self.path.read().unwrap().is_empty() {
@@ -985,7 +993,7 @@ impl ESFDetailedView {
                     }
                 }
 
-                item.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(&serde_json::to_string(&nodes).unwrap())), 42);
+                item.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(&serde_json::to_string(&nodes).unwrap())), CHILD_NODES);
             }
         }
     }
@@ -37,10 +37,11 @@ use cpp_core::Ref;
 use rpfm_lib::packedfile::esf::RecordNodeFlags;
 use rpfm_lib::packedfile::esf::{ESF, NodeType};
 
-const ESF_DATA: i32 = 40;
-const CHILDLESS_NODE: i32 = 41;
-const CHILD_NODES: i32 = 42;
-const RECORD_NODE_NAME: i32 = 43;
+// Roles 41 and 42 are checked by the TreeView filter as extra searchable data, so don't store the node's data in them.
+const ESF_DATA: i32 = 50;
+const CHILDLESS_NODE: i32 = 51;
+pub const CHILD_NODES: i32 = 52;
+const RECORD_NODE_NAME: i32 = 53;
 
 //-------------------------------------------------------------------------------//
 //                          Enums & Structs (and trait)
@@ -20,16 +20,22 @@ use qt_widgets::QSplitter;
 use qt_widgets::QTreeView;
 use qt_widgets::QWidget;
 
+use qt_gui::QStandardItem;
 use qt_gui::QStandardItemModel;
 
+use qt_core::q_item_selection_model::SelectionFlag;
+
 use qt_core::CaseSensitivity;
 use qt_core::ContextMenuPolicy;
 use qt_core::QBox;
+use qt_core::QFlags;
 use qt_core::QPtr;
 use qt_core::QRegExp;
 use qt_core::QSortFilterProxyModel;
 use qt_core::QTimer;
 
+use cpp_core::Ptr;
+
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
@@ -49,7 +55,7 @@ use crate::global_search_ui::GlobalSearchUI;
 use crate::locale::qtr;
 use crate::packedfile_views::esf::esftree::*;
 use crate::packedfile_views::esf::slots::PackedFileESFViewSlots;
-use crate::packedfile_views::PackedFileView;
+use crate::packedfile_views::{DataSource, PackedFileView};
 use crate::packedfile_views::PackFileContentsUI;
 use crate::utils::create_grid_layout;
 
@@ -78,10 +84,12 @@ pub struct PackedFileESFView {
     filter_timer_delayed_updates: QBox<QTimer>,
 
     node_data_panel: QBox<QWidget>,
+    apply_button: QBox<QPushButton>,
 
     detailed_view: Arc<RwLock<ESFDetailedView>>,
 
-    _path: Arc<RwLock<Vec<String>>>,
+    path: Arc<RwLock<Vec<String>>>,
+    data_source: Arc<RwLock<DataSource>>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -148,11 +156,19 @@ impl PackedFileESFView {
         tree_layout.add_widget_5a(&filter_autoexpand_matches_button, 2, 0, 1, 1);
         tree_layout.add_widget_5a(&filter_case_sensitive_button, 2, 1, 1, 1);
 
-        let node_data_panel = QWidget::new_1a(&splitter);
+        // The detailed view removes everything in the node's data panel when it changes of node, so keep the apply button out of it.
+        let node_panel = QWidget::new_1a(&splitter);
+        let node_layout = create_grid_layout(node_panel.static_upcast());
+        node_panel.set_minimum_width(250);
+
+        let node_data_panel = QWidget::new_1a(&node_panel);
         let node_data_layout = create_grid_layout(node_data_panel.static_upcast());
         node_data_layout.set_row_stretch(1000, 100);
         node_data_layout.set_column_stretch(1, 100);
-        node_data_panel.set_minimum_width(250);
+
+        let apply_button = QPushButton::from_q_string_q_widget(&qtr("esf_apply_node"), &node_panel);
+        node_layout.add_widget_5a(&node_data_panel, 0, 0, 1, 1);
+        node_layout.add_widget_5a(&apply_button, 1, 0, 1, 1);
 
         let layout: QPtr<QGridLayout> = packed_file_view.get_mut_widget().layout().static_downcast();
         layout.add_widget_5a(&splitter, 0, 0, 1, 1);
@@ -169,10 +185,12 @@ impl PackedFileESFView {
             filter_timer_delayed_updates,
 
             node_data_panel,
+            apply_button,
 
             detailed_view: Arc::new(RwLock::new(ESFDetailedView::default())),
 
-            _path: packed_file_view.get_path_raw()
+            path: packed_file_view.get_path_raw(),
+            data_source: Arc::new(RwLock::new(packed_file_view.get_data_source())),
         });
 
         view.tree_view.update_treeview(true, ESFTreeViewOperation::Build(data));
@@ -194,8 +212,60 @@ impl PackedFileESFView {
     }
 
     /// This function tries to reload the current view with the provided data.
+    ///
+    /// Expanded and selected nodes are kept expanded and selected, as long as they still exist in the new data.
     pub unsafe fn reload_view(&self, data: &ESF) {
+        let model: QPtr<QStandardItemModel> = self.tree_filter.source_model().static_downcast();
+
+        // Get the expanded and selected nodes before rebuilding the tree, so we can restore them later.
+        let mut expanded_paths = vec![];
+        if model.row_count_0a() > 0 {
+            self.get_expanded_paths(model.item_1a(0), &model, &mut expanded_paths);
+        }
+
+        let selected_paths = self.tree_view.get_items_from_selection(true).iter()
+            .map(|item| <QBox<QTreeView> as ESFTree>::get_path_from_item(*item, &model))
+            .collect::<Vec<Vec<String>>>();
+
+        // The detailed view belongs to a node of the old tree, so we have to discard it without saving it.
+        // Otherwise, it'll overwrite the new data with the old one.
+        self.detailed_view.write().unwrap().clear(&self.node_data_panel);
         self.tree_view.update_treeview(true, ESFTreeViewOperation::Build(data.clone()));
+
+        for path in &expanded_paths {
+            if let Some(item) = Self::get_item_if_exists(path, &model) {
+                self.tree_view.expand(&self.tree_filter.map_from_source(&item.index()));
+            }
+        }
+
+        // Selecting the nodes again takes care of reloading the detailed view.
+        let selection_model = self.tree_view.selection_model();
+        for path in &selected_paths {
+            if let Some(item) = Self::get_item_if_exists(path, &model) {
+                selection_model.select_q_model_index_q_flags_selection_flag(&self.tree_filter.map_from_source(&item.index()), QFlags::from(SelectionFlag::Select));
+            }
+        }
+    }
+
+    /// This function gets the paths of the provided item and all its children, if they're expanded.
+    unsafe fn get_expanded_paths(&self, item: Ptr<QStandardItem>, model: &QPtr<QStandardItemModel>, paths: &mut Vec<Vec<String>>) {
+        if self.tree_view.is_expanded(&self.tree_filter.map_from_source(&item.index())) {
+            paths.push(<QBox<QTreeView> as ESFTree>::get_path_from_item(item, model));
+            for row in 0..item.row_count() {
+                self.get_expanded_paths(item.child_1a(row), model, paths);
+            }
+        }
+    }
+
+    /// This function returns the item with the provided path, if it exists.
+    unsafe fn get_item_if_exists(path: &[String], model: &QPtr<QStandardItemModel>) -> Option<Ptr<QStandardItem>> {
+        if model.row_count_0a() == 0 {
+            return None;
+        }
+
+        // An empty path means the root node.
+        let item = if path.is_empty() { model.item_1a(0) } else { <QBox<QTreeView> as ESFTree>::get_item_from_path(path, model) };
+        if <QBox<QTreeView> as ESFTree>::get_path_from_item(item, model) == path { Some(item) } else { None }
     }
 
     /// This function saves the current view to an ESF struct.
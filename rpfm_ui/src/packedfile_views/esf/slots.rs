@@ -19,6 +19,8 @@ use qt_core::SlotNoArgs;
 use qt_core::SlotOfQString;
 use qt_core::SlotOfBool;
 
+use log::info;
+
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -29,7 +31,7 @@ use crate::dependencies_ui::DependenciesUI;
 use crate::diagnostics_ui::DiagnosticsUI;
 use crate::global_search_ui::GlobalSearchUI;
 use crate::packedfile_views::esf::{esftree::ESFTree, PackedFileESFView};
-use crate::packedfile_views::PackFileContentsUI;
+use crate::packedfile_views::{DataSource, PackFileContentsUI, utils::set_modified};
 use crate::utils::check_regex;
 
 //-------------------------------------------------------------------------------//
@@ -45,6 +47,7 @@ pub struct PackedFileESFViewSlots {
     pub filter_check_regex: QBox<SlotOfQString>,
 
     pub open_node: QBox<SlotNoArgs>,
+    pub apply_node: QBox<SlotNoArgs>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -115,6 +118,20 @@ impl PackedFileESFViewSlots {
             }
         ));
 
+        // Slot to save the edited values of the open node back into the tree.
+        let apply_node = SlotNoArgs::new(&view.tree_view, clone!(
+            app_ui,
+            pack_file_contents_ui,
+            view => move || {
+                info!("Triggering `Apply ESF Node` By Slot");
+                view.detailed_view.read().unwrap().save_to_tree_node(&view.tree_view);
+
+                if let DataSource::PackFile = *view.data_source.read().unwrap() {
+                    set_modified(true, &view.path.read().unwrap(), &app_ui, &pack_file_contents_ui);
+                }
+            }
+        ));
+
         // Return the slots, so we can keep them alive for the duration of the view.
         Self {
             filter_trigger,
@@ -124,6 +141,7 @@ impl PackedFileESFViewSlots {
             filter_check_regex,
 
             open_node,
+            apply_node,
         }
     }
 }
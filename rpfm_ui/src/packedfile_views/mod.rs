@@ -28,6 +28,7 @@ use rpfm_error::{ErrorKind, Result};
 use rpfm_lib::packedfile::{DecodedPackedFile, PackedFileType};
 use rpfm_lib::packedfile::table::{animtable::AnimTable, db::DB, loc::Loc, matched_combat::MatchedCombat};
 use rpfm_lib::packedfile::text::Text;
+use rpfm_lib::packedfile::variant_mesh_definition;
 
 use crate::app_ui::AppUI;
 use crate::CENTRAL_COMMAND;
@@ -364,6 +365,14 @@ impl PackedFileView {
                                     let mut text = Text::default();
                                     let widget = view.get_mut_editor();
                                     let string = get_text_safe(widget).to_std_string();
+
+                                    // Variant Mesh Definitions are saved even if they're not valid, but we warn the user about the problem.
+                                    if self.get_path().last().map(|name| name.to_lowercase().ends_with(variant_mesh_definition::EXTENSION)).unwrap_or(false) {
+                                        if let Err(error) = variant_mesh_definition::validate(&string) {
+                                            show_dialog(&app_ui.main_window, error, false);
+                                        }
+                                    }
+
                                     text.set_contents(&string);
                                     DecodedPackedFile::Text(text)
                                 } else { return Err(ErrorKind::PackedFileSaveError(self.get_path()).into()) }
@@ -49,7 +49,7 @@ use crate::pack_tree::{PackTree, TreePathType, TreeViewOperation};
 use crate::packfile_contents_ui::PackFileContentsUI;
 use crate::packedfile_views::DataSource;
 use crate::QString;
-use crate::utils::{show_dialog, check_regex};
+use crate::utils::{show_cancellable_wait_dialog, show_dialog, check_regex};
 use crate::UI_STATE;
 use crate::ui_state::OperationalMode;
 
@@ -1138,8 +1138,14 @@ impl PackFileContentsSlots {
                     // Otherwise, try to import all of them and report the result.
                     else {
                         app_ui.main_window.set_enabled(false);
-                        let receiver = CENTRAL_COMMAND.send_background(Command::MassImportTSV(data.0, data.1));
-                        let response = CentralCommand::recv(&receiver);
+
+                        // This can take a while, so allow the user to cancel it.
+                        let operation_id = CENTRAL_COMMAND.new_operation();
+                        let wait_dialog = show_cancellable_wait_dialog(&app_ui.main_window, "mass_import_in_progress", operation_id);
+
+                        let receiver = CENTRAL_COMMAND.send_background(Command::MassImportTSV(data.0, data.1, operation_id));
+                        let response = CentralCommand::recv_try(&receiver);
+                        wait_dialog.done(1);
                         match response {
 
                             // If it's success....
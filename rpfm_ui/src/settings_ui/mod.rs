@@ -113,6 +113,7 @@ pub struct SettingsUI {
     pub extra_packfile_use_lazy_loading_label: QBox<QLabel>,
     pub extra_packfile_disable_uuid_regeneration_on_db_tables_label: QBox<QLabel>,
     pub extra_packfile_disable_file_previews_label: QBox<QLabel>,
+    pub extra_packfile_validate_packfile_before_save_label: QBox<QLabel>,
     pub ui_global_use_dark_theme_label: QBox<QLabel>,
     pub ui_window_start_maximized_label: QBox<QLabel>,
     pub ui_window_hide_background_icon_label: QBox<QLabel>,
@@ -132,6 +133,7 @@ pub struct SettingsUI {
     pub extra_packfile_use_lazy_loading_checkbox: QBox<QCheckBox>,
     pub extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox: QBox<QCheckBox>,
     pub extra_packfile_disable_file_previews_checkbox: QBox<QCheckBox>,
+    pub extra_packfile_validate_packfile_before_save_checkbox: QBox<QCheckBox>,
     pub ui_global_use_dark_theme_checkbox: QBox<QCheckBox>,
     pub ui_window_start_maximized_checkbox: QBox<QCheckBox>,
     pub ui_window_hide_background_icon_checkbox: QBox<QCheckBox>,
@@ -429,6 +431,9 @@ impl SettingsUI {
         let extra_packfile_disable_file_previews_label = QLabel::from_q_string_q_widget(&qtr("settings_disable_file_previews"), &general_frame);
         let extra_packfile_disable_file_previews_checkbox = QCheckBox::from_q_widget(&general_frame);
 
+        let extra_packfile_validate_packfile_before_save_label = QLabel::from_q_string_q_widget(&qtr("settings_validate_packfile_before_save"), &general_frame);
+        let extra_packfile_validate_packfile_before_save_checkbox = QCheckBox::from_q_widget(&general_frame);
+
         let ui_global_use_dark_theme_label = QLabel::from_q_string_q_widget(&qtr("settings_ui_dark_theme"), &general_frame);
         let ui_global_use_dark_theme_checkbox = QCheckBox::from_q_widget(&general_frame);
 
@@ -480,6 +485,9 @@ impl SettingsUI {
         general_grid.add_widget_5a(&extra_packfile_disable_file_previews_label, 10, 0, 1, 1);
         general_grid.add_widget_5a(&extra_packfile_disable_file_previews_checkbox, 10, 1, 1, 1);
 
+        general_grid.add_widget_5a(&extra_packfile_validate_packfile_before_save_label, 11, 0, 1, 1);
+        general_grid.add_widget_5a(&extra_packfile_validate_packfile_before_save_checkbox, 11, 1, 1, 1);
+
         general_grid.add_widget_5a(&ui_global_use_dark_theme_label, 13, 0, 1, 1);
         general_grid.add_widget_5a(&ui_global_use_dark_theme_checkbox, 13, 1, 1, 1);
 
@@ -792,6 +800,7 @@ impl SettingsUI {
             extra_packfile_use_lazy_loading_label,
             extra_packfile_disable_uuid_regeneration_on_db_tables_label,
             extra_packfile_disable_file_previews_label,
+            extra_packfile_validate_packfile_before_save_label,
             ui_global_use_dark_theme_label,
             ui_window_start_maximized_label,
             ui_window_hide_background_icon_label,
@@ -811,6 +820,7 @@ impl SettingsUI {
             extra_packfile_use_lazy_loading_checkbox,
             extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox,
             extra_packfile_disable_file_previews_checkbox,
+            extra_packfile_validate_packfile_before_save_checkbox,
             ui_global_use_dark_theme_checkbox,
             ui_window_start_maximized_checkbox,
             ui_window_hide_background_icon_checkbox,
@@ -964,6 +974,7 @@ impl SettingsUI {
         self.extra_packfile_use_lazy_loading_checkbox.set_checked(settings.settings_bool["use_lazy_loading"]);
         self.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.set_checked(settings.settings_bool["disable_uuid_regeneration_on_db_tables"]);
         self.extra_packfile_disable_file_previews_checkbox.set_checked(settings.settings_bool["disable_file_previews"]);
+        self.extra_packfile_validate_packfile_before_save_checkbox.set_checked(settings.settings_bool["validate_packfile_before_save"]);
         self.general_packfile_treeview_resize_to_fit_checkbox.set_checked(settings.settings_bool["packfile_treeview_resize_to_fit"]);
         self.general_packfile_treeview_expand_treeview_when_adding_items_checkbox.set_checked(settings.settings_bool["expand_treeview_when_adding_items"]);
 
@@ -1107,6 +1118,7 @@ impl SettingsUI {
         settings.settings_bool.insert("use_lazy_loading".to_owned(), self.extra_packfile_use_lazy_loading_checkbox.is_checked());
         settings.settings_bool.insert("disable_uuid_regeneration_on_db_tables".to_owned(), self.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.is_checked());
         settings.settings_bool.insert("disable_file_previews".to_owned(), self.extra_packfile_disable_file_previews_checkbox.is_checked());
+        settings.settings_bool.insert("validate_packfile_before_save".to_owned(), self.extra_packfile_validate_packfile_before_save_checkbox.is_checked());
         settings.settings_bool.insert("packfile_treeview_resize_to_fit".to_owned(), self.general_packfile_treeview_resize_to_fit_checkbox.is_checked());
         settings.settings_bool.insert("expand_treeview_when_adding_items".to_owned(), self.general_packfile_treeview_expand_treeview_when_adding_items_checkbox.is_checked());
 
@@ -63,6 +63,7 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     let extra_packfile_use_lazy_loading_tip = qtr("tt_extra_packfile_use_lazy_loading_tip");
     let extra_disable_uuid_regeneration_on_db_tables_label_tip = qtr("tt_extra_disable_uuid_regeneration_on_db_tables_label_tip");
     let extra_packfile_disable_file_previews_tip = qtr("tt_settings_disable_file_previews_tip");
+    let extra_packfile_validate_packfile_before_save_tip = qtr("tt_settings_validate_packfile_before_save_tip");
 
     settings_ui.extra_packfile_autosave_amount_label.set_tool_tip(&autosave_amount_tip);
     settings_ui.extra_packfile_autosave_amount_spinbox.set_tool_tip(&autosave_amount_tip);
@@ -81,6 +82,8 @@ pub unsafe fn set_tips(settings_ui: &Rc<SettingsUI>) {
     settings_ui.extra_packfile_disable_uuid_regeneration_on_db_tables_checkbox.set_tool_tip(&extra_disable_uuid_regeneration_on_db_tables_label_tip);
     settings_ui.extra_packfile_disable_file_previews_label.set_tool_tip(&extra_packfile_disable_file_previews_tip);
     settings_ui.extra_packfile_disable_file_previews_checkbox.set_tool_tip(&extra_packfile_disable_file_previews_tip);
+    settings_ui.extra_packfile_validate_packfile_before_save_label.set_tool_tip(&extra_packfile_validate_packfile_before_save_tip);
+    settings_ui.extra_packfile_validate_packfile_before_save_checkbox.set_tool_tip(&extra_packfile_validate_packfile_before_save_tip);
 
     //-----------------------------------------------//
     // `Debug` tips.
@@ -32,6 +32,9 @@ pub unsafe fn set_connections(ui: &ToolFactionPainter, slots: &ToolFactionPainte
     ui.uniform_restore_initial_values_button.released().connect(&slots.uniform_restore_initial_values);
     ui.uniform_restore_vanilla_values_button.released().connect(&slots.uniform_restore_vanilla_values);
 
+    ui.banner_colour_primary.current_index_changed().connect(&slots.preview_banner_colours);
+    ui.banner_colour_secondary.current_index_changed().connect(&slots.preview_banner_colours);
+
     ui.tool.button_box.button(StandardButton::Cancel).released().connect(ui.tool.get_ref_dialog().slot_close());
     ui.tool.button_box.button(StandardButton::Ok).released().connect(ui.tool.get_ref_dialog().slot_accept());
 }
@@ -24,6 +24,7 @@ use qt_widgets::QPushButton;
 use qt_gui::QColor;
 use qt_gui::QIcon;
 use qt_gui::QPixmap;
+use qt_gui::q_color::NameFormat;
 use qt_gui::QStandardItem;
 use qt_gui::QStandardItemModel;
 
@@ -251,10 +252,17 @@ impl ToolFactionPainter {
         view.load_data()?;
 
         // If we hit ok, save the data back to the PackFile.
-        if view.tool.get_ref_dialog().exec() == 1 {
+        let result = view.tool.get_ref_dialog().exec();
+        if result == 1 {
             view.save_data(app_ui, pack_file_contents_ui, global_search_ui, diagnostics_ui, dependencies_ui)?;
         }
 
+        // If we asked for a template reload (debug only), drop this dialog and build the tool again.
+        else if Tool::is_template_reload_requested(result) {
+            view.tool.get_ref_main_widget().delete_later();
+            return Self::new(app_ui, pack_file_contents_ui, global_search_ui, diagnostics_ui, dependencies_ui);
+        }
+
         // If nothing failed, it means we have successfully saved the data back to disk, or canceled.
         Ok(())
     }
@@ -446,6 +454,19 @@ impl ToolFactionPainter {
         if !missing_fields.is_empty() {
             show_message_warning(&self.tool.message_widget, ErrorKind::ToolEntryDataNotFound(missing_fields.join(", ")));
         }
+
+        self.preview_banner_colours();
+    }
+
+    /// This function updates the colour preview with the banner colours currently in the detailed view.
+    ///
+    /// This doesn't save anything, not even to the faction list. Data only reaches the PackFile when the tool is accepted.
+    pub unsafe fn preview_banner_colours(&self) {
+        let primary = get_color_safe(&self.get_ref_banner_colour_primary().as_ptr().static_upcast()).name_1a(NameFormat::HexRgb).to_std_string();
+        let secondary = get_color_safe(&self.get_ref_banner_colour_secondary().as_ptr().static_upcast()).name_1a(NameFormat::HexRgb).to_std_string();
+        if let Err(error) = self.tool.preview_colours(&primary, &secondary) {
+            show_message_warning(&self.tool.message_widget, error);
+        }
     }
 
     /// This function saves the data of the detailed view to its item in the faction list.
@@ -490,6 +511,8 @@ impl ToolFactionPainter {
         if let Some(banner_tertiary) = data.get("banner_initial_tertiary") {
             set_color_safe(&self.get_ref_banner_colour_tertiary().as_ptr().static_upcast(), &QColor::from_q_string(&QString::from_std_str(format!("#{}", banner_tertiary))).as_ptr());
         }
+
+        self.preview_banner_colours();
     }
 
     /// This function restores the banner colours to its vanilla values when we opened the tool.
@@ -509,6 +532,8 @@ impl ToolFactionPainter {
         if let Some(banner_tertiary) = data.get("banner_vanilla_tertiary") {
             set_color_safe(&self.get_ref_banner_colour_tertiary().as_ptr().static_upcast(), &QColor::from_q_string(&QString::from_std_str(format!("#{}", banner_tertiary))).as_ptr());
         }
+
+        self.preview_banner_colours();
     }
 
     /// This function restores the uniform colours to its initial values when we opened the tool.
@@ -14,6 +14,7 @@ Module with all the code related to `ToolFactionPainterSlots`.
 
 use qt_core::QBox;
 use qt_core::SlotNoArgs;
+use qt_core::SlotOfInt;
 use qt_core::SlotOfQItemSelectionQItemSelection;
 
 use std::rc::Rc;
@@ -35,6 +36,7 @@ pub struct ToolFactionPainterSlots {
     pub banner_restore_vanilla_values: QBox<SlotNoArgs>,
     pub uniform_restore_initial_values: QBox<SlotNoArgs>,
     pub uniform_restore_vanilla_values: QBox<SlotNoArgs>,
+    pub preview_banner_colours: QBox<SlotOfInt>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -102,6 +104,12 @@ impl ToolFactionPainterSlots {
             }
         ));
 
+        let preview_banner_colours = SlotOfInt::new(ui.tool.get_ref_main_widget(), clone!(
+            ui => move |_| {
+                ui.preview_banner_colours();
+            }
+        ));
+
         ToolFactionPainterSlots {
             delayed_updates,
             load_data_to_detailed_view,
@@ -110,6 +118,7 @@ impl ToolFactionPainterSlots {
             banner_restore_vanilla_values,
             uniform_restore_initial_values,
             uniform_restore_vanilla_values,
+            preview_banner_colours,
         }
     }
 }
@@ -14,6 +14,7 @@ Module with all the code for managing the UI.
 This module contains the code to manage the main UI and store all his slots.
 !*/
 
+use qt_widgets::QAction;
 use qt_widgets::QCheckBox;
 use qt_widgets::QComboBox;
 use qt_widgets::QDialogButtonBox;
@@ -26,12 +27,17 @@ use qt_widgets::QTextEdit;
 use qt_widgets::QWidget;
 
 use qt_gui::QColor;
+use qt_gui::QKeySequence;
+use qt_gui::QPainter;
+use qt_gui::QPixmap;
 use qt_gui::q_color::NameFormat;
 
 use qt_core::QBox;
 use qt_core::QObject;
 use qt_core::QPtr;
 use qt_core::QString;
+use qt_core::ShortcutContext;
+use qt_core::SlotNoArgs;
 
 use qt_ui_tools::QUiLoader;
 
@@ -104,6 +110,15 @@ macro_rules! get_data_from_all_sources {
 pub mod faction_painter;
 pub mod unit_editor;
 
+/// Result code the Tool's dialog returns when the debug-only "Reload Template" action is triggered.
+const TEMPLATE_RELOAD_RESULT: i32 = 2;
+
+/// Width of the pixmap used to preview colours.
+const COLOUR_PREVIEW_WIDTH: i32 = 64;
+
+/// Height of the pixmap used to preview colours.
+const COLOUR_PREVIEW_HEIGHT: i32 = 42;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -170,6 +185,19 @@ impl Tool {
         // Close the message widget, as by default is open.
         kmessage_widget_close_safe(&message_widget.as_ptr());
 
+        // On debug builds, add a shortcut (F5) to close the dialog asking for the template to be reloaded.
+        // The tool is then rebuilt from scratch, so any change to the template is visible without restarting RPFM.
+        if cfg!(debug_assertions) {
+            let reload_template = QAction::from_q_string_q_object(&QString::from_std_str("Reload Template"), &main_widget);
+            reload_template.set_shortcut(&QKeySequence::from_q_string(&QString::from_std_str("F5")));
+            reload_template.set_shortcut_context(ShortcutContext::WidgetWithChildrenShortcut);
+            main_widget.add_action(&reload_template);
+
+            let dialog: QPtr<QDialog> = main_widget.static_downcast();
+            let slot_reload_template = SlotNoArgs::new(&main_widget, move || dialog.done(TEMPLATE_RELOAD_RESULT));
+            reload_template.triggered().connect(&slot_reload_template);
+        }
+
         // Dedup the paths.
         let used_paths = PathType::dedup(paths);
 
@@ -188,6 +216,14 @@ impl Tool {
         self.main_widget.static_downcast::<QDialog>()
     }
 
+    /// This function returns if the provided dialog result means the user asked for the template to be reloaded.
+    ///
+    /// Only possible on debug builds. When this returns true, the tool should discard its current dialog and build itself again,
+    /// so the template is re-read from disk and all the widgets (common and tool-specific) are re-wired.
+    pub fn is_template_reload_requested(result: i32) -> bool {
+        cfg!(debug_assertions) && result == TEMPLATE_RELOAD_RESULT
+    }
+
     /// This function sets the title of the Tool's window.
     pub unsafe fn set_title(&self, title: &str) {
         self.get_ref_dialog().set_window_title(&QString::from_std_str(title));
@@ -314,6 +350,26 @@ impl Tool {
         crate::utils::find_widget(main_widget, widget_name)
     }
 
+    /// This function renders a preview of the provided primary/secondary colours into the `colour_preview_label` of the template.
+    ///
+    /// Colours are expected in the same hex format we use in the tables, with or without the leading `#`.
+    /// This only touches the preview. Nothing gets written to the PackFile until the tool is saved.
+    pub unsafe fn preview_colours(&self, primary: &str, secondary: &str) -> Result<()> {
+        let preview_label: QPtr<QLabel> = self.find_widget("colour_preview_label")?;
+        let primary = QColor::from_q_string(&QString::from_std_str(format!("#{}", primary.trim_start_matches('#'))));
+        let secondary = QColor::from_q_string(&QString::from_std_str(format!("#{}", secondary.trim_start_matches('#'))));
+
+        // Primary colour on the top half of the flag, secondary on the bottom one.
+        let pixmap = QPixmap::from_2_int(COLOUR_PREVIEW_WIDTH, COLOUR_PREVIEW_HEIGHT);
+        let painter = QPainter::new_1a(&pixmap);
+        painter.fill_rect_5_int_q_color(0, 0, COLOUR_PREVIEW_WIDTH, COLOUR_PREVIEW_HEIGHT / 2, &primary);
+        painter.fill_rect_5_int_q_color(0, COLOUR_PREVIEW_HEIGHT / 2, COLOUR_PREVIEW_WIDTH, COLOUR_PREVIEW_HEIGHT - COLOUR_PREVIEW_HEIGHT / 2, &secondary);
+        painter.end();
+
+        preview_label.set_pixmap(&pixmap);
+        Ok(())
+    }
+
     /// This function gets the data needed for the tool from a DB table in a generic way.
     ///
     /// Useful for tables of which we can modify any of its columns. If you need to only change some of their columns, use a custom function.
@@ -472,107 +528,117 @@ impl Tool {
 
         // Get the table definition from its first entry, if there is one.
         if let Some(first) = data.first() {
-            if let Some(definition) = first.get(&definition_key) {
-                let mut table = DB::new(&table_name_end_tables, None, &serde_json::from_str(definition)?);
+            let definition = match first.get(&definition_key) {
+                Some(definition) => serde_json::from_str(definition)?,
 
-                // Generate the table's data from empty rows + our data.
-                let table_fields = table.get_ref_definition().get_fields_processed();
-                let table_data = data.par_iter()
-                    .filter_map(|row_data| {
+                // If the data doesn't carry its definition, use the newest one we have in the schema.
+                None => match &*SCHEMA.read().unwrap() {
+                    Some(schema) => schema.get_ref_last_definition_db_from_schema(&table_name_end_tables)?.clone(),
+                    None => return Err(ErrorKind::SchemaNotFound.into()),
+                },
+            };
+
+            let mut table = DB::new(&table_name_end_tables, None, &definition);
+
+            // Generate the table's data from empty rows + our data.
+            let table_fields = table.get_ref_definition().get_fields_processed();
+            let table_data = data.par_iter()
+                .filter_map(|row_data| {
 
-                        // Try to search for the key value for our table.
-                        let row_key_name = format!("{}_{}", table_name, keys[0]);
-                        match row_data.get(&row_key_name) {
-                            Some(row_key) => {
+                    // Try to search for the key value for our table.
+                    let row_key_name = format!("{}_{}", table_name, keys[0]);
+                    match row_data.get(&row_key_name) {
+                        Some(row_key) => {
 
-                                // If found but empty, ignore the entire row.
-                                if row_key.is_empty() {
-                                    return None;
+                            // If found but empty, ignore the entire row.
+                            if row_key.is_empty() {
+                                return None;
+                            }
+
+                            let mut row = table.get_new_row();
+                            for (index, field) in table_fields.iter().enumerate() {
+
+                                // For each field, check if we have data for it, and replace the "empty" row's data with it. Skip invalid values
+                                if let Some(value) = row_data.get(&format!("{}_{}", table_name, field.get_name())) {
+                                    row[index] = match field.get_field_type() {
+                                        FieldType::Boolean => DecodedData::Boolean(value.parse().ok()?),
+                                        FieldType::F32 => DecodedData::F32(value.parse().ok()?),
+                                        FieldType::I16 => DecodedData::I16(value.parse().ok()?),
+                                        FieldType::I32 => DecodedData::I32(value.parse().ok()?),
+                                        FieldType::I64 => DecodedData::I64(value.parse().ok()?),
+                                        FieldType::StringU8 => DecodedData::StringU8(value.to_owned()),
+                                        FieldType::StringU16 => DecodedData::StringU16(value.to_owned()),
+                                        FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(value.to_owned()),
+                                        FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(value.to_owned()),
+                                        FieldType::OptionalI32 => DecodedData::OptionalI32(if value.is_empty() { None } else { Some(value.parse().ok()?) }),
+                                        _ => unimplemented!()
+                                    };
                                 }
+                            }
+
+                            Some(vec![row])
+                        }
+
+                        // If not found, it may be a 1-many relation. Look for keys beginning with it.
+                        None => {
+                            let mut rows = vec![];
+                            let row_key_name_with_bar = format!("{}|", row_key_name);
+                            let keys = row_data.iter().filter_map(|(key, _)|
+
+                                // We need to get the subkey from the key, not from the value!!!
+                                if key.starts_with(&row_key_name_with_bar) {
+                                    let subkeys = key.split('|').collect::<Vec<&str>>();
+                                    if subkeys.len() > 1 {
+                                        Some(subkeys[1..].join("|"))
+                                    }
+
+                                    // This is really an error.
+                                    else {
+                                        None
+                                    }
+                                } else {
+                                    None
+                                }).collect::<Vec<String>>();
 
+                            for key in &keys {
                                 let mut row = table.get_new_row();
                                 for (index, field) in table_fields.iter().enumerate() {
 
                                     // For each field, check if we have data for it, and replace the "empty" row's data with it. Skip invalid values
-                                    if let Some(value) = row_data.get(&format!("{}_{}", table_name, field.get_name())) {
+                                    let row_data_key_name = format!("{}_{}|{}", table_name, field.get_name(), key);
+                                    if let Some(value) = row_data.iter().find_map(|(key, value)| if key.starts_with(&row_data_key_name) { Some(value) } else { None }) {
+
+                                        // If our key is "*" and we're on the key field, use an empty value.
+                                        let value = if field.get_name() == keys[0] && value == "*" { "".to_owned() } else { value.to_owned() };
+
                                         row[index] = match field.get_field_type() {
                                             FieldType::Boolean => DecodedData::Boolean(value.parse().ok()?),
                                             FieldType::F32 => DecodedData::F32(value.parse().ok()?),
                                             FieldType::I16 => DecodedData::I16(value.parse().ok()?),
                                             FieldType::I32 => DecodedData::I32(value.parse().ok()?),
                                             FieldType::I64 => DecodedData::I64(value.parse().ok()?),
-                                            FieldType::StringU8 => DecodedData::StringU8(value.to_owned()),
-                                            FieldType::StringU16 => DecodedData::StringU16(value.to_owned()),
-                                            FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(value.to_owned()),
-                                            FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(value.to_owned()),
+                                            FieldType::StringU8 => DecodedData::StringU8(value),
+                                            FieldType::StringU16 => DecodedData::StringU16(value),
+                                            FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(value),
+                                            FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(value),
+                                            FieldType::OptionalI32 => DecodedData::OptionalI32(if value.is_empty() { None } else { Some(value.parse().ok()?) }),
                                             _ => unimplemented!()
                                         };
                                     }
                                 }
-
-                                Some(vec![row])
+                                rows.push(row);
                             }
 
-                            // If not found, it may be a 1-many relation. Look for keys beginning with it.
-                            None => {
-                                let mut rows = vec![];
-                                let row_key_name_with_bar = format!("{}|", row_key_name);
-                                let keys = row_data.iter().filter_map(|(key, _)|
-
-                                    // We need to get the subkey from the key, not from the value!!!
-                                    if key.starts_with(&row_key_name_with_bar) {
-                                        let subkeys = key.split('|').collect::<Vec<&str>>();
-                                        if subkeys.len() > 1 {
-                                            Some(subkeys[1..].join("|"))
-                                        }
-
-                                        // This is really an error.
-                                        else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    }).collect::<Vec<String>>();
-
-                                for key in &keys {
-                                    let mut row = table.get_new_row();
-                                    for (index, field) in table_fields.iter().enumerate() {
-
-                                        // For each field, check if we have data for it, and replace the "empty" row's data with it. Skip invalid values
-                                        let row_data_key_name = format!("{}_{}|{}", table_name, field.get_name(), key);
-                                        if let Some(value) = row_data.iter().find_map(|(key, value)| if key.starts_with(&row_data_key_name) { Some(value) } else { None }) {
-
-                                            // If our key is "*" and we're on the key field, use an empty value.
-                                            let value = if field.get_name() == keys[0] && value == "*" { "".to_owned() } else { value.to_owned() };
-
-                                            row[index] = match field.get_field_type() {
-                                                FieldType::Boolean => DecodedData::Boolean(value.parse().ok()?),
-                                                FieldType::F32 => DecodedData::F32(value.parse().ok()?),
-                                                FieldType::I16 => DecodedData::I16(value.parse().ok()?),
-                                                FieldType::I32 => DecodedData::I32(value.parse().ok()?),
-                                                FieldType::I64 => DecodedData::I64(value.parse().ok()?),
-                                                FieldType::StringU8 => DecodedData::StringU8(value),
-                                                FieldType::StringU16 => DecodedData::StringU16(value),
-                                                FieldType::OptionalStringU8 => DecodedData::OptionalStringU8(value),
-                                                FieldType::OptionalStringU16 => DecodedData::OptionalStringU16(value),
-                                                _ => unimplemented!()
-                                            };
-                                        }
-                                    }
-                                    rows.push(row);
-                                }
-
-                                Some(rows)
-                            }
+                            Some(rows)
                         }
-                    })
-                    .flatten()
-                    .collect::<Vec<Vec<DecodedData>>>();
+                    }
+                })
+                .flatten()
+                .collect::<Vec<Vec<DecodedData>>>();
 
-                table.set_table_data(&table_data)?;
-                let path = vec!["db".to_owned(), table_name_end_tables.to_owned(), file_name.to_owned()];
-                Ok(PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &path))
-            } else { Err(ErrorKind::Impossibru.into()) }
+            table.set_table_data(&table_data)?;
+            let path = vec!["db".to_owned(), table_name_end_tables.to_owned(), file_name.to_owned()];
+            Ok(PackedFile::new_from_decoded(&DecodedPackedFile::DB(table), &path))
         } else { Err(ErrorKind::Impossibru.into()) }
     }
 
@@ -705,10 +771,20 @@ impl Tool {
         match data.get(&definition_name) {
             Some(definition) => {
                 let definition: Definition = serde_json::from_str(&definition).unwrap();
+
+                // Default values are resolved against the dependencies, so new entries start with valid references.
+                let receiver = CENTRAL_COMMAND.send_background(Command::GetDefaultValuesFromDefinition(definition.clone()));
+                let response = CentralCommand::recv(&receiver);
+                let default_values = match response {
+                    Response::HashMapStringString(data) => data,
+                    _ => panic!("{}{:?}", THREADS_COMMUNICATION_ERROR, response),
+                };
+
                 definition.get_fields_processed()
                     .iter()
                     .filter(|field| !fields_to_ignore.contains(&field.get_name()))
                     .for_each(|field| {
+                        let default_value = default_values.get(field.get_name());
 
                         // First, load the field's label. If it uses a custom one, set it after this function.
                         let label_name = format!("{}_{}_label", table_name, field.get_name());
@@ -731,7 +807,7 @@ impl Tool {
                                         match data.get(&field_key_name) {
                                             Some(data) => widget.set_current_text(&QString::from_std_str(data)),
                                             None => {
-                                                if let Some(default_value) = field.get_default_value(None) {
+                                                if let Some(default_value) = default_value {
                                                     widget.set_current_text(&QString::from_std_str(default_value));
                                                 }
                                             }
@@ -759,7 +835,7 @@ impl Tool {
                                                         }
                                                     },
                                                     None => {
-                                                        if let Some(default_value) = field.get_default_value(None) {
+                                                        if let Some(default_value) = default_value {
                                                             if let Ok(value) = default_value.parse::<bool>() {
                                                                 widget.set_checked(value);
                                                             }
@@ -791,7 +867,7 @@ impl Tool {
                                                         }
                                                     },
                                                     None => {
-                                                        if let Some(default_value) = field.get_default_value(None) {
+                                                        if let Some(default_value) = default_value {
                                                             if let Ok(value) = default_value.parse::<i32>() {
                                                                 widget.set_value(value);
                                                             }
@@ -821,7 +897,7 @@ impl Tool {
                                                         }
                                                     },
                                                     None => {
-                                                        if let Some(default_value) = field.get_default_value(None) {
+                                                        if let Some(default_value) = default_value {
                                                             if let Ok(value) = default_value.parse::<f64>() {
                                                                 widget.set_value(value);
                                                             }
@@ -835,7 +911,8 @@ impl Tool {
                                     FieldType::StringU8 |
                                     FieldType::StringU16 |
                                     FieldType::OptionalStringU8 |
-                                    FieldType::OptionalStringU16 => {
+                                    FieldType::OptionalStringU16 |
+                                    FieldType::OptionalI32 => {
                                         let widget_name = format!("{}_{}_line_edit", table_name, field.get_name());
                                         let widget: Result<QPtr<QLineEdit>> = self.find_widget(&widget_name);
                                         match widget {
@@ -846,7 +923,7 @@ impl Tool {
                                                 match data.get(&field_key_name) {
                                                     Some(data) => widget.set_text(&QString::from_std_str(data)),
                                                     None => {
-                                                        if let Some(default_value) = field.get_default_value(None) {
+                                                        if let Some(default_value) = default_value {
                                                             widget.set_text(&QString::from_std_str(default_value));
                                                         }
                                                     }
@@ -1125,7 +1202,8 @@ impl Tool {
                                     FieldType::StringU8 |
                                     FieldType::StringU16 |
                                     FieldType::OptionalStringU8 |
-                                    FieldType::OptionalStringU16 => {
+                                    FieldType::OptionalStringU16 |
+                                    FieldType::OptionalI32 => {
                                         let widget_name = format!("{}_{}_line_edit", table_name, field.get_name());
                                         let widget: Result<QPtr<QLineEdit>> = self.find_widget(&widget_name);
                                         match widget {
@@ -376,10 +376,17 @@ impl ToolUnitEditor {
         view.load_data()?;
 
         // If we hit ok, save the data back to the PackFile.
-        if view.tool.get_ref_dialog().exec() == 1 {
+        let result = view.tool.get_ref_dialog().exec();
+        if result == 1 {
             view.save_data(app_ui, pack_file_contents_ui, global_search_ui, diagnostics_ui, dependencies_ui)?;
         }
 
+        // If we asked for a template reload (debug only), drop this dialog and build the tool again.
+        else if Tool::is_template_reload_requested(result) {
+            view.tool.get_ref_main_widget().delete_later();
+            return Self::new(app_ui, pack_file_contents_ui, global_search_ui, diagnostics_ui, dependencies_ui);
+        }
+
         // If nothing failed, it means we have successfully saved the data back to disk, or canceled.wh_main_teb_cha_captain_0
         Ok(())
     }
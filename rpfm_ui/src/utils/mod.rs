@@ -51,7 +51,7 @@ use rpfm_lib::{GAME_SELECTED, packedfile::PackedFileType, SENTRY_GUARD};
 
 use crate::ASSETS_PATH;
 use crate::CENTRAL_COMMAND;
-use crate::communications::{CentralCommand, Command, Response, THREADS_COMMUNICATION_ERROR};
+use crate::communications::{CentralCommand, Command, OperationId, Response, THREADS_COMMUNICATION_ERROR};
 use crate::ffi::*;
 use crate::locale::{qtr, qtre};
 use crate::ORANGE;
@@ -156,6 +156,29 @@ pub unsafe fn show_dialog<T: Display>(parent: impl cpp_core::CastInto<Ptr<QWidge
     ).exec();
 }
 
+/// This function creates and shows a modal "wait" dialog with a `Cancel` button, for long-running operations on the background thread.
+///
+/// Clicking `Cancel` cancels the provided operation. Remember to close the dialog once the operation finishes.
+pub unsafe fn show_cancellable_wait_dialog(parent: impl cpp_core::CastInto<Ptr<QWidget>>, text_key: &str, operation_id: OperationId) -> QBox<QMessageBox> {
+    let dialog = QMessageBox::from_icon2_q_string_q_flags_standard_button_q_widget(
+        Icon::Information,
+        &qtr("rpfm_title"),
+        &qtr(text_key),
+        QFlags::from(0),
+        parent,
+    );
+
+    dialog.set_modal(true);
+    let cancel_button = dialog.add_button_q_string_button_role(&qtr("cancel"), qt_widgets::q_message_box::ButtonRole::RejectRole);
+    let cancel_slot = SlotNoArgs::new(&dialog, move || {
+        CENTRAL_COMMAND.send_background(Command::Cancel(operation_id));
+    });
+    cancel_button.released().connect(&cancel_slot);
+
+    dialog.show();
+    dialog
+}
+
 /// This function creates a non-modal dialog, for debugging purpouses.
 ///
 /// It requires:
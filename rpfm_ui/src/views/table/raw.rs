@@ -606,7 +606,9 @@ impl TableView {
                         FieldType::I16 => text.parse::<i16>().is_ok() || text.parse::<f32>().is_ok(),
                         FieldType::I32 => text.parse::<i32>().is_ok() || text.parse::<f32>().is_ok(),
                         FieldType::I64 => text.parse::<i64>().is_ok() || text.parse::<f32>().is_ok(),
-                        FieldType::ColourRGB => u32::from_str_radix(text, 16).is_ok(),
+                        FieldType::ColourRGB |
+                        FieldType::ColourRGBA => u32::from_str_radix(text, 16).is_ok(),
+                        FieldType::OptionalI32 => text.is_empty() || text.parse::<i32>().is_ok(),
 
                         // All these are Strings, so we can skip their checks....
                         FieldType::StringU8 |
@@ -958,7 +960,12 @@ impl TableView {
             FieldType::I16 |
             FieldType::I32 |
             FieldType::I64 => format!("{}", item.data_1a(2).to_long_long_0a()),
-            FieldType::ColourRGB => format!("\"{}\"", item.text().to_std_string().escape_default().to_string()),
+            FieldType::OptionalI32 => {
+                let data_str = item.text().to_std_string();
+                if data_str.is_empty() { "nil".to_owned() } else { data_str }
+            },
+            FieldType::ColourRGB |
+            FieldType::ColourRGBA => format!("\"{}\"", item.text().to_std_string().escape_default().to_string()),
 
             // All these are Strings, so they need to escape certain chars and include commas in Lua.
             FieldType::StringU8 |
@@ -1263,6 +1270,7 @@ impl TableView {
             let default_i32 = "0".to_owned();
             let default_bool = "false".to_owned();
             let default_colour_rgb = "000000".to_owned();
+            let default_colour_rgba = "00000000".to_owned();
 
             let mut real_cells = vec![];
             let mut values = vec![];
@@ -1278,6 +1286,8 @@ impl TableView {
                             FieldType::I32 |
                             FieldType::I64 => values.push(&*default_i32),
                             FieldType::ColourRGB => values.push(&*default_colour_rgb),
+                            FieldType::ColourRGBA => values.push(&*default_colour_rgba),
+                            FieldType::OptionalI32 |
                             FieldType::StringU8 |
                             FieldType::StringU16 |
                             FieldType::OptionalStringU8 |
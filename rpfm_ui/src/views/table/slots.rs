@@ -201,6 +201,17 @@ impl TableViewSlots {
                 if !view.undo_lock.load(Ordering::SeqCst) {
                     let item_old = view.undo_model.item_2a(item.row(), item.column());
 
+                    // Text delegates can't stop invalid values, so if the new one cannot be parsed, put the old one back.
+                    let field_type = view.get_ref_table_definition().get_fields_processed().get(item.column() as usize).map(|field| field.get_ref_field_type().clone());
+                    if let Some(field_type) = field_type {
+                        if !is_valid_cell_text(&field_type, &item.text().to_std_string()) {
+                            let blocker = QSignalBlocker::from_q_object(&view.table_model);
+                            item.set_text(&item_old.text());
+                            blocker.unblock();
+                            return;
+                        }
+                    }
+
                     // Only trigger this if the values are actually different. Checkable cells are tricky. Nested cells an go to hell.
                     if (item_old.text().compare_q_string(item.text().as_ref()) != 0 || item_old.check_state() != item.check_state()) ||
                         item_old.data_1a(ITEM_IS_SEQUENCE).to_bool() && 0 != item_old.data_1a(ITEM_SEQUENCE_DATA).to_string().compare_q_string(&item.data_1a(ITEM_SEQUENCE_DATA).to_string()) {
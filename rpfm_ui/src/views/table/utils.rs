@@ -324,6 +324,40 @@ pub unsafe fn get_default_item_from_field(field: &Field, table_name: Option<&str
             item.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(&text)), ITEM_SOURCE_VALUE);
             item
         },
+        FieldType::ColourRGBA => {
+            let text = if let Some(default_value) = field.get_default_value(table_name) {
+                if u32::from_str_radix(&default_value, 16).is_ok() {
+                    default_value.to_owned()
+                } else {
+                    "00000000".to_owned()
+                }
+            } else {
+                "00000000".to_owned()
+            };
+            let item = QStandardItem::from_q_string(&QString::from_std_str(&text));
+            item.set_tool_tip(&QString::from_std_str(&tre("original_data", &[&text])));
+            item.set_data_2a(&QVariant::from_bool(true), ITEM_HAS_SOURCE_VALUE);
+            item.set_data_2a(&QVariant::from_bool(false), ITEM_IS_SEQUENCE);
+            item.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(&text)), ITEM_SOURCE_VALUE);
+            item
+        },
+        FieldType::OptionalI32 => {
+            let text = if let Some(default_value) = field.get_default_value(table_name) {
+                if default_value.parse::<i32>().is_ok() {
+                    default_value.to_owned()
+                } else {
+                    String::new()
+                }
+            } else {
+                String::new()
+            };
+            let item = QStandardItem::from_q_string(&QString::from_std_str(&text));
+            item.set_tool_tip(&QString::from_std_str(&tre("original_data", &[&text])));
+            item.set_data_2a(&QVariant::from_bool(true), ITEM_HAS_SOURCE_VALUE);
+            item.set_data_2a(&QVariant::from_bool(false), ITEM_IS_SEQUENCE);
+            item.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(&text)), ITEM_SOURCE_VALUE);
+            item
+        },
         FieldType::StringU8 |
         FieldType::StringU16 |
         FieldType::OptionalStringU8 |
@@ -536,7 +570,11 @@ pub unsafe fn get_item_from_decoded_data(data: &DecodedData, keys: &[i32], colum
             item
         },
 
-        DecodedData::ColourRGB(_) => {
+        DecodedData::ColourRGB(_) |
+        DecodedData::ColourRGBA(_) |
+
+        // Optional integers are shown as text, so an empty cell means the value is not there.
+        DecodedData::OptionalI32(_) => {
             let data = data.data_to_string();
             let item = QStandardItem::from_q_string(&QString::from_std_str(&data));
             item.set_tool_tip(&QString::from_std_str(&tre("original_data", &[&data])));
@@ -611,6 +649,8 @@ pub unsafe fn build_columns(
                 FieldType::I32 => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
                 FieldType::I64 => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
                 FieldType::ColourRGB => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
+                FieldType::ColourRGBA => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
+                FieldType::OptionalI32 => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_NUMBER),
                 FieldType::StringU8 => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_STRING),
                 FieldType::StringU16 => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_STRING),
                 FieldType::OptionalStringU8 => table_view_primary.set_column_width(index as i32, COLUMN_SIZE_STRING),
@@ -881,6 +921,11 @@ pub unsafe fn setup_item_delegates(
                     new_colour_item_delegate_safe(&table_view_primary.static_upcast::<QObject>().as_ptr(), column as i32, &timer.as_ptr(), true);
                     new_colour_item_delegate_safe(&table_view_frozen.static_upcast::<QObject>().as_ptr(), column as i32, &timer.as_ptr(), true);
                 },
+
+                // The colour delegate has no alpha channel, so RGBA colours are edited as plain hex strings.
+                // Optional integers need to allow empty values, which the spinbox doesn't, so they're edited as strings too.
+                FieldType::ColourRGBA |
+                FieldType::OptionalI32 |
                 FieldType::StringU8 |
                 FieldType::StringU16 |
                 FieldType::OptionalStringU8 |
@@ -925,6 +970,17 @@ pub unsafe fn sort_column(
     }
 }
 
+/// This function checks if the text of a cell can be turned back into data of the provided type.
+///
+/// Only the types edited with plain text delegates need this, as the other delegates can't produce invalid values.
+pub fn is_valid_cell_text(field_type: &FieldType, text: &str) -> bool {
+    match field_type {
+        FieldType::ColourRGBA => u32::from_str_radix(text, 16).is_ok(),
+        FieldType::OptionalI32 => text.is_empty() || text.parse::<i32>().is_ok(),
+        _ => true,
+    }
+}
+
 /// This function is used to build a table struct with the data of a TableView and it's definition.
 pub unsafe fn get_table_from_view(
     model: &QPtr<QStandardItemModel>,
@@ -953,6 +1009,13 @@ pub unsafe fn get_table_from_view(
 
                 // Colours need parsing to turn them into integers.
                 FieldType::ColourRGB => DecodedData::ColourRGB(u32::from_str_radix(&model.item_2a(row as i32, column as i32).text().to_std_string(), 16).unwrap()),
+                FieldType::ColourRGBA => DecodedData::ColourRGBA(u32::from_str_radix(&model.item_2a(row as i32, column as i32).text().to_std_string(), 16).unwrap()),
+
+                // Optional integers are stored as text, with empty meaning there is no value.
+                FieldType::OptionalI32 => {
+                    let text = model.item_2a(row as i32, column as i32).text().to_std_string();
+                    DecodedData::OptionalI32(if text.is_empty() { None } else { Some(text.parse::<i32>()?) })
+                },
 
                 // All these are just normal Strings.
                 FieldType::StringU8 => DecodedData::StringU8(QString::to_std_string(&model.item_2a(row as i32, column as i32).text())),